@@ -1,7 +1,33 @@
+use crate::config;
 use crate::errors::QuickexError;
-use crate::events::{publish_admin_changed, publish_contract_paused};
+use crate::events::{
+    publish_admin_action_approved, publish_admin_action_executed, publish_admin_action_proposed,
+    publish_admin_changed, publish_admin_council_set, publish_admin_renounce_scheduled,
+    publish_admin_renounced, publish_emergency_withdrawal, publish_function_disabled_updated,
+    publish_guardian_updated, publish_initialized, publish_paused,
+    publish_recovery_address_updated, publish_role_updated, publish_unpaused,
+};
+use crate::fees;
+use crate::migration;
+use crate::solvency;
 use crate::storage;
-use soroban_sdk::{Address, Env};
+use crate::timelock;
+use crate::tokens;
+use crate::types::{AdminAction, AdminCouncil, PendingAdminAction, Role, SaltPolicy};
+use quickex_commitment::MAX_SALT_LEN;
+use soroban_sdk::{vec, Address, Env, Symbol, Vec};
+
+/// Pause bit for deposits of any kind (escrows, hidden escrows, pool notes, relayer stakes).
+pub const PAUSE_DEPOSITS: u32 = 1 << 0;
+/// Pause bit for withdrawals of any kind, including announcements.
+pub const PAUSE_WITHDRAWALS: u32 = 1 << 1;
+/// Pause bit for refunds of expired escrows.
+pub const PAUSE_REFUNDS: u32 = 1 << 2;
+/// Pause bit for `create_escrow`.
+pub const PAUSE_ESCROW_CREATION: u32 = 1 << 3;
+/// Every pausable operation.
+pub const PAUSE_ALL: u32 =
+    PAUSE_DEPOSITS | PAUSE_WITHDRAWALS | PAUSE_REFUNDS | PAUSE_ESCROW_CREATION;
 
 /// Initialize the contract with an admin address.
 ///
@@ -9,17 +35,35 @@ use soroban_sdk::{Address, Env};
 /// The initial admin is allowed to pause/unpause, transfer admin, and upgrade.
 #[allow(dead_code)]
 pub fn initialize(env: &Env, admin: Address) -> Result<(), QuickexError> {
-    if has_admin(env) {
+    if has_admin(env) || storage::get_admin_council(env).is_some() || is_renounced(env) {
         return Err(QuickexError::AlreadyInitialized);
     }
 
-    // Seed admin and paused flags in persistent storage.
+    // Seed the admin and paused flags in instance storage, and the admin activity
+    // time and schema version in persistent storage.
     storage::set_admin(env, &admin);
+    storage::set_admin_last_active(env, env.ledger().timestamp());
     storage::set_paused(env, false);
+    storage::set_schema_version(env, migration::STORAGE_SCHEMA_VERSION);
+    publish_initialized(env, admin);
 
     Ok(())
 }
 
+/// Initialize with `admin` unless a single admin is already set, and return the
+/// admin in force. Lets deployment scripts be re-run safely.
+///
+/// # Errors
+/// - [`QuickexError::AlreadyInitialized`] – the contract is in council mode or the
+///   admin was renounced, so there is no admin to return.
+pub fn ensure_initialized(env: &Env, admin: Address) -> Result<Address, QuickexError> {
+    if let Some(existing) = storage::get_admin(env) {
+        return Ok(existing);
+    }
+    initialize(env, admin.clone())?;
+    Ok(admin)
+}
+
 /// Check if admin has been initialized.
 #[allow(dead_code)]
 pub fn has_admin(env: &Env) -> bool {
@@ -38,25 +82,95 @@ pub fn get_admin(env: &Env) -> Option<Address> {
 ///
 /// - Fails with [`Unauthorized`] if no admin is set.
 /// - Fails with [`Unauthorized`] if `caller` ≠ stored admin.
+///
+/// On success, records the admin as active (see [`claim_admin_after_inactivity`]).
 #[allow(dead_code)]
 pub fn require_admin(env: &Env, caller: &Address) -> Result<(), QuickexError> {
     caller.require_auth();
 
     match storage::get_admin(env) {
-        Some(admin) if admin == *caller => Ok(()),
+        Some(admin) if admin == *caller => {
+            storage::set_admin_last_active(env, env.ledger().timestamp());
+            Ok(())
+        }
         _ => Err(QuickexError::Unauthorized),
     }
 }
 
+/// Require that the caller is the admin or holds `role` (with auth).
+///
+/// - Fails with [`Unauthorized`] if `caller` is neither, or the admin was renounced.
+///
+/// Records the admin as active when the admin is the caller.
+pub fn require_role(env: &Env, caller: &Address, role: Role) -> Result<(), QuickexError> {
+    caller.require_auth();
+
+    if has_role(env, role, caller) {
+        if storage::get_admin(env).as_ref() == Some(caller) {
+            storage::set_admin_last_active(env, env.ledger().timestamp());
+        }
+        return Ok(());
+    }
+    Err(QuickexError::Unauthorized)
+}
+
+/// Whether `account` holds `role`, either explicitly or as the admin.
+///
+/// Always `false` once the admin has been renounced.
+pub fn has_role(env: &Env, role: Role, account: &Address) -> bool {
+    if is_renounced(env) {
+        return false;
+    }
+    storage::get_admin(env).as_ref() == Some(account) || storage::has_role(env, role, account)
+}
+
+/// Grant `role` to `account` (**admin only**).
+///
+/// Emits a `RoleUpdated` event.
+pub fn grant_role(
+    env: &Env,
+    caller: Address,
+    role: Role,
+    account: Address,
+) -> Result<(), QuickexError> {
+    require_admin(env, &caller)?;
+    update_role(env, role, account, true);
+    Ok(())
+}
+
+/// Revoke `role` from `account` (**admin only**).
+///
+/// Emits a `RoleUpdated` event. The admin's implicit roles cannot be revoked.
+pub fn revoke_role(
+    env: &Env,
+    caller: Address,
+    role: Role,
+    account: Address,
+) -> Result<(), QuickexError> {
+    require_admin(env, &caller)?;
+    update_role(env, role, account, false);
+    Ok(())
+}
+
+fn update_role(env: &Env, role: Role, account: Address, granted: bool) {
+    storage::set_role(env, role, &account, granted);
+    publish_role_updated(env, account, role, granted);
+}
+
 /// Set a new admin address (**admin only**).
 ///
 /// Emits an `AdminChanged` event for indexers.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::TimelockRequired`] – a timelock delay is set; queue
+///   [`AdminAction::SetAdmin`] instead.
 #[allow(dead_code)]
 pub fn set_admin(env: &Env, caller: Address, new_admin: Address) -> Result<(), QuickexError> {
     require_admin(env, &caller)?;
+    timelock::require_no_delay(env)?;
 
-    // Safe to unwrap: `require_admin` guarantees an admin is set.
-    let old_admin = storage::get_admin(env).unwrap();
+    let old_admin = storage::get_admin(env).ok_or(QuickexError::Unauthorized)?;
     storage::set_admin(env, &new_admin);
 
     publish_admin_changed(env, old_admin, new_admin);
@@ -64,25 +178,256 @@ pub fn set_admin(env: &Env, caller: Address, new_admin: Address) -> Result<(), Q
     Ok(())
 }
 
-/// Set the paused state (**admin only**).
+/// Set the paused state (**admin or [`Role::Pauser`]**; the guardian may only pause).
 ///
-/// Emits a `ContractPaused` event whenever the flag changes.
+/// Emits a `Paused` or `Unpaused` event covering every operation.
 #[allow(dead_code)]
 pub fn set_paused(env: &Env, caller: Address, new_state: bool) -> Result<(), QuickexError> {
+    if !(new_state && is_guardian(env, &caller)) {
+        require_role(env, &caller, Role::Pauser)?;
+    }
+
+    update_paused_ops(env, &caller, PAUSE_ALL, new_state);
+
+    Ok(())
+}
+
+/// Pause or unpause the operations in the `ops` bitmask (**admin or [`Role::Pauser`]**).
+///
+/// Other operations keep their state, so e.g. deposits can be halted while withdrawals
+/// continue. Emits a `Paused` or `Unpaused` event.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is neither the admin nor a pauser.
+/// - [`QuickexError::InvalidPauseOps`] – `ops` is empty or has bits outside [`PAUSE_ALL`].
+pub fn set_paused_ops(
+    env: &Env,
+    caller: Address,
+    ops: u32,
+    paused: bool,
+) -> Result<(), QuickexError> {
+    require_role(env, &caller, Role::Pauser)?;
+    if ops == 0 || ops & !PAUSE_ALL != 0 {
+        return Err(QuickexError::InvalidPauseOps);
+    }
+
+    update_paused_ops(env, &caller, ops, paused);
+    Ok(())
+}
+
+pub(crate) fn update_paused_ops(env: &Env, caller: &Address, ops: u32, paused: bool) {
+    let current = storage::get_paused_ops(env);
+    let updated = if paused {
+        current | ops
+    } else {
+        current & !ops
+    };
+    storage::set_paused_ops(env, updated);
+    if paused {
+        publish_paused(env, caller.clone(), ops, updated);
+    } else {
+        publish_unpaused(env, caller.clone(), ops, updated);
+    }
+}
+
+/// Entrypoints the kill switch can disable: the user-facing deposit, withdrawal,
+/// refund, relayer, and privacy flows. Admin and governance entrypoints are left out
+/// so a disabled function can always be re-enabled.
+pub const KILLABLE_FUNCTIONS: [&str; 28] = [
+    "withdraw",
+    "attempt_withdraw",
+    "withdraw_delegated",
+    "announce_withdraw",
+    "finalize_withdraw",
+    "withdraw_via_relayer",
+    "stake_relayer",
+    "unstake_relayer",
+    "withdraw_relayer_stake",
+    "enable_privacy",
+    "set_privacy",
+    "deposit",
+    "create_escrow",
+    "deposit_hidden",
+    "withdraw_hidden",
+    "refund_hidden",
+    "deposit_to_pool",
+    "deposit_shielded",
+    "withdraw_with_proof",
+    "deposit_ring_note",
+    "withdraw_with_ring_signature",
+    "deposit_with_commitment",
+    "deposit_with_memo",
+    "refund",
+    "deposit_ephemeral",
+    "gc",
+    "prepare_withdraw",
+    "commit_withdraw",
+];
+
+/// Disable or re-enable the entrypoint `name` (**admin or [`Role::Pauser`]**).
+///
+/// A narrower tool than pausing: one faulty code path can be switched off while the
+/// rest of the contract keeps running. Emits a `FunctionDisabledUpdated` event.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is neither the admin nor a pauser.
+/// - [`QuickexError::UnknownFunction`] – `name` is not in [`KILLABLE_FUNCTIONS`].
+pub fn set_function_disabled(
+    env: &Env,
+    caller: Address,
+    name: Symbol,
+    disabled: bool,
+) -> Result<(), QuickexError> {
+    require_role(env, &caller, Role::Pauser)?;
+    if !KILLABLE_FUNCTIONS
+        .iter()
+        .any(|function| Symbol::new(env, function) == name)
+    {
+        return Err(QuickexError::UnknownFunction);
+    }
+
+    storage::set_function_disabled(env, &name, disabled);
+    publish_function_disabled_updated(env, name, disabled, caller);
+    Ok(())
+}
+
+/// Whether the entrypoint `name` has been disabled.
+pub fn is_function_disabled(env: &Env, name: &Symbol) -> bool {
+    storage::is_function_disabled(env, name)
+}
+
+/// Require that the entrypoint `name` has not been disabled.
+///
+/// # Errors
+/// - [`QuickexError::FunctionDisabled`] – `name` is switched off.
+pub fn require_function_enabled(env: &Env, name: &str) -> Result<(), QuickexError> {
+    if is_function_disabled(env, &Symbol::new(env, name)) {
+        return Err(QuickexError::FunctionDisabled);
+    }
+    Ok(())
+}
+
+/// Set or clear the guardian (**admin only**).
+///
+/// The guardian can pause the whole contract and do nothing else, so a hot monitoring
+/// key can stop it while the admin key stays offline. Emits a `GuardianUpdated` event.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+pub fn set_guardian(
+    env: &Env,
+    caller: Address,
+    guardian: Option<Address>,
+) -> Result<(), QuickexError> {
     require_admin(env, &caller)?;
+    update_guardian(env, guardian, caller);
+    Ok(())
+}
+
+fn update_guardian(env: &Env, guardian: Option<Address>, admin: Address) {
+    storage::set_guardian(env, guardian.as_ref());
+    publish_guardian_updated(env, guardian, admin);
+}
 
-    storage::set_paused(env, new_state);
+/// Default seconds without admin activity after which the recovery address may
+/// claim admin (~180 days; see [`crate::config`]).
+pub const RECOVERY_INACTIVITY_SECS: u64 = 180 * 24 * 60 * 60;
 
-    publish_contract_paused(env, caller, new_state);
+/// Set or clear the recovery address (**admin only**).
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+pub fn set_recovery_address(
+    env: &Env,
+    caller: Address,
+    recovery: Option<Address>,
+) -> Result<(), QuickexError> {
+    require_admin(env, &caller)?;
+    storage::set_recovery_address(env, recovery.as_ref());
+    publish_recovery_address_updated(env, recovery, caller);
+    Ok(())
+}
+
+/// Get the recovery address, if one is set.
+pub fn get_recovery_address(env: &Env) -> Option<Address> {
+    storage::get_recovery_address(env)
+}
+
+/// Take over as admin after the configured inactivity period (by default
+/// [`RECOVERY_INACTIVITY_SECS`]) without admin activity (**recovery address only**).
+///
+/// Any admin call that passes [`require_admin`] resets the clock. The recovery address
+/// is consumed; the new admin can register a fresh one.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the recovery address, there is no
+///   single admin (council mode), or the admin was renounced.
+/// - [`QuickexError::AdminStillActive`] – the inactivity period has not elapsed.
+pub fn claim_admin_after_inactivity(env: &Env, caller: Address) -> Result<(), QuickexError> {
+    caller.require_auth();
+    if is_renounced(env) || storage::get_recovery_address(env).as_ref() != Some(&caller) {
+        return Err(QuickexError::Unauthorized);
+    }
+    let old_admin = storage::get_admin(env).ok_or(QuickexError::Unauthorized)?;
+
+    let inactive_since = storage::get_admin_last_active(env);
+    let inactivity = config::get(env).recovery_inactivity_secs;
+    if env.ledger().timestamp() < inactive_since.saturating_add(inactivity) {
+        return Err(QuickexError::AdminStillActive);
+    }
 
+    storage::set_recovery_address(env, None);
+    storage::set_admin(env, &caller);
+    storage::set_admin_last_active(env, env.ledger().timestamp());
+    publish_admin_changed(env, old_admin, caller);
     Ok(())
 }
 
-/// Check if the contract is paused.
+/// Get the guardian address, if one is set.
+pub fn get_guardian(env: &Env) -> Option<Address> {
+    storage::get_guardian(env)
+}
+
+/// Whether `caller` is the guardian (with auth). Always `false` once the admin has been
+/// renounced, since nobody could unpause afterwards.
+fn is_guardian(env: &Env, caller: &Address) -> bool {
+    if is_renounced(env) || storage::get_guardian(env).as_ref() != Some(caller) {
+        return false;
+    }
+    caller.require_auth();
+    true
+}
+
+/// Bitmask of currently paused operations.
+pub fn paused_ops(env: &Env) -> u32 {
+    storage::get_paused_ops(env)
+}
+
+/// Check if any operation is paused.
 pub fn is_paused(env: &Env) -> bool {
     storage::is_paused(env)
 }
 
+/// Check if the operation `op` (one of the `PAUSE_*` bits) is paused.
+pub fn is_op_paused(env: &Env, op: u32) -> bool {
+    paused_ops(env) & op != 0
+}
+
+/// Require that the operation `op` (one of the `PAUSE_*` bits) may run.
+///
+/// # Errors
+/// - [`QuickexError::MigrationPending`] – an upgrade is waiting for `migrate`.
+/// - [`QuickexError::ContractPaused`] – `op` is paused.
+pub fn require_op_enabled(env: &Env, op: u32) -> Result<(), QuickexError> {
+    if migration::is_pending(env) {
+        return Err(QuickexError::MigrationPending);
+    }
+    if is_op_paused(env, op) {
+        return Err(QuickexError::ContractPaused);
+    }
+    Ok(())
+}
+
 /// Require that the contract is not paused.
 ///
 /// This helper should be called at the start of operations that are blocked when paused.
@@ -93,3 +438,411 @@ pub fn require_not_paused(env: &Env) -> Result<(), QuickexError> {
     }
     Ok(())
 }
+
+/// Set the minimum salt length for commitments (**admin only**).
+///
+/// Applies to `deposit` calls and to withdrawals of escrows created from now on.
+/// Escrows created earlier are exempt so a higher minimum cannot lock existing funds.
+/// Pass `0` to disable the check.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::InvalidSalt`] – `min_len` exceeds the maximum salt length.
+pub fn set_min_salt_len(env: &Env, caller: Address, min_len: u32) -> Result<(), QuickexError> {
+    require_admin(env, &caller)?;
+
+    if min_len as usize > MAX_SALT_LEN {
+        return Err(QuickexError::InvalidSalt);
+    }
+
+    let policy = SaltPolicy {
+        min_len,
+        since: env.ledger().timestamp(),
+    };
+    storage::set_salt_policy(env, &policy);
+
+    Ok(())
+}
+
+/// Switch to admin council mode.
+///
+/// Replaces the single admin with `members`, of which `threshold` must approve each
+/// privileged action (see [`propose_action`]). On an initialized contract the current
+/// admin must authorize the switch. Once set, the council cannot be replaced.
+///
+/// # Errors
+/// - [`QuickexError::AlreadyInitialized`] – a council is already set.
+/// - [`QuickexError::InvalidThreshold`] – `threshold` is zero or above the member
+///   count, or a member is listed twice.
+pub fn initialize_multisig(
+    env: &Env,
+    members: Vec<Address>,
+    threshold: u32,
+) -> Result<(), QuickexError> {
+    if storage::get_admin_council(env).is_some() || is_renounced(env) {
+        return Err(QuickexError::AlreadyInitialized);
+    }
+    if threshold == 0 || threshold > members.len() {
+        return Err(QuickexError::InvalidThreshold);
+    }
+    for (i, member) in members.iter().enumerate() {
+        if members.first_index_of(&member) != Some(i as u32) {
+            return Err(QuickexError::InvalidThreshold);
+        }
+    }
+
+    match storage::get_admin(env) {
+        Some(admin) => {
+            admin.require_auth();
+            storage::remove_admin(env);
+        }
+        None => {
+            storage::set_paused(env, false);
+            storage::set_schema_version(env, migration::STORAGE_SCHEMA_VERSION);
+        }
+    }
+
+    storage::set_admin_council(
+        env,
+        &AdminCouncil {
+            members: members.clone(),
+            threshold,
+        },
+    );
+    publish_admin_council_set(env, members, threshold);
+
+    Ok(())
+}
+
+/// Get the admin council, if multisig mode is on.
+pub fn get_admin_council(env: &Env) -> Option<AdminCouncil> {
+    storage::get_admin_council(env)
+}
+
+/// Get a council action that is still awaiting approvals.
+pub fn get_admin_action(env: &Env, action_id: u64) -> Option<PendingAdminAction> {
+    storage::get_admin_action(env, action_id)
+}
+
+/// Require that `member` belongs to the council (with auth).
+fn require_council_member(env: &Env, member: &Address) -> Result<AdminCouncil, QuickexError> {
+    member.require_auth();
+
+    match storage::get_admin_council(env) {
+        Some(council) if council.members.contains(member) => Ok(council),
+        _ => Err(QuickexError::Unauthorized),
+    }
+}
+
+/// Propose a privileged action (**council member only**). Returns the action id.
+///
+/// The proposal counts as the proposer's approval, so with a threshold of 1 the action
+/// executes immediately. Once approved, timelocked actions (see [`crate::timelock`])
+/// are queued under the same id instead of executing.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – no council is set or `proposer` is not a member.
+/// - Any error from [`validate_action`].
+pub fn propose_action(
+    env: &Env,
+    proposer: Address,
+    action: AdminAction,
+) -> Result<u64, QuickexError> {
+    let council = require_council_member(env, &proposer)?;
+    validate_action(&action)?;
+
+    let action_id = storage::next_admin_action_id(env);
+    let pending = PendingAdminAction {
+        action: action.clone(),
+        approvals: vec![env, proposer.clone()],
+        proposed_at: env.ledger().timestamp(),
+    };
+    publish_admin_action_proposed(env, action_id, proposer.clone(), action);
+
+    record_approval(env, &council, action_id, pending, &proposer);
+    Ok(action_id)
+}
+
+/// Approve a pending council action (**council member only**).
+///
+/// Returns `true` if this approval reached the threshold and the action was executed
+/// (or queued, if timelocked).
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – no council is set or `member` is not a member.
+/// - [`QuickexError::ActionNotFound`] – no pending action has this id (it may have
+///   already executed).
+/// - [`QuickexError::ActionAlreadyApproved`] – `member` has already approved.
+pub fn approve_action(env: &Env, member: Address, action_id: u64) -> Result<bool, QuickexError> {
+    let council = require_council_member(env, &member)?;
+
+    let mut pending =
+        storage::get_admin_action(env, action_id).ok_or(QuickexError::ActionNotFound)?;
+    if pending.approvals.contains(&member) {
+        return Err(QuickexError::ActionAlreadyApproved);
+    }
+    pending.approvals.push_back(member.clone());
+
+    Ok(record_approval(env, &council, action_id, pending, &member))
+}
+
+/// Store `pending` with its latest approval, executing it once the threshold is met.
+fn record_approval(
+    env: &Env,
+    council: &AdminCouncil,
+    action_id: u64,
+    pending: PendingAdminAction,
+    member: &Address,
+) -> bool {
+    publish_admin_action_approved(env, action_id, member.clone(), pending.approvals.len());
+
+    if pending.approvals.len() < council.threshold {
+        storage::put_admin_action(env, action_id, &pending);
+        return false;
+    }
+
+    storage::remove_admin_action(env, action_id);
+    if timelock::is_timelocked(env, &pending.action) {
+        timelock::enqueue(env, action_id, pending.action, member);
+    } else {
+        execute(env, action_id, pending.action, member);
+    }
+    true
+}
+
+/// Role that may perform `action` besides the admin; `None` means admin only.
+fn action_role(action: &AdminAction) -> Option<Role> {
+    match action {
+        AdminAction::SetPaused(_) | AdminAction::SetPausedOps(..) => Some(Role::Pauser),
+        AdminAction::Upgrade(..) => Some(Role::Upgrader),
+        AdminAction::SetWithdrawalFee(_) => Some(Role::FeeManager),
+        _ => None,
+    }
+}
+
+/// Check the parameters of `action` before it is proposed, queued, or batched.
+///
+/// # Errors
+/// - [`QuickexError::InvalidPauseOps`] – a pause mask is empty or has unknown bits.
+/// - [`QuickexError::InvalidFee`] – a withdrawal fee is above [`fees::MAX_FEE_BPS`].
+/// - [`QuickexError::InvalidAmount`] – a cap is negative.
+pub(crate) fn validate_action(action: &AdminAction) -> Result<(), QuickexError> {
+    match action {
+        AdminAction::SetPausedOps(ops, _) if *ops == 0 || ops & !PAUSE_ALL != 0 => {
+            Err(QuickexError::InvalidPauseOps)
+        }
+        AdminAction::SetWithdrawalFee(fee_bps) if *fee_bps > fees::MAX_FEE_BPS => {
+            Err(QuickexError::InvalidFee)
+        }
+        AdminAction::SetTokenCap(_, cap) | AdminAction::SetGlobalCap(cap) if *cap < 0 => {
+            Err(QuickexError::InvalidAmount)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Most actions accepted by [`admin_batch`].
+pub const MAX_BATCH_ACTIONS: u32 = 16;
+
+/// Apply several admin actions atomically (**admin only**).
+///
+/// Every action is checked before any is applied, so the batch takes effect entirely
+/// or not at all. Each action emits its own events and an `AdminActionExecuted`.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::InvalidBatch`] – `actions` is empty or longer than
+///   [`MAX_BATCH_ACTIONS`].
+/// - [`QuickexError::TimelockRequired`] – an action must go through the timelock.
+/// - Any error from [`validate_action`].
+pub fn admin_batch(
+    env: &Env,
+    caller: Address,
+    actions: Vec<AdminAction>,
+) -> Result<(), QuickexError> {
+    require_admin(env, &caller)?;
+    if actions.is_empty() || actions.len() > MAX_BATCH_ACTIONS {
+        return Err(QuickexError::InvalidBatch);
+    }
+    for action in actions.iter() {
+        if timelock::is_timelocked(env, &action) {
+            return Err(QuickexError::TimelockRequired);
+        }
+        validate_action(&action)?;
+    }
+
+    for action in actions.iter() {
+        let action_id = storage::next_admin_action_id(env);
+        execute(env, action_id, action, &caller);
+    }
+    Ok(())
+}
+
+/// Require that `caller` may perform `action` (with auth).
+///
+/// - Fails with [`Unauthorized`] if `caller` is neither the admin nor holds the
+///   action's role.
+pub(crate) fn require_action_auth(
+    env: &Env,
+    caller: &Address,
+    action: &AdminAction,
+) -> Result<(), QuickexError> {
+    match action_role(action) {
+        Some(role) => require_role(env, caller, role),
+        None => require_admin(env, caller),
+    }
+}
+
+/// Apply an authorized `action` and emit `AdminActionExecuted`.
+///
+/// `executor` is reported as the admin in the action's own events.
+pub(crate) fn execute(env: &Env, action_id: u64, action: AdminAction, executor: &Address) {
+    match action.clone() {
+        AdminAction::SetPaused(paused) => update_paused_ops(env, executor, PAUSE_ALL, paused),
+        AdminAction::SetPausedOps(ops, paused) => {
+            update_paused_ops(env, executor, ops & PAUSE_ALL, paused)
+        }
+        AdminAction::Upgrade(wasm_hash, version) => {
+            migration::upgrade(env, wasm_hash, version, executor)
+        }
+        AdminAction::GrantRole(role, account) => update_role(env, role, account, true),
+        AdminAction::RevokeRole(role, account) => update_role(env, role, account, false),
+        AdminAction::SetAdmin(new_admin) => {
+            // A council handing over to a single admin leaves multisig mode.
+            let old_admin = storage::get_admin(env).unwrap_or(executor.clone());
+            storage::remove_admin_council(env);
+            storage::set_admin(env, &new_admin);
+            publish_admin_changed(env, old_admin, new_admin);
+        }
+        AdminAction::SetTimelockDelay(delay) => timelock::set_delay(env, delay),
+        AdminAction::CancelQueued(queued_id) => timelock::cancel(env, queued_id),
+        AdminAction::SetGuardian(guardian) => update_guardian(env, guardian, executor.clone()),
+        AdminAction::SetWithdrawalFee(fee_bps) => {
+            fees::update_withdrawal_fee(env, fee_bps, executor.clone())
+        }
+        AdminAction::SetTokenCap(token, cap) => {
+            tokens::update_token_cap(env, token, cap, executor.clone())
+        }
+        AdminAction::SetGlobalCap(cap) => tokens::update_global_cap(env, cap, executor.clone()),
+        AdminAction::AllowToken(token) => {
+            tokens::update_allowlist(env, token, true, executor.clone())
+        }
+        AdminAction::DisallowToken(token) => {
+            tokens::update_allowlist(env, token, false, executor.clone())
+        }
+    }
+    publish_admin_action_executed(env, action_id, action);
+}
+
+/// Ledgers between scheduling and completing [`renounce_admin`] (~1 day).
+pub const RENOUNCE_DELAY_LEDGERS: u32 = 17_280;
+
+/// Whether the admin has been permanently renounced.
+pub fn is_renounced(env: &Env) -> bool {
+    storage::is_admin_renounced(env)
+}
+
+/// Ledger from which a scheduled renounce can be completed, if one is scheduled.
+pub fn get_renounce_ready_at(env: &Env) -> Option<u32> {
+    storage::get_renounce_scheduled(env).map(|at| at.saturating_add(RENOUNCE_DELAY_LEDGERS))
+}
+
+/// Permanently give up admin control (**admin only**), in two calls.
+///
+/// The first call schedules the renounce and returns `false`. A second call at least
+/// [`RENOUNCE_DELAY_LEDGERS`] ledgers later clears the admin and returns `true`; from
+/// then on no admin or role holder can act, queued timelock actions can no longer run,
+/// and the contract cannot be re-initialized.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::TimelockNotReady`] – the confirmation delay has not elapsed.
+/// - [`QuickexError::ContractPaused`] – the contract is paused; renouncing would freeze it.
+pub fn renounce_admin(env: &Env, caller: Address) -> Result<bool, QuickexError> {
+    require_admin(env, &caller)?;
+    if is_paused(env) {
+        return Err(QuickexError::ContractPaused);
+    }
+
+    let ledger = env.ledger().sequence();
+    let Some(ready_at) = get_renounce_ready_at(env) else {
+        storage::set_renounce_scheduled(env, Some(ledger));
+        publish_admin_renounce_scheduled(env, caller, ledger + RENOUNCE_DELAY_LEDGERS);
+        return Ok(false);
+    };
+    if ledger < ready_at {
+        return Err(QuickexError::TimelockNotReady);
+    }
+
+    storage::set_renounce_scheduled(env, None);
+    storage::set_guardian(env, None);
+    storage::set_recovery_address(env, None);
+    storage::remove_admin(env);
+    storage::set_admin_renounced(env);
+    publish_admin_renounced(env, caller);
+    Ok(true)
+}
+
+/// Cancel a scheduled renounce (**admin only**). No-op if none is scheduled.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+pub fn cancel_renounce_admin(env: &Env, caller: Address) -> Result<(), QuickexError> {
+    require_admin(env, &caller)?;
+    storage::set_renounce_scheduled(env, None);
+    Ok(())
+}
+
+/// Balance of `token` held by the contract but not owed to pending escrows, pool
+/// notes, relayer stakes, or accrued protocol fees (e.g. airdrops or direct transfers).
+///
+/// Each liability counts as at least zero, so a corrupted counter can never make user
+/// funds look like surplus.
+///
+/// # Errors
+/// - [`QuickexError::InvalidToken`] – `token` is not a token contract.
+pub fn sweepable_balance(env: &Env, token: &Address) -> Result<i128, QuickexError> {
+    let balance = tokens::balance(env, token)?;
+    let (locked, accrued_fees, staked) = solvency::liabilities(env, token);
+    let owed = locked
+        .max(0)
+        .saturating_add(accrued_fees.max(0))
+        .saturating_add(staked.max(0));
+    Ok(balance.saturating_sub(owed).max(0))
+}
+
+/// Recover `amount` of `token` not backing any user funds (**admin only, while paused**).
+///
+/// Emits an `EmergencyWithdrawal` event.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::ContractNotPaused`] – the contract is not paused.
+/// - [`QuickexError::MigrationPending`] – an upgrade awaits `migrate`, or storage is
+///   behind this code's schema, so locked totals may not be reconciled yet.
+/// - [`QuickexError::InvalidAmount`] – amount ≤ 0.
+/// - [`QuickexError::ExceedsSurplus`] – amount exceeds [`sweepable_balance`].
+pub fn emergency_withdraw(
+    env: &Env,
+    caller: Address,
+    token: Address,
+    to: Address,
+    amount: i128,
+) -> Result<(), QuickexError> {
+    require_admin(env, &caller)?;
+    if !is_paused(env) {
+        return Err(QuickexError::ContractNotPaused);
+    }
+    migration::require_migrated(env)?;
+    if amount <= 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+    if amount > sweepable_balance(env, &token)? {
+        return Err(QuickexError::ExceedsSurplus);
+    }
+
+    tokens::pay(env, &token, &to, amount)?;
+    publish_emergency_withdrawal(env, token, to, caller, amount);
+
+    Ok(())
+}
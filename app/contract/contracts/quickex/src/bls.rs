@@ -0,0 +1,308 @@
+//! Shared BLS12-381 helpers for the Pedersen and Groth16 modules.
+//!
+//! These avoid relying on SDK conveniences whose availability differs across
+//! soroban-sdk releases (scalar reduction on construction, point negation).
+//!
+//! The host's subgroup checks trap on bytes that do not encode a point on the
+//! curve, so caller-supplied points go through [`g1_checked`] (and [`g2_checked`]),
+//! which first check the encoding and curve equation in software.
+
+#[cfg(feature = "zk-verifier")]
+use soroban_sdk::crypto::bls12_381::G2Affine;
+use soroban_sdk::{
+    crypto::bls12_381::{Fr, G1Affine},
+    Bytes, BytesN, Env, U256,
+};
+
+/// BLS12-381 scalar field order `r`, big-endian.
+pub const FR_MODULUS_BE: [u8; 32] = [
+    0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48, 0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8, 0x05,
+    0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01,
+];
+
+#[cfg(feature = "zk-verifier")]
+/// BLS12-381 base field modulus `p`, big-endian.
+const FP_MODULUS_BE: [u8; 48] = [
+    0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x9a, 0x4b, 0x1b, 0xa7, 0xb6, 0x43, 0x4b, 0xac, 0xd7,
+    0x64, 0x77, 0x4b, 0x84, 0xf3, 0x85, 0x12, 0xbf, 0x67, 0x30, 0xd2, 0xa0, 0xf6, 0xb0, 0xf6, 0x24,
+    0x1e, 0xab, 0xff, 0xfe, 0xb1, 0x53, 0xff, 0xff, 0xb9, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xaa, 0xab,
+];
+
+/// Compression flag in the first byte of a serialized point (must be clear).
+const COMPRESSION_FLAG: u8 = 0x80;
+
+/// Infinity flag in the first byte of a serialized point.
+const INFINITY_FLAG: u8 = 0x40;
+
+/// Sort flag in the first byte of a serialized point (must be clear).
+const SORT_FLAG: u8 = 0x20;
+
+/// BLS12-381 base field modulus `p` as little-endian 64-bit limbs.
+const FP_MODULUS: [u64; 6] = [
+    0xb9feffffffffaaab,
+    0x1eabfffeb153ffff,
+    0x6730d2a0f6b0f624,
+    0x64774b84f38512bf,
+    0x4b1ba7b6434bacd7,
+    0x1a0111ea397fe69a,
+];
+
+/// `R^2 mod p` with `R = 2^384`, used to convert into Montgomery form.
+const FP_R2: [u64; 6] = [
+    0xf4df1f341c341746,
+    0x0a76e6a609d104f1,
+    0x8de5476c4c95b6d5,
+    0x67eb88a9939d83c0,
+    0x9a793e85b519952d,
+    0x11988fe592cae3aa,
+];
+
+/// `-p^-1 mod 2^64`.
+const FP_INV: u64 = 0x89f3fffcfffcfffd;
+
+/// Uncompressed encoding of the G1 point at infinity (infinity flag set, all else zero).
+pub const G1_IDENTITY: [u8; 96] = {
+    let mut bytes = [0u8; 96];
+    bytes[0] = INFINITY_FLAG;
+    bytes
+};
+
+/// Interpret 32 bytes as a big-endian integer and reduce it into the scalar field.
+pub fn fr_from_bytes(env: &Env, bytes: &BytesN<32>) -> Fr {
+    let raw = U256::from_be_bytes(env, &bytes.clone().into());
+    let modulus = U256::from_be_bytes(env, &Bytes::from_array(env, &FR_MODULUS_BE));
+    Fr::from_u256(raw.rem_euclid(&modulus))
+}
+
+#[cfg(feature = "zk-verifier")]
+/// Interpret 32 bytes as a big-endian scalar, rejecting values `>= r`.
+///
+/// Use for values that must have a unique scalar encoding (e.g. nullifier hashes),
+/// where silently reducing would let `x` and `x + r` alias the same scalar.
+pub fn fr_from_canonical_bytes(env: &Env, bytes: &BytesN<32>) -> Option<Fr> {
+    if bytes.to_array() >= FR_MODULUS_BE {
+        return None;
+    }
+    Some(Fr::from_u256(U256::from_be_bytes(
+        env,
+        &bytes.clone().into(),
+    )))
+}
+
+/// Convert a `u128` into a scalar (always canonical since `u128 < r`).
+pub fn fr_from_u128(env: &Env, value: u128) -> Fr {
+    Fr::from_u256(U256::from_u128(env, value))
+}
+
+#[cfg(feature = "zk-verifier")]
+/// Negate a G1 point by mapping `(x, y)` to `(x, p - y)`.
+pub fn g1_neg(env: &Env, point: &G1Affine) -> G1Affine {
+    let mut bytes = point.to_bytes().to_array();
+    if bytes[0] & INFINITY_FLAG != 0 {
+        return point.clone();
+    }
+
+    let y = &mut bytes[48..];
+    if y.iter().all(|b| *b == 0) {
+        return point.clone();
+    }
+
+    let mut borrow = 0i16;
+    for i in (0..48).rev() {
+        let diff = FP_MODULUS_BE[i] as i16 - y[i] as i16 - borrow;
+        if diff < 0 {
+            y[i] = (diff + 256) as u8;
+            borrow = 1;
+        } else {
+            y[i] = diff as u8;
+            borrow = 0;
+        }
+    }
+
+    G1Affine::from_bytes(BytesN::from_array(env, &bytes))
+}
+
+/// Base field element in Montgomery form, used only to check curve equations.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Fp([u64; 6]);
+
+impl Fp {
+    fn from_u64(value: u64) -> Fp {
+        Fp([value, 0, 0, 0, 0, 0]).mul(&Fp(FP_R2))
+    }
+
+    /// Decode 48 canonical big-endian bytes, rejecting values `≥ p`.
+    fn decode(bytes: &[u8]) -> Option<Fp> {
+        let mut limbs = [0u64; 6];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let start = (5 - i) * 8;
+            let mut chunk = [0u8; 8];
+            chunk.copy_from_slice(bytes.get(start..start + 8)?);
+            *limb = u64::from_be_bytes(chunk);
+        }
+        let canonical = (0..6)
+            .rev()
+            .find(|&i| limbs[i] != FP_MODULUS[i])
+            .is_some_and(|i| limbs[i] < FP_MODULUS[i]);
+        canonical.then(|| Fp(limbs).mul(&Fp(FP_R2)))
+    }
+
+    fn add(&self, other: &Fp) -> Fp {
+        let mut sum = [0u64; 6];
+        let mut carry = 0u128;
+        for (i, limb) in sum.iter_mut().enumerate() {
+            let acc = self.0[i] as u128 + other.0[i] as u128 + carry;
+            *limb = acc as u64;
+            carry = acc >> 64;
+        }
+        // Both inputs are below p < 2^381, so the sum fits in 384 bits.
+        Fp::reduce_once(sum)
+    }
+
+    fn sub(&self, other: &Fp) -> Fp {
+        let mut diff = [0u64; 6];
+        let mut borrow = false;
+        for (i, limb) in diff.iter_mut().enumerate() {
+            let (d, b1) = self.0[i].overflowing_sub(other.0[i]);
+            let (d, b2) = d.overflowing_sub(borrow as u64);
+            *limb = d;
+            borrow = b1 | b2;
+        }
+        if borrow {
+            let mut carry = 0u128;
+            for (i, limb) in diff.iter_mut().enumerate() {
+                let acc = *limb as u128 + FP_MODULUS[i] as u128 + carry;
+                *limb = acc as u64;
+                carry = acc >> 64;
+            }
+        }
+        Fp(diff)
+    }
+
+    /// Subtract `p` once if `value ≥ p` (for `value < 2p`; [`Fp::sub`] adds `p` back
+    /// when the subtraction wraps).
+    fn reduce_once(value: [u64; 6]) -> Fp {
+        Fp(value).sub(&Fp(FP_MODULUS))
+    }
+
+    /// `a · b · R^-1 mod p` (Montgomery multiplication, CIOS).
+    fn mul(&self, other: &Fp) -> Fp {
+        let mut t = [0u64; 8];
+        for i in 0..6 {
+            let mut carry = 0u64;
+            for (j, a_j) in self.0.iter().enumerate() {
+                let acc = t[j] as u128 + *a_j as u128 * other.0[i] as u128 + carry as u128;
+                t[j] = acc as u64;
+                carry = (acc >> 64) as u64;
+            }
+            let acc = t[6] as u128 + carry as u128;
+            t[6] = acc as u64;
+            t[7] = (acc >> 64) as u64;
+
+            let m = t[0].wrapping_mul(FP_INV);
+            let acc = t[0] as u128 + m as u128 * FP_MODULUS[0] as u128;
+            let mut carry = (acc >> 64) as u64;
+            for (j, p_j) in FP_MODULUS.iter().enumerate().skip(1) {
+                let acc = t[j] as u128 + m as u128 * *p_j as u128 + carry as u128;
+                t[j - 1] = acc as u64;
+                carry = (acc >> 64) as u64;
+            }
+            let acc = t[6] as u128 + carry as u128;
+            t[5] = acc as u64;
+            t[6] = t[7] + (acc >> 64) as u64;
+        }
+        // The result is below 2p < 2^384, so t[6] is always zero here.
+        Fp::reduce_once([t[0], t[1], t[2], t[3], t[4], t[5]])
+    }
+}
+
+/// Split off the flags of an uncompressed point encoding.
+///
+/// Returns `None` for a malformed encoding, `Some(true)` for the point at infinity
+/// (flag set, every other bit clear) and `Some(false)` for an affine point.
+fn parse_flags(bytes: &[u8]) -> Option<bool> {
+    let flags = bytes[0];
+    if flags & (COMPRESSION_FLAG | SORT_FLAG) != 0 {
+        return None;
+    }
+    if flags & INFINITY_FLAG == 0 {
+        return Some(false);
+    }
+    (flags == INFINITY_FLAG && bytes[1..].iter().all(|b| *b == 0)).then_some(true)
+}
+
+/// Whether `bytes` is a well-formed uncompressed G1 point on `y² = x³ + 4`.
+pub fn g1_is_on_curve(bytes: &[u8; 96]) -> bool {
+    match parse_flags(bytes) {
+        Some(true) => true,
+        Some(false) => {
+            let (Some(x), Some(y)) = (Fp::decode(&bytes[..48]), Fp::decode(&bytes[48..])) else {
+                return false;
+            };
+            y.mul(&y) == x.mul(&x).mul(&x).add(&Fp::from_u64(4))
+        }
+        None => false,
+    }
+}
+
+/// Parse a caller-supplied G1 point, or `None` if it is malformed, off the curve,
+/// or outside the prime-order subgroup.
+pub fn g1_checked(env: &Env, bytes: &BytesN<96>) -> Option<G1Affine> {
+    if !g1_is_on_curve(&bytes.to_array()) {
+        return None;
+    }
+    let point = G1Affine::from_bytes(bytes.clone());
+    env.crypto()
+        .bls12_381()
+        .g1_is_in_subgroup(&point)
+        .then_some(point)
+}
+
+#[cfg(feature = "zk-verifier")]
+/// `(a0 + a1·u)(b0 + b1·u)` in `Fp2 = Fp[u] / (u² + 1)`.
+fn fp2_mul(a: (Fp, Fp), b: (Fp, Fp)) -> (Fp, Fp) {
+    (
+        a.0.mul(&b.0).sub(&a.1.mul(&b.1)),
+        a.0.mul(&b.1).add(&a.1.mul(&b.0)),
+    )
+}
+
+#[cfg(feature = "zk-verifier")]
+/// Decode an `Fp2` element serialized as `c1 || c0`.
+fn fp2_decode(bytes: &[u8]) -> Option<(Fp, Fp)> {
+    Some((
+        Fp::decode(bytes.get(48..96)?)?,
+        Fp::decode(bytes.get(..48)?)?,
+    ))
+}
+
+#[cfg(feature = "zk-verifier")]
+/// Whether `bytes` is a well-formed uncompressed G2 point on `y² = x³ + 4(u + 1)`.
+pub fn g2_is_on_curve(bytes: &[u8; 192]) -> bool {
+    match parse_flags(bytes) {
+        Some(true) => true,
+        Some(false) => {
+            let (Some(x), Some(y)) = (fp2_decode(&bytes[..96]), fp2_decode(&bytes[96..])) else {
+                return false;
+            };
+            let x3 = fp2_mul(fp2_mul(x, x), x);
+            let b = Fp::from_u64(4);
+            fp2_mul(y, y) == (x3.0.add(&b), x3.1.add(&b))
+        }
+        None => false,
+    }
+}
+
+#[cfg(feature = "zk-verifier")]
+/// Parse a caller-supplied G2 point, or `None` if it is malformed, off the curve,
+/// or outside the prime-order subgroup.
+pub fn g2_checked(env: &Env, bytes: &BytesN<192>) -> Option<G2Affine> {
+    if !g2_is_on_curve(&bytes.to_array()) {
+        return None;
+    }
+    let point = G2Affine::from_bytes(bytes.clone());
+    env.crypto()
+        .bls12_381()
+        .g2_is_in_subgroup(&point)
+        .then_some(point)
+}
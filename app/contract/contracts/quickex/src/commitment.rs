@@ -1,5 +1,70 @@
-use crate::errors::QuickexError;
-use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env};
+use crate::{errors::QuickexError, storage};
+use quickex_commitment::{
+    CommitmentError, Sink, ADDRESS_KEY_LEN, MAX_ADDRESS_XDR_LEN, MAX_SALT_LEN,
+};
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, Vec};
+
+impl From<CommitmentError> for QuickexError {
+    fn from(err: CommitmentError) -> Self {
+        match err {
+            CommitmentError::NegativeAmount => QuickexError::InvalidAmount,
+            CommitmentError::SaltTooLong => QuickexError::InvalidSalt,
+        }
+    }
+}
+
+/// Collects a preimage built by [`quickex_commitment`] into host `Bytes`.
+struct Payload(Bytes);
+
+impl Sink for Payload {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+}
+
+impl Payload {
+    fn new(env: &Env) -> Self {
+        Payload(Bytes::new(env))
+    }
+
+    fn sha256(&self, env: &Env) -> BytesN<32> {
+        env.crypto().sha256(&self.0).into()
+    }
+}
+
+/// Copy `XDR(address)` into `buf` and return the used prefix.
+fn address_xdr<'a>(
+    env: &Env,
+    address: &Address,
+    buf: &'a mut [u8; MAX_ADDRESS_XDR_LEN],
+) -> &'a [u8] {
+    let xdr = address.clone().to_xdr(env);
+    let out = &mut buf[..xdr.len() as usize];
+    xdr.copy_into_slice(out);
+    out
+}
+
+/// Fixed-layout `KEY(address)`: a type tag and the 32-byte account key or contract id.
+///
+/// # Errors
+/// - [`QuickexError::UnsupportedAddress`] – `address` is neither an account nor a contract.
+fn address_key(env: &Env, address: &Address) -> Result<[u8; ADDRESS_KEY_LEN], QuickexError> {
+    let mut buf = [0u8; MAX_ADDRESS_XDR_LEN];
+    quickex_commitment::address_key_from_xdr(address_xdr(env, address, &mut buf))
+        .ok_or(QuickexError::UnsupportedAddress)
+}
+
+/// Copy `salt` into `buf` and return the used prefix.
+///
+/// # Errors
+/// - [`QuickexError::InvalidSalt`] – salt is longer than [`MAX_SALT_LEN`].
+fn salt_bytes<'a>(salt: &Bytes, buf: &'a mut [u8; MAX_SALT_LEN]) -> Result<&'a [u8], QuickexError> {
+    let out = buf
+        .get_mut(..salt.len() as usize)
+        .ok_or(QuickexError::InvalidSalt)?;
+    salt.copy_into_slice(out);
+    Ok(out)
+}
 
 /// # Commitment Scheme Invariants
 ///
@@ -49,39 +114,30 @@ use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env};
 /// - BE(amount) = 16-byte big-endian representation of i128
 /// - || = byte concatenation
 ///
+/// The preimage layout lives in the `quickex-commitment` crate so off-chain clients
+/// can build the same bytes.
+///
 pub fn create_amount_commitment(
     env: &Env,
     owner: Address,
     amount: i128,
     salt: Bytes,
 ) -> Result<BytesN<32>, QuickexError> {
+    let mut owner_buf = [0u8; MAX_ADDRESS_XDR_LEN];
+    let mut salt_buf = [0u8; MAX_SALT_LEN];
     if amount < 0 {
         return Err(QuickexError::InvalidAmount);
     }
+    let salt = salt_bytes(&salt, &mut salt_buf)?;
 
-    // Cap salt length as a safeguard
-    if salt.len() > 1024 {
-        return Err(QuickexError::InvalidSalt);
-    }
-
-    let mut payload = Bytes::new(env);
-
-    // Append owner (Address) - using XDR serialization for consistency
-    payload.append(&owner.to_xdr(env));
-
-    // Serialize amount (i128) to big-endian bytes
-    let amount_bytes: [u8; 16] = amount.to_be_bytes();
-
-    // Correct loop iteration over bytes
-    for b in &amount_bytes {
-        payload.push_back(*b);
-    }
-
-    // Append salt
-    payload.append(&salt);
-
-    // Return SHA256 hash
-    Ok(env.crypto().sha256(&payload).into())
+    let mut payload = Payload::new(env);
+    quickex_commitment::write_amount_preimage(
+        &mut payload,
+        address_xdr(env, &owner, &mut owner_buf),
+        amount,
+        salt,
+    )?;
+    Ok(payload.sha256(env))
 }
 
 pub fn verify_amount_commitment(
@@ -96,3 +152,179 @@ pub fn verify_amount_commitment(
         Err(_) => false,
     }
 }
+
+/// [`verify_amount_commitment`] for each `(commitment, owner, amount, salt)`, in order.
+pub fn verify_amount_commitments(
+    env: &Env,
+    items: Vec<(BytesN<32>, Address, i128, Bytes)>,
+) -> Vec<bool> {
+    let mut results = Vec::new(env);
+    for (commitment, owner, amount, salt) in items.iter() {
+        results.push_back(verify_amount_commitment(
+            env, commitment, owner, amount, salt,
+        ));
+    }
+    results
+}
+
+/// Create a scheme v2 commitment that also binds the escrowed token.
+///
+/// Commitment = SHA256(0x02 || XDR(token) || XDR(owner) || BE(amount) || salt)
+///
+/// A v1 [`create_amount_commitment`] hash matches any escrow for (owner, amount,
+/// salt) regardless of the asset funding it; the v2 hash only matches an escrow
+/// funded with `token`. Withdrawals look up the v2 commitment first and fall back
+/// to v1 for escrows created before v2.
+///
+/// # Errors
+/// - [`QuickexError::InvalidAmount`] – amount is negative.
+/// - [`QuickexError::InvalidSalt`] – salt is longer than 1024 bytes.
+pub fn create_token_commitment(
+    env: &Env,
+    token: Address,
+    owner: Address,
+    amount: i128,
+    salt: Bytes,
+) -> Result<BytesN<32>, QuickexError> {
+    let mut token_buf = [0u8; MAX_ADDRESS_XDR_LEN];
+    let mut owner_buf = [0u8; MAX_ADDRESS_XDR_LEN];
+    let mut salt_buf = [0u8; MAX_SALT_LEN];
+    if amount < 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+    let salt = salt_bytes(&salt, &mut salt_buf)?;
+
+    let mut payload = Payload::new(env);
+    quickex_commitment::write_token_preimage(
+        &mut payload,
+        address_xdr(env, &token, &mut token_buf),
+        address_xdr(env, &owner, &mut owner_buf),
+        amount,
+        salt,
+    )?;
+    Ok(payload.sha256(env))
+}
+
+/// Create a scheme v3 commitment: token-bound like v2, over a fixed-layout preimage.
+///
+/// Commitment = SHA256(0x03 || KEY(token) || KEY(owner) || BE(amount) || salt)
+///
+/// `KEY(addr)` is a one-byte type tag (0 = account, 1 = contract) followed by the
+/// 32-byte ed25519 key or contract id, so clients can build the preimage from raw
+/// keys without an XDR encoder. Withdrawals look up v3 before v2 and v1.
+///
+/// # Errors
+/// - [`QuickexError::InvalidAmount`] – amount is negative.
+/// - [`QuickexError::InvalidSalt`] – salt is longer than 1024 bytes.
+/// - [`QuickexError::UnsupportedAddress`] – `token` or `owner` is neither an account nor
+///   a contract.
+pub fn create_fixed_commitment(
+    env: &Env,
+    token: Address,
+    owner: Address,
+    amount: i128,
+    salt: Bytes,
+) -> Result<BytesN<32>, QuickexError> {
+    let mut salt_buf = [0u8; MAX_SALT_LEN];
+    if amount < 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+    let salt = salt_bytes(&salt, &mut salt_buf)?;
+
+    let mut payload = Payload::new(env);
+    quickex_commitment::write_fixed_preimage(
+        &mut payload,
+        &address_key(env, &token)?,
+        &address_key(env, &owner)?,
+        amount,
+        salt,
+    )?;
+    Ok(payload.sha256(env))
+}
+
+/// Hash announced by [`crate::escrow::announce_withdraw`] ahead of a withdrawal:
+/// `SHA256("quickex/announce" || XDR(to) || amount (16-byte BE) || salt || nonce)`.
+///
+/// The random `nonce` keeps the announcement from being linked to the escrow
+/// commitment before the reveal.
+pub fn create_withdraw_announcement(
+    env: &Env,
+    to: Address,
+    amount: i128,
+    salt: Bytes,
+    nonce: BytesN<32>,
+) -> Result<BytesN<32>, QuickexError> {
+    let mut to_buf = [0u8; MAX_ADDRESS_XDR_LEN];
+    let mut salt_buf = [0u8; MAX_SALT_LEN];
+    if amount < 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+    let salt = salt_bytes(&salt, &mut salt_buf)?;
+
+    let mut payload = Payload::new(env);
+    quickex_commitment::write_announcement_preimage(
+        &mut payload,
+        address_xdr(env, &to, &mut to_buf),
+        amount,
+        salt,
+        &nonce.to_array(),
+    )?;
+    Ok(payload.sha256(env))
+}
+
+/// Check the salt of a new deposit: it must not be empty, even when no minimum
+/// length is set, and must meet the admin-set minimum.
+///
+/// # Errors
+/// - [`QuickexError::WeakSalt`] – salt is empty or shorter than the minimum.
+pub fn require_deposit_salt(env: &Env, salt: &Bytes) -> Result<(), QuickexError> {
+    if salt.is_empty() {
+        return Err(QuickexError::WeakSalt);
+    }
+    require_strong_salt(env, salt, env.ledger().timestamp())
+}
+
+/// Check `salt` against the admin-set minimum length for an escrow created at
+/// `created_at`.
+///
+/// Escrows created before the minimum took effect are exempt, so an escrow a client
+/// committed to an empty salt stays claimable unless a minimum was set first.
+///
+/// # Errors
+/// - [`QuickexError::WeakSalt`] – salt is shorter than the minimum.
+pub fn require_strong_salt(env: &Env, salt: &Bytes, created_at: u64) -> Result<(), QuickexError> {
+    match storage::get_salt_policy(env) {
+        Some(policy) if created_at >= policy.since && salt.len() < policy.min_len => {
+            Err(QuickexError::WeakSalt)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Length of salts produced by [`random_salt`].
+pub const RANDOM_SALT_LEN: u32 = 32;
+
+/// A fresh salt drawn from the contract PRNG.
+///
+/// The PRNG is deterministic per transaction and its output is visible to whoever
+/// executes the call, so this only suits clients that explicitly accept
+/// contract-side randomness over generating the salt locally.
+pub fn random_salt(env: &Env) -> Bytes {
+    env.prng().gen_len(RANDOM_SALT_LEN)
+}
+
+/// Domain tag prefixed to escrow nullifier preimages.
+pub const NULLIFIER_TAG: &[u8] = b"quickex/nullifier";
+
+/// Nullifier published when an escrow is withdrawn:
+/// `SHA256(NULLIFIER_TAG || commitment || secret)`.
+///
+/// `secret` is the opening only the claimant knows (the salt, or the blinding factor
+/// of a hidden-amount escrow), so the nullifier cannot be matched to a deposit from
+/// events alone.
+pub fn escrow_nullifier(env: &Env, commitment: &BytesN<32>, secret: &Bytes) -> BytesN<32> {
+    let mut payload = Bytes::from_slice(env, NULLIFIER_TAG);
+    payload.append(&commitment.clone().into());
+    payload.append(secret);
+    env.crypto().sha256(&payload).into()
+}
@@ -7,7 +7,12 @@
 //! golden path regression suite. See `REGRESSION_TESTS.md` and `src/test.rs` module doc.
 
 use crate::{errors::QuickexError, QuickexContract, QuickexContractClient};
-use soroban_sdk::{testutils::Address as _, Address, Bytes, Env};
+use soroban_sdk::{
+    testutils::Address as _,
+    vec,
+    xdr::{AccountId, ContractId, Hash, PublicKey, ScAddress, Uint256},
+    Address, Bytes, Env, TryFromVal,
+};
 
 extern crate std;
 
@@ -356,3 +361,141 @@ fn test_commitment_property_verification_consistency() {
         assert!(!client.verify_amount_commitment(&commitment, &wrong_owner, amount, &salt));
     }
 }
+
+// ============================================================================
+// Scheme v2: token-bound commitments
+// ============================================================================
+
+#[test]
+fn test_token_commitment_binds_token() {
+    let (env, client) = setup();
+    let owner = Address::generate(&env);
+    let token_a = Address::generate(&env);
+    let token_b = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"token_bound");
+
+    let a = client.create_token_commitment(&token_a, &owner, &1_000, &salt);
+    let b = client.create_token_commitment(&token_b, &owner, &1_000, &salt);
+    let v1 = client.create_amount_commitment(&owner, &1_000, &salt);
+
+    assert_eq!(
+        a,
+        client.create_token_commitment(&token_a, &owner, &1_000, &salt)
+    );
+    assert_ne!(a, b);
+    assert_ne!(a, v1);
+    assert_ne!(b, v1);
+}
+
+#[test]
+fn test_token_commitment_rejects_invalid_inputs() {
+    let (env, client) = setup();
+    let owner = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    let salt = Bytes::from_slice(&env, b"salt");
+    let result = client.try_create_token_commitment(&token, &owner, &-1, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidAmount)));
+
+    let long_salt = Bytes::from_slice(&env, &[0u8; 1025]);
+    let result = client.try_create_token_commitment(&token, &owner, &1, &long_salt);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidSalt)));
+}
+
+// ============================================================================
+// Scheme v3: fixed-layout commitments
+// ============================================================================
+
+#[test]
+fn test_fixed_commitment_binds_token_and_differs_from_v2() {
+    let (env, client) = setup();
+    let owner = Address::generate(&env);
+    let token_a = Address::generate(&env);
+    let token_b = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"fixed_layout");
+
+    let a = client.create_fixed_commitment(&token_a, &owner, &1_000, &salt);
+    let b = client.create_fixed_commitment(&token_b, &owner, &1_000, &salt);
+
+    assert_eq!(
+        a,
+        client.create_fixed_commitment(&token_a, &owner, &1_000, &salt)
+    );
+    assert_ne!(a, b);
+    assert_ne!(
+        a,
+        client.create_token_commitment(&token_a, &owner, &1_000, &salt)
+    );
+
+    let result = client.try_create_fixed_commitment(&token_a, &owner, &-1, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidAmount)));
+}
+
+#[test]
+fn test_verify_amount_commitments_batch() {
+    let (env, client) = setup();
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"batch_salt");
+    let commitment = client.create_amount_commitment(&owner, &500, &salt);
+
+    let results = client.verify_amount_commitments(&vec![
+        &env,
+        (commitment.clone(), owner.clone(), 500i128, salt.clone()),
+        (commitment.clone(), other, 500i128, salt.clone()),
+        (commitment.clone(), owner.clone(), 501i128, salt.clone()),
+        (commitment, owner, -1i128, salt),
+    ]);
+    assert_eq!(results, vec![&env, true, false, false, false]);
+
+    assert_eq!(client.verify_amount_commitments(&vec![&env]), vec![&env]);
+}
+
+#[test]
+fn test_shared_crate_matches_contract_commitments() {
+    let (env, client) = setup();
+    let public_key = [7u8; 32];
+    let contract_id = [9u8; 32];
+    let owner = Address::try_from_val(
+        &env,
+        &ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+            public_key,
+        )))),
+    )
+    .unwrap();
+    let token =
+        Address::try_from_val(&env, &ScAddress::Contract(ContractId(Hash(contract_id)))).unwrap();
+    let salt = b"shared_crate_salt";
+
+    let owner_xdr = quickex_commitment::account_address_xdr(&public_key);
+    let token_xdr = quickex_commitment::contract_address_xdr(&contract_id);
+
+    let v1 = quickex_commitment::amount_commitment(&owner_xdr, 750, salt).unwrap();
+    let expected = client.create_amount_commitment(&owner, &750, &Bytes::from_slice(&env, salt));
+    assert_eq!(v1, expected.to_array());
+
+    let v2 = quickex_commitment::token_commitment(&token_xdr, &owner_xdr, 750, salt).unwrap();
+    let expected =
+        client.create_token_commitment(&token, &owner, &750, &Bytes::from_slice(&env, salt));
+    assert_eq!(v2, expected.to_array());
+
+    let owner_key = quickex_commitment::account_address_key(&public_key);
+    let token_key = quickex_commitment::contract_address_key(&contract_id);
+    assert_eq!(
+        quickex_commitment::address_key_from_xdr(&owner_xdr),
+        Some(owner_key)
+    );
+    assert_eq!(
+        quickex_commitment::address_key_from_xdr(&token_xdr),
+        Some(token_key)
+    );
+    let v3 = quickex_commitment::fixed_commitment(&token_key, &owner_key, 750, salt).unwrap();
+    let expected =
+        client.create_fixed_commitment(&token, &owner, &750, &Bytes::from_slice(&env, salt));
+    assert_eq!(v3, expected.to_array());
+
+    assert_eq!(
+        quickex_commitment::amount_commitment(&owner_xdr, -1, salt),
+        Err(quickex_commitment::CommitmentError::NegativeAmount)
+    );
+}
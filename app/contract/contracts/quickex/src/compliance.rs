@@ -0,0 +1,55 @@
+//! # Compliance
+//!
+//! Account blocklist for operators with regulatory obligations. The admin can block
+//! an address so that it can neither deposit nor receive a withdrawal payout; both
+//! fail with [`QuickexError::AccountBlocked`]. Refunds of escrows the address
+//! already owns are not affected, and nothing else about the contract changes.
+
+use soroban_sdk::{Address, Env};
+
+use crate::{admin, errors::QuickexError, events::publish_account_blocklist_updated, storage};
+
+/// Block `account` from depositing and receiving withdrawals (**admin only**).
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+pub fn block_account(env: &Env, caller: Address, account: Address) -> Result<(), QuickexError> {
+    set_blocked(env, caller, account, true)
+}
+
+/// Lift the block on `account` (**admin only**).
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+pub fn unblock_account(env: &Env, caller: Address, account: Address) -> Result<(), QuickexError> {
+    set_blocked(env, caller, account, false)
+}
+
+fn set_blocked(
+    env: &Env,
+    caller: Address,
+    account: Address,
+    blocked: bool,
+) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+
+    storage::set_account_blocked(env, &account, blocked);
+    publish_account_blocklist_updated(env, account, blocked, caller);
+    Ok(())
+}
+
+/// Whether `account` is blocked.
+pub fn is_blocked(env: &Env, account: &Address) -> bool {
+    storage::is_account_blocked(env, account)
+}
+
+/// Fail if `account` is blocked.
+///
+/// # Errors
+/// - [`QuickexError::AccountBlocked`] – `account` is on the blocklist.
+pub fn require_not_blocked(env: &Env, account: &Address) -> Result<(), QuickexError> {
+    if is_blocked(env, account) {
+        return Err(QuickexError::AccountBlocked);
+    }
+    Ok(())
+}
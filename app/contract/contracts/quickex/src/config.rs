@@ -0,0 +1,113 @@
+//! # Deployment Config
+//!
+//! Contract-wide flags and parameters kept in one [`Config`] in instance storage, so
+//! hot paths load them in a single read and integrators fetch them in one call.
+//!
+//! The flags keep their dedicated entrypoints, which check roles and emit events:
+//!
+//! | Field | Set with |
+//! |-------|----------|
+//! | `paused_ops` | `set_paused`, `set_paused_ops` |
+//! | `withdrawal_fee_bps` | `set_withdrawal_fee` |
+//! | `global_cap` | `set_global_cap` |
+//!
+//! The timing parameters used to be compile-time constants; the admin tunes them per
+//! deployment with [`set_config`]. Until a config is stored they default to:
+//!
+//! | Field | Default |
+//! |-------|---------|
+//! | `default_claim_window_secs` | 0 (escrows without a timeout never expire) |
+//! | `max_claim_window_secs` | 0 (no maximum) |
+//! | `reveal_delay_ledgers` | [`REVEAL_DELAY_LEDGERS`] |
+//! | `recovery_inactivity_secs` | [`RECOVERY_INACTIVITY_SECS`] |
+
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    admin::{self, RECOVERY_INACTIVITY_SECS},
+    errors::QuickexError,
+    escrow::REVEAL_DELAY_LEDGERS,
+    events::publish_config_updated,
+    storage,
+    types::Config,
+};
+
+/// Config of a fresh deployment.
+pub fn default_config() -> Config {
+    Config {
+        paused_ops: 0,
+        withdrawal_fee_bps: 0,
+        global_cap: 0,
+        default_claim_window_secs: 0,
+        max_claim_window_secs: 0,
+        reveal_delay_ledgers: REVEAL_DELAY_LEDGERS,
+        recovery_inactivity_secs: RECOVERY_INACTIVITY_SECS,
+    }
+}
+
+/// Current config.
+///
+/// Before the first write (or [`crate::migration::migrate`] to schema 3), the flags
+/// are read from their legacy storage keys.
+pub fn get(env: &Env) -> Config {
+    storage::get_config(env).unwrap_or_else(|| Config {
+        paused_ops: storage::get_legacy_paused_ops(env),
+        withdrawal_fee_bps: storage::get_legacy_withdrawal_fee_bps(env),
+        global_cap: storage::get_legacy_global_cap(env),
+        ..default_config()
+    })
+}
+
+/// Replace the timing parameters (**admin only**).
+///
+/// `config` is usually [`get`] with the timing fields changed: its flags must equal
+/// the current ones, since they are changed through their own entrypoints.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::InvalidConfig`] – a flag differs from its current value, the
+///   default claim window is above the maximum, or `reveal_delay_ledgers` or
+///   `recovery_inactivity_secs` is zero.
+pub fn set_config(env: &Env, caller: Address, config: Config) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+    let current = get(env);
+    let flags_changed = config.paused_ops != current.paused_ops
+        || config.withdrawal_fee_bps != current.withdrawal_fee_bps
+        || config.global_cap != current.global_cap;
+    let window_too_long = config.max_claim_window_secs > 0
+        && config.default_claim_window_secs > config.max_claim_window_secs;
+    if flags_changed
+        || window_too_long
+        || config.reveal_delay_ledgers == 0
+        || config.recovery_inactivity_secs == 0
+    {
+        return Err(QuickexError::InvalidConfig);
+    }
+
+    storage::set_config(env, &config);
+    publish_config_updated(env, config, caller);
+    Ok(())
+}
+
+/// Expiry timestamp for an escrow created now with `timeout_secs` (`0` = never
+/// expires). A zero timeout takes the configured default claim window.
+///
+/// # Errors
+/// - [`QuickexError::InvalidTimeout`] – the timeout is above the maximum claim window.
+pub(crate) fn claim_expiry(env: &Env, timeout_secs: u64) -> Result<u64, QuickexError> {
+    let config = get(env);
+    let timeout_secs = if timeout_secs == 0 {
+        config.default_claim_window_secs
+    } else {
+        timeout_secs
+    };
+    if config.max_claim_window_secs > 0 && timeout_secs > config.max_claim_window_secs {
+        return Err(QuickexError::InvalidTimeout);
+    }
+
+    if timeout_secs > 0 {
+        Ok(env.ledger().timestamp().saturating_add(timeout_secs))
+    } else {
+        Ok(0)
+    }
+}
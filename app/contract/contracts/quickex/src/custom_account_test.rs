@@ -0,0 +1,268 @@
+//! Smart-wallet (custom account) authorization tests.
+//!
+//! Unlike `mock_all_auths`, these tests sign real authorization entries, so every
+//! `require_auth` is resolved by calling the wallet's `__check_auth`.
+
+use crate::{QuickexContract, QuickexContractClient};
+use soroban_sdk::{
+    auth::{Context, CustomAccountInterface},
+    contract, contracterror, contractimpl,
+    crypto::Hash,
+    testutils::{Address as _, Ledger},
+    token,
+    xdr::{
+        InvokeContractArgs, ScVal, SorobanAddressCredentials, SorobanAuthorizationEntry,
+        SorobanAuthorizedFunction, SorobanAuthorizedInvocation, SorobanCredentials,
+    },
+    Address, Bytes, BytesN, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
+};
+
+extern crate std;
+
+const AMOUNT: i128 = 1_000;
+
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PolicyError {
+    NotAllowed = 1,
+}
+
+/// Minimal policy wallet: approves any call into the contracts it was deployed with.
+#[contract]
+pub struct PolicyAccount;
+
+#[contractimpl]
+impl PolicyAccount {
+    pub fn __constructor(env: Env, allowed: Vec<Address>) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "allowed"), &allowed);
+    }
+}
+
+#[contractimpl]
+impl CustomAccountInterface for PolicyAccount {
+    type Signature = ();
+    type Error = PolicyError;
+
+    #[allow(non_snake_case)]
+    fn __check_auth(
+        env: Env,
+        _signature_payload: Hash<32>,
+        _signature: (),
+        auth_contexts: Vec<Context>,
+    ) -> Result<(), PolicyError> {
+        let allowed: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&Symbol::new(&env, "allowed"))
+            .unwrap();
+        for context in auth_contexts.iter() {
+            match context {
+                Context::Contract(call) if allowed.contains(&call.contract) => {}
+                _ => return Err(PolicyError::NotAllowed),
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    client: QuickexContractClient<'a>,
+    token: Address,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickexContract, ());
+    let client = QuickexContractClient::new(&env, &contract_id);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    Setup { env, client, token }
+}
+
+/// Deploy a policy wallet that approves calls into `allowed`.
+fn policy_account(env: &Env, allowed: &[&Address]) -> Address {
+    let mut list = Vec::new(env);
+    for contract in allowed {
+        list.push_back((*contract).clone());
+    }
+    env.register(PolicyAccount, (list,))
+}
+
+fn invocation(
+    env: &Env,
+    contract: &Address,
+    function: &str,
+    args: Vec<Val>,
+    sub_invocations: std::vec::Vec<SorobanAuthorizedInvocation>,
+) -> SorobanAuthorizedInvocation {
+    let args: std::vec::Vec<ScVal> = args
+        .iter()
+        .map(|arg| ScVal::try_from_val(env, &arg).unwrap())
+        .collect();
+    SorobanAuthorizedInvocation {
+        function: SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+            contract_address: contract.into(),
+            function_name: function.try_into().unwrap(),
+            args: args.try_into().unwrap(),
+        }),
+        sub_invocations: sub_invocations.try_into().unwrap(),
+    }
+}
+
+/// Replace the environment's auths with one entry signed by `account`.
+fn authorize(env: &Env, account: &Address, nonce: i64, root: SorobanAuthorizedInvocation) {
+    env.set_auths(&[SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+            address: account.into(),
+            nonce,
+            signature_expiration_ledger: env.ledger().sequence() + 100,
+            signature: ScVal::Void,
+        }),
+        root_invocation: root,
+    }]);
+}
+
+/// Deposit `AMOUNT` from `wallet` as its own escrow, authorized by the wallet.
+fn deposit_from_wallet(s: &Setup, wallet: &Address, timeout_secs: u64) -> (BytesN<32>, Bytes) {
+    token::StellarAssetClient::new(&s.env, &s.token).mint(wallet, &AMOUNT);
+    let salt = Bytes::from_slice(&s.env, b"smart_wallet_salt");
+    let transfer = invocation(
+        &s.env,
+        &s.token,
+        "transfer",
+        (wallet.clone(), s.client.address.clone(), AMOUNT).into_val(&s.env),
+        std::vec![],
+    );
+    authorize(
+        &s.env,
+        wallet,
+        1,
+        invocation(
+            &s.env,
+            &s.client.address,
+            "deposit",
+            (
+                s.token.clone(),
+                AMOUNT,
+                wallet.clone(),
+                salt.clone(),
+                timeout_secs,
+            )
+                .into_val(&s.env),
+            std::vec![transfer],
+        ),
+    );
+    let commitment = s
+        .client
+        .deposit(&s.token, &AMOUNT, wallet, &salt, &timeout_secs);
+    (commitment, salt)
+}
+
+#[test]
+fn test_smart_wallet_deposits_and_withdraws() {
+    let s = setup();
+    let wallet = policy_account(&s.env, &[&s.client.address, &s.token]);
+    let (commitment, salt) = deposit_from_wallet(&s, &wallet, 0);
+
+    authorize(
+        &s.env,
+        &wallet,
+        2,
+        invocation(
+            &s.env,
+            &s.client.address,
+            "withdraw",
+            (
+                s.token.clone(),
+                AMOUNT,
+                commitment.clone(),
+                wallet.clone(),
+                salt.clone(),
+            )
+                .into_val(&s.env),
+            std::vec![],
+        ),
+    );
+    s.client
+        .withdraw(&s.token, &AMOUNT, &commitment, &wallet, &salt);
+    assert_eq!(
+        token::Client::new(&s.env, &s.token).balance(&wallet),
+        AMOUNT
+    );
+}
+
+#[test]
+fn test_smart_wallet_refunds_expired_escrow() {
+    let s = setup();
+    let wallet = policy_account(&s.env, &[&s.client.address, &s.token]);
+    let (commitment, _) = deposit_from_wallet(&s, &wallet, 60);
+    s.env.ledger().with_mut(|l| l.timestamp += 61);
+
+    authorize(
+        &s.env,
+        &wallet,
+        2,
+        invocation(
+            &s.env,
+            &s.client.address,
+            "refund",
+            (commitment.clone(), wallet.clone()).into_val(&s.env),
+            std::vec![],
+        ),
+    );
+    s.client.refund(&commitment, &wallet);
+    assert_eq!(
+        token::Client::new(&s.env, &s.token).balance(&wallet),
+        AMOUNT
+    );
+}
+
+#[test]
+fn test_smart_wallet_as_admin() {
+    let s = setup();
+    let wallet = policy_account(&s.env, &[&s.client.address]);
+    s.client.initialize(&wallet);
+
+    authorize(
+        &s.env,
+        &wallet,
+        1,
+        invocation(
+            &s.env,
+            &s.client.address,
+            "set_paused",
+            (wallet.clone(), true).into_val(&s.env),
+            std::vec![],
+        ),
+    );
+    s.client.set_paused(&wallet, &true);
+    assert!(s.client.is_paused());
+}
+
+#[test]
+fn test_smart_wallet_policy_can_refuse() {
+    let s = setup();
+    let other = Address::generate(&s.env);
+    let wallet = policy_account(&s.env, &[&other]);
+    s.client.initialize(&wallet);
+
+    authorize(
+        &s.env,
+        &wallet,
+        1,
+        invocation(
+            &s.env,
+            &s.client.address,
+            "set_paused",
+            (wallet.clone(), true).into_val(&s.env),
+            std::vec![],
+        ),
+    );
+    assert!(s.client.try_set_paused(&wallet, &true).is_err());
+    assert!(!s.client.is_paused());
+}
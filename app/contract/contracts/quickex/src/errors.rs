@@ -1,37 +1,297 @@
-use soroban_sdk::contracterror;
+use soroban_sdk::{contracterror, Error};
 
-/// Canonical contract error codes.
+use crate::types::{ErrorCategory, ErrorInfo};
+
+/// Declares [`QuickexError`] band by band, plus one exported spec enum per band.
 ///
-/// Code bands:
-/// - 100-199: validation failures
-/// - 200-299: auth/admin failures
-/// - 300-399: state, escrow, and commitment violations
-/// - 900-999: internal/unexpected conditions
-#[contracterror]
-#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
-#[repr(u32)]
-pub enum QuickexError {
-    // Validation failures (100-199)
-    InvalidAmount = 100,
-    InvalidSalt = 101,
-    InvalidPrivacyLevel = 102,
-    // Auth/admin failures (200-299)
-    Unauthorized = 200,
-    AlreadyInitialized = 201,
-    // State, escrow, and commitment violations (300-399)
-    ContractPaused = 300,
-    PrivacyAlreadySet = 301,
-    CommitmentNotFound = 302,
-    CommitmentAlreadyExists = 303,
-    AlreadySpent = 304,
-    InvalidCommitment = 305,
-    CommitmentMismatch = 306,
-    /// Escrow has passed its expiry; withdrawal is no longer possible.
-    EscrowExpired = 307,
-    /// Escrow has not yet expired; refund is not yet available.
-    EscrowNotExpired = 308,
-    /// Caller is not the original owner of the escrow.
-    InvalidOwner = 309,
-    // Internal/unexpected conditions (900-999)
-    InternalError = 900,
+/// The contract spec holds at most 50 cases per error enum, fewer than the full set, so
+/// `QuickexError` itself is not exported. Clients decode a code through its band's enum
+/// or [`error_info`]. Keep every band at 50 codes or fewer; open a new one past that.
+macro_rules! quickex_errors {
+    ($(
+        $(#[$band_meta:meta])*
+        $band:ident {
+            $($(#[$meta:meta])* $name:ident = $code:tt,)*
+        }
+    )*) => {
+        /// Canonical contract error codes.
+        ///
+        /// Code bands:
+        /// - 100-199: validation failures
+        /// - 200-299: auth/admin failures
+        /// - 300-399: state, escrow, and commitment violations
+        /// - 900-999: internal/unexpected conditions
+        #[contracterror(export = false)]
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+        #[repr(u32)]
+        pub enum QuickexError {
+            $($($(#[$meta])* $name = $code,)*)*
+        }
+
+        $(
+            $(#[$band_meta])*
+            #[contracterror]
+            #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+            #[repr(u32)]
+            pub enum $band {
+                $($(#[$meta])* $name = $code,)*
+            }
+        )*
+    };
+}
+
+quickex_errors! {
+    /// Validation failures (100-199).
+    QuickexValidationError {
+        InvalidAmount = 100,
+        InvalidSalt = 101,
+        InvalidPrivacyLevel = 102,
+        /// Supplied curve point is malformed or outside the prime-order subgroup.
+        InvalidPoint = 103,
+        /// Amount is not a registered pool denomination for the token.
+        InvalidDenomination = 104,
+        /// Verifying key has the wrong shape or contains invalid curve points.
+        InvalidVerifyingKey = 105,
+        /// Fee is negative, exceeds the withdrawn amount, or the fee rate is above the maximum.
+        InvalidFee = 106,
+        /// Relayer configuration is invalid or tries to change the stake token.
+        InvalidRelayerConfig = 107,
+        /// Ring has an unsupported size or contains a non-member of the pool.
+        InvalidRing = 108,
+        /// Memo is empty or exceeds the maximum length.
+        InvalidMemo = 109,
+        /// Value is not a canonical element of the requested field.
+        InvalidFieldElement = 110,
+        /// Withdrawal rate limit has a zero-length window.
+        InvalidRateLimit = 111,
+        /// Salt is shorter than the configured minimum length.
+        WeakSalt = 112,
+        /// Deposit amount is not in the token's approved amount buckets.
+        UnapprovedAmount = 113,
+        /// Council threshold is zero or above the member count, or a member is listed twice.
+        InvalidThreshold = 114,
+        /// Pause bitmask is empty or contains unknown operation bits.
+        InvalidPauseOps = 115,
+        /// Scheduled execution time is earlier than the timelock delay allows.
+        InvalidExecuteAfter = 116,
+        /// The token is not on the enabled allowlist, or has no value under the global cap.
+        TokenNotAllowed = 117,
+        /// The maximum number of valued tokens has been reached.
+        TooManyTokens = 118,
+        /// Config has a default claim window above the maximum, or a zero delay.
+        InvalidConfig = 119,
+        /// Escrow timeout is above the configured maximum claim window.
+        InvalidTimeout = 120,
+        /// Admin batch is empty or has too many actions.
+        InvalidBatch = 121,
+        /// The entrypoint name is not one the kill switch can disable.
+        UnknownFunction = 122,
+        /// TTL extension is zero or above the network's maximum entry TTL.
+        InvalidTtl = 123,
+        /// Address is neither an account nor a contract, so it has no fixed-layout key.
+        UnsupportedAddress = 124,
+        /// Escrow sender and recipient are the same address.
+        SameParticipants = 125,
+        /// Address is not a working token contract (its balance query failed).
+        InvalidToken = 126,
+    }
+
+    /// Auth/admin failures (200-299).
+    QuickexAuthError {
+        Unauthorized = 200,
+        AlreadyInitialized = 201,
+    }
+
+    /// State, escrow, and commitment violations (300-399).
+    QuickexStateError {
+        ContractPaused = 300,
+        PrivacyAlreadySet = 301,
+        CommitmentNotFound = 302,
+        CommitmentAlreadyExists = 303,
+        AlreadySpent = 304,
+        /// No longer raised; superseded by `AmountMismatch`. The code stays reserved.
+        InvalidCommitment = 305,
+        CommitmentMismatch = 306,
+        /// Escrow has passed its expiry; withdrawal is no longer possible.
+        EscrowExpired = 307,
+        /// Escrow has not yet expired; refund is not yet available.
+        EscrowNotExpired = 308,
+        /// Caller is not the original owner of the escrow.
+        InvalidOwner = 309,
+        /// Zero-knowledge proof failed verification or is malformed.
+        InvalidProof = 310,
+        /// Proof targets a Merkle root that is not a recent root of the pool.
+        InvalidRoot = 311,
+        /// No verifying key has been registered for the proof system.
+        VerifyingKeyNotSet = 312,
+        /// Pool Merkle tree has no free leaves left.
+        MerkleTreeFull = 313,
+        /// Relayer is not registered, unbonding, or below the minimum stake.
+        RelayerNotActive = 314,
+        /// Address has no relayer stake record.
+        RelayerNotRegistered = 315,
+        /// Stake is still bonded or unbonding.
+        StakeLocked = 316,
+        /// Relayer staking has not been configured by the admin.
+        RelayerConfigNotSet = 317,
+        /// Ring signature failed verification.
+        InvalidSignature = 318,
+        /// No withdrawal announcement matches the revealed values.
+        AnnouncementNotFound = 319,
+        /// The reveal delay since the announcement has not elapsed yet.
+        RevealTooEarly = 320,
+        /// This withdrawal hash has already been announced.
+        AlreadyAnnounced = 321,
+        /// The escrow holds a different token than the one requested.
+        TokenMismatch = 322,
+        /// The account has used up its withdrawals for the current rate-limit window.
+        WithdrawRateLimited = 323,
+        /// No pending council action has this id.
+        ActionNotFound = 324,
+        /// The council member has already approved this action.
+        ActionAlreadyApproved = 325,
+        /// A timelock delay is set, so the action must go through `queue_action`.
+        TimelockRequired = 326,
+        /// The queued action's timelock delay has not elapsed yet.
+        TimelockNotReady = 327,
+        /// The operation is only allowed while the contract is paused.
+        ContractNotPaused = 328,
+        /// Amount exceeds the balance not owed to escrows, pool notes, or relayer stakes.
+        ExceedsSurplus = 329,
+        /// The contract was upgraded and `migrate` has not run yet.
+        MigrationPending = 330,
+        /// No upgrade is pending and storage is already at the current schema version.
+        NothingToMigrate = 331,
+        /// The pending upgrade targets a different code version than the running code.
+        UpgradeVersionMismatch = 332,
+        /// Storage was written by a newer schema than this code understands.
+        SchemaDowngrade = 333,
+        /// The admin was active within the recovery inactivity period.
+        AdminStillActive = 334,
+        /// The token is blocked (for withdrawals, only when frozen).
+        TokenBlocked = 335,
+        /// The deposit would exceed the token's deposit cap.
+        CapExceeded = 336,
+        /// The token's withdrawal volume limit for the current window is used up.
+        RateLimited = 337,
+        /// The account is on the compliance blocklist.
+        AccountBlocked = 338,
+        /// Amount exceeds the protocol fees accrued in the treasury.
+        InsufficientFees = 339,
+        /// The entrypoint has been disabled by the kill switch.
+        FunctionDisabled = 340,
+        /// The escrow is reserved by a prepared two-phase withdrawal.
+        EscrowReserved = 341,
+        /// No live withdrawal ticket exists for the id (never prepared, settled, or expired).
+        TicketNotFound = 342,
+        /// The token contract failed to transfer a payout.
+        TransferFailed = 343,
+        /// The requested amount differs from the amount held in the escrow.
+        AmountMismatch = 344,
+    }
+
+    /// Internal/unexpected conditions (900-999).
+    QuickexInternalError {
+        InternalError = 900,
+        /// An amount or running total does not fit its integer type.
+        ArithmeticOverflow = 901,
+        /// A payout would take the token's locked total below zero, so it predates
+        /// locked totals and needs `backfill_total_locked`.
+        LockedTotalUnderflow = 902,
+    }
+}
+
+impl QuickexError {
+    /// The error with code `code`, if this contract version defines one.
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::try_from(Error::from_contract_error(code)).ok()
+    }
+
+    /// What kind of problem the error reports. Mostly follows the code bands, with
+    /// caps and rate limits split out as [`ErrorCategory::Limits`].
+    pub fn category(self) -> ErrorCategory {
+        use QuickexError::*;
+        match self {
+            Unauthorized | InvalidOwner | InvalidSignature | AdminStillActive | AccountBlocked => {
+                ErrorCategory::Auth
+            }
+            TooManyTokens | MerkleTreeFull | WithdrawRateLimited | CapExceeded | RateLimited
+            | ExceedsSurplus | InsufficientFees => ErrorCategory::Limits,
+            InvalidAmount | InvalidSalt | InvalidPrivacyLevel | InvalidPoint
+            | InvalidDenomination | InvalidVerifyingKey | InvalidFee | InvalidRelayerConfig
+            | InvalidRing | InvalidMemo | InvalidFieldElement | InvalidRateLimit | WeakSalt
+            | UnapprovedAmount | InvalidThreshold | InvalidPauseOps | InvalidExecuteAfter
+            | TokenNotAllowed | InvalidConfig | InvalidTimeout | InvalidBatch | UnknownFunction
+            | InvalidTtl | UnsupportedAddress | SameParticipants | InvalidToken
+            | InvalidCommitment | CommitmentMismatch | AmountMismatch | InvalidProof
+            | TokenMismatch => ErrorCategory::Validation,
+            AlreadyInitialized
+            | ContractPaused
+            | PrivacyAlreadySet
+            | CommitmentNotFound
+            | CommitmentAlreadyExists
+            | AlreadySpent
+            | EscrowExpired
+            | EscrowNotExpired
+            | InvalidRoot
+            | VerifyingKeyNotSet
+            | RelayerNotActive
+            | RelayerNotRegistered
+            | StakeLocked
+            | RelayerConfigNotSet
+            | AnnouncementNotFound
+            | RevealTooEarly
+            | AlreadyAnnounced
+            | ActionNotFound
+            | ActionAlreadyApproved
+            | TimelockRequired
+            | TimelockNotReady
+            | ContractNotPaused
+            | MigrationPending
+            | NothingToMigrate
+            | UpgradeVersionMismatch
+            | SchemaDowngrade
+            | TokenBlocked
+            | FunctionDisabled
+            | EscrowReserved
+            | TicketNotFound
+            | TransferFailed => ErrorCategory::State,
+            InternalError | ArithmeticOverflow | LockedTotalUnderflow => ErrorCategory::Internal,
+        }
+    }
+
+    /// Whether the same call may succeed later without changing its arguments, once
+    /// time passes or an operator acts (unpause, migrate, raise a cap, fund the payer,
+    /// backfill a locked total).
+    pub fn is_retryable(self) -> bool {
+        use QuickexError::*;
+        matches!(
+            self,
+            ContractPaused
+                | MigrationPending
+                | FunctionDisabled
+                | TokenBlocked
+                | EscrowNotExpired
+                | EscrowReserved
+                | RevealTooEarly
+                | TimelockNotReady
+                | StakeLocked
+                | WithdrawRateLimited
+                | RateLimited
+                | CapExceeded
+                | TransferFailed
+                | LockedTotalUnderflow
+        )
+    }
+}
+
+/// Machine-readable metadata for error `code`, or `None` if the code is unknown to
+/// this contract version.
+pub fn error_info(code: u32) -> Option<ErrorInfo> {
+    let error = QuickexError::from_code(code)?;
+    Some(ErrorInfo {
+        code,
+        category: error.category(),
+        retryable: error.is_retryable(),
+    })
 }
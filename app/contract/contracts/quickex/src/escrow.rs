@@ -1,9 +1,12 @@
 //! Escrow core logic: deposit, withdraw, and refund.
 //!
+//! Hidden-amount escrows (see [`crate::pedersen`]) follow the same state machine via
+//! `deposit_hidden`, `withdraw_hidden`, and `refund_hidden`.
+//!
 //! # State Machine
 //!
 //! ```text
-//! [*] --> Pending  : deposit() / deposit_with_commitment()
+//! [*] --> Pending  : deposit() / deposit_with_commitment() / deposit_ephemeral()
 //! Pending --> Spent    : withdraw(proof)  [current_time < expires_at OR no expiry]
 //! Pending --> Refunded : refund(owner)    [current_time >= expires_at]
 //! ```
@@ -14,14 +17,25 @@
 //! - Both fail with [`AlreadySpent`] if status is not `Pending`.
 //! - `refund` fails with [`InvalidOwner`] if caller ≠ `entry.owner`.
 
-use soroban_sdk::{token, Address, Bytes, BytesN, Env};
+use soroban_sdk::{
+    xdr::{FromXdr, ToXdr},
+    Address, Bytes, BytesN, Env, MuxedAddress, Vec,
+};
 
 use crate::{
-    commitment,
+    commitment, compliance, config,
     errors::QuickexError,
-    events,
-    storage::{get_escrow, has_escrow, put_escrow},
-    types::{EscrowEntry, EscrowStatus},
+    events, fees, pedersen, pool, privacy, relayer, stats,
+    storage::{
+        self, get_hidden_escrow, has_escrow, has_hidden_escrow, put_escrow, put_hidden_escrow,
+        put_memo,
+    },
+    store::EscrowStore,
+    tokens, treasury,
+    types::{
+        BlindingProof, EscrowEntry, EscrowStatus, HiddenEscrowEntry, WithdrawReceipt,
+        WithdrawTicket,
+    },
 };
 
 // ---------------------------------------------------------------------------
@@ -31,8 +45,13 @@ use crate::{
 /// Returns `true` when an escrow has expired according to the ledger clock.
 ///
 /// An escrow with `expires_at == 0` never expires.
-fn is_expired(env: &Env, entry: &EscrowEntry) -> bool {
-    entry.expires_at > 0 && env.ledger().timestamp() >= entry.expires_at
+fn is_expired<S: EscrowStore>(store: &S, entry: &EscrowEntry) -> bool {
+    is_past_expiry(store, entry.expires_at)
+}
+
+/// Returns `true` when `expires_at` is set and has been reached.
+fn is_past_expiry<S: EscrowStore>(store: &S, expires_at: u64) -> bool {
+    expires_at > 0 && store.now() >= expires_at
 }
 
 // ---------------------------------------------------------------------------
@@ -44,11 +63,17 @@ fn is_expired(env: &Env, entry: &EscrowEntry) -> bool {
 /// - Transfers `amount` from `owner` to the contract.
 /// - Sets status to `Pending`.
 /// - If `timeout_secs > 0`, the escrow expires `timeout_secs` seconds after creation.
-///   Pass `0` for a non-expiring escrow.
+///   Pass `0` for the configured default claim window (non-expiring unless set).
 ///
 /// # Errors
 /// - [`InvalidAmount`] – amount ≤ 0.
+/// - [`UnapprovedAmount`] – amount is not an approved bucket for `token`.
 /// - [`InvalidSalt`] – salt > 1024 bytes.
+/// - [`WeakSalt`] – salt is shorter than the admin-set minimum.
+/// - [`InvalidTimeout`] – `timeout_secs` is above the configured maximum claim window.
+/// - [`CommitmentAlreadyExists`] – an escrow for the same owner, amount and salt is on
+///   record, pending or closed.
+/// - [`AccountBlocked`] – `owner` is on the compliance blocklist.
 pub fn deposit(
     env: &Env,
     token: Address,
@@ -60,16 +85,19 @@ pub fn deposit(
     if amount <= 0 {
         return Err(QuickexError::InvalidAmount);
     }
+    tokens::require_deposit_allowed(env, &token, amount)?;
+    compliance::require_not_blocked(env, &owner)?;
+    pool::require_bucketed(env, &token, amount)?;
 
     owner.require_auth();
 
-    let commitment = commitment::create_amount_commitment(env, owner.clone(), amount, salt)?;
     let now = env.ledger().timestamp();
-    let expires_at = if timeout_secs > 0 {
-        now.saturating_add(timeout_secs)
-    } else {
-        0
-    };
+    commitment::require_deposit_salt(env, &salt)?;
+    let commitment = commitment::create_amount_commitment(env, owner.clone(), amount, salt)?;
+    if has_escrow(env, &commitment.clone().into()) {
+        return Err(QuickexError::CommitmentAlreadyExists);
+    }
+    let expires_at = config::claim_expiry(env, timeout_secs)?;
 
     let entry = EscrowEntry {
         token: token.clone(),
@@ -81,11 +109,22 @@ pub fn deposit(
     };
 
     put_escrow(env, &commitment.clone().into(), &entry);
+    storage::adjust_total_locked(env, &token, amount)?;
+    stats::record_deposit(env, &token, amount);
+    storage::bump_instance_ttl(env);
+    privacy::index_commitment(env, &owner, &commitment);
 
-    let token_client = token::Client::new(env, &token);
-    token_client.transfer(&owner, env.current_contract_address(), &amount);
+    tokens::collect(env, &token, &owner, amount)?;
 
-    events::publish_escrow_deposited(env, commitment.clone(), owner, token, amount, expires_at);
+    events::publish_escrow_deposited(
+        env,
+        commitment.clone(),
+        owner,
+        token,
+        amount,
+        expires_at,
+        None,
+    );
 
     Ok(commitment)
 }
@@ -101,18 +140,68 @@ pub fn deposit(
 ///
 /// # Errors
 /// - [`InvalidAmount`] – amount ≤ 0.
+/// - [`UnapprovedAmount`] – amount is not an approved bucket for `token`.
 /// - [`CommitmentAlreadyExists`] – commitment already in storage.
 pub fn deposit_with_commitment(
     env: &Env,
-    from: Address,
+    from: MuxedAddress,
+    token: Address,
+    amount: i128,
+    commitment: BytesN<32>,
+    timeout_secs: u64,
+) -> Result<(), QuickexError> {
+    deposit_committed(env, from, token, amount, commitment, timeout_secs, false)
+}
+
+/// Longest claim window of an ephemeral escrow, in seconds.
+pub const MAX_EPHEMERAL_TIMEOUT_SECS: u64 = 3_600;
+
+/// Deposit using a pre-generated commitment into an ephemeral escrow.
+///
+/// Same as [`deposit_with_commitment`], but the full entry lives in temporary storage
+/// for the claim window plus [`storage::EPHEMERAL_REFUND_LEDGERS`], and the escrow is
+/// not indexed for [`list_claimable`](crate::privacy::list_claimable). A persistent
+/// claim is kept until the escrow is withdrawn or refunded, so funds left after the
+/// entry expires stay locked for, and refundable by, the depositor, and the commitment
+/// can never be funded twice.
+///
+/// # Errors
+/// - [`InvalidTimeout`] – `timeout_secs` is 0 or above [`MAX_EPHEMERAL_TIMEOUT_SECS`].
+/// - Any error from [`deposit_with_commitment`].
+pub fn deposit_ephemeral(
+    env: &Env,
+    from: MuxedAddress,
     token: Address,
     amount: i128,
     commitment: BytesN<32>,
     timeout_secs: u64,
 ) -> Result<(), QuickexError> {
+    deposit_committed(env, from, token, amount, commitment, timeout_secs, true)
+}
+
+/// Ledgers an ephemeral escrow must live: its claim window at 5s ledgers plus the
+/// refund period.
+fn ephemeral_ttl(timeout_secs: u64) -> u32 {
+    timeout_secs.div_ceil(5) as u32 + storage::EPHEMERAL_REFUND_LEDGERS
+}
+
+fn deposit_committed(
+    env: &Env,
+    from: MuxedAddress,
+    token: Address,
+    amount: i128,
+    commitment: BytesN<32>,
+    timeout_secs: u64,
+    ephemeral: bool,
+) -> Result<(), QuickexError> {
+    let mux_id = from.id();
+    let from = from.address();
     if amount <= 0 {
         return Err(QuickexError::InvalidAmount);
     }
+    tokens::require_deposit_allowed(env, &token, amount)?;
+    compliance::require_not_blocked(env, &from)?;
+    pool::require_bucketed(env, &token, amount)?;
 
     from.require_auth();
 
@@ -120,16 +209,18 @@ pub fn deposit_with_commitment(
         return Err(QuickexError::CommitmentAlreadyExists);
     }
 
-    let token_client = token::Client::new(env, &token);
-    token_client.transfer(&from, env.current_contract_address(), &amount);
-
     let now = env.ledger().timestamp();
-    let expires_at = if timeout_secs > 0 {
-        now.saturating_add(timeout_secs)
+    let expires_at = if ephemeral {
+        if timeout_secs == 0 || timeout_secs > MAX_EPHEMERAL_TIMEOUT_SECS {
+            return Err(QuickexError::InvalidTimeout);
+        }
+        now + timeout_secs
     } else {
-        0
+        config::claim_expiry(env, timeout_secs)?
     };
 
+    tokens::collect(env, &token, &from, amount)?;
+
     let entry = EscrowEntry {
         token: token.clone(),
         amount,
@@ -139,12 +230,60 @@ pub fn deposit_with_commitment(
         expires_at,
     };
 
-    put_escrow(env, &commitment.clone().into(), &entry);
-    events::publish_escrow_deposited(env, commitment, from, token, amount, expires_at);
+    if ephemeral {
+        let live_ledgers = ephemeral_ttl(timeout_secs);
+        storage::put_ephemeral_escrow(env, &commitment.clone().into(), &entry, live_ledgers);
+    } else {
+        put_escrow(env, &commitment.clone().into(), &entry);
+        privacy::index_commitment(env, &from, &commitment);
+    }
+    storage::adjust_total_locked(env, &token, amount)?;
+    stats::record_deposit(env, &token, amount);
+    storage::bump_instance_ttl(env);
+    events::publish_escrow_deposited(env, commitment, from, token, amount, expires_at, mux_id);
+
+    Ok(())
+}
+
+/// Maximum length of an encrypted recipient memo, in bytes.
+pub const MAX_MEMO_LEN: u32 = 1024;
+
+/// Deposit with a commitment and attach an encrypted memo for the recipient.
+///
+/// The memo is opaque to the contract: typically an ECIES/AEAD ciphertext of the note
+/// opening (amount, salt) under the recipient's public key. It is stored under the
+/// commitment and published in an `EscrowMemo` event so wallets can trial-decrypt
+/// memos to discover incoming escrows.
+///
+/// # Errors
+/// - [`InvalidMemo`] – memo is empty or longer than [`MAX_MEMO_LEN`].
+/// - Any error from [`deposit_with_commitment`].
+pub fn deposit_with_memo(
+    env: &Env,
+    from: MuxedAddress,
+    token: Address,
+    amount: i128,
+    commitment: BytesN<32>,
+    timeout_secs: u64,
+    memo: Bytes,
+) -> Result<(), QuickexError> {
+    if memo.is_empty() || memo.len() > MAX_MEMO_LEN {
+        return Err(QuickexError::InvalidMemo);
+    }
+
+    deposit_with_commitment(env, from, token, amount, commitment.clone(), timeout_secs)?;
+
+    put_memo(env, &commitment, &memo);
+    events::publish_escrow_memo(env, commitment, memo);
 
     Ok(())
 }
 
+/// Encrypted memo attached to `commitment`, if any.
+pub fn get_memo(env: &Env, commitment: &BytesN<32>) -> Option<Bytes> {
+    storage::get_memo(env, commitment)
+}
+
 // ---------------------------------------------------------------------------
 // withdraw
 // ---------------------------------------------------------------------------
@@ -152,50 +291,562 @@ pub fn deposit_with_commitment(
 /// Withdraw escrowed funds by proving commitment ownership.
 ///
 /// The caller (`to`) must authorize. The commitment is recomputed from
-/// `to`, `amount`, and `salt` and must match an existing pending escrow.
+/// `token`, `to`, `amount`, and `salt` (scheme v3, then v2, falling back to v1), must
+/// equal `expected` when one is given, and must match an existing pending escrow of
+/// `token`. `to` receives `amount` minus the protocol fee (see [`fees`]). If `to` is
+/// a muxed account, the payout goes to it and its id is reported in `EscrowWithdrawn`.
 ///
 /// # Errors
 /// - [`InvalidAmount`] – amount ≤ 0.
+/// - [`CommitmentMismatch`] – the recomputed commitment is not `expected`.
 /// - [`CommitmentNotFound`] – no escrow for computed commitment.
 /// - [`EscrowExpired`] – escrow has passed its expiry.
 /// - [`AlreadySpent`] – escrow already spent or refunded.
-/// - [`InvalidCommitment`] – stored amount ≠ requested amount.
-pub fn withdraw(env: &Env, amount: i128, to: Address, salt: Bytes) -> Result<bool, QuickexError> {
+/// - [`AmountMismatch`] – stored amount ≠ requested amount.
+/// - [`TokenMismatch`] – escrow holds a different token than `token`.
+/// - [`WeakSalt`] – salt is shorter than the minimum in force when the escrow was created.
+/// - [`WithdrawRateLimited`] – `to` has used up its withdrawals for the current window.
+/// - [`TokenBlocked`] – `token` is blocked with withdrawals frozen.
+/// - [`AccountBlocked`] – `to` is on the compliance blocklist.
+pub fn withdraw(
+    env: &Env,
+    token: Address,
+    amount: i128,
+    expected: Option<BytesN<32>>,
+    to: MuxedAddress,
+    salt: Bytes,
+) -> Result<WithdrawReceipt, QuickexError> {
+    let owner = to.address();
+    let (commitment, entry) = spend(env, &token, amount, expected, &owner, salt)?;
+
+    let fee = fees::pay_out(env, &entry.token, &to, amount)?;
+
+    #[cfg(feature = "privacy-pool")]
+    pool::record_spend(env, &commitment, &entry);
+
+    let receipt = record_receipt(env, &commitment, &entry, fee);
+    events::publish_escrow_withdrawn(env, commitment, owner, entry.token, amount, to.id());
+
+    Ok(receipt)
+}
+
+/// Build the receipt of withdrawing `entry` with `fee` deducted, and store it unless
+/// the owner's privacy level hides amounts (as in the `EscrowWithdrawn` event).
+fn record_receipt(
+    env: &Env,
+    commitment: &BytesN<32>,
+    entry: &EscrowEntry,
+    fee: i128,
+) -> WithdrawReceipt {
+    let receipt = WithdrawReceipt {
+        commitment: commitment.clone(),
+        token: entry.token.clone(),
+        gross: entry.amount,
+        fee,
+        net: entry.amount - fee,
+        ledger: env.ledger().sequence(),
+    };
+    if privacy::effective_level(env, &entry.owner) < privacy::LEVEL_HIDE_AMOUNT {
+        storage::put_receipt(env, &receipt);
+    }
+    receipt
+}
+
+/// Withdraw escrowed funds through a relayer that is paid `fee` out of the escrow.
+///
+/// `to` must authorize (which covers `relayer` and `fee`) but need not submit the
+/// transaction or hold XLM: the relayer submits it and pays the network fee. `to`
+/// receives `amount - fee` minus the protocol fee. The relayer must be active in the
+/// [`relayer`] registry and authorize, so it is accountable for the submission.
+///
+/// # Errors
+/// - [`InvalidFee`] – fee < 0 or fee exceeds what is left after the protocol fee.
+/// - [`RelayerNotActive`] – relayer is not staked and active.
+/// - Any error from [`withdraw`].
+pub fn withdraw_via_relayer(
+    env: &Env,
+    relayer: Address,
+    token: Address,
+    to: Address,
+    amount: i128,
+    salt: Bytes,
+    fee: i128,
+) -> Result<bool, QuickexError> {
+    let protocol_fee = fees::withdrawal_fee(env, &to, amount);
+    if fee < 0 || fee > amount - protocol_fee {
+        return Err(QuickexError::InvalidFee);
+    }
+
+    relayer::require_active(env, &relayer)?;
+    relayer.require_auth();
+
+    let (commitment, entry) = spend(env, &token, amount, None, &to, salt)?;
+
+    let net = amount - fee - protocol_fee;
+    if net > 0 {
+        tokens::pay(env, &entry.token, &to, net)?;
+    }
+    if fee > 0 {
+        tokens::pay(env, &entry.token, &relayer, fee)?;
+    }
+    treasury::credit(env, &entry.token, protocol_fee)?;
+    if protocol_fee > 0 {
+        events::publish_fee_collected(
+            env,
+            entry.token.clone(),
+            to.clone(),
+            amount,
+            protocol_fee,
+            net,
+        );
+    }
+
+    #[cfg(feature = "privacy-pool")]
+    pool::record_spend(env, &commitment, &entry);
+
+    record_receipt(env, &commitment, &entry, fee + protocol_fee);
+    events::publish_escrow_withdrawn(
+        env,
+        commitment.clone(),
+        to,
+        entry.token.clone(),
+        amount,
+        None,
+    );
+    events::publish_relayer_fee_paid(env, commitment, relayer, entry.token, fee);
+
+    Ok(true)
+}
+
+/// Default ledgers that must close between [`announce_withdraw`] and
+/// [`finalize_withdraw`] (see [`crate::config`]).
+pub const REVEAL_DELAY_LEDGERS: u32 = 1;
+
+/// Pre-register a withdrawal by publishing only its hash.
+///
+/// `announcement` is [`commitment::create_withdraw_announcement`]`(to, amount, salt, nonce)`.
+/// It reveals nothing about the escrow, so an observer cannot use it to claim first.
+/// Anyone may announce; the hash binds `to`, who must still authorize the finalize.
+///
+/// # Errors
+/// - [`AlreadyAnnounced`] – this hash is already pending.
+pub fn announce_withdraw(env: &Env, announcement: BytesN<32>) -> Result<u32, QuickexError> {
+    if storage::get_withdraw_announcement(env, &announcement).is_some() {
+        return Err(QuickexError::AlreadyAnnounced);
+    }
+
+    let ledger = env.ledger().sequence();
+    storage::put_withdraw_announcement(env, &announcement, ledger);
+    events::publish_withdraw_announced(env, announcement, ledger);
+
+    Ok(ledger)
+}
+
+/// Complete an announced withdrawal at least the configured reveal delay (by default
+/// [`REVEAL_DELAY_LEDGERS`]) ledgers later.
+///
+/// The salt is revealed only here, after the claim for `to` has been fixed on-chain,
+/// so a mempool observer replaying it has no earlier announcement of their own.
+///
+/// # Errors
+/// - [`AnnouncementNotFound`] – no announcement for the revealed values.
+/// - [`RevealTooEarly`] – announced less than the reveal delay ago.
+/// - Any error from [`withdraw`].
+pub fn finalize_withdraw(
+    env: &Env,
+    token: Address,
+    to: Address,
+    amount: i128,
+    salt: Bytes,
+    nonce: BytesN<32>,
+) -> Result<bool, QuickexError> {
+    let announcement =
+        commitment::create_withdraw_announcement(env, to.clone(), amount, salt.clone(), nonce)?;
+    let announced_at = storage::get_withdraw_announcement(env, &announcement)
+        .ok_or(QuickexError::AnnouncementNotFound)?;
+
+    let reveal_delay = config::get(env).reveal_delay_ledgers;
+    if env.ledger().sequence() < announced_at.saturating_add(reveal_delay) {
+        return Err(QuickexError::RevealTooEarly);
+    }
+
+    storage::remove_withdraw_announcement(env, &announcement);
+
+    withdraw(env, token, amount, None, to.into(), salt).map(|_| true)
+}
+
+/// Ledgers a [`prepare_withdraw`] ticket reserves its escrow for (~10 minutes).
+pub const WITHDRAW_TICKET_LEDGERS: u32 = 120;
+
+/// Domain tag hashed into withdrawal ticket ids.
+const TICKET_TAG: &[u8] = b"quickex/ticket";
+
+/// Reserve an escrow for a two-phase withdrawal and return the ticket id.
+///
+/// Runs every check of [`withdraw`] without moving funds, then reserves the escrow
+/// for [`WITHDRAW_TICKET_LEDGERS`] ledgers: until the ticket is settled or expires,
+/// other withdrawals and refunds fail with [`EscrowReserved`]. Integrating contracts
+/// call this, run their own logic, then [`commit_withdraw`] or [`abort_withdraw`].
+///
+/// # Errors
+/// - [`EscrowReserved`] – the escrow is already reserved by a live ticket.
+/// - Any error from [`withdraw`] except the rate and volume limits, which are
+///   applied on commit.
+pub fn prepare_withdraw(
+    env: &Env,
+    token: Address,
+    amount: i128,
+    to: Address,
+    salt: Bytes,
+) -> Result<BytesN<32>, QuickexError> {
     if amount <= 0 {
         return Err(QuickexError::InvalidAmount);
     }
+    compliance::require_not_blocked(env, &to)?;
 
     to.require_auth();
 
-    let commitment = commitment::create_amount_commitment(env, to.clone(), amount, salt)?;
-    let commitment_bytes: Bytes = commitment.clone().into();
+    let commitment = resolve_commitment(env, &token, &to, amount, salt.clone())?;
+    let entry = check_spendable(env, &commitment, &token, amount)?;
+    commitment::require_strong_salt(env, &salt, entry.created_at)?;
+
+    let ledger = env.ledger().sequence();
+    let mut preimage = Bytes::from_slice(env, TICKET_TAG);
+    preimage.append(&commitment.clone().into());
+    preimage.append(&Bytes::from_array(env, &ledger.to_be_bytes()));
+    let id: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+    let ticket = WithdrawTicket {
+        commitment: commitment.clone(),
+        token,
+        to,
+        amount,
+        salt,
+    };
+    storage::put_withdraw_ticket(env, &id, &ticket, WITHDRAW_TICKET_LEDGERS);
+    events::publish_withdraw_prepared(
+        env,
+        id.clone(),
+        commitment,
+        ledger + WITHDRAW_TICKET_LEDGERS,
+    );
+
+    Ok(id)
+}
+
+/// Settle a prepared withdrawal: release the reservation and withdraw as [`withdraw`]
+/// would, paying the ticket's recipient.
+///
+/// # Errors
+/// - [`TicketNotFound`] – no live ticket has this id.
+/// - Any error from [`withdraw`] (e.g. [`EscrowExpired`] if the escrow expired since
+///   it was prepared).
+pub fn commit_withdraw(env: &Env, ticket_id: BytesN<32>) -> Result<bool, QuickexError> {
+    let ticket =
+        storage::get_withdraw_ticket(env, &ticket_id).ok_or(QuickexError::TicketNotFound)?;
+    storage::remove_withdraw_ticket(env, &ticket_id, &ticket);
+
+    withdraw(
+        env,
+        ticket.token,
+        ticket.amount,
+        None,
+        ticket.to.into(),
+        ticket.salt,
+    )
+    .map(|_| true)
+}
+
+/// Release a prepared withdrawal without moving funds. The ticket's recipient must
+/// authorize.
+///
+/// # Errors
+/// - [`TicketNotFound`] – no live ticket has this id.
+pub fn abort_withdraw(env: &Env, ticket_id: BytesN<32>) -> Result<(), QuickexError> {
+    let ticket =
+        storage::get_withdraw_ticket(env, &ticket_id).ok_or(QuickexError::TicketNotFound)?;
+    ticket.to.require_auth();
+
+    storage::remove_withdraw_ticket(env, &ticket_id, &ticket);
+    events::publish_withdraw_aborted(env, ticket_id, ticket.commitment);
+    Ok(())
+}
+
+/// Domain tag prefixed to the message signed for [`withdraw_delegated`].
+pub const DELEGATION_TAG: &[u8] = b"quickex/delegate";
 
-    let entry: EscrowEntry =
-        get_escrow(env, &commitment_bytes).ok_or(QuickexError::CommitmentNotFound)?;
+/// Message the commitment owner signs to authorize a delegated payout:
+/// `DELEGATION_TAG || XDR(contract) || commitment || XDR(payout_to)`.
+pub fn delegation_message(env: &Env, commitment: &BytesN<32>, payout_to: &Address) -> Bytes {
+    let mut message = Bytes::from_slice(env, DELEGATION_TAG);
+    message.append(&env.current_contract_address().to_xdr(env));
+    message.append(&commitment.clone().into());
+    message.append(&payout_to.clone().to_xdr(env));
+    message
+}
+
+/// Stellar account address (`G...`) controlled by an ed25519 public key.
+fn account_address(env: &Env, public_key: &BytesN<32>) -> Result<Address, QuickexError> {
+    // XDR of ScVal::Address(ScAddress::Account(PublicKey::Ed25519(public_key))).
+    let mut xdr = Bytes::from_array(env, &[0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 0]);
+    xdr.append(&public_key.clone().into());
+    Address::from_xdr(env, &xdr).map_err(|_| QuickexError::InternalError)
+}
+
+/// Order of the ed25519 group, ℓ = 2^252 + 27742317777372353535851937790883648493,
+/// little-endian.
+const ED25519_ORDER: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10,
+];
+
+/// Reject a signature the host would refuse to parse: its scalar half `S` must be
+/// canonical (`S < ℓ`). The host's `ed25519_verify` traps on such input, so this
+/// check turns a malformed signature into an error.
+fn require_canonical_signature(signature: &BytesN<64>) -> Result<(), QuickexError> {
+    let bytes = signature.to_array();
+    for i in (0..32).rev() {
+        let (s, l) = (bytes[32 + i], ED25519_ORDER[i]);
+        if s != l {
+            return if s < l {
+                Ok(())
+            } else {
+                Err(QuickexError::InvalidSignature)
+            };
+        }
+    }
+    Err(QuickexError::InvalidSignature)
+}
+
+/// Withdraw an escrow owned by an ed25519 account, paying out to a different address.
+///
+/// The commitment owner is the account whose public key is `owner_pubkey`. Instead of
+/// authorizing the call, the owner signs [`delegation_message`] off-chain, so a
+/// custodian or exchange can submit it and receive the funds at `payout_to`. The
+/// signature covers the contract and commitment, and each escrow can be spent once,
+/// so it cannot be replayed.
+///
+/// The signature is checked against the account's master key only, independent of
+/// the account's signer thresholds.
+///
+/// # Errors
+/// - [`InvalidAmount`] – amount ≤ 0.
+/// - [`InvalidSignature`] – `signature` is malformed (non-canonical `S`).
+/// - Any error from [`withdraw`] for the owner's commitment.
+///
+/// # Panics
+/// Traps if a well-formed `signature` does not verify against the message: the
+/// host's `ed25519_verify` has no non-trapping form. The escrow and the signature
+/// shape are checked first, so those failures return an error instead.
+pub fn withdraw_delegated(
+    env: &Env,
+    token: Address,
+    payout_to: Address,
+    amount: i128,
+    salt: Bytes,
+    owner_pubkey: BytesN<32>,
+    signature: BytesN<64>,
+) -> Result<WithdrawReceipt, QuickexError> {
+    if amount <= 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+
+    compliance::require_not_blocked(env, &payout_to)?;
+
+    let owner = account_address(env, &owner_pubkey)?;
+    let commitment = resolve_commitment(env, &token, &owner, amount, salt.clone())?;
+    let entry = check_spendable(env, &commitment, &token, amount)?;
+    require_canonical_signature(&signature)?;
+
+    let message = delegation_message(env, &commitment, &payout_to);
+    env.crypto()
+        .ed25519_verify(&owner_pubkey, &message, &signature);
+
+    mark_spent(env, &commitment, &entry)?;
+    commitment::require_strong_salt(env, &salt, entry.created_at)?;
+    privacy::record_withdrawal(env, &owner)?;
+
+    let nullifier = commitment::escrow_nullifier(env, &commitment, &salt);
+    events::publish_nullifier_spent(env, nullifier);
+
+    let fee = fees::pay_out(env, &entry.token, &payout_to.into(), amount)?;
+
+    #[cfg(feature = "privacy-pool")]
+    pool::record_spend(env, &commitment, &entry);
+
+    let receipt = record_receipt(env, &commitment, &entry, fee);
+    events::publish_escrow_withdrawn(env, commitment, owner, entry.token, amount, None);
+
+    Ok(receipt)
+}
+
+/// Validate a withdrawal by `to` and mark the escrow `Spent`.
+///
+/// Returns the commitment and the escrow entry as it was before spending.
+fn spend(
+    env: &Env,
+    token: &Address,
+    amount: i128,
+    expected: Option<BytesN<32>>,
+    to: &Address,
+    salt: Bytes,
+) -> Result<(BytesN<32>, EscrowEntry), QuickexError> {
+    if amount <= 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+    compliance::require_not_blocked(env, to)?;
+
+    to.require_auth();
+
+    let commitment = resolve_commitment(env, token, to, amount, salt.clone())?;
+    if expected.is_some_and(|expected| expected != commitment) {
+        return Err(QuickexError::CommitmentMismatch);
+    }
+    let entry = check_spendable(env, &commitment, token, amount)?;
+    mark_spent(env, &commitment, &entry)?;
+    commitment::require_strong_salt(env, &salt, entry.created_at)?;
+    privacy::record_withdrawal(env, to)?;
+
+    let nullifier = commitment::escrow_nullifier(env, &commitment, &salt);
+    events::publish_nullifier_spent(env, nullifier);
+
+    Ok((commitment, entry))
+}
+
+/// Commitment under which the escrow for (`token`, `owner`, `amount`, `salt`) is stored.
+///
+/// Prefers the fixed-layout scheme v3 commitment, then the token-bound scheme v2
+/// one, and falls back to the legacy v1 commitment when neither escrow exists.
+/// Addresses without a fixed-layout key skip v3.
+pub(crate) fn resolve_commitment<S: EscrowStore>(
+    store: &S,
+    token: &Address,
+    owner: &Address,
+    amount: i128,
+    salt: Bytes,
+) -> Result<BytesN<32>, QuickexError> {
+    let env = store.env();
+    if let Ok(v3) =
+        commitment::create_fixed_commitment(env, token.clone(), owner.clone(), amount, salt.clone())
+    {
+        if store.has_escrow(&v3.clone().into()) {
+            return Ok(v3);
+        }
+    }
+    let v2 = commitment::create_token_commitment(
+        env,
+        token.clone(),
+        owner.clone(),
+        amount,
+        salt.clone(),
+    )?;
+    if store.has_escrow(&v2.clone().into()) {
+        return Ok(v2);
+    }
+    commitment::create_amount_commitment(env, owner.clone(), amount, salt)
+}
+
+/// Load a pending escrow. Closed ones are reported as spent from their status alone,
+/// without reading an entry that may be pruned or past its TTL.
+fn load_escrow<S: EscrowStore>(store: &S, commitment: &Bytes) -> Result<EscrowEntry, QuickexError> {
+    if store.is_closed(commitment) {
+        return Err(QuickexError::AlreadySpent);
+    }
+    store
+        .escrow(commitment)
+        .ok_or(QuickexError::CommitmentNotFound)
+}
+
+/// Escrow state checks for withdrawing `commitment` for `amount` of `token`: pending,
+/// unreserved, unexpired, and matching amount and token.
+///
+/// Returns the escrow entry.
+pub(crate) fn validate_withdrawal<S: EscrowStore>(
+    store: &S,
+    commitment: &BytesN<32>,
+    token: &Address,
+    amount: i128,
+) -> Result<EscrowEntry, QuickexError> {
+    let entry = load_escrow(store, &commitment.clone().into())?;
 
     if entry.status != EscrowStatus::Pending {
         return Err(QuickexError::AlreadySpent);
     }
 
+    if store.is_reserved(commitment) {
+        return Err(QuickexError::EscrowReserved);
+    }
+
     // Guard: block withdrawal if expired.
-    if is_expired(env, &entry) {
+    if is_expired(store, &entry) {
         return Err(QuickexError::EscrowExpired);
     }
 
     if entry.amount != amount {
-        return Err(QuickexError::InvalidCommitment);
+        return Err(QuickexError::AmountMismatch);
     }
 
-    let mut updated = entry.clone();
-    updated.status = EscrowStatus::Spent;
-    put_escrow(env, &commitment_bytes, &updated);
+    if entry.token != *token {
+        return Err(QuickexError::TokenMismatch);
+    }
+    Ok(entry)
+}
 
-    let token_client = token::Client::new(env, &entry.token);
-    token_client.transfer(&env.current_contract_address(), &to, &amount);
+/// Check that the escrow for `commitment` can be withdrawn for `amount` of `token`.
+fn check_spendable(
+    env: &Env,
+    commitment: &BytesN<32>,
+    token: &Address,
+    amount: i128,
+) -> Result<EscrowEntry, QuickexError> {
+    let entry = validate_withdrawal(env, commitment, token, amount)?;
+    tokens::require_withdraw_allowed(env, token)?;
+    Ok(entry)
+}
 
-    events::publish_escrow_withdrawn(env, commitment, to, entry.token, amount);
+/// Escrow state checks for `caller` refunding `commitment`: pending, expired, owned by
+/// `caller`, and unreserved.
+///
+/// Returns the escrow entry.
+pub(crate) fn validate_refund<S: EscrowStore>(
+    store: &S,
+    commitment: &BytesN<32>,
+    caller: &Address,
+) -> Result<EscrowEntry, QuickexError> {
+    let entry = load_escrow(store, &commitment.clone().into())?;
 
-    Ok(true)
+    if entry.status != EscrowStatus::Pending {
+        return Err(QuickexError::AlreadySpent);
+    }
+
+    if !is_expired(store, &entry) {
+        return Err(QuickexError::EscrowNotExpired);
+    }
+
+    if *caller != entry.owner {
+        return Err(QuickexError::InvalidOwner);
+    }
+
+    if store.is_reserved(commitment) {
+        return Err(QuickexError::EscrowReserved);
+    }
+    Ok(entry)
+}
+
+/// Mark the escrow `entry` for `commitment`, already checked with
+/// [`check_spendable`], as `Spent`.
+fn mark_spent(env: &Env, commitment: &BytesN<32>, entry: &EscrowEntry) -> Result<(), QuickexError> {
+    let commitment_bytes: Bytes = commitment.clone().into();
+    tokens::record_withdrawal_volume(env, &entry.token, entry.amount)?;
+
+    storage::close_escrow(env, &commitment_bytes, entry, EscrowStatus::Spent);
+    storage::remove_from_owner_index(env, &entry.owner, commitment);
+    storage::adjust_total_locked(env, &entry.token, -entry.amount)?;
+    stats::record_withdrawal(env, &entry.token, entry.amount);
+    storage::bump_instance_ttl(env);
+    storage::push_gc(env, commitment);
+
+    Ok(())
 }
 
 // ---------------------------------------------------------------------------
@@ -217,29 +868,223 @@ pub fn refund(env: &Env, commitment: BytesN<32>, caller: Address) -> Result<(),
     caller.require_auth();
 
     let commitment_bytes: Bytes = commitment.clone().into();
-    let entry: EscrowEntry =
-        get_escrow(env, &commitment_bytes).ok_or(QuickexError::CommitmentNotFound)?;
+    let entry = validate_refund(env, &commitment, &caller)?;
+    tokens::require_withdraw_allowed(env, &entry.token)?;
+
+    storage::close_escrow(env, &commitment_bytes, &entry, EscrowStatus::Refunded);
+    storage::remove_from_owner_index(env, &entry.owner, &commitment);
+    storage::adjust_total_locked(env, &entry.token, -entry.amount)?;
+    stats::record_refund(env, &entry.token, entry.amount);
+    storage::bump_instance_ttl(env);
+    storage::push_gc(env, &commitment);
+
+    tokens::pay(env, &entry.token, &entry.owner, entry.amount)?;
+
+    events::publish_escrow_refunded(env, entry.owner, commitment, entry.token, entry.amount);
+
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Hidden-amount escrows (Pedersen commitments)
+// ---------------------------------------------------------------------------
+
+/// Deposit into a hidden-amount escrow.
+///
+/// The depositor supplies `R = r·H` with a proof of knowledge of `r`; the contract
+/// derives `C = amount·G + R` so the stored commitment always matches the
+/// transferred amount. `commitment` must equal `SHA256(XDR(to) || C)`; it
+/// is computed off-chain because the recipient is not revealed here.
+///
+/// # Errors
+/// - [`InvalidAmount`] – amount ≤ 0.
+/// - [`UnapprovedAmount`] – amount is not an approved bucket for `token`.
+/// - [`InvalidPoint`] – `blinding.blinding_point` is not a valid G1 subgroup point.
+/// - [`InvalidProof`] – `blinding` does not prove `R = r·H` for this `commitment`.
+/// - [`CommitmentAlreadyExists`] – commitment already in storage.
+pub fn deposit_hidden(
+    env: &Env,
+    from: Address,
+    token: Address,
+    amount: i128,
+    commitment: BytesN<32>,
+    blinding: BlindingProof,
+    timeout_secs: u64,
+) -> Result<(), QuickexError> {
+    if amount <= 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+    tokens::require_deposit_allowed(env, &token, amount)?;
+    compliance::require_not_blocked(env, &from)?;
+    pool::require_bucketed(env, &token, amount)?;
+
+    from.require_auth();
+
+    if has_hidden_escrow(env, &commitment) {
+        return Err(QuickexError::CommitmentAlreadyExists);
+    }
+
+    let blinding_point = pedersen::parse_point(env, blinding.blinding_point.clone())?;
+    pedersen::verify_blinding_proof(env, &commitment, &blinding_point, &blinding)?;
+    let amount_commitment = pedersen::commit_with_blinding_point(env, amount, &blinding_point)?;
+
+    tokens::collect(env, &token, &from, amount)?;
+
+    let expires_at = config::claim_expiry(env, timeout_secs)?;
+    let entry = HiddenEscrowEntry {
+        token: token.clone(),
+        amount_commitment,
+        owner: from.clone(),
+        status: EscrowStatus::Pending,
+        created_at: env.ledger().timestamp(),
+        expires_at,
+    };
+
+    put_hidden_escrow(env, &commitment, &entry);
+    storage::adjust_total_locked(env, &token, amount)?;
+    stats::record_deposit(env, &token, amount);
+    storage::bump_instance_ttl(env);
+    events::publish_escrow_deposited(env, commitment, from, token, amount, expires_at, None);
+
+    Ok(())
+}
+
+/// Load a pending hidden escrow. Closed ones are reported as spent from their status
+/// alone.
+fn load_hidden_escrow(env: &Env, key: &BytesN<32>) -> Result<HiddenEscrowEntry, QuickexError> {
+    if storage::is_hidden_closed(env, key) {
+        return Err(QuickexError::AlreadySpent);
+    }
+    get_hidden_escrow(env, key).ok_or(QuickexError::CommitmentNotFound)
+}
+
+/// Withdraw a hidden-amount escrow by opening its Pedersen commitment.
+///
+/// Recomputes `C = amount·G + r·H` from (`amount`, `blinding`) and looks up
+/// `SHA256(XDR(to) || C)`. No stored amount is compared; the binding property of
+/// the commitment guarantees `amount` is the deposited value.
+///
+/// # Errors
+/// - [`InvalidAmount`] – amount ≤ 0.
+/// - [`CommitmentNotFound`] – no hidden escrow for the recomputed key.
+/// - [`AlreadySpent`] – escrow already spent or refunded.
+/// - [`EscrowExpired`] – escrow has passed its expiry.
+/// - [`WithdrawRateLimited`] – `to` has used up its withdrawals for the current window.
+pub fn withdraw_hidden(
+    env: &Env,
+    to: Address,
+    amount: i128,
+    blinding: BytesN<32>,
+) -> Result<bool, QuickexError> {
+    if amount <= 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+    compliance::require_not_blocked(env, &to)?;
+
+    to.require_auth();
+
+    let amount_commitment = pedersen::create_pedersen_commitment(env, amount, blinding.clone())?;
+    let key = pedersen::hidden_escrow_key(env, &to, &amount_commitment);
+
+    let entry = load_hidden_escrow(env, &key)?;
 
     if entry.status != EscrowStatus::Pending {
         return Err(QuickexError::AlreadySpent);
     }
 
-    if !is_expired(env, &entry) {
+    if is_past_expiry(env, entry.expires_at) {
+        return Err(QuickexError::EscrowExpired);
+    }
+    tokens::require_withdraw_allowed(env, &entry.token)?;
+    tokens::record_withdrawal_volume(env, &entry.token, amount)?;
+
+    storage::close_hidden_escrow(env, &key, EscrowStatus::Spent);
+    storage::push_gc(env, &key);
+    storage::adjust_total_locked(env, &entry.token, -amount)?;
+    stats::record_withdrawal(env, &entry.token, amount);
+    storage::bump_instance_ttl(env);
+    privacy::record_withdrawal(env, &to)?;
+
+    let nullifier = commitment::escrow_nullifier(env, &key, &blinding.into());
+    events::publish_nullifier_spent(env, nullifier);
+
+    fees::pay_out(env, &entry.token, &(&to).into(), amount)?;
+
+    events::publish_escrow_withdrawn(env, key, to, entry.token, amount, None);
+
+    Ok(true)
+}
+
+/// Refund an expired hidden-amount escrow to its depositor.
+///
+/// The depositor must open the commitment since the amount is not stored.
+///
+/// # Errors
+/// - [`CommitmentNotFound`] – no hidden escrow for the given commitment.
+/// - [`AlreadySpent`] – escrow already in a terminal state.
+/// - [`EscrowNotExpired`] – escrow has no timeout or timeout not yet reached.
+/// - [`InvalidOwner`] – caller is not the original owner.
+/// - [`CommitmentMismatch`] – (`amount`, `blinding`) does not open the stored commitment.
+pub fn refund_hidden(
+    env: &Env,
+    commitment: BytesN<32>,
+    caller: Address,
+    amount: i128,
+    blinding: BytesN<32>,
+) -> Result<(), QuickexError> {
+    caller.require_auth();
+
+    let entry = load_hidden_escrow(env, &commitment)?;
+
+    if entry.status != EscrowStatus::Pending {
+        return Err(QuickexError::AlreadySpent);
+    }
+
+    if !is_past_expiry(env, entry.expires_at) {
         return Err(QuickexError::EscrowNotExpired);
     }
 
     if caller != entry.owner {
         return Err(QuickexError::InvalidOwner);
     }
+    tokens::require_withdraw_allowed(env, &entry.token)?;
 
-    let mut updated = entry.clone();
-    updated.status = EscrowStatus::Refunded;
-    put_escrow(env, &commitment_bytes, &updated);
+    if amount <= 0 || !pedersen::verify_opening(env, &entry.amount_commitment, amount, blinding) {
+        return Err(QuickexError::CommitmentMismatch);
+    }
 
-    let token_client = token::Client::new(env, &entry.token);
-    token_client.transfer(&env.current_contract_address(), &entry.owner, &entry.amount);
+    storage::close_hidden_escrow(env, &commitment, EscrowStatus::Refunded);
+    storage::push_gc(env, &commitment);
+    storage::adjust_total_locked(env, &entry.token, -amount)?;
+    stats::record_refund(env, &entry.token, amount);
+    storage::bump_instance_ttl(env);
 
-    events::publish_escrow_refunded(env, entry.owner, commitment, entry.token, entry.amount);
+    tokens::pay(env, &entry.token, &entry.owner, amount)?;
+
+    events::publish_escrow_refunded(env, entry.owner, commitment, entry.token, amount);
 
     Ok(())
 }
+
+// ---------------------------------------------------------------------------
+// prune_spent
+// ---------------------------------------------------------------------------
+
+/// Reclaim storage held by spent or refunded escrows. Returns how many were pruned.
+///
+/// Each commitment (or hidden escrow key) whose escrow is in a terminal status but still
+/// has its entry stored loses the entry and keeps only its status, which keeps the
+/// commitment from being reused and withdrawals reporting [`AlreadySpent`]. Withdrawals
+/// and refunds only write the status, so every closed escrow qualifies until pruned.
+/// Pending and unknown commitments are skipped.
+pub fn prune_spent(env: &Env, commitments: Vec<BytesN<32>>) -> u32 {
+    let mut pruned = 0;
+    for commitment in commitments.iter() {
+        if storage::prune_escrow(env, &commitment.clone().into())
+            || storage::prune_hidden_escrow(env, &commitment)
+        {
+            pruned += 1;
+        }
+    }
+    pruned
+}
@@ -0,0 +1,61 @@
+//! Shared setup for the escrow property tests in [`crate::lifecycle_test`] and
+//! [`crate::model_test`].
+
+extern crate std;
+
+use proptest::test_runner::Config as ProptestConfig;
+use soroban_sdk::{
+    testutils::{Address as _, EnvTestConfig},
+    token, Address, Env,
+};
+
+use crate::{QuickexContract, QuickexContractClient};
+
+/// Cases per property test run. Each case deploys a fresh contract, so the default is
+/// kept low; `PROPTEST_CASES` raises it for longer local runs.
+pub(crate) fn config() -> ProptestConfig {
+    let cases = std::env::var("PROPTEST_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(32);
+    ProptestConfig::with_cases(cases)
+}
+
+/// An initialized contract with all auths mocked, a token, and `USERS` accounts
+/// holding it.
+pub(crate) struct Fixture<'a, const USERS: usize> {
+    pub env: Env,
+    pub client: QuickexContractClient<'a>,
+    pub admin: Address,
+    pub token: Address,
+    pub users: [Address; USERS],
+}
+
+impl<const USERS: usize> Fixture<'_, USERS> {
+    /// Deploy the contract and mint `minted` of a new token to each user.
+    ///
+    /// Cases are drawn from a random seed, so the env writes no test snapshot.
+    pub fn new(minted: i128) -> Self {
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.mock_all_auths();
+        let client = QuickexContractClient::new(&env, &env.register(QuickexContract, ()));
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let users = core::array::from_fn(|_| Address::generate(&env));
+        for user in &users {
+            token::StellarAssetClient::new(&env, &token).mint(user, &minted);
+        }
+        Fixture {
+            env,
+            client,
+            admin,
+            token,
+            users,
+        }
+    }
+}
@@ -1,41 +1,70 @@
-use soroban_sdk::{contractevent, Address, BytesN, Env};
+//! # Events
+//!
+//! Every contract event is defined here, so the schema in `docs/events-schema.md` has a
+//! single source. Topics start with `"quickex"` and the event name, and every payload
+//! carries [`EVENT_SCHEMA_VERSION`], so indexers can filter on the prefix and pick the
+//! layout by version when fields are added.
+//!
+//! Payloads also carry a `sequence` number that increases by one with every event the
+//! contract emits, so consumers merging several RPC providers can spot gaps and
+//! reordering.
 
-#[contractevent(topics = ["PrivacyToggled"])]
+use soroban_sdk::{contractevent, Address, Bytes, BytesN, Env, Symbol, Vec};
+
+use crate::{
+    privacy, storage,
+    types::{AdminAction, Config, Role, WithdrawRejection},
+};
+
+/// Version of the event payload layout, bumped whenever a field is added or changed.
+pub const EVENT_SCHEMA_VERSION: u32 = 4;
+
+#[contractevent(topics = ["quickex", "PrivacyToggled"])]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PrivacyToggledEvent {
     #[topic]
     pub owner: Address,
 
     pub enabled: bool,
+    pub schema_version: u32,
+    pub sequence: u64,
     pub timestamp: u64,
 }
 
-#[contractevent(topics = ["EscrowWithdrawn"]) ]
+#[contractevent(topics = ["quickex", "EscrowWithdrawn"]) ]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EscrowWithdrawnEvent {
     #[topic]
     pub commitment: BytesN<32>,
 
     #[topic]
-    pub owner: Address,
+    pub owner: Option<Address>,
 
     pub token: Address,
-    pub amount: i128,
+    pub amount: Option<i128>,
+    pub amount_magnitude: Option<u32>,
+    pub mux_id: Option<u64>,
+    pub schema_version: u32,
+    pub sequence: u64,
     pub timestamp: u64,
 }
 
-#[contractevent(topics = ["EscrowDeposited"]) ]
+#[contractevent(topics = ["quickex", "EscrowDeposited"]) ]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EscrowDepositedEvent {
     #[topic]
     pub commitment: BytesN<32>,
 
     #[topic]
-    pub owner: Address,
+    pub owner: Option<Address>,
 
     pub token: Address,
-    pub amount: i128,
+    pub amount: Option<i128>,
+    pub amount_magnitude: Option<u32>,
     pub expires_at: u64,
+    pub mux_id: Option<u64>,
+    pub schema_version: u32,
+    pub sequence: u64,
     pub timestamp: u64,
 }
 
@@ -43,138 +72,1870 @@ pub(crate) fn publish_privacy_toggled(env: &Env, owner: Address, enabled: bool)
     PrivacyToggledEvent {
         owner,
         enabled,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
         timestamp: env.ledger().timestamp(),
     }
     .publish(env);
 }
 
-#[allow(dead_code)]
-#[contractevent(topics = ["ContractPaused"])]
+#[contractevent(topics = ["quickex", "Paused"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PausedEvent {
+    #[topic]
+    pub by: Address,
+
+    pub ops: u32,
+    pub paused_ops: u32,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_paused(env: &Env, by: Address, ops: u32, paused_ops: u32) {
+    PausedEvent {
+        by,
+        ops,
+        paused_ops,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "Unpaused"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnpausedEvent {
+    #[topic]
+    pub by: Address,
+
+    pub ops: u32,
+    pub paused_ops: u32,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_unpaused(env: &Env, by: Address, ops: u32, paused_ops: u32) {
+    UnpausedEvent {
+        by,
+        ops,
+        paused_ops,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "FunctionDisabledUpdated"])]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ContractPausedEvent {
+pub struct FunctionDisabledUpdatedEvent {
     #[topic]
+    pub name: Symbol,
+
+    pub disabled: bool,
     pub admin: Address,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_function_disabled_updated(
+    env: &Env,
+    name: Symbol,
+    disabled: bool,
+    admin: Address,
+) {
+    FunctionDisabledUpdatedEvent {
+        name,
+        disabled,
+        admin,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "GuardianUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuardianUpdatedEvent {
+    #[topic]
+    pub guardian: Option<Address>,
 
-    pub paused: bool,
+    pub admin: Address,
+    pub schema_version: u32,
+    pub sequence: u64,
     pub timestamp: u64,
 }
 
-#[allow(dead_code)]
-pub(crate) fn publish_contract_paused(env: &Env, admin: Address, paused: bool) {
-    ContractPausedEvent {
+pub(crate) fn publish_guardian_updated(env: &Env, guardian: Option<Address>, admin: Address) {
+    GuardianUpdatedEvent {
+        guardian,
         admin,
-        paused,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
         timestamp: env.ledger().timestamp(),
     }
     .publish(env);
 }
 
-#[allow(dead_code)]
-#[contractevent(topics = ["AdminChanged"])]
+#[contractevent(topics = ["quickex", "TokenAllowlistUpdated"])]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct AdminChangedEvent {
+pub struct TokenAllowlistUpdatedEvent {
     #[topic]
-    pub old_admin: Address,
+    pub token: Address,
+
+    pub allowed: bool,
+    pub admin: Address,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_token_allowlist_updated(
+    env: &Env,
+    token: Address,
+    allowed: bool,
+    admin: Address,
+) {
+    TokenAllowlistUpdatedEvent {
+        token,
+        allowed,
+        admin,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
 
+#[contractevent(topics = ["quickex", "TokenBlocklistUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenBlocklistUpdatedEvent {
     #[topic]
-    pub new_admin: Address,
+    pub token: Address,
 
+    pub blocked: bool,
+    pub freeze_withdrawals: bool,
+    pub admin: Address,
+    pub schema_version: u32,
+    pub sequence: u64,
     pub timestamp: u64,
 }
 
-#[allow(dead_code)]
-pub(crate) fn publish_admin_changed(env: &Env, old_admin: Address, new_admin: Address) {
-    AdminChangedEvent {
-        old_admin,
-        new_admin,
+pub(crate) fn publish_token_blocklist_updated(
+    env: &Env,
+    token: Address,
+    blocked: bool,
+    freeze_withdrawals: bool,
+    admin: Address,
+) {
+    TokenBlocklistUpdatedEvent {
+        token,
+        blocked,
+        freeze_withdrawals,
+        admin,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
         timestamp: env.ledger().timestamp(),
     }
     .publish(env);
 }
 
-#[contractevent(topics = ["ContractUpgraded"])]
+#[contractevent(topics = ["quickex", "TokenCapUpdated"])]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ContractUpgradedEvent {
+pub struct TokenCapUpdatedEvent {
     #[topic]
-    pub new_wasm_hash: BytesN<32>,
+    pub token: Address,
+
+    pub cap: i128,
+    pub admin: Address,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_token_cap_updated(env: &Env, token: Address, cap: i128, admin: Address) {
+    TokenCapUpdatedEvent {
+        token,
+        cap,
+        admin,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
 
+#[contractevent(topics = ["quickex", "GlobalCapUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GlobalCapUpdatedEvent {
     #[topic]
     pub admin: Address,
 
+    pub cap: i128,
+    pub schema_version: u32,
+    pub sequence: u64,
     pub timestamp: u64,
 }
 
-pub(crate) fn publish_contract_upgraded(env: &Env, new_wasm_hash: BytesN<32>, admin: &Address) {
-    ContractUpgradedEvent {
-        new_wasm_hash,
-        admin: admin.clone(),
+pub(crate) fn publish_global_cap_updated(env: &Env, cap: i128, admin: Address) {
+    GlobalCapUpdatedEvent {
+        admin,
+        cap,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
         timestamp: env.ledger().timestamp(),
     }
     .publish(env);
 }
 
-pub(crate) fn publish_escrow_withdrawn(
+#[contractevent(topics = ["quickex", "TokenValueUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenValueUpdatedEvent {
+    #[topic]
+    pub token: Address,
+
+    pub value: i128,
+    pub admin: Address,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_token_value_updated(env: &Env, token: Address, value: i128, admin: Address) {
+    TokenValueUpdatedEvent {
+        token,
+        value,
+        admin,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "WithdrawVolumeLimitUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawVolumeLimitUpdatedEvent {
+    #[topic]
+    pub token: Address,
+
+    pub max_amount: i128,
+    pub window_ledgers: u32,
+    pub admin: Address,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_withdraw_volume_limit_updated(
     env: &Env,
-    commitment: BytesN<32>,
-    owner: Address,
     token: Address,
-    amount: i128,
+    max_amount: i128,
+    window_ledgers: u32,
+    admin: Address,
 ) {
-    EscrowWithdrawnEvent {
-        commitment,
-        owner,
+    WithdrawVolumeLimitUpdatedEvent {
         token,
-        amount,
+        max_amount,
+        window_ledgers,
+        admin,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
         timestamp: env.ledger().timestamp(),
     }
     .publish(env);
 }
 
-pub(crate) fn publish_escrow_deposited(
+#[contractevent(topics = ["quickex", "AccountBlocklistUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountBlocklistUpdatedEvent {
+    #[topic]
+    pub account: Address,
+
+    pub blocked: bool,
+    pub admin: Address,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_account_blocklist_updated(
+    env: &Env,
+    account: Address,
+    blocked: bool,
+    admin: Address,
+) {
+    AccountBlocklistUpdatedEvent {
+        account,
+        blocked,
+        admin,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "ConfigUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigUpdatedEvent {
+    #[topic]
+    pub admin: Address,
+
+    pub config: Config,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_config_updated(env: &Env, config: Config, admin: Address) {
+    ConfigUpdatedEvent {
+        admin,
+        config,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "FeesWithdrawn"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeesWithdrawnEvent {
+    #[topic]
+    pub token: Address,
+
+    #[topic]
+    pub to: Address,
+
+    pub admin: Address,
+    pub amount: i128,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_fees_withdrawn(
     env: &Env,
-    commitment: BytesN<32>,
-    owner: Address,
     token: Address,
+    to: Address,
+    admin: Address,
     amount: i128,
-    expires_at: u64,
 ) {
-    EscrowDepositedEvent {
-        commitment,
-        owner,
+    FeesWithdrawnEvent {
         token,
+        to,
+        admin,
         amount,
-        expires_at,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
         timestamp: env.ledger().timestamp(),
     }
     .publish(env);
 }
 
-#[contractevent(topics = ["EscrowRefunded"])]
+#[contractevent(topics = ["quickex", "FeeCollected"])]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct EscrowRefundedEvent {
+pub struct FeeCollectedEvent {
     #[topic]
-    pub commitment: BytesN<32>,
+    pub token: Address,
 
     #[topic]
-    pub owner: Address,
+    pub recipient: Option<Address>,
 
-    pub token: Address,
-    pub amount: i128,
+    pub gross: Option<i128>,
+    pub fee: Option<i128>,
+    pub net: Option<i128>,
+    pub schema_version: u32,
+    pub sequence: u64,
     pub timestamp: u64,
 }
 
-pub(crate) fn publish_escrow_refunded(
+/// `recipient` and the amounts follow the recipient's privacy level, like the escrow
+/// events (the fee would reveal the gross amount).
+pub(crate) fn publish_fee_collected(
     env: &Env,
-    owner: Address,
-    commitment: BytesN<32>,
     token: Address,
-    amount: i128,
+    recipient: Address,
+    gross: i128,
+    fee: i128,
+    net: i128,
 ) {
-    EscrowRefundedEvent {
-        commitment,
-        owner,
+    let redacted = redact(env, recipient, gross);
+    let gross = redacted.amount;
+    FeeCollectedEvent {
         token,
-        amount,
+        recipient: redacted.owner,
+        fee: gross.map(|_| fee),
+        net: gross.map(|_| net),
+        gross,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "WithdrawalFeeUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalFeeUpdatedEvent {
+    #[topic]
+    pub admin: Address,
+
+    pub fee_bps: u32,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_withdrawal_fee_updated(env: &Env, admin: Address, fee_bps: u32) {
+    WithdrawalFeeUpdatedEvent {
+        admin,
+        fee_bps,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "FeeExemptionUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeExemptionUpdatedEvent {
+    #[topic]
+    pub account: Address,
+
+    pub exempt: bool,
+    pub admin: Address,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_fee_exemption_updated(
+    env: &Env,
+    account: Address,
+    exempt: bool,
+    admin: Address,
+) {
+    FeeExemptionUpdatedEvent {
+        account,
+        exempt,
+        admin,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "RecoveryAddressUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryAddressUpdatedEvent {
+    #[topic]
+    pub recovery: Option<Address>,
+
+    pub admin: Address,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_recovery_address_updated(
+    env: &Env,
+    recovery: Option<Address>,
+    admin: Address,
+) {
+    RecoveryAddressUpdatedEvent {
+        recovery,
+        admin,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "Initialized"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitializedEvent {
+    #[topic]
+    pub admin: Address,
+
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_initialized(env: &Env, admin: Address) {
+    InitializedEvent {
+        admin,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[allow(dead_code)]
+#[contractevent(topics = ["quickex", "AdminChanged"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminChangedEvent {
+    #[topic]
+    pub old_admin: Address,
+
+    #[topic]
+    pub new_admin: Address,
+
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+#[allow(dead_code)]
+pub(crate) fn publish_admin_changed(env: &Env, old_admin: Address, new_admin: Address) {
+    AdminChangedEvent {
+        old_admin,
+        new_admin,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "AdminRenounceScheduled"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminRenounceScheduledEvent {
+    #[topic]
+    pub admin: Address,
+
+    pub ready_at_ledger: u32,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_admin_renounce_scheduled(env: &Env, admin: Address, ready_at_ledger: u32) {
+    AdminRenounceScheduledEvent {
+        admin,
+        ready_at_ledger,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "AdminRenounced"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminRenouncedEvent {
+    #[topic]
+    pub admin: Address,
+
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_admin_renounced(env: &Env, admin: Address) {
+    AdminRenouncedEvent {
+        admin,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "EmergencyWithdrawal"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyWithdrawalEvent {
+    #[topic]
+    pub token: Address,
+
+    #[topic]
+    pub to: Address,
+
+    pub admin: Address,
+    pub amount: i128,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_emergency_withdrawal(
+    env: &Env,
+    token: Address,
+    to: Address,
+    admin: Address,
+    amount: i128,
+) {
+    EmergencyWithdrawalEvent {
+        token,
+        to,
+        admin,
+        amount,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "UpgradeScheduled"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeScheduledEvent {
+    #[topic]
+    pub action_id: u64,
+
+    #[topic]
+    pub new_hash: BytesN<32>,
+
+    pub version: u32,
+    pub execute_after: u64,
+    pub scheduled_by: Address,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_upgrade_scheduled(
+    env: &Env,
+    action_id: u64,
+    new_hash: BytesN<32>,
+    version: u32,
+    execute_after: u64,
+    scheduled_by: Address,
+) {
+    UpgradeScheduledEvent {
+        action_id,
+        new_hash,
+        version,
+        execute_after,
+        scheduled_by,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "UpgradeCancelled"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeCancelledEvent {
+    #[topic]
+    pub action_id: u64,
+
+    #[topic]
+    pub new_hash: BytesN<32>,
+
+    pub version: u32,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_upgrade_cancelled(
+    env: &Env,
+    action_id: u64,
+    new_hash: BytesN<32>,
+    version: u32,
+) {
+    UpgradeCancelledEvent {
+        action_id,
+        new_hash,
+        version,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "UpgradeExecuted"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeExecutedEvent {
+    #[topic]
+    pub new_hash: BytesN<32>,
+
+    #[topic]
+    pub admin: Address,
+
+    pub old_hash: Option<BytesN<32>>,
+    pub version: u32,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_upgrade_executed(
+    env: &Env,
+    old_hash: Option<BytesN<32>>,
+    new_hash: BytesN<32>,
+    admin: &Address,
+    version: u32,
+) {
+    UpgradeExecutedEvent {
+        new_hash,
+        admin: admin.clone(),
+        old_hash,
+        version,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "StorageMigrated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StorageMigratedEvent {
+    pub from_schema: u32,
+    pub to_schema: u32,
+    pub version: u32,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_storage_migrated(env: &Env, from_schema: u32, to_schema: u32, version: u32) {
+    StorageMigratedEvent {
+        from_schema,
+        to_schema,
+        version,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "LegacyEscrowsMigrated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyEscrowsMigratedEvent {
+    #[topic]
+    pub admin: Address,
+
+    pub migrated: u32,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_legacy_escrows_migrated(env: &Env, admin: Address, migrated: u32) {
+    LegacyEscrowsMigratedEvent {
+        admin,
+        migrated,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "TotalLockedBackfilled"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TotalLockedBackfilledEvent {
+    #[topic]
+    pub token: Address,
+
+    pub old_total: i128,
+    pub new_total: i128,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_total_locked_backfilled(
+    env: &Env,
+    token: Address,
+    old_total: i128,
+    new_total: i128,
+) {
+    TotalLockedBackfilledEvent {
+        token,
+        old_total,
+        new_total,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "RoleUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleUpdatedEvent {
+    #[topic]
+    pub account: Address,
+
+    pub role: Role,
+    pub granted: bool,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_role_updated(env: &Env, account: Address, role: Role, granted: bool) {
+    RoleUpdatedEvent {
+        account,
+        role,
+        granted,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "TimelockDelayUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimelockDelayUpdatedEvent {
+    pub delay: u64,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_timelock_delay_updated(env: &Env, delay: u64) {
+    TimelockDelayUpdatedEvent {
+        delay,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "AdminActionQueued"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminActionQueuedEvent {
+    #[topic]
+    pub action_id: u64,
+
+    #[topic]
+    pub queued_by: Address,
+
+    pub action: AdminAction,
+    pub eta: u64,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_admin_action_queued(
+    env: &Env,
+    action_id: u64,
+    queued_by: Address,
+    action: AdminAction,
+    eta: u64,
+) {
+    AdminActionQueuedEvent {
+        action_id,
+        queued_by,
+        action,
+        eta,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "AdminActionCancelled"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminActionCancelledEvent {
+    #[topic]
+    pub action_id: u64,
+
+    pub action: AdminAction,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_admin_action_cancelled(env: &Env, action_id: u64, action: AdminAction) {
+    AdminActionCancelledEvent {
+        action_id,
+        action,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "AdminCouncilSet"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminCouncilSetEvent {
+    pub members: Vec<Address>,
+    pub threshold: u32,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_admin_council_set(env: &Env, members: Vec<Address>, threshold: u32) {
+    AdminCouncilSetEvent {
+        members,
+        threshold,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "AdminActionProposed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminActionProposedEvent {
+    #[topic]
+    pub action_id: u64,
+
+    #[topic]
+    pub proposer: Address,
+
+    pub action: AdminAction,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_admin_action_proposed(
+    env: &Env,
+    action_id: u64,
+    proposer: Address,
+    action: AdminAction,
+) {
+    AdminActionProposedEvent {
+        action_id,
+        proposer,
+        action,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "AdminActionApproved"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminActionApprovedEvent {
+    #[topic]
+    pub action_id: u64,
+
+    #[topic]
+    pub member: Address,
+
+    pub approvals: u32,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_admin_action_approved(
+    env: &Env,
+    action_id: u64,
+    member: Address,
+    approvals: u32,
+) {
+    AdminActionApprovedEvent {
+        action_id,
+        member,
+        approvals,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "AdminActionExecuted"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminActionExecutedEvent {
+    #[topic]
+    pub action_id: u64,
+
+    pub action: AdminAction,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_admin_action_executed(env: &Env, action_id: u64, action: AdminAction) {
+    AdminActionExecutedEvent {
+        action_id,
+        action,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+/// Escrow event fields after applying the owner's privacy level (`None` = redacted).
+struct Redacted {
+    owner: Option<Address>,
+    amount: Option<i128>,
+    amount_magnitude: Option<u32>,
+}
+
+/// Decimal order of magnitude `k` of `amount`, so that `10^k ≤ amount < 10^(k+1)`.
+pub fn amount_magnitude(amount: i128) -> u32 {
+    amount.max(1).ilog10()
+}
+
+/// Apply `owner`'s privacy level to escrow event fields. At
+/// [`LEVEL_HIDE_AMOUNT`](privacy::LEVEL_HIDE_AMOUNT) the exact amount is replaced by
+/// its [`amount_magnitude`]; at [`LEVEL_HIDE_ALL`](privacy::LEVEL_HIDE_ALL) both are
+/// left out. Auditors read exact figures through `get_escrow_details`.
+fn redact(env: &Env, owner: Address, amount: i128) -> Redacted {
+    let level = privacy::effective_level(env, &owner);
+    Redacted {
+        amount: (level < privacy::LEVEL_HIDE_AMOUNT).then_some(amount),
+        amount_magnitude: (level == privacy::LEVEL_HIDE_AMOUNT).then(|| amount_magnitude(amount)),
+        owner: (level < privacy::LEVEL_HIDE_ALL).then_some(owner),
+    }
+}
+
+pub(crate) fn publish_escrow_withdrawn(
+    env: &Env,
+    commitment: BytesN<32>,
+    owner: Address,
+    token: Address,
+    amount: i128,
+    mux_id: Option<u64>,
+) {
+    let redacted = redact(env, owner, amount);
+    EscrowWithdrawnEvent {
+        commitment,
+        mux_id: redacted.owner.as_ref().and(mux_id),
+        owner: redacted.owner,
+        token,
+        amount: redacted.amount,
+        amount_magnitude: redacted.amount_magnitude,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+pub(crate) fn publish_escrow_deposited(
+    env: &Env,
+    commitment: BytesN<32>,
+    owner: Address,
+    token: Address,
+    amount: i128,
+    expires_at: u64,
+    mux_id: Option<u64>,
+) {
+    let redacted = redact(env, owner, amount);
+    EscrowDepositedEvent {
+        commitment,
+        mux_id: redacted.owner.as_ref().and(mux_id),
+        owner: redacted.owner,
+        token,
+        amount: redacted.amount,
+        amount_magnitude: redacted.amount_magnitude,
+        expires_at,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "EscrowCreated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowCreatedEvent {
+    #[topic]
+    pub id: u64,
+
+    pub from: Address,
+    pub to: Address,
+    pub token: Address,
+    pub amount: u64,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_escrow_created(
+    env: &Env,
+    id: u64,
+    from: Address,
+    to: Address,
+    token: Address,
+    amount: u64,
+) {
+    EscrowCreatedEvent {
+        id,
+        from,
+        to,
+        token,
+        amount,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "TtlExtended"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlExtendedEvent {
+    #[topic]
+    pub keeper: Address,
+
+    pub commitment: Option<BytesN<32>>,
+    pub live_until_ledger: u32,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_ttl_extended(
+    env: &Env,
+    keeper: Address,
+    commitment: Option<BytesN<32>>,
+    live_until_ledger: u32,
+) {
+    TtlExtendedEvent {
+        keeper,
+        commitment,
+        live_until_ledger,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "EscrowRestored"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowRestoredEvent {
+    #[topic]
+    pub commitment: BytesN<32>,
+
+    pub live_until_ledger: u32,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_escrow_restored(env: &Env, commitment: BytesN<32>, live_until_ledger: u32) {
+    EscrowRestoredEvent {
+        commitment,
+        live_until_ledger,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "GarbageCollected"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GarbageCollectedEvent {
+    #[topic]
+    pub keeper: Address,
+
+    pub cleaned: u32,
+    pub bounty_token: Option<Address>,
+    pub bounty: i128,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_garbage_collected(
+    env: &Env,
+    keeper: Address,
+    cleaned: u32,
+    bounty_token: Option<Address>,
+    bounty: i128,
+) {
+    GarbageCollectedEvent {
+        keeper,
+        cleaned,
+        bounty_token,
+        bounty,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "GcBountyUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GcBountyUpdatedEvent {
+    #[topic]
+    pub token: Address,
+
+    pub amount: i128,
+    pub admin: Address,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_gc_bounty_updated(env: &Env, token: Address, amount: i128, admin: Address) {
+    GcBountyUpdatedEvent {
+        token,
+        amount,
+        admin,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "EscrowMemo"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowMemoEvent {
+    #[topic]
+    pub commitment: BytesN<32>,
+
+    pub memo: Bytes,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_escrow_memo(env: &Env, commitment: BytesN<32>, memo: Bytes) {
+    EscrowMemoEvent {
+        commitment,
+        memo,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "WithdrawAnnounced"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawAnnouncedEvent {
+    #[topic]
+    pub announcement: BytesN<32>,
+
+    pub ledger: u32,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_withdraw_announced(env: &Env, announcement: BytesN<32>, ledger: u32) {
+    WithdrawAnnouncedEvent {
+        announcement,
+        ledger,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "WithdrawPrepared"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawPreparedEvent {
+    #[topic]
+    pub ticket: BytesN<32>,
+
+    pub commitment: BytesN<32>,
+    pub expires_ledger: u32,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_withdraw_prepared(
+    env: &Env,
+    ticket: BytesN<32>,
+    commitment: BytesN<32>,
+    expires_ledger: u32,
+) {
+    WithdrawPreparedEvent {
+        ticket,
+        commitment,
+        expires_ledger,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "WithdrawAborted"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawAbortedEvent {
+    #[topic]
+    pub ticket: BytesN<32>,
+
+    pub commitment: BytesN<32>,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_withdraw_aborted(env: &Env, ticket: BytesN<32>, commitment: BytesN<32>) {
+    WithdrawAbortedEvent {
+        ticket,
+        commitment,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "NullifierSpent"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NullifierSpentEvent {
+    #[topic]
+    pub nullifier: BytesN<32>,
+
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_nullifier_spent(env: &Env, nullifier: BytesN<32>) {
+    NullifierSpentEvent {
+        nullifier,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "RelayerFeePaid"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelayerFeePaidEvent {
+    #[topic]
+    pub commitment: BytesN<32>,
+
+    #[topic]
+    pub relayer: Address,
+
+    pub token: Address,
+    pub fee: i128,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_relayer_fee_paid(
+    env: &Env,
+    commitment: BytesN<32>,
+    relayer: Address,
+    token: Address,
+    fee: i128,
+) {
+    RelayerFeePaidEvent {
+        commitment,
+        relayer,
+        token,
+        fee,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "EscrowRefunded"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowRefundedEvent {
+    #[topic]
+    pub commitment: BytesN<32>,
+
+    #[topic]
+    pub owner: Option<Address>,
+
+    pub token: Address,
+    pub amount: Option<i128>,
+    pub amount_magnitude: Option<u32>,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_escrow_refunded(
+    env: &Env,
+    owner: Address,
+    commitment: BytesN<32>,
+    token: Address,
+    amount: i128,
+) {
+    let redacted = redact(env, owner, amount);
+    EscrowRefundedEvent {
+        commitment,
+        owner: redacted.owner,
+        token,
+        amount: redacted.amount,
+        amount_magnitude: redacted.amount_magnitude,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[cfg(feature = "privacy-pool")]
+#[contractevent(topics = ["quickex", "DenominationUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DenominationUpdatedEvent {
+    #[topic]
+    pub token: Address,
+
+    pub denomination: i128,
+    pub enabled: bool,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+#[cfg(feature = "privacy-pool")]
+pub(crate) fn publish_denomination_updated(
+    env: &Env,
+    token: Address,
+    denomination: i128,
+    enabled: bool,
+) {
+    DenominationUpdatedEvent {
+        token,
+        denomination,
+        enabled,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "AmountBucketUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AmountBucketUpdatedEvent {
+    #[topic]
+    pub token: Address,
+
+    pub amount: i128,
+    pub enabled: bool,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_amount_bucket_updated(
+    env: &Env,
+    token: Address,
+    amount: i128,
+    enabled: bool,
+) {
+    AmountBucketUpdatedEvent {
+        token,
+        amount,
+        enabled,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[cfg(feature = "zk-verifier")]
+#[contractevent(topics = ["quickex", "ShieldedDeposited"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShieldedDepositedEvent {
+    #[topic]
+    pub token: Address,
+
+    pub denomination: i128,
+    pub leaf: BytesN<32>,
+    pub leaf_index: u32,
+    pub root: BytesN<32>,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+#[cfg(feature = "zk-verifier")]
+pub(crate) fn publish_shielded_deposited(
+    env: &Env,
+    token: Address,
+    denomination: i128,
+    leaf: BytesN<32>,
+    leaf_index: u32,
+    root: BytesN<32>,
+) {
+    ShieldedDepositedEvent {
+        token,
+        denomination,
+        leaf,
+        leaf_index,
+        root,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[cfg(feature = "zk-verifier")]
+#[contractevent(topics = ["quickex", "ShieldedWithdrawn"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShieldedWithdrawnEvent {
+    #[topic]
+    pub nullifier_hash: BytesN<32>,
+
+    #[topic]
+    pub recipient: Address,
+
+    pub token: Address,
+    pub amount: i128,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+#[cfg(feature = "zk-verifier")]
+pub(crate) fn publish_shielded_withdrawn(
+    env: &Env,
+    nullifier_hash: BytesN<32>,
+    recipient: Address,
+    token: Address,
+    amount: i128,
+) {
+    ShieldedWithdrawnEvent {
+        nullifier_hash,
+        recipient,
+        token,
+        amount,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[cfg(feature = "privacy-pool")]
+#[contractevent(topics = ["quickex", "RingNoteDeposited"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RingNoteDepositedEvent {
+    #[topic]
+    pub token: Address,
+
+    pub denomination: i128,
+    pub public_key: BytesN<96>,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+#[cfg(feature = "privacy-pool")]
+pub(crate) fn publish_ring_note_deposited(
+    env: &Env,
+    token: Address,
+    denomination: i128,
+    public_key: BytesN<96>,
+) {
+    RingNoteDepositedEvent {
+        token,
+        denomination,
+        public_key,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[cfg(feature = "privacy-pool")]
+#[contractevent(topics = ["quickex", "RingWithdrawn"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RingWithdrawnEvent {
+    #[topic]
+    pub key_image: BytesN<96>,
+
+    #[topic]
+    pub recipient: Address,
+
+    pub token: Address,
+    pub amount: i128,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+#[cfg(feature = "privacy-pool")]
+pub(crate) fn publish_ring_withdrawn(
+    env: &Env,
+    key_image: BytesN<96>,
+    recipient: Address,
+    token: Address,
+    amount: i128,
+) {
+    RingWithdrawnEvent {
+        key_image,
+        recipient,
+        token,
+        amount,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[cfg(feature = "zk-verifier")]
+#[contractevent(topics = ["quickex", "VerifyingKeyUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifyingKeyUpdatedEvent {
+    #[topic]
+    pub admin: Address,
+
+    pub version: u32,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+#[cfg(feature = "zk-verifier")]
+pub(crate) fn publish_verifying_key_updated(env: &Env, admin: Address, version: u32) {
+    VerifyingKeyUpdatedEvent {
+        admin,
+        version,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "RelayerStaked"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelayerStakedEvent {
+    #[topic]
+    pub relayer: Address,
+
+    pub amount: i128,
+    pub total_stake: i128,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_relayer_staked(env: &Env, relayer: Address, amount: i128, total_stake: i128) {
+    RelayerStakedEvent {
+        relayer,
+        amount,
+        total_stake,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "RelayerUnstaked"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelayerUnstakedEvent {
+    #[topic]
+    pub relayer: Address,
+
+    pub unlock_at: u64,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_relayer_unstaked(env: &Env, relayer: Address, unlock_at: u64) {
+    RelayerUnstakedEvent {
+        relayer,
+        unlock_at,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "RelayerStakeWithdrawn"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelayerStakeWithdrawnEvent {
+    #[topic]
+    pub relayer: Address,
+
+    pub amount: i128,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_relayer_stake_withdrawn(env: &Env, relayer: Address, amount: i128) {
+    RelayerStakeWithdrawnEvent {
+        relayer,
+        amount,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "RelayerSlashed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelayerSlashedEvent {
+    #[topic]
+    pub relayer: Address,
+
+    pub amount: i128,
+    pub reason: Symbol,
+    pub remaining_stake: i128,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_relayer_slashed(
+    env: &Env,
+    relayer: Address,
+    amount: i128,
+    reason: Symbol,
+    remaining_stake: i128,
+) {
+    RelayerSlashedEvent {
+        relayer,
+        amount,
+        reason,
+        remaining_stake,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "InvariantViolated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvariantViolatedEvent {
+    #[topic]
+    pub token: Address,
+
+    pub balance: i128,
+    pub liabilities: i128,
+    pub auto_paused: bool,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_invariant_violated(
+    env: &Env,
+    token: Address,
+    balance: i128,
+    liabilities: i128,
+    auto_paused: bool,
+) {
+    InvariantViolatedEvent {
+        token,
+        balance,
+        liabilities,
+        auto_paused,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "InvariantAutoPauseUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvariantAutoPauseUpdatedEvent {
+    #[topic]
+    pub admin: Address,
+
+    pub enabled: bool,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_invariant_auto_pause_updated(env: &Env, enabled: bool, admin: Address) {
+    InvariantAutoPauseUpdatedEvent {
+        admin,
+        enabled,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "WithdrawRejected"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawRejectedEvent {
+    #[topic]
+    pub commitment: BytesN<32>,
+
+    #[topic]
+    pub to: Address,
+
+    pub class: WithdrawRejection,
+    pub count: u64,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_withdraw_rejected(
+    env: &Env,
+    commitment: BytesN<32>,
+    to: Address,
+    class: WithdrawRejection,
+    count: u64,
+) {
+    WithdrawRejectedEvent {
+        commitment,
+        to,
+        class,
+        count,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["quickex", "WithdrawTelemetryUpdated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawTelemetryUpdatedEvent {
+    #[topic]
+    pub admin: Address,
+
+    pub enabled: bool,
+    pub schema_version: u32,
+    pub sequence: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_withdraw_telemetry_updated(env: &Env, enabled: bool, admin: Address) {
+    WithdrawTelemetryUpdatedEvent {
+        admin,
+        enabled,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: storage::next_event_sequence(env),
         timestamp: env.ledger().timestamp(),
     }
     .publish(env);
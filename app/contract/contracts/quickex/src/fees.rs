@@ -0,0 +1,116 @@
+//! # Protocol Fees
+//!
+//! An optional protocol fee charged on escrow withdrawals, in basis points of the
+//! withdrawn amount:
+//!
+//! ```text
+//! fee = amount * fee_bps / 10_000      (0 if the recipient is exempt)
+//! to receives amount - fee (minus any relayer fee)
+//! ```
+//!
+//! The fee stays in the contract's [`crate::treasury`], segregated from escrow
+//! liabilities, until the admin withdraws it. The rate starts at 0; the admin or a
+//! [`Role::FeeManager`] can change it and exempt recipients such as partner
+//! integrations or treasury addresses.
+
+use soroban_sdk::{Address, Env, MuxedAddress};
+
+use crate::{
+    admin,
+    errors::QuickexError,
+    events::{
+        publish_fee_collected, publish_fee_exemption_updated, publish_withdrawal_fee_updated,
+    },
+    storage, tokens, treasury,
+    types::Role,
+};
+
+/// Basis-point denominator.
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Highest withdrawal fee that can be configured (10%).
+pub const MAX_FEE_BPS: u32 = 1_000;
+
+/// Set the withdrawal fee in basis points (**admin or [`Role::FeeManager`]**).
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is neither the admin nor a fee manager.
+/// - [`QuickexError::InvalidFee`] – `fee_bps` is above [`MAX_FEE_BPS`].
+pub fn set_withdrawal_fee(env: &Env, caller: Address, fee_bps: u32) -> Result<(), QuickexError> {
+    admin::require_role(env, &caller, Role::FeeManager)?;
+    if fee_bps > MAX_FEE_BPS {
+        return Err(QuickexError::InvalidFee);
+    }
+
+    update_withdrawal_fee(env, fee_bps, caller);
+    Ok(())
+}
+
+pub(crate) fn update_withdrawal_fee(env: &Env, fee_bps: u32, admin: Address) {
+    storage::set_withdrawal_fee_bps(env, fee_bps);
+    publish_withdrawal_fee_updated(env, admin, fee_bps);
+}
+
+/// Current withdrawal fee in basis points.
+pub fn withdrawal_fee_bps(env: &Env) -> u32 {
+    storage::get_withdrawal_fee_bps(env)
+}
+
+/// Exempt `account` from (or subject it to) the withdrawal fee (**admin or
+/// [`Role::FeeManager`]**).
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is neither the admin nor a fee manager.
+pub fn set_fee_exempt(
+    env: &Env,
+    caller: Address,
+    account: Address,
+    exempt: bool,
+) -> Result<(), QuickexError> {
+    admin::require_role(env, &caller, Role::FeeManager)?;
+
+    storage::set_fee_exempt(env, &account, exempt);
+    publish_fee_exemption_updated(env, account, exempt, caller);
+    Ok(())
+}
+
+/// Whether withdrawals paid to `account` skip the fee.
+pub fn is_fee_exempt(env: &Env, account: &Address) -> bool {
+    storage::is_fee_exempt(env, account)
+}
+
+/// Protocol fee owed on a withdrawal of `amount` paid to `to`.
+pub fn withdrawal_fee(env: &Env, to: &Address, amount: i128) -> i128 {
+    let fee_bps = withdrawal_fee_bps(env);
+    if fee_bps == 0 || is_fee_exempt(env, to) {
+        return 0;
+    }
+    // Split so that `amount * fee_bps` cannot overflow; the result is exact.
+    let fee_bps = fee_bps as i128;
+    amount / BPS_DENOMINATOR * fee_bps + amount % BPS_DENOMINATOR * fee_bps / BPS_DENOMINATOR
+}
+
+/// Pay `amount` of `token` out to `to`, keeping the protocol fee. Returns the fee.
+///
+/// Emits `FeeCollected` when a fee is kept.
+///
+/// # Errors
+/// - [`QuickexError::ArithmeticOverflow`] – the accrued fees would overflow.
+/// - [`QuickexError::TransferFailed`] – the token contract failed the transfer.
+pub(crate) fn pay_out(
+    env: &Env,
+    token: &Address,
+    to: &MuxedAddress,
+    amount: i128,
+) -> Result<i128, QuickexError> {
+    let recipient = to.address();
+    let fee = withdrawal_fee(env, &recipient, amount);
+    if amount > fee {
+        tokens::pay(env, token, to, amount - fee)?;
+    }
+    treasury::credit(env, token, fee)?;
+    if fee > 0 {
+        publish_fee_collected(env, token.clone(), recipient, amount, fee, amount - fee);
+    }
+    Ok(fee)
+}
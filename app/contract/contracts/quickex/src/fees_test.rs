@@ -0,0 +1,192 @@
+//! Protocol withdrawal fee and exemption tests.
+
+use crate::{
+    errors::QuickexError, test_fixture::deploy_initialized, types::WithdrawReceipt,
+    QuickexContractClient,
+};
+use soroban_sdk::{testutils::Address as _, token, Address, Bytes, BytesN, Env};
+
+const AMOUNT: i128 = 10_000;
+const FEE_BPS: u32 = 50;
+
+struct Setup<'a> {
+    env: Env,
+    client: QuickexContractClient<'a>,
+    admin: Address,
+    token: Address,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let (env, client, admin, token) = deploy_initialized();
+    Setup {
+        env,
+        client,
+        admin,
+        token,
+    }
+}
+
+/// Deposit `AMOUNT` claimable by `to` and return the commitment and salt.
+fn deposit_for(s: &Setup, to: &Address) -> (BytesN<32>, Bytes) {
+    let depositor = Address::generate(&s.env);
+    token::StellarAssetClient::new(&s.env, &s.token).mint(&depositor, &AMOUNT);
+    let salt = Bytes::from_slice(&s.env, b"fee_test_salt");
+    let commitment = s.client.create_amount_commitment(to, &AMOUNT, &salt);
+    s.client
+        .deposit_with_commitment(&depositor, &s.token, &AMOUNT, &commitment, &0);
+    (commitment, salt)
+}
+
+#[test]
+fn test_withdrawal_fee_is_kept_unless_exempt() {
+    let s = setup();
+    s.client.set_withdrawal_fee(&s.admin, &FEE_BPS);
+    assert_eq!(s.client.get_withdrawal_fee(), FEE_BPS);
+    let token_client = token::Client::new(&s.env, &s.token);
+    let fee = AMOUNT * FEE_BPS as i128 / 10_000;
+
+    let to = Address::generate(&s.env);
+    let (commitment, salt) = deposit_for(&s, &to);
+    let receipt = s
+        .client
+        .withdraw(&s.token, &AMOUNT, &commitment, &to, &salt);
+    assert_eq!(
+        receipt,
+        WithdrawReceipt {
+            commitment,
+            token: s.token.clone(),
+            gross: AMOUNT,
+            fee,
+            net: AMOUNT - fee,
+            ledger: s.env.ledger().sequence(),
+        }
+    );
+    assert_eq!(token_client.balance(&to), AMOUNT - fee);
+    assert_eq!(token_client.balance(&s.client.address), fee);
+    assert_eq!(s.client.sweepable_balance(&s.token), 0);
+
+    let partner = Address::generate(&s.env);
+    s.client.set_fee_exempt(&s.admin, &partner, &true);
+    assert!(s.client.is_fee_exempt(&partner));
+    let (commitment, salt) = deposit_for(&s, &partner);
+    let receipt = s
+        .client
+        .withdraw(&s.token, &AMOUNT, &commitment, &partner, &salt);
+    assert_eq!((receipt.fee, receipt.net), (0, AMOUNT));
+    assert_eq!(token_client.balance(&partner), AMOUNT);
+
+    s.client.set_fee_exempt(&s.admin, &partner, &false);
+    assert!(!s.client.is_fee_exempt(&partner));
+}
+
+#[test]
+fn test_fee_settings_require_fee_manager() {
+    let s = setup();
+    let manager = Address::generate(&s.env);
+    let stranger = Address::generate(&s.env);
+
+    assert_eq!(
+        s.client.try_set_withdrawal_fee(&stranger, &FEE_BPS),
+        Err(Ok(QuickexError::Unauthorized))
+    );
+    assert_eq!(
+        s.client.try_set_fee_exempt(&stranger, &stranger, &true),
+        Err(Ok(QuickexError::Unauthorized))
+    );
+
+    s.client
+        .grant_role(&s.admin, &crate::types::Role::FeeManager, &manager);
+    s.client.set_withdrawal_fee(&manager, &FEE_BPS);
+    assert_eq!(
+        s.client
+            .try_set_withdrawal_fee(&manager, &(crate::fees::MAX_FEE_BPS + 1)),
+        Err(Ok(QuickexError::InvalidFee))
+    );
+}
+
+#[test]
+fn test_treasury_holds_fees_until_withdrawn() {
+    let s = setup();
+    s.client.set_withdrawal_fee(&s.admin, &FEE_BPS);
+    let token_client = token::Client::new(&s.env, &s.token);
+    let fee = AMOUNT * FEE_BPS as i128 / 10_000;
+
+    let to = Address::generate(&s.env);
+    let (commitment, salt) = deposit_for(&s, &to);
+    s.client
+        .withdraw(&s.token, &AMOUNT, &commitment, &to, &salt);
+    assert_eq!(s.client.treasury_balance(&s.token), fee);
+
+    let treasury = Address::generate(&s.env);
+    assert_eq!(
+        s.client
+            .try_withdraw_fees(&s.admin, &s.token, &treasury, &(fee + 1)),
+        Err(Ok(QuickexError::InsufficientFees))
+    );
+    s.client.withdraw_fees(&s.admin, &s.token, &treasury, &fee);
+    assert_eq!(token_client.balance(&treasury), fee);
+    assert_eq!(s.client.treasury_balance(&s.token), 0);
+}
+
+#[test]
+fn test_gc_pays_keeper_bounty_from_treasury() {
+    let s = setup();
+    s.client.set_withdrawal_fee(&s.admin, &FEE_BPS);
+    let token_client = token::Client::new(&s.env, &s.token);
+    let fee = AMOUNT * FEE_BPS as i128 / 10_000;
+
+    let to = Address::generate(&s.env);
+    let depositor = Address::generate(&s.env);
+    token::StellarAssetClient::new(&s.env, &s.token).mint(&depositor, &AMOUNT);
+    let salt = Bytes::from_slice(&s.env, b"gc_memo_salt");
+    let memo_commitment = s.client.create_amount_commitment(&to, &AMOUNT, &salt);
+    s.client.deposit_with_memo(
+        &depositor,
+        &s.token,
+        &AMOUNT,
+        &memo_commitment,
+        &0,
+        &Bytes::from_slice(&s.env, b"invoice 7"),
+    );
+    s.client
+        .withdraw(&s.token, &AMOUNT, &memo_commitment, &to, &salt);
+
+    // Escrows without a memo still leave their entry to clean.
+    let (commitment, salt) = deposit_for(&s, &to);
+    s.client
+        .withdraw(&s.token, &AMOUNT, &commitment, &to, &salt);
+    assert_eq!(s.client.gc_queue_len(), 2);
+    assert_eq!(s.client.treasury_balance(&s.token), 2 * fee);
+
+    assert_eq!(
+        s.client.try_set_gc_bounty(&s.admin, &s.token, &-1),
+        Err(Ok(QuickexError::InvalidAmount))
+    );
+    let bounty = 30;
+    s.client.set_gc_bounty(&s.admin, &s.token, &bounty);
+    assert_eq!(s.client.get_gc_bounty(), Some((s.token.clone(), bounty)));
+
+    let keeper = Address::generate(&s.env);
+    assert_eq!(s.client.gc(&keeper, &10), 2);
+    assert_eq!(token_client.balance(&keeper), 2 * bounty);
+    assert_eq!(s.client.treasury_balance(&s.token), 2 * fee - 2 * bounty);
+    assert_eq!(s.client.get_memo(&memo_commitment), None);
+    assert_eq!(s.client.gc_queue_len(), 0);
+
+    assert_eq!(s.client.gc(&keeper, &10), 0);
+    assert_eq!(token_client.balance(&keeper), 2 * bounty);
+
+    s.client.set_gc_bounty(&s.admin, &s.token, &0);
+    assert_eq!(s.client.get_gc_bounty(), None);
+}
+
+#[test]
+fn test_withdrawal_fee_does_not_overflow() {
+    let s = setup();
+    s.client.set_withdrawal_fee(&s.admin, &FEE_BPS);
+    let to = Address::generate(&s.env);
+    let fee = s.env.as_contract(&s.client.address, || {
+        crate::fees::withdrawal_fee(&s.env, &to, i128::MAX)
+    });
+    assert_eq!(fee, i128::MAX / (10_000 / FEE_BPS as i128));
+}
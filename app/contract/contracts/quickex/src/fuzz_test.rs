@@ -0,0 +1,1045 @@
+//! Arbitrary inputs through the public entrypoints must not trap.
+//!
+//! Every call goes through the `try_` client, where a contract error comes back as
+//! `Err(Ok(QuickexError))` and a trap (panic, host error, failed sub-call) as
+//! `Err(Err(InvokeError))`. Inputs are drawn from a seeded generator so failures
+//! reproduce.
+//!
+//! Calls that can hand over or give up admin control run against a fresh contract each
+//! round, so one round cannot lock the next out of the admin entrypoints. Every
+//! entrypoint is either fuzzed or listed in [`SKIPPED`] with the reason, and the test
+//! checks that against the contract spec of the release WASM (see
+//! [`crate::spec_fixture`]).
+//!
+//! The API is not fully panic-free. Besides the skipped upgrade calls, a well-formed
+//! but wrong signature in `withdraw_delegated` for an escrow that exists traps, since
+//! the host's `ed25519_verify` aborts; random commitments never reach it.
+
+extern crate std;
+
+use core::fmt::Debug;
+use std::collections::BTreeSet;
+
+use crate::{
+    admin::KILLABLE_FUNCTIONS,
+    types::{AdminAction, BlindingProof, Config, Role, WithdrawRejection},
+    QuickexContract, QuickexContractClient,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, vec, Address, Bytes, BytesN, Env, InvokeError, Symbol, Vec,
+};
+
+const ROUNDS: u32 = 24;
+
+/// Entrypoints the fuzzer does not call, and why.
+const SKIPPED: &[(&str, &str)] = &[
+    (
+        "upgrade",
+        "the host traps when `new_wasm_hash` was never uploaded, and tests cannot upload one",
+    ),
+    (
+        "execute_upgrade",
+        "runs the scheduled upgrade, so it traps the same way as `upgrade`",
+    ),
+];
+
+/// Xorshift generator; deterministic so a failing round can be replayed.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn pick<T: Clone>(&mut self, items: &[T]) -> T {
+        items[(self.next() % items.len() as u64) as usize].clone()
+    }
+
+    fn bool(&mut self) -> bool {
+        self.next() & 1 == 0
+    }
+
+    fn i128(&mut self) -> i128 {
+        let random = ((self.next() as i128) << 64) | self.next() as i128;
+        self.pick(&[0, 1, -1, 100, i128::MAX, i128::MIN, random, random >> 70])
+    }
+
+    fn u64(&mut self) -> u64 {
+        let random = self.next();
+        self.pick(&[0, 1, 60, 3_600, u64::MAX, random, random >> 40])
+    }
+
+    fn u32(&mut self) -> u32 {
+        self.u64() as u32
+    }
+
+    fn bytes(&mut self, env: &Env) -> Bytes {
+        let len = self.pick(&[0usize, 1, 7, 16, 32, 33, 257, 1_024, 1_025, 4_096]);
+        let mut bytes = Bytes::new(env);
+        for _ in 0..len {
+            bytes.push_back(self.next() as u8);
+        }
+        bytes
+    }
+
+    fn bytes_n<const N: usize>(&mut self, env: &Env) -> BytesN<N> {
+        let mut array = [0u8; N];
+        for byte in array.iter_mut() {
+            *byte = self.next() as u8;
+        }
+        BytesN::from_array(env, &array)
+    }
+
+    fn role(&mut self) -> Role {
+        self.pick(&[
+            Role::Pauser,
+            Role::Upgrader,
+            Role::FeeManager,
+            Role::Auditor,
+        ])
+    }
+
+    /// An admin action other than `Upgrade`, which traps like [`SKIPPED`] `upgrade`
+    /// once it runs.
+    fn admin_action(&mut self, account: &Address, token: &Address) -> AdminAction {
+        match self.next() % 13 {
+            0 => AdminAction::SetPaused(self.bool()),
+            1 => AdminAction::SetPausedOps(self.u32(), self.bool()),
+            2 => AdminAction::GrantRole(self.role(), account.clone()),
+            3 => AdminAction::RevokeRole(self.role(), account.clone()),
+            4 => AdminAction::SetAdmin(account.clone()),
+            5 => AdminAction::SetTimelockDelay(self.u64()),
+            6 => AdminAction::CancelQueued(self.u64()),
+            7 => AdminAction::SetGuardian(self.pick(&[None, Some(account.clone())])),
+            8 => AdminAction::SetWithdrawalFee(self.u32()),
+            9 => AdminAction::SetTokenCap(token.clone(), self.i128()),
+            10 => AdminAction::SetGlobalCap(self.i128()),
+            11 => AdminAction::AllowToken(token.clone()),
+            _ => AdminAction::DisallowToken(token.clone()),
+        }
+    }
+}
+
+/// Panic if `result` is a trap, and record `name` as fuzzed.
+fn assert_no_trap<T, E: Debug>(
+    fuzzed: &mut BTreeSet<&'static str>,
+    name: &'static str,
+    result: Result<T, Result<E, InvokeError>>,
+) {
+    fuzzed.insert(name);
+    if let Err(Err(error)) = result {
+        panic!("{name} trapped: {error:?}");
+    }
+}
+
+/// Deploy and initialize the contract, with a token minted to a user.
+///
+/// Returns the client, admin, token and user.
+fn deploy(env: &Env) -> (QuickexContractClient<'_>, Address, Address, Address) {
+    env.mock_all_auths();
+    let client = QuickexContractClient::new(env, &env.register(QuickexContract, ()));
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    let user = Address::generate(env);
+    token::StellarAssetClient::new(env, &token).mint(&user, &1_000_000);
+    (client, admin, token, user)
+}
+
+#[test]
+fn test_arbitrary_inputs_never_trap() {
+    let mut fuzzed = BTreeSet::new();
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    fuzz_shared_contract(&mut fuzzed, &mut rng);
+    fuzz_admin_control(&mut fuzzed, &mut rng);
+
+    let skipped: BTreeSet<_> = SKIPPED.iter().map(|(name, _)| *name).collect();
+    assert!(
+        fuzzed.is_disjoint(&skipped),
+        "fuzzed and skipped: {:?}",
+        fuzzed.intersection(&skipped).collect::<std::vec::Vec<_>>()
+    );
+    let Some(entrypoints) = crate::spec_fixture::entrypoints() else {
+        return;
+    };
+    let covered: BTreeSet<_> = fuzzed
+        .union(&skipped)
+        .map(|name| std::string::String::from(*name))
+        .collect();
+    let missing: std::vec::Vec<_> = entrypoints.difference(&covered).collect();
+    assert!(
+        missing.is_empty(),
+        "neither fuzzed nor skipped: {missing:?}"
+    );
+    let unknown: std::vec::Vec<_> = covered.difference(&entrypoints).collect();
+    assert!(unknown.is_empty(), "not entrypoints: {unknown:?}");
+}
+
+/// Everything that leaves the admin in control, against one contract for all rounds.
+fn fuzz_shared_contract(fuzzed: &mut BTreeSet<&'static str>, rng: &mut Rng) {
+    let env = Env::default();
+    let (client, admin, token, user) = deploy(&env);
+
+    // A real token, a plain account, and a contract that is not a token.
+    let tokens = [token.clone(), user.clone(), client.address.clone()];
+    let accounts = [admin.clone(), user.clone(), Address::generate(&env)];
+
+    for _ in 0..ROUNDS {
+        let token = rng.pick(&tokens);
+        let account = rng.pick(&accounts);
+        let other = rng.pick(&accounts);
+        let amount = rng.i128();
+        let salt = rng.bytes(&env);
+        let commitment = rng.bytes_n::<32>(&env);
+        let timeout = rng.u64();
+
+        assert_no_trap(fuzzed, "initialize", client.try_initialize(&account));
+        assert_no_trap(
+            fuzzed,
+            "ensure_initialized",
+            client.try_ensure_initialized(&account),
+        );
+
+        assert_no_trap(
+            fuzzed,
+            "deposit",
+            client.try_deposit(&token, &amount, &account, &salt, &timeout),
+        );
+        assert_no_trap(
+            fuzzed,
+            "deposit_with_commitment",
+            client.try_deposit_with_commitment(&account, &token, &amount, &commitment, &timeout),
+        );
+        assert_no_trap(
+            fuzzed,
+            "deposit_ephemeral",
+            client.try_deposit_ephemeral(&account, &token, &amount, &commitment, &timeout),
+        );
+        assert_no_trap(
+            fuzzed,
+            "deposit_with_memo",
+            client.try_deposit_with_memo(
+                &account,
+                &token,
+                &amount,
+                &rng.bytes_n::<32>(&env),
+                &timeout,
+                &rng.bytes(&env),
+            ),
+        );
+        assert_no_trap(
+            fuzzed,
+            "deposit_hidden",
+            client.try_deposit_hidden(
+                &account,
+                &token,
+                &amount,
+                &rng.bytes_n::<32>(&env),
+                &BlindingProof {
+                    blinding_point: rng.bytes_n::<96>(&env),
+                    nonce_point: rng.bytes_n::<96>(&env),
+                    response: rng.bytes_n::<32>(&env),
+                },
+                &timeout,
+            ),
+        );
+        assert_no_trap(
+            fuzzed,
+            "withdraw",
+            client.try_withdraw(&token, &amount, &commitment, &account, &salt),
+        );
+        assert_no_trap(
+            fuzzed,
+            "attempt_withdraw",
+            client.try_attempt_withdraw(&token, &amount, &commitment, &account, &salt),
+        );
+        assert_no_trap(
+            fuzzed,
+            "withdraw_delegated",
+            client.try_withdraw_delegated(
+                &token,
+                &other,
+                &amount,
+                &salt,
+                &rng.bytes_n::<32>(&env),
+                &rng.bytes_n::<64>(&env),
+            ),
+        );
+        assert_no_trap(
+            fuzzed,
+            "withdraw_via_relayer",
+            client.try_withdraw_via_relayer(&other, &token, &account, &amount, &salt, &rng.i128()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "withdraw_hidden",
+            client.try_withdraw_hidden(&account, &amount, &rng.bytes_n::<32>(&env)),
+        );
+        assert_no_trap(
+            fuzzed,
+            "refund_hidden",
+            client.try_refund_hidden(&commitment, &account, &amount, &rng.bytes_n::<32>(&env)),
+        );
+        assert_no_trap(
+            fuzzed,
+            "announce_withdraw",
+            client.try_announce_withdraw(&commitment),
+        );
+        assert_no_trap(
+            fuzzed,
+            "prepare_withdraw",
+            client.try_prepare_withdraw(&token, &amount, &account, &salt),
+        );
+        assert_no_trap(
+            fuzzed,
+            "commit_withdraw",
+            client.try_commit_withdraw(&commitment),
+        );
+        assert_no_trap(
+            fuzzed,
+            "abort_withdraw",
+            client.try_abort_withdraw(&commitment),
+        );
+        assert_no_trap(
+            fuzzed,
+            "finalize_withdraw",
+            client.try_finalize_withdraw(&token, &account, &amount, &salt, &commitment),
+        );
+        assert_no_trap(fuzzed, "refund", client.try_refund(&commitment, &account));
+        assert_no_trap(
+            fuzzed,
+            "create_escrow",
+            client.try_create_escrow(&account, &other, &token, &rng.u64()),
+        );
+
+        assert_no_trap(
+            fuzzed,
+            "create_amount_commitment",
+            client.try_create_amount_commitment(&account, &amount, &salt),
+        );
+        assert_no_trap(
+            fuzzed,
+            "create_token_commitment",
+            client.try_create_token_commitment(&token, &account, &amount, &salt),
+        );
+        assert_no_trap(
+            fuzzed,
+            "create_fixed_commitment",
+            client.try_create_fixed_commitment(&token, &account, &amount, &salt),
+        );
+        assert_no_trap(
+            fuzzed,
+            "verify_amount_commitment",
+            client.try_verify_amount_commitment(&commitment, &account, &amount, &salt),
+        );
+        let items: Vec<(BytesN<32>, Address, i128, Bytes)> = vec![
+            &env,
+            (commitment.clone(), account.clone(), amount, salt.clone()),
+        ];
+        assert_no_trap(
+            fuzzed,
+            "verify_amount_commitments",
+            client.try_verify_amount_commitments(&items),
+        );
+        assert_no_trap(
+            fuzzed,
+            "create_pedersen_commitment",
+            client.try_create_pedersen_commitment(&amount, &rng.bytes_n::<32>(&env)),
+        );
+        assert_no_trap(
+            fuzzed,
+            "verify_proof_view",
+            client.try_verify_proof_view(&amount, &salt, &account),
+        );
+        assert_no_trap(fuzzed, "generate_salt", client.try_generate_salt());
+
+        assert_no_trap(
+            fuzzed,
+            "enable_privacy",
+            client.try_enable_privacy(&account, &rng.u32()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "privacy_status",
+            client.try_privacy_status(&account),
+        );
+        assert_no_trap(
+            fuzzed,
+            "privacy_history",
+            client.try_privacy_history(&account),
+        );
+        assert_no_trap(
+            fuzzed,
+            "privacy_history_page",
+            client.try_privacy_history_page(&account, &rng.u32(), &rng.u32()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "set_privacy",
+            client.try_set_privacy(&account, &rng.bool()),
+        );
+        assert_no_trap(fuzzed, "get_privacy", client.try_get_privacy(&account));
+        assert_no_trap(
+            fuzzed,
+            "list_claimable",
+            client.try_list_claimable(&account),
+        );
+        assert_no_trap(
+            fuzzed,
+            "list_claimable_page",
+            client.try_list_claimable_page(&account, &rng.u32()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "set_withdraw_rate_limit",
+            client.try_set_withdraw_rate_limit(&account, &rng.u32(), &rng.u64()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_withdraw_rate_limit",
+            client.try_get_withdraw_rate_limit(&account),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_escrow_details",
+            client.try_get_escrow_details(&commitment, &account),
+        );
+        assert_no_trap(fuzzed, "get_memo", client.try_get_memo(&commitment));
+        assert_no_trap(fuzzed, "get_receipt", client.try_get_receipt(&commitment));
+        assert_no_trap(
+            fuzzed,
+            "get_commitment_state",
+            client.try_get_commitment_state(&commitment),
+        );
+
+        assert_no_trap(
+            fuzzed,
+            "proof_of_reserves",
+            client.try_proof_of_reserves(&token),
+        );
+        assert_no_trap(
+            fuzzed,
+            "sweepable_balance",
+            client.try_sweepable_balance(&token),
+        );
+        assert_no_trap(
+            fuzzed,
+            "check_invariants",
+            client.try_check_invariants(&token),
+        );
+        assert_no_trap(
+            fuzzed,
+            "set_invariant_auto_pause",
+            client.try_set_invariant_auto_pause(&account, &rng.bool()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_invariant_auto_pause",
+            client.try_get_invariant_auto_pause(),
+        );
+        assert_no_trap(
+            fuzzed,
+            "set_withdraw_telemetry",
+            client.try_set_withdraw_telemetry(&account, &rng.bool()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_withdraw_telemetry",
+            client.try_get_withdraw_telemetry(),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_rejected_withdrawals",
+            client.try_get_rejected_withdrawals(&rng.pick(&[
+                WithdrawRejection::WrongSalt,
+                WithdrawRejection::Spent,
+                WithdrawRejection::Expired,
+            ])),
+        );
+        assert_no_trap(
+            fuzzed,
+            "total_value_locked",
+            client.try_total_value_locked(),
+        );
+        assert_no_trap(fuzzed, "stats", client.try_stats());
+        assert_no_trap(fuzzed, "health_check", client.try_health_check());
+        assert_no_trap(
+            fuzzed,
+            "emergency_withdraw",
+            client.try_emergency_withdraw(&account, &token, &other, &amount),
+        );
+        assert_no_trap(
+            fuzzed,
+            "withdraw_fees",
+            client.try_withdraw_fees(&account, &token, &other, &amount),
+        );
+        assert_no_trap(
+            fuzzed,
+            "treasury_balance",
+            client.try_treasury_balance(&token),
+        );
+
+        assert_no_trap(
+            fuzzed,
+            "set_withdrawal_fee",
+            client.try_set_withdrawal_fee(&account, &rng.u32()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_withdrawal_fee",
+            client.try_get_withdrawal_fee(),
+        );
+        assert_no_trap(
+            fuzzed,
+            "set_fee_exempt",
+            client.try_set_fee_exempt(&account, &other, &rng.bool()),
+        );
+        assert_no_trap(fuzzed, "is_fee_exempt", client.try_is_fee_exempt(&other));
+        assert_no_trap(
+            fuzzed,
+            "allow_token",
+            client.try_allow_token(&account, &token),
+        );
+        assert_no_trap(
+            fuzzed,
+            "is_token_allowed",
+            client.try_is_token_allowed(&token),
+        );
+        assert_no_trap(
+            fuzzed,
+            "disallow_token",
+            client.try_disallow_token(&account, &token),
+        );
+        assert_no_trap(
+            fuzzed,
+            "block_token",
+            client.try_block_token(&account, &token, &rng.bool()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_token_block",
+            client.try_get_token_block(&token),
+        );
+        assert_no_trap(
+            fuzzed,
+            "unblock_token",
+            client.try_unblock_token(&account, &token),
+        );
+        assert_no_trap(
+            fuzzed,
+            "set_token_cap",
+            client.try_set_token_cap(&account, &token, &rng.i128()),
+        );
+        assert_no_trap(fuzzed, "get_token_cap", client.try_get_token_cap(&token));
+        assert_no_trap(
+            fuzzed,
+            "set_global_cap",
+            client.try_set_global_cap(&account, &rng.i128()),
+        );
+        assert_no_trap(fuzzed, "get_global_cap", client.try_get_global_cap());
+        assert_no_trap(
+            fuzzed,
+            "set_token_value",
+            client.try_set_token_value(&account, &token, &rng.i128()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_token_value",
+            client.try_get_token_value(&token),
+        );
+        assert_no_trap(
+            fuzzed,
+            "set_withdraw_volume_limit",
+            client.try_set_withdraw_volume_limit(&account, &token, &rng.i128(), &rng.u32()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_withdraw_volume_limit",
+            client.try_get_withdraw_volume_limit(&token),
+        );
+        assert_no_trap(
+            fuzzed,
+            "set_amount_bucket",
+            client.try_set_amount_bucket(&account, &token, &rng.i128(), &false),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_amount_buckets",
+            client.try_get_amount_buckets(&token),
+        );
+        assert_no_trap(
+            fuzzed,
+            "set_min_salt_len",
+            client.try_set_min_salt_len(&account, &rng.u32()),
+        );
+        assert_no_trap(fuzzed, "get_salt_policy", client.try_get_salt_policy());
+        assert_no_trap(
+            fuzzed,
+            "set_timelock_delay",
+            client.try_set_timelock_delay(&account, &rng.u64()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_timelock_delay",
+            client.try_get_timelock_delay(),
+        );
+        assert_no_trap(
+            fuzzed,
+            "set_paused_ops",
+            client.try_set_paused_ops(&account, &rng.u32(), &false),
+        );
+        assert_no_trap(fuzzed, "paused_ops", client.try_paused_ops());
+        assert_no_trap(fuzzed, "is_paused", client.try_is_paused());
+        assert_no_trap(
+            fuzzed,
+            "set_gc_bounty",
+            client.try_set_gc_bounty(&account, &token, &rng.i128()),
+        );
+        assert_no_trap(fuzzed, "get_gc_bounty", client.try_get_gc_bounty());
+        assert_no_trap(fuzzed, "gc", client.try_gc(&other, &rng.u32()));
+        assert_no_trap(fuzzed, "gc_queue_len", client.try_gc_queue_len());
+        assert_no_trap(
+            fuzzed,
+            "bump_entries",
+            client.try_bump_entries(&other, &vec![&env, commitment.clone()], &rng.u32()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "bump_instance",
+            client.try_bump_instance(&rng.u32()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "extend_instance_ttl",
+            client.try_extend_instance_ttl(&other),
+        );
+        assert_no_trap(
+            fuzzed,
+            "extend_escrow_ttl",
+            client.try_extend_escrow_ttl(&other, &commitment),
+        );
+        assert_no_trap(
+            fuzzed,
+            "restore_escrow",
+            client.try_restore_escrow(&commitment),
+        );
+        assert_no_trap(
+            fuzzed,
+            "prune_spent",
+            client.try_prune_spent(&vec![&env, commitment.clone()]),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_admin_action",
+            client.try_get_admin_action(&rng.u64()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_queued_action",
+            client.try_get_queued_action(&rng.u64()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "execute_action",
+            client.try_execute_action(&rng.u64()),
+        );
+
+        assert_no_trap(
+            fuzzed,
+            "check_upgrade",
+            client.try_check_upgrade(&rng.bytes_n::<32>(&env)),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_scheduled_upgrade",
+            client.try_get_scheduled_upgrade(),
+        );
+        assert_no_trap(fuzzed, "migrate", client.try_migrate());
+        assert_no_trap(
+            fuzzed,
+            "migrate_storage",
+            client.try_migrate_storage(&account, &vec![&env, commitment.clone()]),
+        );
+        assert_no_trap(
+            fuzzed,
+            "backfill_total_locked",
+            client.try_backfill_total_locked(&account, &token),
+        );
+        assert_no_trap(
+            fuzzed,
+            "debug_dump_entry",
+            client.try_debug_dump_entry(&account, &commitment),
+        );
+        assert_no_trap(fuzzed, "version", client.try_version());
+        assert_no_trap(
+            fuzzed,
+            "storage_layout_version",
+            client.try_storage_layout_version(),
+        );
+        assert_no_trap(fuzzed, "error_info", client.try_error_info(&rng.u32()));
+        assert_no_trap(fuzzed, "event_sequence", client.try_event_sequence());
+
+        assert_no_trap(fuzzed, "get_admin", client.try_get_admin());
+        assert_no_trap(fuzzed, "get_admin_council", client.try_get_admin_council());
+        assert_no_trap(
+            fuzzed,
+            "get_admin_last_active",
+            client.try_get_admin_last_active(),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_renounce_ready_at",
+            client.try_get_renounce_ready_at(),
+        );
+        assert_no_trap(
+            fuzzed,
+            "is_admin_renounced",
+            client.try_is_admin_renounced(),
+        );
+        assert_no_trap(fuzzed, "get_guardian", client.try_get_guardian());
+        assert_no_trap(
+            fuzzed,
+            "get_recovery_address",
+            client.try_get_recovery_address(),
+        );
+        assert_no_trap(fuzzed, "get_config", client.try_get_config());
+        assert_no_trap(fuzzed, "has_role", client.try_has_role(&rng.role(), &other));
+        assert_no_trap(fuzzed, "is_blocked", client.try_is_blocked(&other));
+        let name = Symbol::new(&env, rng.pick(&KILLABLE_FUNCTIONS));
+        assert_no_trap(
+            fuzzed,
+            "is_function_disabled",
+            client.try_is_function_disabled(&name),
+        );
+
+        assert_no_trap(
+            fuzzed,
+            "set_relayer_config",
+            client.try_set_relayer_config(&account, &token, &rng.i128(), &rng.u64()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_relayer_config",
+            client.try_get_relayer_config(),
+        );
+        assert_no_trap(
+            fuzzed,
+            "stake_relayer",
+            client.try_stake_relayer(&other, &amount),
+        );
+        assert_no_trap(
+            fuzzed,
+            "slash_relayer",
+            client.try_slash_relayer(&account, &other, &rng.i128(), &Symbol::new(&env, "fuzz")),
+        );
+        assert_no_trap(fuzzed, "get_relayer", client.try_get_relayer(&other));
+        assert_no_trap(
+            fuzzed,
+            "get_active_relayers",
+            client.try_get_active_relayers(),
+        );
+        assert_no_trap(
+            fuzzed,
+            "get_active_relayers_page",
+            client.try_get_active_relayers_page(&rng.u32()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "unstake_relayer",
+            client.try_unstake_relayer(&other),
+        );
+        assert_no_trap(
+            fuzzed,
+            "withdraw_relayer_stake",
+            client.try_withdraw_relayer_stake(&other),
+        );
+
+        #[cfg(feature = "privacy-pool")]
+        {
+            assert_no_trap(
+                fuzzed,
+                "set_denomination",
+                client.try_set_denomination(&account, &token, &amount, &true),
+            );
+            assert_no_trap(
+                fuzzed,
+                "get_denominations",
+                client.try_get_denominations(&token),
+            );
+            assert_no_trap(
+                fuzzed,
+                "deposit_to_pool",
+                client.try_deposit_to_pool(&account, &token, &amount, &commitment),
+            );
+            assert_no_trap(
+                fuzzed,
+                "deposit_ring_note",
+                client.try_deposit_ring_note(&account, &token, &amount, &rng.bytes_n::<96>(&env)),
+            );
+            assert_no_trap(fuzzed, "pool_stats", client.try_pool_stats(&token, &amount));
+            let signature = crate::types::RingSignature {
+                key_image: rng.bytes_n::<96>(&env),
+                challenge: rng.bytes_n::<32>(&env),
+                responses: vec![&env, rng.bytes_n::<32>(&env)],
+            };
+            assert_no_trap(
+                fuzzed,
+                "withdraw_with_ring_signature",
+                client.try_withdraw_with_ring_signature(
+                    &token,
+                    &amount,
+                    &other,
+                    &vec![&env, rng.bytes_n::<96>(&env)],
+                    &signature,
+                ),
+            );
+        }
+
+        #[cfg(feature = "zk-verifier")]
+        {
+            let vk = crate::types::VerifyingKey {
+                alpha: rng.bytes_n::<96>(&env),
+                beta: rng.bytes_n::<192>(&env),
+                gamma: rng.bytes_n::<192>(&env),
+                delta: rng.bytes_n::<192>(&env),
+                ic: vec![
+                    &env,
+                    rng.bytes_n::<96>(&env),
+                    rng.bytes_n::<96>(&env),
+                    rng.bytes_n::<96>(&env),
+                    rng.bytes_n::<96>(&env),
+                ],
+            };
+            assert_no_trap(
+                fuzzed,
+                "set_verifying_key",
+                client.try_set_verifying_key(&account, &vk),
+            );
+            let version = rng.u32();
+            assert_no_trap(
+                fuzzed,
+                "get_verifying_key",
+                client.try_get_verifying_key(&rng.pick(&[None, Some(version)])),
+            );
+            assert_no_trap(
+                fuzzed,
+                "deposit_shielded",
+                client.try_deposit_shielded(&account, &token, &amount, &commitment),
+            );
+            assert_no_trap(
+                fuzzed,
+                "get_pool_size",
+                client.try_get_pool_size(&token, &amount),
+            );
+            assert_no_trap(
+                fuzzed,
+                "get_known_roots",
+                client.try_get_known_roots(&token, &amount),
+            );
+            assert_no_trap(
+                fuzzed,
+                "get_pool_root",
+                client.try_get_pool_root(&token, &amount),
+            );
+            // A known root gets past the root check to the proof itself.
+            let root = match rng.bool() {
+                true => client.get_pool_root(&token, &amount),
+                false => rng.bytes_n::<32>(&env),
+            };
+            let proof = crate::types::Groth16Proof {
+                a: rng.bytes_n::<96>(&env),
+                b: rng.bytes_n::<192>(&env),
+                c: rng.bytes_n::<96>(&env),
+            };
+            let public_inputs = crate::types::WithdrawPublicInputs {
+                token: token.clone(),
+                denomination: amount,
+                root: root.clone(),
+                nullifier_hash: rng.bytes_n::<32>(&env),
+                recipient: other.clone(),
+            };
+            assert_no_trap(
+                fuzzed,
+                "withdraw_with_proof",
+                client.try_withdraw_with_proof(&proof, &public_inputs),
+            );
+            let mut siblings = Vec::new(&env);
+            for _ in 0..rng.pick(&[0, 1, crate::merkle::TREE_DEPTH, 33]) {
+                siblings.push_back(rng.bytes_n::<32>(&env));
+            }
+            let path = crate::types::MerklePath {
+                index: rng.u32(),
+                siblings,
+            };
+            assert_no_trap(
+                fuzzed,
+                "verify_merkle_proof",
+                client.try_verify_merkle_proof(&commitment, &path, &root),
+            );
+            assert_no_trap(
+                fuzzed,
+                "poseidon_hash",
+                client.try_poseidon_hash(
+                    &rng.pick(&[
+                        crate::types::PoseidonField::Bls12381,
+                        crate::types::PoseidonField::Bn254,
+                    ]),
+                    &commitment,
+                    &rng.bytes_n::<32>(&env),
+                ),
+            );
+        }
+
+        // Undo any pause a round may have set so later rounds reach the handlers.
+        client.set_paused_ops(&admin, &crate::admin::PAUSE_ALL, &false);
+    }
+}
+
+/// Calls that can hand over or give up admin control, against a fresh contract each
+/// round. `schedule_upgrade` comes last so no later call can run the upgrade.
+fn fuzz_admin_control(fuzzed: &mut BTreeSet<&'static str>, rng: &mut Rng) {
+    for _ in 0..ROUNDS {
+        let env = Env::default();
+        let (client, admin, token, user) = deploy(&env);
+        let tokens = [token.clone(), user.clone(), client.address.clone()];
+        let accounts = [admin.clone(), user.clone(), Address::generate(&env)];
+        let token = rng.pick(&tokens);
+        let account = rng.pick(&accounts);
+        let other = rng.pick(&accounts);
+
+        let config = Config {
+            default_claim_window_secs: rng.u64(),
+            max_claim_window_secs: rng.u64(),
+            reveal_delay_ledgers: rng.u32(),
+            recovery_inactivity_secs: rng.u64(),
+            ..client.get_config()
+        };
+        assert_no_trap(
+            fuzzed,
+            "set_config",
+            client.try_set_config(&account, &config),
+        );
+        assert_no_trap(
+            fuzzed,
+            "grant_role",
+            client.try_grant_role(&account, &rng.role(), &other),
+        );
+        assert_no_trap(
+            fuzzed,
+            "revoke_role",
+            client.try_revoke_role(&account, &rng.role(), &other),
+        );
+        assert_no_trap(
+            fuzzed,
+            "set_paused",
+            client.try_set_paused(&account, &rng.bool()),
+        );
+        assert_no_trap(
+            fuzzed,
+            "set_guardian",
+            client.try_set_guardian(&account, &rng.pick(&[None, Some(other.clone())])),
+        );
+        assert_no_trap(
+            fuzzed,
+            "set_recovery_address",
+            client.try_set_recovery_address(&account, &rng.pick(&[None, Some(other.clone())])),
+        );
+        assert_no_trap(
+            fuzzed,
+            "block_account",
+            client.try_block_account(&account, &other),
+        );
+        assert_no_trap(
+            fuzzed,
+            "unblock_account",
+            client.try_unblock_account(&account, &rng.pick(&accounts)),
+        );
+        let name = Symbol::new(&env, rng.pick(&KILLABLE_FUNCTIONS));
+        assert_no_trap(
+            fuzzed,
+            "disable_function",
+            client.try_disable_function(&account, &name),
+        );
+        assert_no_trap(
+            fuzzed,
+            "enable_function",
+            client.try_enable_function(&account, &name),
+        );
+
+        let action = rng.admin_action(&other, &token);
+        assert_no_trap(
+            fuzzed,
+            "queue_action",
+            client.try_queue_action(&account, &action),
+        );
+        assert_no_trap(
+            fuzzed,
+            "cancel_action",
+            client.try_cancel_action(&account, &rng.u64()),
+        );
+        let actions = vec![
+            &env,
+            rng.admin_action(&other, &token),
+            rng.admin_action(&other, &token),
+        ];
+        assert_no_trap(
+            fuzzed,
+            "admin_batch",
+            client.try_admin_batch(&account, &actions),
+        );
+
+        env.ledger()
+            .with_mut(|ledger| ledger.timestamp += rng.next() % 100_000_000);
+        assert_no_trap(
+            fuzzed,
+            "execute_action",
+            client.try_execute_action(&(rng.next() % 4)),
+        );
+        assert_no_trap(
+            fuzzed,
+            "claim_admin_after_inactivity",
+            client.try_claim_admin_after_inactivity(&other),
+        );
+        assert_no_trap(
+            fuzzed,
+            "renounce_admin",
+            client.try_renounce_admin(&account),
+        );
+        assert_no_trap(
+            fuzzed,
+            "cancel_renounce_admin",
+            client.try_cancel_renounce_admin(&rng.pick(&accounts)),
+        );
+        assert_no_trap(fuzzed, "set_admin", client.try_set_admin(&account, &other));
+
+        let members = vec![&env, account.clone(), other.clone()];
+        assert_no_trap(
+            fuzzed,
+            "initialize_multisig",
+            client.try_initialize_multisig(&members, &rng.u32()),
+        );
+        let action = rng.admin_action(&other, &token);
+        assert_no_trap(
+            fuzzed,
+            "propose_action",
+            client.try_propose_action(&rng.pick(&accounts), &action),
+        );
+        assert_no_trap(
+            fuzzed,
+            "approve_action",
+            client.try_approve_action(&rng.pick(&accounts), &(rng.next() % 4)),
+        );
+
+        assert_no_trap(
+            fuzzed,
+            "schedule_upgrade",
+            client.try_schedule_upgrade(&account, &rng.bytes_n::<32>(&env), &rng.u32(), &rng.u64()),
+        );
+    }
+}
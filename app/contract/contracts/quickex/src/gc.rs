@@ -0,0 +1,89 @@
+//! # Garbage Collection
+//!
+//! Spent and refunded escrows leave storage behind: persistent and hidden-amount
+//! escrows keep their full entry next to their status, and deposits with a memo keep
+//! the memo. Every escrow is queued when it reaches a terminal status, and anyone can
+//! call [`gc`] to clean the oldest ones, reducing entries to their status (as
+//! `prune_spent` does) and removing memos. Ephemeral escrows have nothing left to
+//! clean and are skipped without a bounty. If the admin set a bounty, the caller is paid from the treasury for each
+//! entry cleaned, so storage hygiene does not depend on the operator.
+//!
+//! The bounty should stay below what it costs to create a cleanable entry (network
+//! fees plus the protocol fee of a withdrawal); otherwise keepers can farm it.
+
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    admin, compliance,
+    errors::QuickexError,
+    events::{publish_garbage_collected, publish_gc_bounty_updated},
+    storage, tokens, treasury,
+};
+
+/// Clean up to `limit` queued escrows and pay `keeper` the bounty for each one that
+/// still held storage. Returns how many were cleaned.
+///
+/// # Errors
+/// - [`QuickexError::AccountBlocked`] – a bounty is due and `keeper` is on the
+///   compliance blocklist.
+pub fn gc(env: &Env, keeper: Address, limit: u32) -> Result<u32, QuickexError> {
+    keeper.require_auth();
+
+    let mut cleaned = 0;
+    for _ in 0..limit {
+        let Some(commitment) = storage::pop_gc(env) else {
+            break;
+        };
+        let pruned = storage::prune_hidden_escrow(env, &commitment)
+            || storage::prune_escrow(env, &commitment.clone().into());
+        let memo_removed = storage::remove_memo(env, &commitment);
+        if pruned || memo_removed {
+            cleaned += 1;
+        }
+    }
+
+    let (bounty_token, bounty) = match storage::get_gc_bounty(env) {
+        Some((token, per_entry)) => {
+            let due = per_entry.saturating_mul(i128::from(cleaned));
+            (
+                Some(token.clone()),
+                due.min(treasury::treasury_balance(env, &token)),
+            )
+        }
+        None => (None, 0),
+    };
+    if let (Some(bounty_token), true) = (&bounty_token, bounty > 0) {
+        compliance::require_not_blocked(env, &keeper)?;
+        storage::adjust_accrued_fees(env, bounty_token, -bounty)?;
+        tokens::pay(env, bounty_token, &keeper, bounty)?;
+    }
+
+    publish_garbage_collected(env, keeper, cleaned, bounty_token, bounty);
+    Ok(cleaned)
+}
+
+/// Set the bounty paid from `token`'s treasury per entry cleaned by [`gc`]
+/// (**admin only**). An `amount` of 0 removes the bounty.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::InvalidAmount`] – amount is negative.
+pub fn set_bounty(
+    env: &Env,
+    caller: Address,
+    token: Address,
+    amount: i128,
+) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+    if amount < 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+
+    if amount == 0 {
+        storage::set_gc_bounty(env, None);
+    } else {
+        storage::set_gc_bounty(env, Some(&(token.clone(), amount)));
+    }
+    publish_gc_bounty_updated(env, token, amount, caller);
+    Ok(())
+}
@@ -0,0 +1,69 @@
+//! Groth16 proof verification over BLS12-381.
+//!
+//! Checks the standard Groth16 equation using the pairing host function:
+//!
+//! ```text
+//! e(A, B) = e(α, β) · e(L, γ) · e(C, δ)
+//! L       = IC_0 + Σ x_i · IC_i
+//! ```
+//!
+//! rearranged as a single product `e(-A, B) · e(α, β) · e(L, γ) · e(C, δ) = 1`.
+//! Points use the uncompressed serialization of the soroban BLS12-381 host
+//! functions (96-byte G1, 192-byte G2).
+
+use soroban_sdk::{
+    crypto::bls12_381::{Fr, G1Affine, G2Affine},
+    vec, Env, Vec,
+};
+
+use crate::{
+    bls,
+    errors::QuickexError,
+    types::{Groth16Proof, VerifyingKey},
+};
+
+/// Verify `proof` against `vk` for the given public inputs.
+///
+/// Returns `Ok(false)` when the pairing check fails.
+///
+/// # Errors
+/// - [`QuickexError::InvalidProof`] – the number of public inputs does not match the
+///   key, or a proof point is malformed or outside its prime-order subgroup.
+pub fn verify(
+    env: &Env,
+    vk: &VerifyingKey,
+    proof: &Groth16Proof,
+    public_inputs: &Vec<Fr>,
+) -> Result<bool, QuickexError> {
+    if vk.ic.len() != public_inputs.len() + 1 {
+        return Err(QuickexError::InvalidProof);
+    }
+
+    let (Some(a), Some(b), Some(c)) = (
+        bls::g1_checked(env, &proof.a),
+        bls::g2_checked(env, &proof.b),
+        bls::g1_checked(env, &proof.c),
+    ) else {
+        return Err(QuickexError::InvalidProof);
+    };
+
+    let bls12 = env.crypto().bls12_381();
+
+    let mut vk_x = G1Affine::from_bytes(vk.ic.get_unchecked(0));
+    for (i, input) in public_inputs.iter().enumerate() {
+        let ic = G1Affine::from_bytes(vk.ic.get_unchecked(i as u32 + 1));
+        vk_x = bls12.g1_add(&vk_x, &bls12.g1_mul(&ic, &input));
+    }
+
+    let neg_a = bls::g1_neg(env, &a);
+    let g1_points = vec![env, neg_a, G1Affine::from_bytes(vk.alpha.clone()), vk_x, c];
+    let g2_points = vec![
+        env,
+        b,
+        G2Affine::from_bytes(vk.beta.clone()),
+        G2Affine::from_bytes(vk.gamma.clone()),
+        G2Affine::from_bytes(vk.delta.clone()),
+    ];
+
+    Ok(bls12.pairing_check(g1_points, g2_points))
+}
@@ -1,24 +1,109 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, Vec};
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, MuxedAddress, Symbol, Vec};
 
 mod admin;
+mod bls;
 mod commitment;
 #[cfg(test)]
 mod commitment_test;
+mod compliance;
+mod config;
+#[cfg(test)]
+mod custom_account_test;
 mod errors;
 mod escrow;
+#[cfg(test)]
+mod escrow_fixture;
 mod events;
+mod fees;
+#[cfg(test)]
+mod fees_test;
+#[cfg(test)]
+mod fuzz_test;
+mod gc;
+#[cfg(feature = "zk-verifier")]
+mod groth16;
+#[cfg(test)]
+mod lifecycle_test;
+#[cfg(feature = "zk-verifier")]
+mod merkle;
+mod migration;
+#[cfg(test)]
+mod model_test;
+mod pedersen;
+#[cfg(test)]
+mod pedersen_test;
+mod pool;
+#[cfg(all(test, feature = "privacy-pool"))]
+mod pool_test;
+#[cfg(feature = "zk-verifier")]
+mod poseidon;
+#[cfg(feature = "zk-verifier")]
+mod poseidon_params;
+#[cfg(all(test, feature = "zk-verifier"))]
+mod poseidon_test;
 mod privacy;
+mod relayer;
+#[cfg(test)]
+mod relayer_test;
+#[cfg(feature = "privacy-pool")]
+mod ring;
+#[cfg(all(test, feature = "privacy-pool"))]
+mod ring_test;
+#[cfg(feature = "zk-verifier")]
+mod shielded;
+#[cfg(all(test, feature = "zk-verifier"))]
+mod shielded_test;
+mod solvency;
+#[cfg(test)]
+mod solvency_test;
+#[cfg(test)]
+mod spec_fixture;
+mod stats;
 mod storage;
 #[cfg(test)]
 mod storage_test;
+mod store;
+#[cfg(test)]
+mod store_test;
+mod telemetry;
+#[cfg(test)]
+mod telemetry_test;
 #[cfg(test)]
 mod test;
+#[cfg(test)]
+mod test_fixture;
+mod timelock;
+#[cfg(test)]
+mod timelock_test;
+mod tokens;
+#[cfg(test)]
+mod transfer_test;
+mod treasury;
+mod ttl;
 mod types;
+#[cfg(feature = "zk-verifier")]
+mod verifier;
+#[cfg(all(test, feature = "zk-verifier"))]
+mod verifier_test;
 
-use errors::QuickexError;
+// Entrypoints declare the spec's generic contract `Error`: `QuickexError` has more cases
+// than one spec enum may hold, so its codes are exported per band (see `errors`).
+use errors::QuickexError as Error;
 use storage::*;
-use types::{EscrowEntry, EscrowStatus, PrivacyAwareEscrowView};
+use types::{
+    AdminAction, AdminCouncil, BlindingProof, ClaimablePage, Config, ContractStats, ErrorInfo,
+    EscrowEntry, EscrowStatus, InvariantReport, PendingAdminAction, PrivacyAwareEscrowView,
+    PrivacyHistoryPage, QueuedAdminAction, RelayerConfig, RelayerInfo, RelayerPage, Role,
+    SaltPolicy, UpgradeReport, WithdrawRateLimit, WithdrawReceipt, WithdrawRejection,
+    WithdrawVolumeLimit,
+};
+#[cfg(feature = "zk-verifier")]
+use types::{
+    Groth16Proof, MerklePath, PoseidonField, VerifyingKey, VerifyingKeyRecord, WithdrawPublicInputs,
+};
+#[cfg(feature = "privacy-pool")]
+use types::{PoolStats, RingSignature};
 
 /// QuickEx Privacy Contract
 ///
@@ -39,437 +124,2855 @@ pub struct QuickexContract;
 impl QuickexContract {
     /// Withdraw escrowed funds by proving commitment ownership.
     ///
-    /// The caller (`to`) must authorize; the commitment is recomputed from `to`, `amount`, and `salt`
-    /// and must match an existing pending escrow entry.
+    /// The caller (`to`) must authorize; the commitment is recomputed from `token`, `to`, `amount`,
+    /// and `salt`, must equal `commitment`, and must match an existing pending escrow entry. Returns a [`WithdrawReceipt`] with the
+    /// gross amount, protocol fee and net amount paid to `to`.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `_token` - Reserved; token is stored in the escrow entry
+    /// * `token` - Token the escrow is expected to hold
     /// * `amount` - Amount to withdraw; must be positive and match the escrow amount
     /// * `commitment` - Commitment hash for the escrow being withdrawn
-    /// * `to` - Recipient address (must authorize the call)
+    /// * `to` - Recipient address (must authorize the call); a muxed (`M...`) account is
+    ///   paid directly and its id is reported as `mux_id` in `EscrowWithdrawn`
     /// * `salt` - Salt used when creating the original deposit commitment
     ///
     /// # Errors
     /// * `InvalidAmount` - Amount is zero or negative
     /// * `ContractPaused` - Contract is currently paused
-    /// * `CommitmentMismatch` - Provided commitment does not match (`to`, `amount`, `salt`)
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `CommitmentMismatch` - Provided commitment does not match (`token`, `to`, `amount`, `salt`)
     /// * `CommitmentNotFound` - No escrow exists for the provided commitment
     /// * `EscrowExpired` - Escrow has passed its expiry timestamp
     /// * `AlreadySpent` - Escrow has already been withdrawn or refunded
-    /// * `InvalidCommitment` - Escrow amount does not match the requested amount
+    /// * `AmountMismatch` - Escrow amount does not match the requested amount
+    /// * `TokenMismatch` - Escrow holds a different token than `token`
+    /// * `WeakSalt` - Salt is shorter than the minimum in force when the escrow was created
+    /// * `WithdrawRateLimited` - `to` has used up its withdrawals for the current window
+    /// * `TokenBlocked` - The token is blocked with withdrawals frozen
+    /// * `RateLimited` - The token's withdrawal volume limit for the window is used up
+    /// * `AccountBlocked` - `to` is on the compliance blocklist
     pub fn withdraw(
         env: Env,
-        _token: &Address,
+        token: &Address,
         amount: i128,
-        _commitment: BytesN<32>,
-        to: Address,
+        commitment: BytesN<32>,
+        to: MuxedAddress,
         salt: Bytes,
-    ) -> Result<bool, QuickexError> {
-        if admin::is_paused(&env) {
-            return Err(QuickexError::ContractPaused);
-        }
-        escrow::withdraw(&env, amount, to, salt)
+    ) -> Result<WithdrawReceipt, Error> {
+        admin::require_function_enabled(&env, "withdraw")?;
+        admin::require_op_enabled(&env, admin::PAUSE_WITHDRAWALS)?;
+        escrow::withdraw(&env, token.clone(), amount, Some(commitment), to, salt)
     }
 
-    /// Set a numeric privacy level for an account (legacy/level-based API).
+    /// Withdraw an escrow as [`withdraw`](QuickexContract::withdraw) does, counting
+    /// rejected attempts while withdrawal telemetry is on.
     ///
-    /// Records the level in storage and appends it to the account's privacy history.
-    /// For boolean on/off privacy, prefer [`set_privacy`](QuickexContract::set_privacy).
+    /// With telemetry on (see [`set_withdraw_telemetry`](QuickexContract::set_withdraw_telemetry)),
+    /// a wrong salt, spent escrow or expired escrow is counted, emits `WithdrawRejected`
+    /// with its [`WithdrawRejection`] class and returns `None` instead of failing, since
+    /// a failed call keeps no record. Otherwise returns the [`WithdrawReceipt`].
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `account` - The account to configure
-    /// * `privacy_level` - Numeric level (0 = off, higher = more privacy; interpretation is application-specific)
-    pub fn enable_privacy(env: Env, account: Address, privacy_level: u32) -> bool {
-        set_privacy_level(&env, &account, privacy_level);
-        add_privacy_history(&env, &account, privacy_level);
-        true
-    }
-
-    /// Get the current numeric privacy level for an account.
-    ///
-    /// Returns `None` if no level has been set.
+    /// * `token` - Token the escrow is expected to hold
+    /// * `amount` - Amount to withdraw; must be positive and match the escrow amount
+    /// * `commitment` - Commitment hash for the escrow being withdrawn
+    /// * `to` - Recipient address (must authorize the call)
+    /// * `salt` - Salt used when creating the original deposit commitment
     ///
-    /// # Arguments
-    /// * `env` - The contract environment
-    /// * `account` - The account to query
-    pub fn privacy_status(env: Env, account: Address) -> Option<u32> {
-        get_privacy_level(&env, &account)
+    /// # Errors
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * Any error from [`withdraw`](QuickexContract::withdraw), except a counted
+    ///   rejection while telemetry is on
+    pub fn attempt_withdraw(
+        env: Env,
+        token: Address,
+        amount: i128,
+        commitment: BytesN<32>,
+        to: MuxedAddress,
+        salt: Bytes,
+    ) -> Result<Option<WithdrawReceipt>, Error> {
+        admin::require_function_enabled(&env, "attempt_withdraw")?;
+        admin::require_op_enabled(&env, admin::PAUSE_WITHDRAWALS)?;
+        telemetry::attempt_withdraw(&env, token, amount, commitment, to, salt)
     }
 
-    /// Get the history of privacy level changes for an account.
-    ///
-    /// Returns a vector of levels in chronological order (oldest first).
+    /// Withdraw an escrow on behalf of its owner to a different payout address.
     ///
-    /// # Arguments
-    /// * `env` - The contract environment
-    /// * `account` - The account to query
-    pub fn privacy_history(env: Env, account: Address) -> Vec<u32> {
-        get_privacy_history(&env, &account)
-    }
-
-    /// Enable or disable privacy for an account.
+    /// For escrows whose owner is a Stellar account: the owner signs
+    /// `"quickex/delegate" || XDR(contract) || commitment || XDR(payout_to)` with the
+    /// account's ed25519 key, and anyone (e.g. an exchange or custodian) submits the
+    /// signature to receive the funds at `payout_to`. The owner need not authorize the
+    /// invocation itself. Returns a [`WithdrawReceipt`] as [`withdraw`](QuickexContract::withdraw)
+    /// does.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `owner` - The account address to configure
-    /// * `enabled` - `true` to enable privacy, `false` to disable
+    /// * `token` - Token the escrow is expected to hold
+    /// * `payout_to` - Address that receives the funds
+    /// * `amount` - Escrowed amount; must match the escrow
+    /// * `salt` - Salt used when creating the original deposit commitment
+    /// * `owner_pubkey` - ed25519 public key of the owner account
+    /// * `signature` - Owner's ed25519 signature over the delegation message
     ///
     /// # Errors
     /// * `ContractPaused` - Contract is currently paused
-    /// * `PrivacyAlreadySet` - Privacy state is already at the requested value
-    pub fn set_privacy(env: Env, owner: Address, enabled: bool) -> Result<(), QuickexError> {
-        if admin::is_paused(&env) {
-            return Err(QuickexError::ContractPaused);
-        }
-        privacy::set_privacy(&env, owner, enabled)
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `InvalidAmount` - Amount is zero or negative
+    /// * `CommitmentNotFound` - No escrow exists for (owner, `amount`, `salt`)
+    /// * `EscrowExpired` - Escrow has passed its expiry timestamp
+    /// * `AlreadySpent` - Escrow has already been withdrawn or refunded
+    /// * `InvalidSignature` - Signature is malformed (non-canonical scalar)
+    ///
+    /// Traps if a well-formed signature does not verify.
+    pub fn withdraw_delegated(
+        env: Env,
+        token: Address,
+        payout_to: Address,
+        amount: i128,
+        salt: Bytes,
+        owner_pubkey: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> Result<WithdrawReceipt, Error> {
+        admin::require_function_enabled(&env, "withdraw_delegated")?;
+        admin::require_op_enabled(&env, admin::PAUSE_WITHDRAWALS)?;
+        escrow::withdraw_delegated(
+            &env,
+            token,
+            payout_to,
+            amount,
+            salt,
+            owner_pubkey,
+            signature,
+        )
     }
 
-    /// Check the current privacy status of an account
+    /// Announce a withdrawal by its hash, first step of a commit-reveal withdrawal.
+    ///
+    /// `announcement` is
+    /// `SHA256("quickex/announce" || XDR(to) || amount (16-byte BE) || salt || nonce)`
+    /// with a random 32-byte `nonce`, computed off-chain. Nothing about the escrow is revealed
+    /// until [`finalize_withdraw`](QuickexContract::finalize_withdraw).
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `owner` - The account address to query
+    /// * `announcement` - Hash of the withdrawal to be finalized later
     ///
     /// # Returns
-    /// * `bool` - Current privacy status (true = enabled)
-    pub fn get_privacy(env: Env, owner: Address) -> bool {
-        privacy::get_privacy(&env, owner)
+    /// The ledger sequence the announcement was recorded at.
+    ///
+    /// # Errors
+    /// * `ContractPaused` - Contract is currently paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `AlreadyAnnounced` - This hash is already pending
+    pub fn announce_withdraw(env: Env, announcement: BytesN<32>) -> Result<u32, Error> {
+        admin::require_function_enabled(&env, "announce_withdraw")?;
+        admin::require_op_enabled(&env, admin::PAUSE_WITHDRAWALS)?;
+        escrow::announce_withdraw(&env, announcement)
     }
 
-    /// Deposit funds and create an escrow entry keyed by `SHA256(owner || amount || salt)`.
+    /// Reveal and execute an announced withdrawal.
     ///
-    /// Transfers `amount` from `owner` to the contract and stores an escrow entry.
+    /// Must be called at least one ledger after
+    /// [`announce_withdraw`](QuickexContract::announce_withdraw). A front-runner copying
+    /// the revealed salt cannot have announced a matching hash for their own address
+    /// beforehand, so the claim stays with `to`.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `token` - The token contract address
-    /// * `amount` - Amount to deposit; must be positive
-    /// * `owner` - Owner of the funds (must authorize)
-    /// * `salt` - Random salt (0–1024 bytes) for uniqueness
-    /// * `timeout_secs` - Seconds from now until the escrow expires (0 = no expiry)
+    /// * `token` - Token the escrow is expected to hold
+    /// * `to` - Recipient address (must authorize the call)
+    /// * `amount` - Escrowed amount; must match the escrow
+    /// * `salt` - Salt used when creating the original deposit commitment
+    /// * `nonce` - Nonce used in the announcement hash
     ///
     /// # Errors
-    /// * `InvalidAmount` - Amount is zero or negative
-    /// * `InvalidSalt` - Salt length exceeds 1024 bytes
     /// * `ContractPaused` - Contract is currently paused
-    /// * `CommitmentAlreadyExists` - An escrow for this commitment already exists
-    pub fn deposit(
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `AnnouncementNotFound` - No announcement matches (`to`, `amount`, `salt`, `nonce`)
+    /// * `RevealTooEarly` - Announcement was made in the current ledger
+    /// * `CommitmentNotFound` - No escrow exists for (`to`, `amount`, `salt`)
+    /// * `EscrowExpired` - Escrow has passed its expiry timestamp
+    /// * `AlreadySpent` - Escrow has already been withdrawn or refunded
+    pub fn finalize_withdraw(
         env: Env,
         token: Address,
+        to: Address,
         amount: i128,
-        owner: Address,
         salt: Bytes,
-        timeout_secs: u64,
-    ) -> Result<BytesN<32>, QuickexError> {
-        if admin::is_paused(&env) {
-            return Err(QuickexError::ContractPaused);
-        }
-        escrow::deposit(&env, token, amount, owner, salt, timeout_secs)
+        nonce: BytesN<32>,
+    ) -> Result<bool, Error> {
+        admin::require_function_enabled(&env, "finalize_withdraw")?;
+        admin::require_op_enabled(&env, admin::PAUSE_WITHDRAWALS)?;
+        escrow::finalize_withdraw(&env, token, to, amount, salt, nonce)
     }
 
-    /// Create a deterministic commitment hash for an amount (off-chain / pre-deposit use).
+    /// Reserve an escrow for a two-phase withdrawal and return its ticket id.
     ///
-    /// Computes `SHA256(owner || amount || salt)`. Not a zero-knowledge proof; same inputs
-    /// always yield the same hash. Use for API shape validation and audit trails.
+    /// Runs the checks of `withdraw` without moving funds and reserves the escrow for
+    /// about 10 minutes (`WITHDRAW_TICKET_LEDGERS`), so an integrating contract can run
+    /// its own logic before calling `commit_withdraw`, or `abort_withdraw` to roll back.
+    /// Other withdrawals and refunds of the escrow fail while the ticket lives.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `owner` - The owner address
-    /// * `amount` - Non-negative amount in token base units
-    /// * `salt` - Random bytes (0–1024 bytes) for uniqueness
+    /// * `token` - Token the escrow is expected to hold
+    /// * `amount` - Escrow amount
+    /// * `to` - Recipient (must authorize)
+    /// * `salt` - Salt used when creating the deposit commitment
     ///
     /// # Errors
-    /// * `InvalidAmount` - Amount is negative
-    /// * `InvalidSalt` - Salt length exceeds 1024 bytes
-    pub fn create_amount_commitment(
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `EscrowReserved` - The escrow is already reserved by a live ticket
+    /// * Any error from `withdraw` except `WithdrawRateLimited` and `RateLimited`,
+    ///   which are checked on commit
+    pub fn prepare_withdraw(
         env: Env,
-        owner: Address,
+        token: Address,
         amount: i128,
+        to: Address,
         salt: Bytes,
-    ) -> Result<BytesN<32>, QuickexError> {
-        commitment::create_amount_commitment(&env, owner, amount, salt)
+    ) -> Result<BytesN<32>, Error> {
+        admin::require_function_enabled(&env, "prepare_withdraw")?;
+        admin::require_op_enabled(&env, admin::PAUSE_WITHDRAWALS)?;
+        escrow::prepare_withdraw(&env, token, amount, to, salt)
     }
 
-    /// Verify that a commitment hash matches the given `owner`, `amount`, and `salt`.
+    /// Settle a ticket from `prepare_withdraw`, paying its recipient as `withdraw` would.
     ///
-    /// Recomputes the commitment and compares. Returns `false` if inputs are invalid or don't match.
+    /// The ticket's recipient must authorize.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `commitment` - 32-byte commitment hash to verify
-    /// * `owner` - Claimed owner
-    /// * `amount` - Claimed amount (must be non-negative)
-    /// * `salt` - Salt used when creating the commitment
-    pub fn verify_amount_commitment(
-        env: Env,
-        commitment: BytesN<32>,
-        owner: Address,
-        amount: i128,
-        salt: Bytes,
-    ) -> bool {
-        commitment::verify_amount_commitment(&env, commitment, owner, amount, salt)
+    /// * `ticket` - Ticket id returned by `prepare_withdraw`
+    ///
+    /// # Errors
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `TicketNotFound` - No live ticket has this id
+    /// * Any error from `withdraw`
+    pub fn commit_withdraw(env: Env, ticket: BytesN<32>) -> Result<bool, Error> {
+        admin::require_function_enabled(&env, "commit_withdraw")?;
+        admin::require_op_enabled(&env, admin::PAUSE_WITHDRAWALS)?;
+        escrow::commit_withdraw(&env, ticket)
     }
 
-    /// Create an escrow record and increment the global escrow counter.
+    /// Release a ticket from `prepare_withdraw` without moving funds.
     ///
-    /// Returns the new counter value. Parameters `_from`, `_to`, `_amount` are reserved for
-    /// future use; the implementation only increments the counter.
+    /// The ticket's recipient must authorize. Never paused or disabled, so reservations
+    /// can always be rolled back.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `_from` - Reserved (depositor address for future use)
-    /// * `_to` - Reserved (recipient address for future use)
-    /// * `_amount` - Reserved (amount for future use)
-    pub fn create_escrow(env: Env, _from: Address, _to: Address, _amount: u64) -> u64 {
-        increment_escrow_counter(&env)
-    }
-
-    /// Health check for deployment and monitoring.
+    /// * `ticket` - Ticket id returned by `prepare_withdraw`
     ///
-    /// Returns `true` if the contract is deployed and callable. No state or auth required.
-    pub fn health_check() -> bool {
-        true
+    /// # Errors
+    /// * `TicketNotFound` - No live ticket has this id
+    pub fn abort_withdraw(env: Env, ticket: BytesN<32>) -> Result<(), Error> {
+        escrow::abort_withdraw(&env, ticket)
     }
 
-    /// Deposit funds using a pre-generated 32-byte commitment hash.
+    /// Withdraw escrowed funds through a relayer that submits the transaction.
     ///
-    /// Transfers `amount` from `from` to the contract and stores an escrow keyed by
-    /// `commitment`. The depositor must authorize. Use when the commitment was created
-    /// off-chain or via [`create_amount_commitment`](QuickexContract::create_amount_commitment).
+    /// The relayer pays the network fee and receives `fee` out of the escrowed amount, so
+    /// recipients holding no XLM can still claim. `to` must authorize the invocation,
+    /// which binds `relayer` and `fee`; `to` receives `amount - fee`. The relayer must be
+    /// active in the relayer registry and authorize.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `from` - Depositor (must authorize the token transfer)
-    /// * `token` - Token contract address
-    /// * `amount` - Amount to deposit; must be positive
-    /// * `commitment` - 32-byte commitment hash (must be unique)
-    /// * `timeout_secs` - Seconds from now until the escrow expires (0 = no expiry)
+    /// * `relayer` - Active relayer submitting the call (must authorize); receives `fee`
+    /// * `token` - Token the escrow is expected to hold
+    /// * `to` - Recipient address (must authorize the call)
+    /// * `amount` - Escrowed amount; must match the escrow
+    /// * `salt` - Salt used when creating the original deposit commitment
+    /// * `fee` - Relayer fee, between 0 and `amount`
     ///
     /// # Errors
-    /// * `InvalidAmount` - Amount is zero or negative
     /// * `ContractPaused` - Contract is currently paused
-    /// * `CommitmentAlreadyExists` - An escrow for this commitment already exists
-    pub fn deposit_with_commitment(
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `InvalidFee` - Fee is negative or exceeds `amount`
+    /// * `RelayerNotActive` - Relayer is not staked and active
+    /// * `InvalidAmount` - Amount is zero or negative
+    /// * `CommitmentNotFound` - No escrow exists for (`to`, `amount`, `salt`)
+    /// * `EscrowExpired` - Escrow has passed its expiry timestamp
+    /// * `AlreadySpent` - Escrow has already been withdrawn or refunded
+    pub fn withdraw_via_relayer(
         env: Env,
-        from: Address,
+        relayer: Address,
         token: Address,
+        to: Address,
         amount: i128,
-        commitment: BytesN<32>,
-        timeout_secs: u64,
-    ) -> Result<(), QuickexError> {
-        if admin::is_paused(&env) {
-            return Err(QuickexError::ContractPaused);
-        }
-        escrow::deposit_with_commitment(&env, from, token, amount, commitment, timeout_secs)
+        salt: Bytes,
+        fee: i128,
+    ) -> Result<bool, Error> {
+        admin::require_function_enabled(&env, "withdraw_via_relayer")?;
+        admin::require_op_enabled(&env, admin::PAUSE_WITHDRAWALS)?;
+        escrow::withdraw_via_relayer(&env, relayer, token, to, amount, salt, fee)
     }
 
-    /// Refund an expired escrow back to its original owner.
+    /// Configure relayer staking (**Admin only**).
     ///
-    /// Can only be called after `expires_at` is reached. The caller must be the
-    /// original depositor. The escrow must still be `Pending`.
+    /// The stake token is fixed by the first call; later calls may only change the
+    /// minimum stake and unbonding period.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `commitment` - 32-byte commitment hash identifying the escrow
-    /// * `caller` - Must equal the original depositor (must authorize)
+    /// * `caller` - Caller address (must equal admin)
+    /// * `stake_token` - Token relayers must stake
+    /// * `min_stake` - Minimum stake for a relayer to be active; must be positive
+    /// * `unbonding_period` - Seconds a relayer must wait after `unstake_relayer` before withdrawing
     ///
     /// # Errors
-    /// * `CommitmentNotFound` - No escrow exists for the commitment
-    /// * `AlreadySpent` - Escrow is already in a terminal state
-    /// * `EscrowNotExpired` - Escrow has no expiry or has not yet expired
-    /// * `InvalidOwner` - Caller is not the original owner
-    pub fn refund(env: Env, commitment: BytesN<32>, caller: Address) -> Result<(), QuickexError> {
-        escrow::refund(&env, commitment, caller)
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `InvalidRelayerConfig` - `min_stake` is not positive, or `stake_token` changed
+    pub fn set_relayer_config(
+        env: Env,
+        caller: Address,
+        stake_token: Address,
+        min_stake: i128,
+        unbonding_period: u64,
+    ) -> Result<(), Error> {
+        relayer::set_relayer_config(&env, caller, stake_token, min_stake, unbonding_period)
     }
 
-    /// Initialize the contract with an admin address (one-time only).
+    /// Get the relayer staking configuration (read-only).
+    ///
+    /// Returns `None` if staking has not been configured.
+    pub fn get_relayer_config(env: Env) -> Option<RelayerConfig> {
+        relayer::get_relayer_config(&env)
+    }
+
+    /// Stake tokens to become (or stay) an eligible relayer.
     ///
-    /// Sets the admin who can pause/unpause, transfer admin, and upgrade the contract.
+    /// The relayer becomes active once its total stake reaches the minimum. Staking while
+    /// unbonding cancels the unbonding.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `admin` - The admin address to set
+    /// * `relayer` - Relayer address (must authorize the token transfer)
+    /// * `amount` - Amount of the stake token to lock; must be positive
     ///
     /// # Errors
-    /// * `AlreadyInitialized` - Contract has already been initialized
-    pub fn initialize(env: Env, admin: Address) -> Result<(), QuickexError> {
-        admin::initialize(&env, admin)
+    /// * `ContractPaused` - Contract is currently paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `RelayerConfigNotSet` - Staking has not been configured
+    /// * `InvalidAmount` - Amount is zero or negative
+    pub fn stake_relayer(env: Env, relayer: Address, amount: i128) -> Result<(), Error> {
+        admin::require_function_enabled(&env, "stake_relayer")?;
+        admin::require_op_enabled(&env, admin::PAUSE_DEPOSITS)?;
+        relayer::stake(&env, relayer, amount)
     }
 
-    /// Pause or unpause the contract (**Admin only**).
+    /// Stop relaying and start the unbonding period.
     ///
-    /// When paused, certain operations may be blocked. Caller must equal the stored admin.
+    /// The relayer is removed from the active set immediately; its stake remains
+    /// slashable until withdrawn.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `caller` - Caller address (must equal admin)
-    /// * `new_state` - `true` to pause, `false` to unpause
+    /// * `relayer` - Relayer address (must authorize)
     ///
     /// # Errors
-    /// * `Unauthorized` - Caller is not the admin, or admin not set
-    pub fn set_paused(env: Env, caller: Address, new_state: bool) -> Result<(), QuickexError> {
-        admin::set_paused(&env, caller, new_state)
+    /// * `RelayerNotRegistered` - Relayer has no stake
+    pub fn unstake_relayer(env: Env, relayer: Address) -> Result<(), Error> {
+        admin::require_function_enabled(&env, "unstake_relayer")?;
+        relayer::unstake(&env, relayer)
     }
 
-    /// Transfer admin rights to a new address (**Admin only**).
+    /// Withdraw a relayer's remaining stake after unbonding. Returns the amount returned.
     ///
-    /// Caller must equal the current admin. The new admin can later transfer again.
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `relayer` - Relayer address (must authorize)
+    ///
+    /// # Errors
+    /// * `RelayerNotRegistered` - Relayer has no stake
+    /// * `StakeLocked` - Relayer has not unstaked or unbonding has not finished
+    pub fn withdraw_relayer_stake(env: Env, relayer: Address) -> Result<i128, Error> {
+        admin::require_function_enabled(&env, "withdraw_relayer_stake")?;
+        relayer::withdraw_stake(&env, relayer)
+    }
+
+    /// Slash a relayer's stake for censorship or griefing (**Admin only**).
+    ///
+    /// Slashed tokens are sent to the admin. The relayer is deactivated if its stake
+    /// falls below the minimum.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `caller` - Caller address (must equal current admin)
-    /// * `new_admin` - New admin address
+    /// * `caller` - Caller address (must equal admin)
+    /// * `relayer` - Relayer to slash
+    /// * `amount` - Amount to slash; must be positive and at most the current stake
+    /// * `reason` - Short reason code recorded in the event
     ///
     /// # Errors
     /// * `Unauthorized` - Caller is not the admin, or admin not set
-    pub fn set_admin(env: Env, caller: Address, new_admin: Address) -> Result<(), QuickexError> {
-        admin::set_admin(&env, caller, new_admin)
+    /// * `RelayerNotRegistered` - Relayer has no stake
+    /// * `InvalidAmount` - Amount is not positive or exceeds the stake
+    pub fn slash_relayer(
+        env: Env,
+        caller: Address,
+        relayer: Address,
+        amount: i128,
+        reason: Symbol,
+    ) -> Result<(), Error> {
+        relayer::slash(&env, caller, relayer, amount, reason)
     }
 
-    /// Check if the contract is currently paused.
+    /// Get a relayer's stake record (read-only).
     ///
-    /// Returns `true` if paused, `false` otherwise.
-    pub fn is_paused(env: Env) -> bool {
-        admin::is_paused(&env)
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `relayer` - Relayer address
+    pub fn get_relayer(env: Env, relayer: Address) -> Option<RelayerInfo> {
+        relayer::get_relayer(&env, &relayer)
     }
 
-    /// Get the current admin address.
+    /// List relayers currently eligible to submit relayed withdrawals (read-only).
     ///
-    /// Returns `None` if the contract has not been initialized.
-    pub fn get_admin(env: Env) -> Option<Address> {
-        admin::get_admin(&env)
+    /// Clients pick a relayer from this list and inspect its stake with
+    /// [`get_relayer`](QuickexContract::get_relayer).
+    pub fn get_active_relayers(env: Env) -> Vec<Address> {
+        relayer::get_active_relayers(&env)
     }
 
-    /// Get the status of an escrow by its commitment hash (read-only).
+    /// Get one page of the relayers `get_active_relayers` returns (read-only).
     ///
-    /// Returns `Pending`, `Spent`, `Expired`, or `Refunded` if an escrow exists; `None` otherwise.
+    /// Suited to large registries, whose full list may not fit in one call. Pass
+    /// `cursor = 0` for the first page, then the returned `next_cursor` until it is
+    /// `None`. Pages hold up to 32 relayers and may be empty while more follow.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `commitment` - 32-byte commitment hash used as the escrow key
-    pub fn get_commitment_state(env: Env, commitment: BytesN<32>) -> Option<EscrowStatus> {
-        let commitment_bytes: Bytes = commitment.into();
-        let entry: Option<EscrowEntry> = get_escrow(&env, &commitment_bytes);
-        entry.map(|e| e.status)
+    /// * `cursor` - Page to read
+    pub fn get_active_relayers_page(env: Env, cursor: u32) -> RelayerPage {
+        relayer::get_active_relayers_page(&env, cursor)
     }
 
-    /// Verify withdrawal parameters without submitting a transaction (read-only).
+    /// Set a numeric privacy level for an account.
     ///
-    /// Recomputes the commitment from `amount`, `salt`, and `owner`, then checks that an
-    /// escrow exists with status `Pending`, matching amount, and not yet expired.
+    /// Records the level in storage and appends it to the account's privacy history.
+    /// The level controls what views and escrow events disclose about the account's
+    /// escrows to third parties:
+    ///
+    /// * `0` - Public: all details shown
+    /// * `1` - Hide amounts
+    /// * `2` - Hide owner and amounts; `get_commitment_state` returns `None`
+    ///
+    /// The boolean [`set_privacy`](QuickexContract::set_privacy) toggle, when on, acts as level 2.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `amount` - Amount to verify (non-negative)
-    /// * `salt` - Salt used when creating the deposit
-    /// * `owner` - Owner of the escrow
-    pub fn verify_proof_view(env: Env, amount: i128, salt: Bytes, owner: Address) -> bool {
-        let commitment_result =
-            commitment::create_amount_commitment(&env, owner.clone(), amount, salt);
-
-        let commitment = match commitment_result {
-            Ok(c) => c,
-            Err(_) => return false,
-        };
-
-        let commitment_bytes: Bytes = commitment.into();
-        let entry: Option<EscrowEntry> = get_escrow(&env, &commitment_bytes);
-
-        match entry {
-            Some(e) => {
-                if e.status != EscrowStatus::Pending {
-                    return false;
-                }
-                if e.expires_at > 0 && env.ledger().timestamp() >= e.expires_at {
-                    return false;
-                }
-                e.amount == amount
-            }
-            None => false,
-        }
+    /// * `account` - The account to configure (must authorize)
+    /// * `privacy_level` - Level 0–2
+    ///
+    /// # Errors
+    /// * `InvalidPrivacyLevel` - Level is greater than 2
+    pub fn enable_privacy(env: Env, account: Address, privacy_level: u32) -> Result<bool, Error> {
+        admin::require_function_enabled(&env, "enable_privacy")?;
+        privacy::set_privacy_level(&env, account, privacy_level)?;
+        Ok(true)
     }
 
-    /// Get a privacy-aware view of escrow details for a commitment hash (read-only).
-    ///
-    /// Returns a [`PrivacyAwareEscrowView`] if an escrow exists for the commitment,
-    /// or `None` otherwise.
+    /// Get the current numeric privacy level for an account.
     ///
-    /// ## Privacy behaviour
-    /// - If the escrow owner **has privacy enabled** and `caller` is **not** the owner,
-    ///   the `amount` and `owner` fields are returned as `None`.
-    /// - If privacy is **disabled**, or `caller` equals the escrow owner,
-    ///   all fields are returned in full.
+    /// Returns `None` if no level has been set.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `commitment` - 32-byte commitment hash identifying the escrow
+    /// * `account` - The account to query
+    pub fn privacy_status(env: Env, account: Address) -> Option<u32> {
+        get_privacy_level(&env, &account)
+    }
+
+    /// Get the history of privacy level changes for an account.
+    ///
+    /// Returns the levels newest first. Only the most recent 32 changes are kept; use
+    /// [`privacy_history_page`](QuickexContract::privacy_history_page) for timestamps.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `account` - The account to query
+    pub fn privacy_history(env: Env, account: Address) -> Vec<u32> {
+        privacy::privacy_history(&env, &account)
+    }
+
+    /// Get one page of an account's privacy history, with timestamps (read-only).
+    ///
+    /// Entries are newest first. Pass `cursor = 0` for the first page, then the returned
+    /// `next_cursor` until it is `None`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `account` - The account to query
+    /// * `cursor` - Number of newer entries to skip
+    /// * `limit` - Maximum entries to return (clamped to 20)
+    pub fn privacy_history_page(
+        env: Env,
+        account: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> PrivacyHistoryPage {
+        privacy::privacy_history_page(&env, &account, cursor, limit)
+    }
+
+    /// Enable or disable privacy for an account.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `owner` - The account address to configure
+    /// * `enabled` - `true` to enable privacy, `false` to disable
+    ///
+    /// # Errors
+    /// * `ContractPaused` - Contract is currently paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `PrivacyAlreadySet` - Privacy state is already at the requested value
+    pub fn set_privacy(env: Env, owner: Address, enabled: bool) -> Result<(), Error> {
+        admin::require_function_enabled(&env, "set_privacy")?;
+        if admin::is_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        privacy::set_privacy(&env, owner, enabled)
+    }
+
+    /// Check the current privacy status of an account
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `owner` - The account address to query
+    ///
+    /// # Returns
+    /// * `bool` - Current privacy status (true = enabled)
+    pub fn get_privacy(env: Env, owner: Address) -> bool {
+        privacy::get_privacy(&env, owner)
+    }
+
+    /// List the pending escrows an owner can withdraw or refund (read-only).
+    ///
+    /// Lets wallets discover unclaimed escrows without scanning events since genesis.
+    /// Escrows from `deposit` are listed under their owner and escrows from
+    /// `deposit_with_commitment` under the depositor. Nothing is indexed or listed for
+    /// owners at privacy level 2 (or with the boolean toggle on).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `owner` - The account to query
+    pub fn list_claimable(env: Env, owner: Address) -> Vec<BytesN<32>> {
+        privacy::list_claimable(&env, &owner)
+    }
+
+    /// Get one page of the escrows `list_claimable` returns (read-only).
+    ///
+    /// Suited to owners with many pending escrows, whose full list may not fit in one
+    /// call. Pass `cursor = 0` for the first page, then the returned `next_cursor` until
+    /// it is `None`. Pages hold up to 32 escrows, oldest first, and may be empty while
+    /// more follow.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `owner` - The account to query
+    /// * `cursor` - Page to read
+    pub fn list_claimable_page(env: Env, owner: Address, cursor: u32) -> ClaimablePage {
+        privacy::list_claimable_page(&env, &owner, cursor)
+    }
+
+    /// Limit how many withdrawals an account can perform per time window.
+    ///
+    /// Applies to withdrawals where `account` is the commitment owner, whether called
+    /// directly, through a relayer, or delegated, and to hidden-amount withdrawals.
+    /// Windows are fixed: the first withdrawal after a window closes opens a new one.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `account` - The account to configure (must authorize)
+    /// * `max_withdrawals` - Withdrawals allowed per window; `0` removes the limit
+    /// * `window_secs` - Window length in seconds
+    ///
+    /// # Errors
+    /// * `InvalidRateLimit` - `max_withdrawals` is positive and `window_secs` is zero
+    pub fn set_withdraw_rate_limit(
+        env: Env,
+        account: Address,
+        max_withdrawals: u32,
+        window_secs: u64,
+    ) -> Result<(), Error> {
+        privacy::set_withdraw_rate_limit(&env, account, max_withdrawals, window_secs)
+    }
+
+    /// Get an account's withdrawal rate limit, if any (read-only).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `account` - The account to query
+    pub fn get_withdraw_rate_limit(env: Env, account: Address) -> Option<WithdrawRateLimit> {
+        get_withdraw_rate_limit(&env, &account)
+    }
+
+    /// Deposit funds and create an escrow entry keyed by `SHA256(owner || amount || salt)`.
+    ///
+    /// Transfers `amount` from `owner` to the contract and stores an escrow entry.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token` - The token contract address
+    /// * `amount` - Amount to deposit; must be positive
+    /// * `owner` - Owner of the funds (must authorize)
+    /// * `salt` - Random salt (1–1024 bytes) for uniqueness
+    /// * `timeout_secs` - Seconds from now until the escrow expires (0 = the configured
+    ///   default claim window, which is no expiry unless set)
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - Amount is zero or negative
+    /// * `InvalidSalt` - Salt length exceeds 1024 bytes
+    /// * `WeakSalt` - Salt is empty or shorter than the minimum set with `set_min_salt_len`
+    /// * `ContractPaused` - Contract is currently paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `TokenNotAllowed` - The token allowlist is in force and the token is not on it
+    /// * `CapExceeded` - The deposit would exceed the token's cap or the global cap
+    /// * `InvalidTimeout` - `timeout_secs` is above the configured maximum claim window
+    /// * `CommitmentAlreadyExists` - An escrow for this commitment already exists
+    pub fn deposit(
+        env: Env,
+        token: Address,
+        amount: i128,
+        owner: Address,
+        salt: Bytes,
+        timeout_secs: u64,
+    ) -> Result<BytesN<32>, Error> {
+        admin::require_function_enabled(&env, "deposit")?;
+        admin::require_op_enabled(&env, admin::PAUSE_DEPOSITS)?;
+        escrow::deposit(&env, token, amount, owner, salt, timeout_secs)
+    }
+
+    /// Create a deterministic commitment hash for an amount (off-chain / pre-deposit use).
+    ///
+    /// Computes `SHA256(owner || amount || salt)`. Not a zero-knowledge proof; same inputs
+    /// always yield the same hash. Use for API shape validation and audit trails.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `owner` - The owner address
+    /// * `amount` - Non-negative amount in token base units
+    /// * `salt` - Random bytes (0–1024 bytes) for uniqueness
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - Amount is negative
+    /// * `InvalidSalt` - Salt length exceeds 1024 bytes
+    pub fn create_amount_commitment(
+        env: Env,
+        owner: Address,
+        amount: i128,
+        salt: Bytes,
+    ) -> Result<BytesN<32>, Error> {
+        commitment::create_amount_commitment(&env, owner, amount, salt)
+    }
+
+    /// Create a token-bound (scheme v2) commitment for a deposit.
+    ///
+    /// Computes `SHA256(0x02 || token || owner || amount || salt)`. Pass the result to
+    /// [`deposit_with_commitment`](QuickexContract::deposit_with_commitment) with the
+    /// same `token`; unlike a v1 commitment it can only match an escrow of that token.
+    /// Withdrawals accept every scheme.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token` - Token the escrow will hold
+    /// * `owner` - The owner address
+    /// * `amount` - Non-negative amount in token base units
+    /// * `salt` - Random bytes (0–1024 bytes) for uniqueness
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - Amount is negative
+    /// * `InvalidSalt` - Salt length exceeds 1024 bytes
+    pub fn create_token_commitment(
+        env: Env,
+        token: Address,
+        owner: Address,
+        amount: i128,
+        salt: Bytes,
+    ) -> Result<BytesN<32>, Error> {
+        commitment::create_token_commitment(&env, token, owner, amount, salt)
+    }
+
+    /// Create a fixed-layout (scheme v3) commitment for a deposit.
+    ///
+    /// Computes `SHA256(0x03 || KEY(token) || KEY(owner) || amount || salt)`, where
+    /// `KEY(addr)` is a type tag (0 = account, 1 = contract) and the 32-byte key. Binds
+    /// the token like scheme v2, but clients can build the preimage from raw keys without
+    /// XDR-encoding addresses. Pass the result to
+    /// [`deposit_with_commitment`](QuickexContract::deposit_with_commitment).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token` - Token the escrow will hold
+    /// * `owner` - The owner address
+    /// * `amount` - Non-negative amount in token base units
+    /// * `salt` - Random bytes (0–1024 bytes) for uniqueness
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - Amount is negative
+    /// * `InvalidSalt` - Salt length exceeds 1024 bytes
+    /// * `UnsupportedAddress` - `token` or `owner` is neither an account nor a contract
+    pub fn create_fixed_commitment(
+        env: Env,
+        token: Address,
+        owner: Address,
+        amount: i128,
+        salt: Bytes,
+    ) -> Result<BytesN<32>, Error> {
+        commitment::create_fixed_commitment(&env, token, owner, amount, salt)
+    }
+
+    /// Verify that a commitment hash matches the given `owner`, `amount`, and `salt`.
+    ///
+    /// Recomputes the commitment and compares. Returns `false` if inputs are invalid or don't match.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `commitment` - 32-byte commitment hash to verify
+    /// * `owner` - Claimed owner
+    /// * `amount` - Claimed amount (must be non-negative)
+    /// * `salt` - Salt used when creating the commitment
+    pub fn verify_amount_commitment(
+        env: Env,
+        commitment: BytesN<32>,
+        owner: Address,
+        amount: i128,
+        salt: Bytes,
+    ) -> bool {
+        commitment::verify_amount_commitment(&env, commitment, owner, amount, salt)
+    }
+
+    /// Verify many commitments in one call (read-only).
+    ///
+    /// Returns one result per item, in order, each as
+    /// [`verify_amount_commitment`](QuickexContract::verify_amount_commitment) would.
+    /// Meant for indexers and reconciliation jobs simulating a single call instead of
+    /// one round-trip per commitment; the batch size is bounded only by the budget.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `items` - `(commitment, owner, amount, salt)` tuples to verify
+    pub fn verify_amount_commitments(
+        env: Env,
+        items: Vec<(BytesN<32>, Address, i128, Bytes)>,
+    ) -> Vec<bool> {
+        commitment::verify_amount_commitments(&env, items)
+    }
+
+    /// Create an escrow record and increment the global escrow counter.
+    ///
+    /// Returns the new counter value. No tokens move; the implementation only increments
+    /// the counter and emits `EscrowCreated` with the id and the given parameters.
+    /// `from` must authorize, so the event cannot be forged on someone else's behalf.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Depositor address (must authorize)
+    /// * `to` - Recipient address
+    /// * `token` - Token contract address of the escrow
+    /// * `amount` - Escrow amount; must be positive
+    ///
+    /// # Errors
+    /// * `ContractPaused` - Escrow creation is paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `InvalidAmount` - Amount is zero
+    /// * `SameParticipants` - `from` and `to` are the same address
+    pub fn create_escrow(
+        env: Env,
+        from: Address,
+        to: Address,
+        token: Address,
+        amount: u64,
+    ) -> Result<u64, Error> {
+        admin::require_function_enabled(&env, "create_escrow")?;
+        admin::require_op_enabled(&env, admin::PAUSE_ESCROW_CREATION)?;
+        if amount == 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if from == to {
+            return Err(Error::SameParticipants);
+        }
+        from.require_auth();
+        let id = increment_escrow_counter(&env);
+        events::publish_escrow_created(&env, id, from, to, token, amount);
+        Ok(id)
+    }
+
+    /// Create a hiding Pedersen commitment `amount·G + r·H` (off-chain / pre-deposit use).
+    ///
+    /// Unlike [`create_amount_commitment`](QuickexContract::create_amount_commitment), the
+    /// result reveals nothing about `amount` without the blinding factor. Intended for
+    /// simulation only: submitting it on-chain would publish `blinding`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `amount` - Non-negative amount in token base units
+    /// * `blinding` - 32-byte blinding scalar `r` (reduced mod the BLS12-381 group order)
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - Amount is negative
+    pub fn create_pedersen_commitment(
+        env: Env,
+        amount: i128,
+        blinding: BytesN<32>,
+    ) -> Result<BytesN<96>, Error> {
+        pedersen::create_pedersen_commitment(&env, amount, blinding)
+    }
+
+    /// Deposit funds into a hidden-amount escrow.
+    ///
+    /// The stored entry holds only the Pedersen commitment `C = amount·G + blinding_point`,
+    /// never the cleartext amount. `commitment` is the storage key `SHA256(XDR(to) || C)`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Depositor (must authorize the token transfer)
+    /// * `token` - Token contract address
+    /// * `amount` - Amount to deposit; must be positive
+    /// * `commitment` - 32-byte recipient-bound key (must be unique)
+    /// * `blinding` - `r·H` as an uncompressed 96-byte G1 point, with a Schnorr proof of
+    ///   knowledge of `r` bound to `commitment`
+    /// * `timeout_secs` - Seconds from now until the escrow expires (0 = the configured
+    ///   default claim window, which is no expiry unless set)
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - Amount is zero or negative
+    /// * `InvalidPoint` - `blinding.blinding_point` is not a valid G1 point
+    /// * `InvalidProof` - The proof in `blinding` does not verify for `commitment`
+    /// * `ContractPaused` - Contract is currently paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `TokenNotAllowed` - The token allowlist is in force and the token is not on it
+    /// * `CapExceeded` - The deposit would exceed the token's cap or the global cap
+    /// * `CommitmentAlreadyExists` - An escrow for this commitment already exists
+    pub fn deposit_hidden(
+        env: Env,
+        from: Address,
+        token: Address,
+        amount: i128,
+        commitment: BytesN<32>,
+        blinding: BlindingProof,
+        timeout_secs: u64,
+    ) -> Result<(), Error> {
+        admin::require_function_enabled(&env, "deposit_hidden")?;
+        admin::require_op_enabled(&env, admin::PAUSE_DEPOSITS)?;
+        escrow::deposit_hidden(
+            &env,
+            from,
+            token,
+            amount,
+            commitment,
+            blinding,
+            timeout_secs,
+        )
+    }
+
+    /// Withdraw a hidden-amount escrow by opening its Pedersen commitment.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `to` - Recipient address (must authorize the call)
+    /// * `amount` - Committed amount
+    /// * `blinding` - Blinding scalar `r` used for the commitment
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - Amount is zero or negative
+    /// * `ContractPaused` - Contract is currently paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `CommitmentNotFound` - No hidden escrow matches (`to`, `amount`, `blinding`)
+    /// * `AlreadySpent` - Escrow has already been withdrawn or refunded
+    /// * `EscrowExpired` - Escrow has passed its expiry timestamp
+    pub fn withdraw_hidden(
+        env: Env,
+        to: Address,
+        amount: i128,
+        blinding: BytesN<32>,
+    ) -> Result<bool, Error> {
+        admin::require_function_enabled(&env, "withdraw_hidden")?;
+        admin::require_op_enabled(&env, admin::PAUSE_WITHDRAWALS)?;
+        escrow::withdraw_hidden(&env, to, amount, blinding)
+    }
+
+    /// Refund an expired hidden-amount escrow back to its depositor.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `commitment` - 32-byte key identifying the hidden escrow
+    /// * `caller` - Must equal the original depositor (must authorize)
+    /// * `amount` - Committed amount
+    /// * `blinding` - Blinding scalar `r` used for the commitment
+    ///
+    /// # Errors
+    /// * `CommitmentNotFound` - No hidden escrow exists for the commitment
+    /// * `AlreadySpent` - Escrow is already in a terminal state
+    /// * `EscrowNotExpired` - Escrow has no expiry or has not yet expired
+    /// * `InvalidOwner` - Caller is not the original owner
+    /// * `CommitmentMismatch` - (`amount`, `blinding`) does not open the stored commitment
+    /// * `ContractPaused` - Refunds are paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    pub fn refund_hidden(
+        env: Env,
+        commitment: BytesN<32>,
+        caller: Address,
+        amount: i128,
+        blinding: BytesN<32>,
+    ) -> Result<(), Error> {
+        admin::require_function_enabled(&env, "refund_hidden")?;
+        admin::require_op_enabled(&env, admin::PAUSE_REFUNDS)?;
+        escrow::refund_hidden(&env, commitment, caller, amount, blinding)
+    }
+
+    /// Approve or remove an escrow deposit amount for a token (**Admin only**).
+    ///
+    /// Once any amount is approved, `deposit`, `deposit_with_commitment`,
+    /// `deposit_with_memo`, and `deposit_hidden` only accept approved amounts or pool
+    /// denominations for that token, so deposits cannot carry unique fingerprint amounts.
+    /// Removing the last approved amount lifts the restriction.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `token` - Token contract address
+    /// * `amount` - Deposit amount in token base units; must be positive
+    /// * `enabled` - `true` to approve the amount, `false` to remove it
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `InvalidAmount` - Amount is zero or negative
+    pub fn set_amount_bucket(
+        env: Env,
+        caller: Address,
+        token: Address,
+        amount: i128,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        pool::set_amount_bucket(&env, caller, token, amount, enabled)
+    }
+
+    /// Get the approved escrow deposit amounts for a token (empty = any amount).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token` - Token contract address
+    pub fn get_amount_buckets(env: Env, token: Address) -> Vec<i128> {
+        pool::get_amount_buckets(&env, &token)
+    }
+
+    /// Get escrow counters and per-token volumes (read-only).
+    ///
+    /// Returns how many escrows are pending, spent and refunded, and how much of each
+    /// token was deposited, withdrawn and refunded, so dashboards need not scan events.
+    /// Escrows created before this view existed are not counted.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    pub fn stats(env: Env) -> ContractStats {
+        stats::stats(&env)
+    }
+
+    /// Compare the contract's `token` balance with what it owes (read-only).
+    ///
+    /// Returns `(contract_token_balance, sum_of_pending_entries)`. The second value is the
+    /// locked total maintained on every deposit, withdrawal, and refund across plain and
+    /// hidden escrows, ring notes, and shielded notes. Reserves are fully backed when the
+    /// first value is at least the second. Relayer stakes are not counted as liabilities.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token` - Token contract address
+    ///
+    /// # Errors
+    /// * `InvalidToken` - `token` is not a token contract
+    pub fn proof_of_reserves(env: Env, token: Address) -> Result<(i128, i128), Error> {
+        let balance = tokens::balance(&env, &token)?;
+        Ok((balance, get_total_locked(&env, &token)))
+    }
+
+    /// Amount of a token the admin could recover with `emergency_withdraw` (read-only).
+    ///
+    /// The contract balance minus pending escrows, pool notes, accrued protocol fees,
+    /// and (for the stake token) relayer stakes, floored at zero.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token` - Token contract address
+    ///
+    /// # Errors
+    /// * `InvalidToken` - `token` is not a token contract
+    pub fn sweepable_balance(env: Env, token: Address) -> Result<i128, Error> {
+        admin::sweepable_balance(&env, &token)
+    }
+
+    /// Check that the contract holds enough `token` to cover what it owes.
+    ///
+    /// Compares the balance against pending escrows and pool notes, accrued protocol
+    /// fees, and (for the stake token) relayer stakes. Anyone may call it. On a
+    /// shortfall it emits `InvariantViolated` and, if auto-pause is on, pauses every
+    /// operation.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token` - Token contract address
+    ///
+    /// # Errors
+    /// * `InvalidToken` - `token` is not a token contract
+    pub fn check_invariants(env: Env, token: Address) -> Result<InvariantReport, Error> {
+        solvency::check_invariants(&env, token)
+    }
+
+    /// Turn auto-pause on a failed `check_invariants` on or off (**Admin only**).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Admin address (must authorize)
+    /// * `enabled` - Whether a shortfall pauses the contract
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin
+    pub fn set_invariant_auto_pause(env: Env, caller: Address, enabled: bool) -> Result<(), Error> {
+        solvency::set_auto_pause(&env, caller, enabled)
+    }
+
+    /// Whether a failed `check_invariants` pauses the contract (read-only).
+    pub fn get_invariant_auto_pause(env: Env) -> bool {
+        solvency::auto_pause(&env)
+    }
+
+    /// Turn counting of rejected withdrawals in `attempt_withdraw` on or off (**Admin only**).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Admin address (must authorize)
+    /// * `enabled` - Whether rejections are counted
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin
+    pub fn set_withdraw_telemetry(env: Env, caller: Address, enabled: bool) -> Result<(), Error> {
+        telemetry::set_enabled(&env, caller, enabled)
+    }
+
+    /// Whether `attempt_withdraw` counts rejected withdrawals (read-only).
+    pub fn get_withdraw_telemetry(env: Env) -> bool {
+        telemetry::is_enabled(&env)
+    }
+
+    /// Number of rejected withdrawals counted for `class` (read-only).
+    pub fn get_rejected_withdrawals(env: Env, class: WithdrawRejection) -> u64 {
+        telemetry::rejected(&env, class)
+    }
+
+    /// Health check for deployment and monitoring.
+    ///
+    /// Returns `true` if the contract is deployed and callable. No state or auth required.
+    pub fn health_check() -> bool {
+        true
+    }
+
+    /// Deposit funds using a pre-generated 32-byte commitment hash.
+    ///
+    /// Transfers `amount` from `from` to the contract and stores an escrow keyed by
+    /// `commitment`. The depositor must authorize. Use when the commitment was created
+    /// off-chain or via [`create_amount_commitment`](QuickexContract::create_amount_commitment).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Depositor (must authorize the token transfer); a muxed (`M...`) account's
+    ///   id is reported as `mux_id` in `EscrowDeposited`
+    /// * `token` - Token contract address
+    /// * `amount` - Amount to deposit; must be positive
+    /// * `commitment` - 32-byte commitment hash (must be unique)
+    /// * `timeout_secs` - Seconds from now until the escrow expires (0 = the configured
+    ///   default claim window, which is no expiry unless set)
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - Amount is zero or negative
+    /// * `ContractPaused` - Contract is currently paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `TokenNotAllowed` - The token allowlist is in force and the token is not on it
+    /// * `CapExceeded` - The deposit would exceed the token's cap or the global cap
+    /// * `CommitmentAlreadyExists` - An escrow for this commitment already exists
+    pub fn deposit_with_commitment(
+        env: Env,
+        from: MuxedAddress,
+        token: Address,
+        amount: i128,
+        commitment: BytesN<32>,
+        timeout_secs: u64,
+    ) -> Result<(), Error> {
+        admin::require_function_enabled(&env, "deposit_with_commitment")?;
+        admin::require_op_enabled(&env, admin::PAUSE_DEPOSITS)?;
+        escrow::deposit_with_commitment(&env, from, token, amount, commitment, timeout_secs)
+    }
+
+    /// Deposit with a pre-generated commitment into a short-lived escrow.
+    ///
+    /// Same as [`deposit_with_commitment`](QuickexContract::deposit_with_commitment), but the
+    /// escrow is kept in temporary storage for its claim window plus about a day
+    /// (`EPHEMERAL_REFUND_LEDGERS`) in which the depositor can refund. Suited to instant
+    /// point-of-sale claims. Only a compact claim is kept in persistent storage until the
+    /// escrow is withdrawn or refunded, so the depositor can still refund after the
+    /// temporary entry expires. Ephemeral escrows are not listed by `list_claimable`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Depositor (must authorize the token transfer); a muxed (`M...`) account's
+    ///   id is reported as `mux_id` in `EscrowDeposited`
+    /// * `token` - Token contract address
+    /// * `amount` - Amount to deposit; must be positive
+    /// * `commitment` - 32-byte commitment hash (must be unique)
+    /// * `timeout_secs` - Seconds from now until the escrow expires, 1 to 3600
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - Amount is zero or negative
+    /// * `InvalidTimeout` - `timeout_secs` is 0 or above 3600
+    /// * `ContractPaused` - Contract is currently paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `TokenNotAllowed` - The token allowlist is in force and the token is not on it
+    /// * `CapExceeded` - The deposit would exceed the token's cap or the global cap
+    /// * `CommitmentAlreadyExists` - An escrow for this commitment already exists
+    pub fn deposit_ephemeral(
+        env: Env,
+        from: MuxedAddress,
+        token: Address,
+        amount: i128,
+        commitment: BytesN<32>,
+        timeout_secs: u64,
+    ) -> Result<(), Error> {
+        admin::require_function_enabled(&env, "deposit_ephemeral")?;
+        admin::require_op_enabled(&env, admin::PAUSE_DEPOSITS)?;
+        escrow::deposit_ephemeral(&env, from, token, amount, commitment, timeout_secs)
+    }
+
+    /// Deposit with a pre-generated commitment and attach an encrypted recipient memo.
+    ///
+    /// Same as [`deposit_with_commitment`](QuickexContract::deposit_with_commitment), plus an
+    /// opaque memo (e.g. an ECIES ciphertext of the amount and salt for the recipient).
+    /// The memo is stored under the commitment and emitted in an `EscrowMemo` event so
+    /// recipient wallets can discover incoming escrows by trial decryption.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Depositor (must authorize the token transfer); a muxed (`M...`) account's
+    ///   id is reported as `mux_id` in `EscrowDeposited`
+    /// * `token` - Token contract address
+    /// * `amount` - Amount to deposit; must be positive
+    /// * `commitment` - 32-byte commitment hash (must be unique)
+    /// * `timeout_secs` - Seconds from now until the escrow expires (0 = the configured
+    ///   default claim window, which is no expiry unless set)
+    /// * `memo` - Encrypted memo, 1–1024 bytes
+    ///
+    /// # Errors
+    /// * `InvalidMemo` - Memo is empty or longer than 1024 bytes
+    /// * `InvalidAmount` - Amount is zero or negative
+    /// * `ContractPaused` - Contract is currently paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `TokenNotAllowed` - The token allowlist is in force and the token is not on it
+    /// * `CapExceeded` - The deposit would exceed the token's cap or the global cap
+    /// * `CommitmentAlreadyExists` - An escrow for this commitment already exists
+    pub fn deposit_with_memo(
+        env: Env,
+        from: MuxedAddress,
+        token: Address,
+        amount: i128,
+        commitment: BytesN<32>,
+        timeout_secs: u64,
+        memo: Bytes,
+    ) -> Result<(), Error> {
+        admin::require_function_enabled(&env, "deposit_with_memo")?;
+        admin::require_op_enabled(&env, admin::PAUSE_DEPOSITS)?;
+        escrow::deposit_with_memo(&env, from, token, amount, commitment, timeout_secs, memo)
+    }
+
+    /// Get the encrypted memo attached to a deposit (read-only).
+    ///
+    /// Returns `None` if the deposit had no memo.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `commitment` - 32-byte commitment hash identifying the escrow
+    pub fn get_memo(env: Env, commitment: BytesN<32>) -> Option<Bytes> {
+        escrow::get_memo(&env, &commitment)
+    }
+
+    /// Refund an expired escrow back to its original owner.
+    ///
+    /// Can only be called after `expires_at` is reached. The caller must be the
+    /// original depositor. The escrow must still be `Pending`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `commitment` - 32-byte commitment hash identifying the escrow
+    /// * `caller` - Must equal the original depositor (must authorize)
+    ///
+    /// # Errors
+    /// * `CommitmentNotFound` - No escrow exists for the commitment
+    /// * `AlreadySpent` - Escrow is already in a terminal state
+    /// * `EscrowNotExpired` - Escrow has no expiry or has not yet expired
+    /// * `InvalidOwner` - Caller is not the original owner
+    /// * `ContractPaused` - Refunds are paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    pub fn refund(env: Env, commitment: BytesN<32>, caller: Address) -> Result<(), Error> {
+        admin::require_function_enabled(&env, "refund")?;
+        admin::require_op_enabled(&env, admin::PAUSE_REFUNDS)?;
+        escrow::refund(&env, commitment, caller)
+    }
+
+    /// Initialize the contract with an admin address (one-time only).
+    ///
+    /// Sets the admin who can pause/unpause, transfer admin, and upgrade the contract,
+    /// and emits `Initialized`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - The admin address to set
+    ///
+    /// # Errors
+    /// * `AlreadyInitialized` - Contract has already been initialized
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        admin::initialize(&env, admin)
+    }
+
+    /// Idempotent `initialize` for deployment scripts and factories.
+    ///
+    /// Initializes with `admin` on first use; afterwards returns the admin already set
+    /// instead of failing, so the script can be re-run and check who is in control.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - The admin address to set if the contract is uninitialized
+    ///
+    /// # Errors
+    /// * `AlreadyInitialized` - Contract is in council mode or the admin was renounced
+    pub fn ensure_initialized(env: Env, admin: Address) -> Result<Address, Error> {
+        admin::ensure_initialized(&env, admin)
+    }
+
+    /// Switch to admin council (multisig) mode.
+    ///
+    /// Replaces the single admin with `members`; privileged actions (pause, upgrade) then
+    /// need `threshold` member approvals via `propose_action` / `approve_action`, and the
+    /// single-admin entrypoints fail with `Unauthorized`. If an admin is already set, it
+    /// must authorize the switch. The council cannot be replaced afterwards.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `members` - Council member addresses
+    /// * `threshold` - Approvals required to execute an action
+    ///
+    /// # Errors
+    /// * `AlreadyInitialized` - A council is already set
+    /// * `InvalidThreshold` - Threshold is zero or exceeds the member count, or a member repeats
+    pub fn initialize_multisig(
+        env: Env,
+        members: Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), Error> {
+        admin::initialize_multisig(&env, members, threshold)
+    }
+
+    /// Propose a privileged action (**Council member only**).
+    ///
+    /// The proposal counts as the proposer's approval. Returns the action id to pass to
+    /// `approve_action`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `proposer` - Council member proposing the action (must authorize)
+    /// * `action` - Action to execute once approved
+    ///
+    /// # Errors
+    /// * `Unauthorized` - No council is set, or proposer is not a member
+    /// * `InvalidPauseOps` / `InvalidFee` / `InvalidAmount` - The action's parameters are invalid
+    pub fn propose_action(env: Env, proposer: Address, action: AdminAction) -> Result<u64, Error> {
+        admin::propose_action(&env, proposer, action)
+    }
+
+    /// Apply several admin actions atomically (**Admin only**).
+    ///
+    /// Rolls out a coordinated change (e.g. pause deposits, raise the fee, and lower a
+    /// cap) in one transaction, with no window of half-applied config. All actions are
+    /// validated first; if any fails, none is applied. At most 16 actions.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `actions` - Actions to apply, in order
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `InvalidBatch` - `actions` is empty or has more than 16 entries
+    /// * `TimelockRequired` - An action must be queued through the timelock
+    /// * `InvalidPauseOps` / `InvalidFee` / `InvalidAmount` - An action's parameters are invalid
+    pub fn admin_batch(env: Env, caller: Address, actions: Vec<AdminAction>) -> Result<(), Error> {
+        admin::admin_batch(&env, caller, actions)
+    }
+
+    /// Approve a pending council action (**Council member only**).
+    ///
+    /// The approval that reaches the threshold executes the action. Returns `true` if the
+    /// action was executed by this call.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `member` - Approving council member (must authorize)
+    /// * `action_id` - Id returned by `propose_action`
+    ///
+    /// # Errors
+    /// * `Unauthorized` - No council is set, or member is not a member
+    /// * `ActionNotFound` - No pending action has this id
+    /// * `ActionAlreadyApproved` - Member has already approved this action
+    pub fn approve_action(env: Env, member: Address, action_id: u64) -> Result<bool, Error> {
+        admin::approve_action(&env, member, action_id)
+    }
+
+    /// Recover stuck or airdropped tokens (**Admin only**, while paused).
+    ///
+    /// Only the surplus reported by `sweepable_balance` can be withdrawn, so funds owed to
+    /// pending escrows, pool notes, and relayer stakes are never touched.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `token` - Token to recover
+    /// * `to` - Recipient of the recovered tokens
+    /// * `amount` - Amount to recover
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `ContractNotPaused` - Contract is not paused
+    /// * `MigrationPending` - `migrate` has not run yet, so locked totals may be stale
+    /// * `InvalidAmount` - Amount is zero or negative
+    /// * `ExceedsSurplus` - Amount exceeds the sweepable balance
+    pub fn emergency_withdraw(
+        env: Env,
+        caller: Address,
+        token: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        admin::emergency_withdraw(&env, caller, token, to, amount)
+    }
+
+    /// Permanently give up admin control (**Admin only**), in two calls.
+    ///
+    /// The first call schedules the renounce and returns `false`; calling again at least
+    /// `RENOUNCE_DELAY_LEDGERS` (17,280, ~1 day) ledgers later clears the admin and
+    /// returns `true`. Afterwards no admin, council, or role holder can act and the
+    /// contract cannot be re-initialized, making the deployment immutable.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `TimelockNotReady` - The confirmation delay has not elapsed yet
+    /// * `ContractPaused` - Contract is paused (it would stay paused forever)
+    pub fn renounce_admin(env: Env, caller: Address) -> Result<bool, Error> {
+        admin::renounce_admin(&env, caller)
+    }
+
+    /// Cancel a scheduled admin renounce (**Admin only**).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn cancel_renounce_admin(env: Env, caller: Address) -> Result<(), Error> {
+        admin::cancel_renounce_admin(&env, caller)
+    }
+
+    /// Ledger from which a scheduled admin renounce can be completed (read-only).
+    ///
+    /// Returns `None` if no renounce is scheduled.
+    pub fn get_renounce_ready_at(env: Env) -> Option<u32> {
+        admin::get_renounce_ready_at(&env)
+    }
+
+    /// Check whether the admin has been permanently renounced (read-only).
+    pub fn is_admin_renounced(env: Env) -> bool {
+        admin::is_renounced(&env)
+    }
+
+    /// Grant a role to an address (**Admin only**).
+    ///
+    /// `Pauser` may call `set_paused`, `Upgrader` may call `upgrade`, `FeeManager` may
+    /// change fee settings, and `Auditor` may see escrow details hidden by the owner's
+    /// privacy level, without holding any other admin power.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `role` - Role to grant
+    /// * `account` - Address receiving the role
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn grant_role(
+        env: Env,
+        caller: Address,
+        role: Role,
+        account: Address,
+    ) -> Result<(), Error> {
+        admin::grant_role(&env, caller, role, account)
+    }
+
+    /// Revoke a role from an address (**Admin only**).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `role` - Role to revoke
+    /// * `account` - Address losing the role
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn revoke_role(
+        env: Env,
+        caller: Address,
+        role: Role,
+        account: Address,
+    ) -> Result<(), Error> {
+        admin::revoke_role(&env, caller, role, account)
+    }
+
+    /// Check whether an address holds a role (read-only).
+    ///
+    /// The admin holds every role.
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        admin::has_role(&env, role, &account)
+    }
+
+    /// Replace the deployment config (**Admin only**).
+    ///
+    /// Tunes the default and maximum escrow claim windows, the announce/finalize
+    /// reveal delay, and the admin recovery inactivity period without an upgrade.
+    /// Start from `get_config()`: the paused ops, withdrawal fee, and global cap must
+    /// be left unchanged, since they have their own entrypoints.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `config` - New config
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `InvalidConfig` - A flag was changed, the default claim window is above the
+    ///   maximum, or a delay is zero
+    pub fn set_config(env: Env, caller: Address, config: Config) -> Result<(), Error> {
+        config::set_config(&env, caller, config)
+    }
+
+    /// Get every contract-wide flag and parameter in one call (read-only).
+    ///
+    /// Returns the paused-operation mask, withdrawal fee, global cap, and timing
+    /// parameters.
+    pub fn get_config(env: Env) -> Config {
+        config::get(&env)
+    }
+
+    /// Set the timelock delay for sensitive admin actions (**Admin only**).
+    ///
+    /// Once non-zero, `upgrade`, `set_admin`, and further delay changes must be scheduled
+    /// with `queue_action` and can only run `delay` seconds later via `execute_action`.
+    /// This entrypoint only works while no delay is set.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `delay` - Delay in seconds
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `TimelockRequired` - A delay is already set; queue `SetTimelockDelay` instead
+    pub fn set_timelock_delay(env: Env, caller: Address, delay: u64) -> Result<(), Error> {
+        timelock::set_timelock_delay(&env, caller, delay)
+    }
+
+    /// Get the timelock delay in seconds (read-only). `0` means no timelock.
+    pub fn get_timelock_delay(env: Env) -> u64 {
+        timelock::delay(&env)
+    }
+
+    /// Schedule an admin action to run after the timelock delay.
+    ///
+    /// Caller needs the same rights as for the direct call (admin, or the `Pauser` /
+    /// `Upgrader` role for pause and upgrade). Returns the action id for
+    /// `execute_action` and `cancel_action`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must authorize)
+    /// * `action` - Action to schedule
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller may not perform the action
+    pub fn queue_action(env: Env, caller: Address, action: AdminAction) -> Result<u64, Error> {
+        timelock::queue_action(&env, caller, action)
+    }
+
+    /// Execute a queued admin action once its delay has elapsed. Callable by anyone.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `action_id` - Id returned by `queue_action` (or the council proposal id)
+    ///
+    /// # Errors
+    /// * `ActionNotFound` - Nothing is queued under this id
+    /// * `TimelockNotReady` - The delay has not elapsed yet
+    pub fn execute_action(env: Env, action_id: u64) -> Result<(), Error> {
+        timelock::execute_action(&env, action_id)
+    }
+
+    /// Cancel a queued admin action (**Admin only**).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `action_id` - Id of the queued action
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `ActionNotFound` - Nothing is queued under this id
+    pub fn cancel_action(env: Env, caller: Address, action_id: u64) -> Result<(), Error> {
+        timelock::cancel_action(&env, caller, action_id)
+    }
+
+    /// Get a queued admin action that has not run or been cancelled (read-only).
+    pub fn get_queued_action(env: Env, action_id: u64) -> Option<QueuedAdminAction> {
+        timelock::get_queued_action(&env, action_id)
+    }
+
+    /// Announce an upgrade that takes effect no earlier than `execute_after` (**Admin or
+    /// Upgrader**).
+    ///
+    /// `execute_after` must be at least the timelock delay away, giving users an on-chain
+    /// window to exit. Replaces any previously scheduled upgrade. Cancel with
+    /// `cancel_action` and the returned id.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (admin or upgrader; must authorize)
+    /// * `new_wasm_hash` - 32-byte hash of the new WASM code
+    /// * `new_version` - Code version compiled into the new WASM
+    /// * `execute_after` - Earliest ledger timestamp for `execute_upgrade`
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is neither the admin nor an upgrader
+    /// * `InvalidExecuteAfter` - `execute_after` is sooner than the timelock delay allows
+    pub fn schedule_upgrade(
+        env: Env,
+        caller: Address,
+        new_wasm_hash: BytesN<32>,
+        new_version: u32,
+        execute_after: u64,
+    ) -> Result<u64, Error> {
+        timelock::schedule_upgrade(&env, caller, new_wasm_hash, new_version, execute_after)
+    }
+
+    /// Run the scheduled upgrade once `execute_after` has passed. Callable by anyone.
+    ///
+    /// # Errors
+    /// * `ActionNotFound` - No upgrade is scheduled
+    /// * `TimelockNotReady` - `execute_after` has not passed yet
+    ///
+    /// Traps if the scheduled WASM hash has not been uploaded.
+    pub fn execute_upgrade(env: Env) -> Result<(), Error> {
+        timelock::execute_upgrade(&env)
+    }
+
+    /// Get the scheduled upgrade, if one is pending (read-only).
+    pub fn get_scheduled_upgrade(env: Env) -> Option<QueuedAdminAction> {
+        timelock::get_scheduled_upgrade(&env)
+    }
+
+    /// Get the admin council (read-only).
+    ///
+    /// Returns `None` if the contract is not in council mode.
+    pub fn get_admin_council(env: Env) -> Option<AdminCouncil> {
+        admin::get_admin_council(&env)
+    }
+
+    /// Get a council action awaiting approvals (read-only).
+    ///
+    /// Returns `None` if the id is unknown or the action has already executed.
+    pub fn get_admin_action(env: Env, action_id: u64) -> Option<PendingAdminAction> {
+        admin::get_admin_action(&env, action_id)
+    }
+
+    /// Pause or unpause the contract (**Admin or Pauser**; the guardian may only pause).
+    ///
+    /// When paused, certain operations may be blocked. Caller must be the stored admin or
+    /// hold the `Pauser` role, or be the guardian when `new_state` is `true`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (admin, pauser, or guardian)
+    /// * `new_state` - `true` to pause, `false` to unpause
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is neither the admin nor a pauser (nor the guardian, when pausing)
+    pub fn set_paused(env: Env, caller: Address, new_state: bool) -> Result<(), Error> {
+        admin::set_paused(&env, caller, new_state)
+    }
+
+    /// Set or clear the guardian (**Admin only**).
+    ///
+    /// The guardian can call `set_paused(true)` and nothing else, so a hot monitoring key
+    /// can stop the contract fast while the admin key stays offline.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `guardian` - New guardian, or `None` to remove it
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn set_guardian(env: Env, caller: Address, guardian: Option<Address>) -> Result<(), Error> {
+        admin::set_guardian(&env, caller, guardian)
+    }
+
+    /// Get the guardian address (read-only).
+    ///
+    /// Returns `None` if no guardian is set.
+    pub fn get_guardian(env: Env) -> Option<Address> {
+        admin::get_guardian(&env)
+    }
+
+    /// Set or clear the recovery address (**Admin only**).
+    ///
+    /// The recovery address can take over as admin with `claim_admin_after_inactivity`
+    /// once the admin has made no admin call for about 180 days.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `recovery` - New recovery address, or `None` to remove it
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn set_recovery_address(
+        env: Env,
+        caller: Address,
+        recovery: Option<Address>,
+    ) -> Result<(), Error> {
+        admin::set_recovery_address(&env, caller, recovery)
+    }
+
+    /// Get the recovery address (read-only).
+    ///
+    /// Returns `None` if no recovery address is set.
+    pub fn get_recovery_address(env: Env) -> Option<Address> {
+        admin::get_recovery_address(&env)
+    }
+
+    /// Get the ledger timestamp of the admin's last admin call (read-only).
+    pub fn get_admin_last_active(env: Env) -> u64 {
+        storage::get_admin_last_active(&env)
+    }
+
+    /// Claim admin rights after the admin has been inactive (**Recovery address only**).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The recovery address (must authorize)
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the recovery address, or there is no single admin
+    /// * `AdminStillActive` - The admin made an admin call within the inactivity period
+    pub fn claim_admin_after_inactivity(env: Env, caller: Address) -> Result<(), Error> {
+        admin::claim_admin_after_inactivity(&env, caller)
+    }
+
+    /// Set the protocol withdrawal fee in basis points (**Admin or FeeManager**).
+    ///
+    /// The fee is kept by the contract on every escrow withdrawal not paid to an exempt
+    /// address. Defaults to 0.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (admin or fee manager; must authorize)
+    /// * `fee_bps` - Fee in basis points, at most 1000 (10%)
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is neither the admin nor a fee manager
+    /// * `InvalidFee` - `fee_bps` is above the maximum
+    pub fn set_withdrawal_fee(env: Env, caller: Address, fee_bps: u32) -> Result<(), Error> {
+        fees::set_withdrawal_fee(&env, caller, fee_bps)
+    }
+
+    /// Get the protocol withdrawal fee in basis points (read-only).
+    pub fn get_withdrawal_fee(env: Env) -> u32 {
+        fees::withdrawal_fee_bps(&env)
+    }
+
+    /// Exempt an address from the withdrawal fee, or remove the exemption (**Admin or
+    /// FeeManager**).
+    ///
+    /// Withdrawals paid to an exempt address (e.g. a partner integration or treasury)
+    /// are not charged the protocol fee.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (admin or fee manager; must authorize)
+    /// * `account` - Address to update
+    /// * `exempt` - `true` to exempt, `false` to charge the fee again
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is neither the admin nor a fee manager
+    pub fn set_fee_exempt(
+        env: Env,
+        caller: Address,
+        account: Address,
+        exempt: bool,
+    ) -> Result<(), Error> {
+        fees::set_fee_exempt(&env, caller, account, exempt)
+    }
+
+    /// Check whether an address is exempt from the withdrawal fee (read-only).
+    pub fn is_fee_exempt(env: Env, account: Address) -> bool {
+        fees::is_fee_exempt(&env, &account)
+    }
+
+    /// Get the protocol fees of a token held in the treasury (read-only).
+    ///
+    /// These funds are kept apart from escrow liabilities and are not sweepable.
+    pub fn treasury_balance(env: Env, token: Address) -> i128 {
+        treasury::treasury_balance(&env, &token)
+    }
+
+    /// Withdraw accrued protocol fees from the treasury (**Admin only**).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `token` - Token whose fees to withdraw
+    /// * `to` - Recipient of the fees
+    /// * `amount` - Amount to withdraw; at most `treasury_balance(token)`
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `InvalidAmount` - Amount is zero or negative
+    /// * `InsufficientFees` - Amount exceeds the treasury balance
+    /// * `AccountBlocked` - `to` is on the compliance blocklist
+    pub fn withdraw_fees(
+        env: Env,
+        caller: Address,
+        token: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        treasury::withdraw_fees(&env, caller, token, to, amount)
+    }
+
+    /// Add a token to the deposit allowlist (**Admin only**).
+    ///
+    /// The first call puts the allowlist in force: from then on, deposits of tokens not
+    /// on the list fail with `TokenNotAllowed`. Existing escrows can still be withdrawn
+    /// and refunded.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `token` - Token to allow
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn allow_token(env: Env, caller: Address, token: Address) -> Result<(), Error> {
+        tokens::allow_token(&env, caller, token)
+    }
+
+    /// Remove a token from the deposit allowlist (**Admin only**).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `token` - Token to disallow
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn disallow_token(env: Env, caller: Address, token: Address) -> Result<(), Error> {
+        tokens::disallow_token(&env, caller, token)
+    }
+
+    /// Block a token for incident response (**Admin or Pauser**).
+    ///
+    /// Deposits of a blocked token fail with `TokenBlocked`. With `freeze_withdrawals`,
+    /// its withdrawals and refunds fail too until `unblock_token`. Other tokens are
+    /// unaffected.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (admin or pauser; must authorize)
+    /// * `token` - Token to block
+    /// * `freeze_withdrawals` - Also stop withdrawals and refunds of the token
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is neither the admin nor a pauser
+    pub fn block_token(
+        env: Env,
+        caller: Address,
+        token: Address,
+        freeze_withdrawals: bool,
+    ) -> Result<(), Error> {
+        tokens::block_token(&env, caller, token, freeze_withdrawals)
+    }
+
+    /// Lift a token block (**Admin or Pauser**).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is neither the admin nor a pauser
+    pub fn unblock_token(env: Env, caller: Address, token: Address) -> Result<(), Error> {
+        tokens::unblock_token(&env, caller, token)
+    }
+
+    /// Get a token's block status (read-only).
+    ///
+    /// Returns `Some(freeze_withdrawals)` while the token is blocked, `None` otherwise.
+    pub fn get_token_block(env: Env, token: Address) -> Option<bool> {
+        tokens::token_block(&env, &token)
+    }
+
+    /// Cap how much of a token the contract may hold for users (**Admin only**).
+    ///
+    /// Deposits that would push the token's locked total (see `proof_of_reserves`) above
+    /// `cap` fail with `CapExceeded`. Pass `0` to remove the cap.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `token` - Token to cap
+    /// * `cap` - Maximum locked total, or `0` for no cap
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `InvalidAmount` - `cap` is negative
+    pub fn set_token_cap(
+        env: Env,
+        caller: Address,
+        token: Address,
+        cap: i128,
+    ) -> Result<(), Error> {
+        tokens::set_token_cap(&env, caller, token, cap)
+    }
+
+    /// Get a token's deposit cap (read-only). `0` means uncapped.
+    pub fn get_token_cap(env: Env, token: Address) -> i128 {
+        tokens::token_cap(&env, &token)
+    }
+
+    /// Cap the value locked across all tokens (**Admin only**).
+    ///
+    /// Tokens are valued with `set_token_value`. While the cap is set, deposits that would
+    /// push `total_value_locked` above it fail with `CapExceeded`, and tokens without a
+    /// value fail with `TokenNotAllowed`. Pass `0` to remove the cap.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `cap` - Maximum total value, or `0` for no cap
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `InvalidAmount` - `cap` is negative
+    pub fn set_global_cap(env: Env, caller: Address, cap: i128) -> Result<(), Error> {
+        tokens::set_global_cap(&env, caller, cap)
+    }
+
+    /// Get the global cap in value units (read-only). `0` means uncapped.
+    pub fn get_global_cap(env: Env) -> i128 {
+        tokens::global_cap(&env)
+    }
+
+    /// Set the value of one whole token unit for the global cap (**Admin only**).
+    ///
+    /// `value` is in value units scaled by 10^7. Pass `0` to remove the value. At most 32
+    /// tokens can have a value.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `token` - Token to value
+    /// * `value` - Value per whole token unit, scaled by 10^7
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `InvalidAmount` - `value` is negative
+    /// * `TooManyTokens` - 32 tokens already have a value
+    pub fn set_token_value(
+        env: Env,
+        caller: Address,
+        token: Address,
+        value: i128,
+    ) -> Result<(), Error> {
+        tokens::set_token_value(&env, caller, token, value)
+    }
+
+    /// Get a token's value for the global cap (read-only). `0` means no value is set.
+    pub fn get_token_value(env: Env, token: Address) -> i128 {
+        tokens::token_value(&env, &token)
+    }
+
+    /// Get the value locked across all valued tokens (read-only).
+    ///
+    /// # Errors
+    /// * `ArithmeticOverflow` - The total does not fit an `i128`
+    pub fn total_value_locked(env: Env) -> Result<i128, Error> {
+        tokens::total_value_locked(&env)
+    }
+
+    /// Cap the amount of a token withdrawn per window of ledgers (**Admin only**).
+    ///
+    /// Withdrawals beyond `max_amount` within `window_ledgers` ledgers fail with
+    /// `RateLimited`, an automatic brake against drain-style exploits. Refunds are not
+    /// limited. Pass `max_amount = 0` to remove the limit.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `token` - Token to limit
+    /// * `max_amount` - Most that can be withdrawn per window, or `0` for no limit
+    /// * `window_ledgers` - Window length in ledgers
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `InvalidRateLimit` - `max_amount` is negative, or positive with a zero window
+    pub fn set_withdraw_volume_limit(
+        env: Env,
+        caller: Address,
+        token: Address,
+        max_amount: i128,
+        window_ledgers: u32,
+    ) -> Result<(), Error> {
+        tokens::set_withdraw_volume_limit(&env, caller, token, max_amount, window_ledgers)
+    }
+
+    /// Get a token's withdrawal volume limit (read-only).
+    pub fn get_withdraw_volume_limit(env: Env, token: Address) -> Option<WithdrawVolumeLimit> {
+        tokens::withdraw_volume_limit(&env, &token)
+    }
+
+    /// Block an account for compliance (**Admin only**).
+    ///
+    /// A blocked account cannot deposit or be the payout target of a withdrawal (both
+    /// fail with `AccountBlocked`). Refunds of its existing escrows still work.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `account` - Account to block
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn block_account(env: Env, caller: Address, account: Address) -> Result<(), Error> {
+        compliance::block_account(&env, caller, account)
+    }
+
+    /// Lift a compliance block on an account (**Admin only**).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn unblock_account(env: Env, caller: Address, account: Address) -> Result<(), Error> {
+        compliance::unblock_account(&env, caller, account)
+    }
+
+    /// Check whether an account is on the compliance blocklist (read-only).
+    pub fn is_blocked(env: Env, account: Address) -> bool {
+        compliance::is_blocked(&env, &account)
+    }
+
+    /// Check whether deposits of a token are accepted by the allowlist (read-only).
+    ///
+    /// Always `true` while the allowlist is not in force.
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        tokens::is_token_allowed(&env, &token)
+    }
+
+    /// Pause or unpause individual operations (**Admin or Pauser**).
+    ///
+    /// `ops` is a bitmask of `1` = deposits (including pool notes and relayer stakes),
+    /// `2` = withdrawals, `4` = refunds, `8` = escrow creation. Operations outside `ops`
+    /// keep their state, so deposits can be halted while users still withdraw.
+    /// `set_paused(true)` pauses all of them.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (admin or pauser)
+    /// * `ops` - Bitmask of operations to update
+    /// * `state` - `true` to pause, `false` to unpause
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is neither the admin nor a pauser
+    /// * `InvalidPauseOps` - `ops` is zero or contains unknown bits
+    pub fn set_paused_ops(env: Env, caller: Address, ops: u32, state: bool) -> Result<(), Error> {
+        admin::set_paused_ops(&env, caller, ops, state)
+    }
+
+    /// Get the bitmask of paused operations (read-only). `0` means fully operational.
+    pub fn paused_ops(env: Env) -> u32 {
+        admin::paused_ops(&env)
+    }
+
+    /// Switch off a single entrypoint (**Admin or Pauser**).
+    ///
+    /// Calls to `name` fail with `FunctionDisabled` until `enable_function`, while the
+    /// rest of the contract keeps running. Only user-facing flows (deposits,
+    /// withdrawals, refunds, relayer staking, privacy settings) can be disabled.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (admin or pauser)
+    /// * `name` - Entrypoint name, e.g. `withdraw_hidden`
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is neither the admin nor a pauser
+    /// * `UnknownFunction` - `name` is not an entrypoint that can be disabled
+    pub fn disable_function(env: Env, caller: Address, name: Symbol) -> Result<(), Error> {
+        admin::set_function_disabled(&env, caller, name, true)
+    }
+
+    /// Re-enable an entrypoint switched off by `disable_function` (**Admin or Pauser**).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is neither the admin nor a pauser
+    /// * `UnknownFunction` - `name` is not an entrypoint that can be disabled
+    pub fn enable_function(env: Env, caller: Address, name: Symbol) -> Result<(), Error> {
+        admin::set_function_disabled(&env, caller, name, false)
+    }
+
+    /// Whether the entrypoint `name` is disabled (read-only).
+    pub fn is_function_disabled(env: Env, name: Symbol) -> bool {
+        admin::is_function_disabled(&env, &name)
+    }
+
+    /// Set the minimum salt length for commitments (**Admin only**).
+    ///
+    /// Enforced on `deposit` and on withdrawals of escrows created after this call;
+    /// older escrows are exempt so existing funds are never locked. Escrows created
+    /// with `deposit_with_commitment` are checked when the salt is revealed at
+    /// withdrawal, since the contract never sees it at deposit. `0` disables the check.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `min_len` - Minimum salt length in bytes (at most 1024)
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `InvalidSalt` - `min_len` exceeds 1024
+    pub fn set_min_salt_len(env: Env, caller: Address, min_len: u32) -> Result<(), Error> {
+        admin::set_min_salt_len(&env, caller, min_len)
+    }
+
+    /// Get the minimum salt length policy (read-only).
+    ///
+    /// Returns `None` if no minimum has been set.
+    pub fn get_salt_policy(env: Env) -> Option<SaltPolicy> {
+        get_salt_policy(&env)
+    }
+
+    /// Generate a 32-byte salt from the contract PRNG.
+    ///
+    /// Only for clients that explicitly opt into contract-side randomness: the value is
+    /// visible to whoever executes the call (e.g. the RPC node simulating it), so salts
+    /// generated locally by the client remain the private default.
+    pub fn generate_salt(env: Env) -> Bytes {
+        commitment::random_salt(&env)
+    }
+
+    /// Transfer admin rights to a new address (**Admin only**).
+    ///
+    /// Caller must equal the current admin. The new admin can later transfer again.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal current admin)
+    /// * `new_admin` - New admin address
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `TimelockRequired` - A timelock delay is set; use `queue_action` instead
+    pub fn set_admin(env: Env, caller: Address, new_admin: Address) -> Result<(), Error> {
+        admin::set_admin(&env, caller, new_admin)
+    }
+
+    /// Check if the contract is currently paused.
+    ///
+    /// Returns `true` if any operation is paused, `false` otherwise. See `paused_ops` for
+    /// which ones.
+    pub fn is_paused(env: Env) -> bool {
+        admin::is_paused(&env)
+    }
+
+    /// Get the current admin address.
+    ///
+    /// Returns `None` if the contract has not been initialized.
+    pub fn get_admin(env: Env) -> Option<Address> {
+        admin::get_admin(&env)
+    }
+
+    /// Get the payout details of a withdrawn escrow (read-only).
+    ///
+    /// Lets a client whose withdrawal succeeded but whose result was lost confirm it.
+    /// Receipts of `withdraw`, `withdraw_delegated`, `withdraw_via_relayer` and the
+    /// commit-reveal and two-phase flows are kept for about 7 days
+    /// (`ESCROW_ARCHIVE_TTL` ledgers).
+    ///
+    /// Returns `None` if the escrow was not withdrawn, if the receipt expired, or if the
+    /// owner's privacy level hides amounts (no receipt is stored for them).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `commitment` - 32-byte commitment hash of the escrow
+    pub fn get_receipt(env: Env, commitment: BytesN<32>) -> Option<WithdrawReceipt> {
+        storage::get_receipt(&env, &commitment)
+    }
+
+    /// Get the status of an escrow by its commitment hash (read-only).
+    ///
+    /// Returns `Pending`, `Spent`, `Expired`, or `Refunded` if an escrow exists; `None` otherwise.
+    /// Also returns `None` when the escrow owner's privacy level is 2 (or the boolean toggle
+    /// is on); the owner can use [`get_escrow_details`](QuickexContract::get_escrow_details).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `commitment` - 32-byte commitment hash used as the escrow key
+    pub fn get_commitment_state(env: Env, commitment: BytesN<32>) -> Option<EscrowStatus> {
+        let commitment_bytes: Bytes = commitment.into();
+        let entry: EscrowEntry = get_escrow(&env, &commitment_bytes)?;
+        if privacy::effective_level(&env, &entry.owner) >= privacy::LEVEL_HIDE_ALL {
+            return None;
+        }
+        Some(entry.status)
+    }
+
+    /// Verify withdrawal parameters without submitting a transaction (read-only).
+    ///
+    /// Recomputes the commitment from `amount`, `salt`, and `owner`, then checks that an
+    /// escrow exists with status `Pending`, matching amount, and not yet expired.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `amount` - Amount to verify (non-negative)
+    /// * `salt` - Salt used when creating the deposit
+    /// * `owner` - Owner of the escrow
+    pub fn verify_proof_view(env: Env, amount: i128, salt: Bytes, owner: Address) -> bool {
+        let commitment_result =
+            commitment::create_amount_commitment(&env, owner.clone(), amount, salt);
+
+        let commitment = match commitment_result {
+            Ok(c) => c,
+            Err(_) => return false,
+        };
+
+        let commitment_bytes: Bytes = commitment.into();
+        let entry: Option<EscrowEntry> = get_escrow(&env, &commitment_bytes);
+
+        match entry {
+            Some(e) => {
+                if e.status != EscrowStatus::Pending {
+                    return false;
+                }
+                if e.expires_at > 0 && env.ledger().timestamp() >= e.expires_at {
+                    return false;
+                }
+                e.amount == amount
+            }
+            None => false,
+        }
+    }
+
+    /// Get a privacy-aware view of escrow details for a commitment hash (read-only).
+    ///
+    /// Returns a [`PrivacyAwareEscrowView`] if an escrow exists for the commitment,
+    /// or `None` otherwise.
+    ///
+    /// ## Privacy behaviour
+    /// When `caller` is neither the escrow owner nor an auditor, fields are hidden
+    /// according to the owner's effective privacy level (see
+    /// [`enable_privacy`](QuickexContract::enable_privacy)) and `redacted` is `true`:
+    /// - level 1: `amount` is `None`
+    /// - level 2 or boolean privacy on: `amount` and `owner` are `None`
+    ///
+    /// The owner and holders of the `Auditor` role (granted with
+    /// [`grant_role`](QuickexContract::grant_role)) see all fields. When that lifts a
+    /// redaction, `caller` must authorize, so passing the owner's address without their
+    /// signature still returns the redacted view.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `commitment` - 32-byte commitment hash identifying the escrow
     /// * `caller` - Address of the caller; used to determine whether full details
     ///   are returned when privacy is enabled
     pub fn get_escrow_details(
         env: Env,
-        commitment: BytesN<32>,
+        commitment: BytesN<32>,
+        caller: Address,
+    ) -> Option<PrivacyAwareEscrowView> {
+        let commitment_bytes: Bytes = commitment.into();
+        let entry = get_escrow(&env, &commitment_bytes)?;
+        Some(privacy::escrow_view(&env, entry, &caller))
+    }
+
+    /// Upgrade the contract to a new WASM implementation (**Admin or Upgrader**).
+    ///
+    /// Caller must be the admin or hold the `Upgrader` role, and authorize. The new WASM
+    /// must be pre-uploaded to the network. Emits an upgrade event for audit.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (admin or upgrader; must authorize)
+    /// * `new_wasm_hash` - 32-byte hash of the new WASM code
+    /// * `new_version` - Code version compiled into the new WASM
+    ///
+    /// Until the new code's `migrate` runs, deposits, withdrawals, refunds, and escrow
+    /// creation fail with `MigrationPending`.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is neither the admin nor an upgrader
+    /// * `TimelockRequired` - A timelock delay is set; use `queue_action` instead
+    /// * `MigrationPending` - The previous upgrade has not been migrated yet
+    /// * `SchemaDowngrade` - Storage was written by a newer schema than this code's
+    ///
+    /// Call `check_upgrade` first to see every blocker at once.
+    ///
+    /// Traps if `new_wasm_hash` has not been uploaded.
+    ///
+    /// # Security
+    /// Updates the contract's executable code. Use with care in production.
+    pub fn upgrade(
+        env: Env,
+        caller: Address,
+        new_wasm_hash: BytesN<32>,
+        new_version: u32,
+    ) -> Result<(), Error> {
+        admin::require_role(&env, &caller, Role::Upgrader)?;
+        timelock::require_no_delay(&env)?;
+        migration::require_upgradable(&env)?;
+
+        migration::upgrade(&env, new_wasm_hash, new_version, &caller);
+
+        Ok(())
+    }
+
+    /// Check whether an upgrade to `wasm_hash` would be accepted now (read-only).
+    ///
+    /// Returns an `UpgradeReport` listing every blocker (renounced admin, active
+    /// timelock, pending migration, storage schema ahead of the code) together with the
+    /// versions, paused operations, and the execution time of a scheduled upgrade to
+    /// `wasm_hash`, if any. Does not verify that the WASM has been uploaded.
+    pub fn check_upgrade(env: Env, wasm_hash: BytesN<32>) -> UpgradeReport {
+        migration::check_upgrade(&env, &wasm_hash)
+    }
+
+    /// Complete an upgrade by migrating storage to this code's schema version.
+    ///
+    /// Must run exactly once after `upgrade`; normal operations stay blocked until it
+    /// succeeds. Callable by anyone. Returns the new storage schema version.
+    ///
+    /// # Errors
+    /// * `SchemaDowngrade` - Storage was written by a newer schema than this code's
+    /// * `NothingToMigrate` - No upgrade is pending and storage is already current
+    /// * `UpgradeVersionMismatch` - The upgrade recorded a different code version than
+    ///   the one now running
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        migration::migrate(&env)
+    }
+
+    /// Move escrows stored under the legacy `("escrow", commitment)` key into the
+    /// current layout (**Admin only**). Returns how many were moved.
+    ///
+    /// Commitments without a legacy entry, or already migrated, are skipped, so the
+    /// call can be repeated in batches.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Admin address (must authorize)
+    /// * `commitments` - Commitments of the escrows to move
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin
+    pub fn migrate_storage(
+        env: Env,
         caller: Address,
-    ) -> Option<PrivacyAwareEscrowView> {
-        let commitment_bytes: Bytes = commitment.into();
-        let entry = get_escrow(&env, &commitment_bytes)?;
+        commitments: Vec<BytesN<32>>,
+    ) -> Result<u32, Error> {
+        migration::migrate_legacy_escrows(&env, caller, commitments)
+    }
 
-        let privacy_on = privacy::get_privacy(&env, entry.owner.clone());
-        let is_owner = caller == entry.owner;
-
-        if privacy_on && !is_owner {
-            Some(PrivacyAwareEscrowView {
-                token: entry.token,
-                amount: None,
-                owner: None,
-                status: entry.status,
-                created_at: entry.created_at,
-                expires_at: entry.expires_at,
-            })
-        } else {
-            Some(PrivacyAwareEscrowView {
-                token: entry.token,
-                amount: Some(entry.amount),
-                owner: Some(entry.owner),
-                status: entry.status,
-                created_at: entry.created_at,
-                expires_at: entry.expires_at,
-            })
-        }
+    /// Raise a token's locked total to its balance less accrued fees and relayer stakes
+    /// (**Admin only**). Returns the new total.
+    ///
+    /// `migrate` does this for every token in `stats`; use this for tokens deposited
+    /// only before stats were recorded. Never lowers a total.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Admin address (must authorize)
+    /// * `token` - Token whose total to backfill
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin
+    /// * `InvalidToken` - `token` is not a token contract
+    pub fn backfill_total_locked(env: Env, caller: Address, token: Address) -> Result<i128, Error> {
+        migration::backfill_total_locked(&env, caller, token)
+    }
+
+    /// Get `(code version, storage schema version)`.
+    ///
+    /// A schema version behind this code's, or a pending upgrade, means `migrate` has not
+    /// completed yet.
+    pub fn version(env: Env) -> (u32, u32) {
+        migration::version(&env)
     }
-    /// Upgrade the contract to a new WASM implementation (**Admin only**).
+
+    /// Get the persisted storage layout (schema) version (read-only).
+    ///
+    /// The second element of [`version`](QuickexContract::version); see the schema
+    /// table in `src/migration.rs` for what each version changed.
+    pub fn storage_layout_version(env: Env) -> u32 {
+        migration::schema_version(&env)
+    }
+
+    /// Get machine-readable metadata for an error code (read-only).
     ///
-    /// Caller must equal admin and authorize. The new WASM must be pre-uploaded to the network.
-    /// Emits an upgrade event for audit.
+    /// Returns the code's category (auth, state, validation, limits or internal) and
+    /// whether the same call may succeed later unchanged, so clients can share retry
+    /// and display logic across contract versions. Returns `None` for codes this
+    /// version does not define.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `caller` - Caller address (must equal admin; must authorize)
-    /// * `new_wasm_hash` - 32-byte hash of the new WASM code
+    /// * `code` - Error code from a failed invocation
+    pub fn error_info(_env: Env, code: u32) -> Option<ErrorInfo> {
+        errors::error_info(code)
+    }
+
+    /// Get the raw stored escrow entry for a commitment (**Admin only**).
+    ///
+    /// Returns the entry's XDR-encoded `ScVal` exactly as stored (a
+    /// `PackedEscrowEntry` tuple, or a field map for older or legacy-key entries), or
+    /// `None` if no entry exists. Meant for diagnosing entries that fail to decode on a
+    /// live deployment.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `commitment` - Commitment of the escrow
     ///
     /// # Errors
     /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn debug_dump_entry(
+        env: Env,
+        caller: Address,
+        commitment: BytesN<32>,
+    ) -> Result<Option<Bytes>, Error> {
+        migration::debug_dump_entry(&env, caller, commitment)
+    }
+
+    /// Extend a pending escrow's storage TTL to the maximum and emit `TtlExtended`.
     ///
-    /// # Security
-    /// Updates the contract's executable code. Use with care in production.
-    pub fn upgrade(
+    /// Callable by anyone; `keeper` is recorded in the event. Returns the last ledger
+    /// the escrow now lives to.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `keeper` - Address extending the entry (must authorize)
+    /// * `commitment` - Commitment of the pending escrow
+    ///
+    /// # Errors
+    /// * `AlreadySpent` - Escrow has already been withdrawn or refunded
+    /// * `CommitmentNotFound` - No escrow exists for `commitment`
+    pub fn extend_escrow_ttl(
+        env: Env,
+        keeper: Address,
+        commitment: BytesN<32>,
+    ) -> Result<u32, Error> {
+        ttl::extend_escrow(&env, keeper, commitment)
+    }
+
+    /// Re-extend a pending escrow after its archived storage was restored.
+    ///
+    /// First half of a two-transaction recovery for an escrow whose TTL ran out: submit
+    /// this call with the archived entries restored (simulation reports them; protocol 23
+    /// restores them automatically, earlier networks need a `RestoreFootprint` operation
+    /// first), then claim with `withdraw` or `refund` as usual. Extends the escrow, its
+    /// memo, and its owner-index entries to the maximum TTL and emits `EscrowRestored`.
+    /// Callable by anyone. Returns the last ledger the escrow now lives to.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `commitment` - Commitment of the pending escrow
+    ///
+    /// # Errors
+    /// * `AlreadySpent` - Escrow has already been withdrawn or refunded
+    /// * `CommitmentNotFound` - No escrow exists for `commitment`
+    pub fn restore_escrow(env: Env, commitment: BytesN<32>) -> Result<u32, Error> {
+        ttl::restore_escrow(&env, commitment)
+    }
+
+    /// Extend many escrows' storage TTL in one call, emitting `TtlExtended` for each.
+    ///
+    /// Each escrow found is extended to live at least `extend_to` more ledgers; entries
+    /// that already do are left unchanged. Unknown, spent, and refunded commitments are
+    /// skipped. Callable by anyone; returns how many escrows were found.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `keeper` - Address extending the entries (must authorize)
+    /// * `commitments` - Commitments of the escrows to extend
+    /// * `extend_to` - Minimum remaining TTL in ledgers
+    ///
+    /// # Errors
+    /// * `InvalidTtl` - `extend_to` is zero or above the network's maximum TTL
+    pub fn bump_entries(
+        env: Env,
+        keeper: Address,
+        commitments: Vec<BytesN<32>>,
+        extend_to: u32,
+    ) -> Result<u32, Error> {
+        ttl::bump_entries(&env, keeper, commitments, extend_to)
+    }
+
+    /// Extend the contract instance's TTL so it lives at least `extend_to` more ledgers.
+    ///
+    /// Permissionless and emits no event; keepers that want their extension recorded
+    /// use [`extend_instance_ttl`](QuickexContract::extend_instance_ttl) instead. Deposits,
+    /// withdrawals, and refunds already top up the instance TTL when it runs low, so this
+    /// only matters for a contract that sees no traffic. Returns the last ledger the
+    /// instance lives to at least.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `extend_to` - Ledgers the instance must live for from now
+    ///
+    /// # Errors
+    /// * `InvalidTtl` - `extend_to` is zero or above the network's maximum TTL
+    pub fn bump_instance(env: Env, extend_to: u32) -> Result<u32, Error> {
+        ttl::bump_instance(&env, extend_to)
+    }
+
+    /// Extend the contract instance's TTL to the maximum and emit `TtlExtended`.
+    ///
+    /// Callable by anyone; `keeper` is recorded in the event. Returns the last ledger
+    /// the instance now lives to.
+    pub fn extend_instance_ttl(env: Env, keeper: Address) -> u32 {
+        ttl::extend_instance(&env, keeper)
+    }
+
+    /// Reclaim storage held by spent or refunded escrows.
+    ///
+    /// Callable by anyone. Withdrawals and refunds only record the escrow's status, so
+    /// its entry stays in storage until pruned here; the status is kept and still
+    /// blocks reuse of the commitment. Pending and unknown
+    /// commitments are skipped. Returns how many were pruned.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `commitments` - Commitments (or hidden escrow keys) to prune
+    pub fn prune_spent(env: Env, commitments: Vec<BytesN<32>>) -> u32 {
+        escrow::prune_spent(&env, commitments)
+    }
+
+    /// Clean up to `limit` spent or refunded escrows that still hold storage, oldest
+    /// first, and pay `keeper` the bounty for each.
+    ///
+    /// Hidden-amount escrows are reduced to their status and memos are removed. The
+    /// bounty set with `set_gc_bounty` is paid from the treasury, up to its balance.
+    /// Returns how many escrows were cleaned; `gc_queue_len` reports how many wait.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `keeper` - Address receiving the bounty (must authorize)
+    /// * `limit` - Maximum number of queued escrows to process
+    ///
+    /// # Errors
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `AccountBlocked` - A bounty is due and `keeper` is on the compliance blocklist
+    pub fn gc(env: Env, keeper: Address, limit: u32) -> Result<u32, Error> {
+        admin::require_function_enabled(&env, "gc")?;
+        gc::gc(&env, keeper, limit)
+    }
+
+    /// Number of spent or refunded escrows waiting for `gc` (read-only).
+    pub fn gc_queue_len(env: Env) -> u64 {
+        storage::gc_queue_len(&env)
+    }
+
+    /// Set the bounty paid per escrow cleaned by `gc` (**Admin only**).
+    ///
+    /// The bounty is paid in `token` from the treasury. Keep it below the cost of
+    /// creating a cleanable escrow, or keepers can farm it.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Admin address (must authorize)
+    /// * `token` - Treasury token the bounty is paid in
+    /// * `amount` - Bounty per cleaned escrow; 0 removes the bounty
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin
+    /// * `InvalidAmount` - Amount is negative
+    pub fn set_gc_bounty(
         env: Env,
         caller: Address,
-        new_wasm_hash: BytesN<32>,
-    ) -> Result<(), QuickexError> {
-        let admin = admin::get_admin(&env).ok_or(QuickexError::Unauthorized)?;
-        if caller != admin {
-            return Err(QuickexError::Unauthorized);
-        }
+        token: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        gc::set_bounty(&env, caller, token, amount)
+    }
 
-        caller.require_auth();
+    /// Get the `gc` bounty token and amount per cleaned escrow, if set (read-only).
+    pub fn get_gc_bounty(env: Env) -> Option<(Address, i128)> {
+        storage::get_gc_bounty(&env)
+    }
 
-        env.deployer()
-            .update_current_contract_wasm(new_wasm_hash.clone());
+    /// Get the `sequence` number of the last event emitted (0 if none yet).
+    ///
+    /// Every event carries the next number, so an indexer that has seen fewer events
+    /// than this knows it missed some.
+    pub fn event_sequence(env: Env) -> u64 {
+        get_event_sequence(&env)
+    }
+}
 
-        events::publish_contract_upgraded(&env, new_wasm_hash, &admin);
+/// Fixed-denomination pools and ring notes (`privacy-pool` feature).
+#[cfg(feature = "privacy-pool")]
+#[contractimpl]
+impl QuickexContract {
+    /// Enable or disable a pool denomination for a token (**Admin only**).
+    ///
+    /// Disabling only blocks new pool deposits; existing notes stay withdrawable.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `token` - Token contract address
+    /// * `denomination` - Exact deposit amount in token base units; must be positive
+    /// * `enabled` - `true` to accept deposits of this size, `false` to stop them
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `InvalidAmount` - Denomination is zero or negative
+    pub fn set_denomination(
+        env: Env,
+        caller: Address,
+        token: Address,
+        denomination: i128,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        pool::set_denomination(&env, caller, token, denomination, enabled)
+    }
 
-        Ok(())
+    /// Get the pool denominations currently accepted for a token.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token` - Token contract address
+    pub fn get_denominations(env: Env, token: Address) -> Vec<i128> {
+        pool::get_denominations(&env, &token)
+    }
+
+    /// Deposit exactly one denomination into its fixed-denomination pool.
+    ///
+    /// Behaves like [`deposit_with_commitment`](QuickexContract::deposit_with_commitment)
+    /// with no expiry, but only accepts amounts registered via
+    /// [`set_denomination`](QuickexContract::set_denomination). Every note in a pool has
+    /// the same value, so withdrawals cannot be matched to deposits by amount.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Depositor (must authorize the token transfer)
+    /// * `token` - Token contract address
+    /// * `denomination` - Registered denomination to deposit
+    /// * `commitment` - 32-byte `SHA256(owner || denomination || salt)` commitment
+    ///
+    /// # Errors
+    /// * `ContractPaused` - Contract is currently paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `TokenNotAllowed` - The token allowlist is in force and the token is not on it
+    /// * `CapExceeded` - The deposit would exceed the token's cap or the global cap
+    /// * `InvalidDenomination` - Amount is not a registered denomination for the token
+    /// * `CommitmentAlreadyExists` - An escrow for this commitment already exists
+    pub fn deposit_to_pool(
+        env: Env,
+        from: Address,
+        token: Address,
+        denomination: i128,
+        commitment: BytesN<32>,
+    ) -> Result<(), Error> {
+        admin::require_function_enabled(&env, "deposit_to_pool")?;
+        admin::require_op_enabled(&env, admin::PAUSE_DEPOSITS)?;
+        pool::deposit_to_pool(&env, from, token, denomination, commitment)
+    }
+
+    /// Get anonymity-set statistics for a (token, denomination) pool (read-only).
+    ///
+    /// Returns pending and spent note counts plus the age distribution of pending
+    /// notes, so wallets can warn when the anonymity set is too small to be meaningful.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token` - Token contract address
+    /// * `denomination` - Pool denomination
+    pub fn pool_stats(env: Env, token: Address, denomination: i128) -> PoolStats {
+        pool::pool_stats(&env, &token, denomination)
+    }
+
+    /// Deposit exactly one denomination into its pool as a ring note.
+    ///
+    /// The note is identified by a one-time BLS12-381 G1 public key `P = x·G`; the owner
+    /// later withdraws with a ring signature (see
+    /// [`withdraw_with_ring_signature`](QuickexContract::withdraw_with_ring_signature)).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Depositor (must authorize the token transfer)
+    /// * `token` - Token contract address
+    /// * `denomination` - Registered denomination to deposit
+    /// * `public_key` - One-time public key (uncompressed 96-byte G1 point)
+    ///
+    /// # Errors
+    /// * `ContractPaused` - Contract is currently paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `TokenNotAllowed` - The token allowlist is in force and the token is not on it
+    /// * `CapExceeded` - The deposit would exceed the token's cap or the global cap
+    /// * `InvalidDenomination` - Amount is not a registered denomination for the token
+    /// * `InvalidPoint` - Public key is not a valid G1 point
+    /// * `CommitmentAlreadyExists` - Public key already registered
+    pub fn deposit_ring_note(
+        env: Env,
+        from: Address,
+        token: Address,
+        denomination: i128,
+        public_key: BytesN<96>,
+    ) -> Result<(), Error> {
+        admin::require_function_enabled(&env, "deposit_ring_note")?;
+        admin::require_op_enabled(&env, admin::PAUSE_DEPOSITS)?;
+        ring::deposit_ring_note(&env, from, token, denomination, public_key)
+    }
+
+    /// Withdraw a ring note by proving ownership of one of `ring` without revealing which.
+    ///
+    /// Verifies a linkable ring signature (LSAG) over 2–16 ring notes of the same pool.
+    /// The key image links signatures by the same key, so each note is withdrawable once.
+    /// Anyone may submit the call; the signature binds `recipient`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token` - Pool token
+    /// * `denomination` - Pool denomination
+    /// * `recipient` - Address that receives one denomination
+    /// * `ring` - Public keys of the ring notes
+    /// * `signature` - Key image, initial challenge, and one response per ring member
+    ///
+    /// # Errors
+    /// * `ContractPaused` - Contract is currently paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `InvalidRing` - Bad ring size or a member is not a ring note of this pool
+    /// * `InvalidPoint` - Key image is not a valid G1 point
+    /// * `AlreadySpent` - Key image already used
+    /// * `InvalidSignature` - Signature does not verify
+    pub fn withdraw_with_ring_signature(
+        env: Env,
+        token: Address,
+        denomination: i128,
+        recipient: Address,
+        ring: Vec<BytesN<96>>,
+        signature: RingSignature,
+    ) -> Result<(), Error> {
+        admin::require_function_enabled(&env, "withdraw_with_ring_signature")?;
+        admin::require_op_enabled(&env, admin::PAUSE_WITHDRAWALS)?;
+        ring::withdraw_with_ring_signature(&env, token, denomination, recipient, ring, signature)
+    }
+}
+
+/// Groth16 shielded pool and its hashing helpers (`zk-verifier` feature).
+#[cfg(feature = "zk-verifier")]
+#[contractimpl]
+impl QuickexContract {
+    /// Register or rotate the Groth16 verifying key for shielded withdrawals (**Admin only**).
+    ///
+    /// Stores `vk` under the next version number and makes it current; withdrawals are
+    /// verified against the current version only. Returns the new version.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `vk` - Verifying key of the withdrawal circuit (3 public inputs, so 4 `ic` points)
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `InvalidVerifyingKey` - Wrong number of `ic` points or an invalid curve point
+    pub fn set_verifying_key(env: Env, caller: Address, vk: VerifyingKey) -> Result<u32, Error> {
+        verifier::set_verifying_key(&env, caller, vk)
+    }
+
+    /// Get a registered verifying key (read-only).
+    ///
+    /// Returns `None` if no key has been registered or `version` does not exist.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `version` - Version to fetch; `None` for the current key
+    pub fn get_verifying_key(env: Env, version: Option<u32>) -> Option<VerifyingKeyRecord> {
+        verifier::get_verifying_key(&env, version)
+    }
+
+    /// Deposit exactly one denomination into its pool as a shielded note.
+    ///
+    /// Only the Merkle leaf `SHA256(nullifier || secret)` is published. Returns the
+    /// leaf index, needed off-chain to build the membership proof.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Depositor (must authorize the token transfer)
+    /// * `token` - Token contract address
+    /// * `denomination` - Registered denomination to deposit
+    /// * `leaf` - 32-byte note commitment inserted into the pool's Merkle tree
+    ///
+    /// # Errors
+    /// * `ContractPaused` - Contract is currently paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `TokenNotAllowed` - The token allowlist is in force and the token is not on it
+    /// * `CapExceeded` - The deposit would exceed the token's cap or the global cap
+    /// * `InvalidDenomination` - Amount is not a registered denomination for the token
+    /// * `MerkleTreeFull` - The pool's tree has no free leaves
+    pub fn deposit_shielded(
+        env: Env,
+        from: Address,
+        token: Address,
+        denomination: i128,
+        leaf: BytesN<32>,
+    ) -> Result<u32, Error> {
+        admin::require_function_enabled(&env, "deposit_shielded")?;
+        admin::require_op_enabled(&env, admin::PAUSE_DEPOSITS)?;
+        shielded::deposit_shielded(&env, from, token, denomination, leaf)
+    }
+
+    /// Withdraw a shielded note with a Groth16 proof of Merkle membership.
+    ///
+    /// Reveals only the nullifier hash and recipient; the note, its owner, and its
+    /// position in the tree stay private. Anyone may submit the call.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `proof` - Groth16 proof over BLS12-381
+    /// * `public_inputs` - Pool, root, nullifier hash, and recipient bound by the proof
+    ///
+    /// # Errors
+    /// * `ContractPaused` - Contract is currently paused
+    /// * `FunctionDisabled` - This entrypoint has been disabled by the kill switch
+    /// * `MigrationPending` - An upgrade is waiting for `migrate`
+    /// * `InvalidRoot` - Root is not a recent root of the pool
+    /// * `AlreadySpent` - Nullifier hash has already been used
+    /// * `VerifyingKeyNotSet` - No verifying key registered
+    /// * `InvalidProof` - Proof does not verify
+    pub fn withdraw_with_proof(
+        env: Env,
+        proof: Groth16Proof,
+        public_inputs: WithdrawPublicInputs,
+    ) -> Result<(), Error> {
+        admin::require_function_enabled(&env, "withdraw_with_proof")?;
+        admin::require_op_enabled(&env, admin::PAUSE_WITHDRAWALS)?;
+        shielded::withdraw_with_proof(&env, proof, public_inputs)
+    }
+
+    /// Get the current Merkle root of a shielded pool (read-only).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token` - Token contract address
+    /// * `denomination` - Pool denomination
+    pub fn get_pool_root(env: Env, token: Address, denomination: i128) -> BytesN<32> {
+        shielded::get_pool_root(&env, &token, denomination)
+    }
+
+    /// Compute the two-to-one Poseidon hash `permute([0, left, right])[0]` (read-only).
+    ///
+    /// SNARK-friendly alternative to SHA-256 for note commitments, e.g. a shielded pool
+    /// leaf `Poseidon(nullifier, secret)`. Lets clients check their off-chain
+    /// implementation against the contract's.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `field` - Scalar field to hash over (`Bls12381` or `Bn254`)
+    /// * `left` - First input, canonical big-endian field element
+    /// * `right` - Second input, canonical big-endian field element
+    ///
+    /// # Errors
+    /// * `InvalidFieldElement` - An input is not below the field modulus
+    pub fn poseidon_hash(
+        env: Env,
+        field: PoseidonField,
+        left: BytesN<32>,
+        right: BytesN<32>,
+    ) -> Result<BytesN<32>, Error> {
+        poseidon::hash2(&env, field, &left, &right)
+    }
+
+    /// Check a Merkle membership proof for a shielded pool leaf (read-only).
+    ///
+    /// Hashes `leaf` up along `path` and compares with `root`. Lets wallets and relayers
+    /// sanity-check the path they feed into a withdrawal proof before submitting it; pair
+    /// it with [`get_known_roots`](QuickexContract::get_known_roots) to confirm the root
+    /// is still accepted.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `leaf` - Note commitment (`SHA256(nullifier || secret)`)
+    /// * `path` - Leaf index and the 20 sibling hashes from the leaf level up
+    /// * `root` - Expected tree root
+    ///
+    /// # Returns
+    /// `false` if the path is malformed or does not lead to `root`.
+    pub fn verify_merkle_proof(
+        env: Env,
+        leaf: BytesN<32>,
+        path: MerklePath,
+        root: BytesN<32>,
+    ) -> bool {
+        merkle::verify_proof(&env, &leaf, &path, &root)
+    }
+
+    /// Get the Merkle roots a withdrawal proof may target (read-only).
+    ///
+    /// The pool keeps its last 30 roots, oldest first with the current root last, so a
+    /// proof built against a slightly stale root remains valid while other deposits land.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token` - Token contract address
+    /// * `denomination` - Pool denomination
+    pub fn get_known_roots(env: Env, token: Address, denomination: i128) -> Vec<BytesN<32>> {
+        shielded::get_known_roots(&env, &token, denomination)
+    }
+
+    /// Get the number of shielded notes deposited into a pool (read-only).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token` - Token contract address
+    /// * `denomination` - Pool denomination
+    pub fn get_pool_size(env: Env, token: Address, denomination: i128) -> u32 {
+        shielded::get_pool_size(&env, &token, denomination)
     }
 }
@@ -0,0 +1,221 @@
+//! Property tests for the escrow lifecycle.
+//!
+//! Random sequences of deposits, withdrawals, refunds, clock moves and pauses run
+//! against the contract and a small model of the expected escrow states. After every
+//! step the contract must agree with the model: an escrow is paid out at most once,
+//! and the tokens held by the contract always equal the pending escrow amounts.
+
+extern crate std;
+
+use proptest::prelude::*;
+use soroban_sdk::{testutils::Ledger as _, token, Bytes, BytesN};
+
+use crate::{
+    escrow_fixture::{config, Fixture},
+    types::EscrowStatus,
+};
+
+const USERS: usize = 3;
+const MINTED: i128 = 1_000_000;
+const AMOUNTS: [i128; 3] = [100, 250, 1_000];
+
+#[derive(Clone, Debug)]
+enum Op {
+    Deposit {
+        user: usize,
+        amount: usize,
+        salt: u8,
+        timeout: u64,
+    },
+    Withdraw {
+        escrow: usize,
+    },
+    Refund {
+        escrow: usize,
+        user: usize,
+    },
+    Advance(u64),
+    Pause(bool),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        3 => (0..USERS, 0..AMOUNTS.len(), 0..4u8, prop_oneof![Just(0u64), 10..100u64]).prop_map(
+            |(user, amount, salt, timeout)| Op::Deposit {
+                user,
+                amount,
+                salt,
+                timeout,
+            }
+        ),
+        3 => any::<usize>().prop_map(|escrow| Op::Withdraw { escrow }),
+        2 => (any::<usize>(), 0..USERS).prop_map(|(escrow, user)| Op::Refund { escrow, user }),
+        1 => (1..120u64).prop_map(Op::Advance),
+        1 => any::<bool>().prop_map(Op::Pause),
+    ]
+}
+
+/// Expected state of one deposited escrow.
+struct Escrow {
+    user: usize,
+    amount: i128,
+    salt: Bytes,
+    commitment: BytesN<32>,
+    expires_at: u64,
+    status: EscrowStatus,
+}
+
+struct Harness<'a> {
+    fx: Fixture<'a, USERS>,
+    escrows: std::vec::Vec<Escrow>,
+    paused: bool,
+}
+
+impl Harness<'_> {
+    fn new() -> Self {
+        Harness {
+            fx: Fixture::new(MINTED),
+            escrows: std::vec::Vec::new(),
+            paused: false,
+        }
+    }
+
+    fn now(&self) -> u64 {
+        self.fx.env.ledger().timestamp()
+    }
+
+    fn is_expired(&self, escrow: &Escrow) -> bool {
+        escrow.expires_at > 0 && self.now() >= escrow.expires_at
+    }
+
+    fn apply(&mut self, op: Op) {
+        match op {
+            Op::Deposit {
+                user,
+                amount,
+                salt,
+                timeout,
+            } => {
+                let amount = AMOUNTS[amount];
+                let salt = Bytes::from_array(&self.fx.env, &[salt; 16]);
+                let owner = &self.fx.users[user];
+                let result =
+                    self.fx
+                        .client
+                        .try_deposit(&self.fx.token, &amount, owner, &salt, &timeout);
+                let commitment = self
+                    .fx
+                    .client
+                    .create_amount_commitment(owner, &amount, &salt);
+                let duplicate = self.escrows.iter().any(|e| e.commitment == commitment);
+                assert_eq!(result.is_ok(), !self.paused && !duplicate, "deposit");
+                if result.is_ok() {
+                    self.escrows.push(Escrow {
+                        user,
+                        amount,
+                        salt,
+                        commitment,
+                        expires_at: if timeout == 0 {
+                            0
+                        } else {
+                            self.now() + timeout
+                        },
+                        status: EscrowStatus::Pending,
+                    });
+                }
+            }
+            Op::Withdraw { escrow } => {
+                let Some(index) = self.pick(escrow) else {
+                    return;
+                };
+                let e = &self.escrows[index];
+                let expected =
+                    !self.paused && e.status == EscrowStatus::Pending && !self.is_expired(e);
+                let result = self.fx.client.try_withdraw(
+                    &self.fx.token,
+                    &e.amount,
+                    &e.commitment,
+                    &self.fx.users[e.user],
+                    &e.salt,
+                );
+                assert_eq!(result.is_ok(), expected, "withdraw");
+                if expected {
+                    self.escrows[index].status = EscrowStatus::Spent;
+                }
+            }
+            Op::Refund { escrow, user } => {
+                let Some(index) = self.pick(escrow) else {
+                    return;
+                };
+                let e = &self.escrows[index];
+                let expected = !self.paused
+                    && e.status == EscrowStatus::Pending
+                    && self.is_expired(e)
+                    && e.user == user;
+                let result = self
+                    .fx
+                    .client
+                    .try_refund(&e.commitment, &self.fx.users[user]);
+                assert_eq!(result.is_ok(), expected, "refund");
+                if expected {
+                    self.escrows[index].status = EscrowStatus::Refunded;
+                }
+            }
+            Op::Advance(secs) => {
+                let now = self.now();
+                self.fx.env.ledger().set_timestamp(now + secs);
+            }
+            Op::Pause(paused) => {
+                self.fx.client.set_paused(&self.fx.admin, &paused);
+                self.paused = paused;
+            }
+        }
+    }
+
+    fn pick(&self, escrow: usize) -> Option<usize> {
+        (!self.escrows.is_empty()).then(|| escrow % self.escrows.len())
+    }
+
+    fn check_invariants(&self) {
+        let token = token::Client::new(&self.fx.env, &self.fx.token);
+        let pending: i128 = self
+            .escrows
+            .iter()
+            .filter(|e| e.status == EscrowStatus::Pending)
+            .map(|e| e.amount)
+            .sum();
+        let held = token.balance(&self.fx.client.address);
+        assert_eq!(held, pending, "contract balance backs pending escrows");
+        assert_eq!(
+            self.fx.client.proof_of_reserves(&self.fx.token),
+            (held, pending)
+        );
+
+        let user_total: i128 = self.fx.users.iter().map(|u| token.balance(u)).sum();
+        assert_eq!(
+            user_total + held,
+            MINTED * USERS as i128,
+            "tokens conserved"
+        );
+
+        for e in &self.escrows {
+            assert_eq!(
+                self.fx.client.get_commitment_state(&e.commitment),
+                Some(e.status)
+            );
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn prop_lifecycle_keeps_invariants(ops in prop::collection::vec(op(), 1..40)) {
+        let mut harness = Harness::new();
+        for op in ops {
+            harness.apply(op);
+            harness.check_invariants();
+        }
+    }
+}
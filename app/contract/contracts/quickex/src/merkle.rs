@@ -0,0 +1,133 @@
+//! Incremental Merkle tree of shielded pool notes.
+//!
+//! Each (token, denomination) pool keeps an append-only binary tree of depth
+//! [`TREE_DEPTH`] whose leaves are note commitments supplied by depositors. Only the
+//! rightmost "filled subtree" at each level and the current root are stored, so an
+//! insert costs [`TREE_DEPTH`] hashes and constant storage.
+//!
+//! ```text
+//! node   = SHA256(left || right)
+//! zero_0 = SHA256("quickex/merkle/zero")
+//! zero_i = SHA256(zero_{i-1} || zero_{i-1})
+//! ```
+//!
+//! Empty positions hash as `zero_i`, so the root of an empty tree is `zero_DEPTH`.
+
+use soroban_sdk::{Bytes, BytesN, Env, Vec};
+
+use crate::{
+    errors::QuickexError,
+    types::{MerklePath, MerkleTree},
+};
+
+/// Tree depth; a pool holds at most `2^TREE_DEPTH` notes.
+pub const TREE_DEPTH: u32 = 20;
+
+/// Number of recent roots accepted by [`is_known_root`].
+pub const ROOT_HISTORY_SIZE: u32 = 30;
+
+/// Preimage of the level-0 zero value.
+const ZERO_LEAF_SEED: &[u8] = b"quickex/merkle/zero";
+
+/// Hash two child nodes into their parent.
+pub fn hash_pair(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut payload = Bytes::from_array(env, &left.to_array());
+    payload.append(&right.clone().into());
+    env.crypto().sha256(&payload).into()
+}
+
+/// Zero values for levels `0..=TREE_DEPTH`.
+pub fn zero_hashes(env: &Env) -> Vec<BytesN<32>> {
+    let mut zeros = Vec::new(env);
+    let mut current: BytesN<32> = env
+        .crypto()
+        .sha256(&Bytes::from_slice(env, ZERO_LEAF_SEED))
+        .into();
+    zeros.push_back(current.clone());
+    for _ in 0..TREE_DEPTH {
+        current = hash_pair(env, &current, &current);
+        zeros.push_back(current.clone());
+    }
+    zeros
+}
+
+/// A tree with no leaves.
+pub fn empty_tree(env: &Env) -> MerkleTree {
+    let zeros = zero_hashes(env);
+    let mut filled_subtrees = Vec::new(env);
+    for level in 0..TREE_DEPTH {
+        filled_subtrees.push_back(zeros.get_unchecked(level));
+    }
+    let root = zeros.get_unchecked(TREE_DEPTH);
+    MerkleTree {
+        next_index: 0,
+        filled_subtrees,
+        root: root.clone(),
+        recent_roots: Vec::from_array(env, [root]),
+    }
+}
+
+/// Append `leaf` and update the root. Returns the leaf index.
+///
+/// # Errors
+/// - [`QuickexError::MerkleTreeFull`] – the tree already holds `2^TREE_DEPTH` leaves.
+pub fn insert(env: &Env, tree: &mut MerkleTree, leaf: BytesN<32>) -> Result<u32, QuickexError> {
+    let index = tree.next_index;
+    if u64::from(index) >= 1u64 << TREE_DEPTH {
+        return Err(QuickexError::MerkleTreeFull);
+    }
+
+    let zeros = zero_hashes(env);
+    let mut current = leaf;
+    let mut position = index;
+    for level in 0..TREE_DEPTH {
+        current = if position & 1 == 0 {
+            tree.filled_subtrees.set(level, current.clone());
+            hash_pair(env, &current, &zeros.get_unchecked(level))
+        } else {
+            hash_pair(env, &tree.filled_subtrees.get_unchecked(level), &current)
+        };
+        position /= 2;
+    }
+
+    tree.recent_roots.push_back(current.clone());
+    if tree.recent_roots.len() > ROOT_HISTORY_SIZE {
+        tree.recent_roots.pop_front();
+    }
+    tree.root = current;
+    tree.next_index = index + 1;
+    Ok(index)
+}
+
+/// Returns `true` if `root` is the current root or one of the last
+/// [`ROOT_HISTORY_SIZE`] roots.
+pub fn is_known_root(tree: &MerkleTree, root: &BytesN<32>) -> bool {
+    tree.recent_roots.contains(root)
+}
+
+/// Root obtained by hashing `leaf` up along `path`.
+///
+/// Returns `None` if the path does not have exactly [`TREE_DEPTH`] siblings or the
+/// index is out of range.
+pub fn compute_root(env: &Env, leaf: &BytesN<32>, path: &MerklePath) -> Option<BytesN<32>> {
+    if path.siblings.len() != TREE_DEPTH || u64::from(path.index) >= 1u64 << TREE_DEPTH {
+        return None;
+    }
+
+    let mut current = leaf.clone();
+    let mut position = path.index;
+    for sibling in path.siblings.iter() {
+        current = if position & 1 == 0 {
+            hash_pair(env, &current, &sibling)
+        } else {
+            hash_pair(env, &sibling, &current)
+        };
+        position /= 2;
+    }
+    Some(current)
+}
+
+/// Returns `true` if `path` proves that `leaf` is in the tree with root `root`.
+pub fn verify_proof(env: &Env, leaf: &BytesN<32>, path: &MerklePath, root: &BytesN<32>) -> bool {
+    compute_root(env, leaf, path).is_some_and(|computed| computed == *root)
+}
@@ -0,0 +1,315 @@
+//! # Upgrades and Storage Migration
+//!
+//! An upgrade is a two-step operation:
+//!
+//! ```text
+//! upgrade(caller, wasm_hash, version)   old code: swap WASM, record PendingUpgrade
+//!          │   deposits / withdrawals / refunds fail with MigrationPending
+//!          ▼
+//! migrate()                             new code: run storage steps, bump schema version
+//! ```
+//!
+//! `version` is the [`CONTRACT_VERSION`] compiled into the new WASM; `migrate` refuses
+//! to run unless it matches, so the old code can never "complete" its own upgrade.
+//!
+//! ## Storage schema versions
+//!
+//! | Version | Change |
+//! |---------|--------|
+//! | 1       | Original layout (implied when no version is stored). |
+//! | 2       | Legacy `Paused` flag folded into the `PausedOps` bitmask. |
+//! | 3       | `PausedOps`, `WithdrawalFeeBps`, and `GlobalCap` moved into the instance `Config`. |
+//! | 4       | `Admin` moved to instance storage, next to `Config`. |
+//! | 5       | `TotalLocked` backfilled for every token in `EscrowStats` (see below). |
+//!
+//! Values read on nearly every call (admin, paused operations, fees) live in instance
+//! storage, which is loaded with the contract and shares its TTL. `SchemaVersion` and
+//! `PendingUpgrade` stay persistent: the code being replaced writes them, and the new
+//! code must find them where the old code left them.
+//!
+//! ## Locked totals
+//!
+//! Deposits made before `TotalLocked` existed were never added to it, so paying them
+//! out could drive the total negative and let `emergency_withdraw` sweep user funds.
+//! Escrows cannot be enumerated on-chain, so the v5 step raises each total to the
+//! most the contract can owe escrows and pool notes: its balance less accrued fees and
+//! relayer stakes. That may over-reserve tokens sent to the contract directly, but
+//! never under-reserves. Tokens used only before `EscrowStats` existed are not known
+//! on-chain; the admin backfills them with [`backfill_total_locked`].
+
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, Vec};
+
+use crate::{
+    admin, config,
+    errors::QuickexError,
+    events::{
+        publish_legacy_escrows_migrated, publish_storage_migrated, publish_total_locked_backfilled,
+        publish_upgrade_executed,
+    },
+    privacy, solvency,
+    storage::{self, DataKey},
+    timelock, tokens,
+    types::{AdminAction, EscrowStatus, UpgradeBlocker, UpgradeRecord, UpgradeReport},
+};
+
+/// Version of this contract code.
+pub const CONTRACT_VERSION: u32 = 1;
+
+/// Storage schema version this code expects.
+pub const STORAGE_SCHEMA_VERSION: u32 = 5;
+
+/// Persisted storage schema version (1 for deployments that predate versioning).
+pub fn schema_version(env: &Env) -> u32 {
+    storage::get_schema_version(env).unwrap_or(1)
+}
+
+/// `(code version, persisted storage schema version)`.
+///
+/// A schema version below [`STORAGE_SCHEMA_VERSION`], or an upgrade still pending,
+/// means the deployment is half-migrated.
+pub fn version(env: &Env) -> (u32, u32) {
+    (CONTRACT_VERSION, schema_version(env))
+}
+
+/// Raw XDR (`ScVal`) of the stored escrow entry for `commitment` (**admin only**).
+///
+/// Returns the value exactly as stored, without decoding, so entries that fail to
+/// load (corrupted, or in a legacy format) can still be inspected. See
+/// [`storage::get_raw_escrow`] for the keys searched.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – `caller` is not the admin.
+pub fn debug_dump_entry(
+    env: &Env,
+    caller: Address,
+    commitment: BytesN<32>,
+) -> Result<Option<Bytes>, QuickexError> {
+    admin::require_admin(env, &caller)?;
+    Ok(storage::get_raw_escrow(env, &commitment).map(|value| value.to_xdr(env)))
+}
+
+/// Whether an upgrade is waiting for [`migrate`].
+pub fn is_pending(env: &Env) -> bool {
+    storage::get_pending_upgrade(env).is_some()
+}
+
+/// Report whether an upgrade to `wasm_hash` would be accepted now, and why not.
+///
+/// Checks the admin, timelock, pending migration, and schema version, so operators
+/// see every problem up front. Whether `wasm_hash` has been uploaded cannot be
+/// checked on-chain.
+pub fn check_upgrade(env: &Env, wasm_hash: &BytesN<32>) -> UpgradeReport {
+    let mut blockers = Vec::new(env);
+    if admin::is_renounced(env) {
+        blockers.push_back(UpgradeBlocker::AdminRenounced);
+    }
+    if timelock::delay(env) > 0 {
+        blockers.push_back(UpgradeBlocker::TimelockRequired);
+    }
+    if is_pending(env) {
+        blockers.push_back(UpgradeBlocker::MigrationPending);
+    }
+    let schema_version = schema_version(env);
+    if schema_version > STORAGE_SCHEMA_VERSION {
+        blockers.push_back(UpgradeBlocker::SchemaAhead);
+    }
+
+    let scheduled_at = timelock::get_scheduled_upgrade(env).and_then(|queued| {
+        matches!(&queued.action, AdminAction::Upgrade(hash, _) if hash == wasm_hash)
+            .then_some(queued.eta)
+    });
+
+    UpgradeReport {
+        allowed: blockers.is_empty(),
+        blockers,
+        code_version: CONTRACT_VERSION,
+        schema_version,
+        paused_ops: admin::paused_ops(env),
+        scheduled_at,
+    }
+}
+
+/// Fail unless storage is fully migrated to this code's schema.
+///
+/// # Errors
+/// - [`QuickexError::MigrationPending`] – an upgrade is waiting for [`migrate`], or the
+///   schema is behind [`STORAGE_SCHEMA_VERSION`] (e.g. locked totals not backfilled).
+pub fn require_migrated(env: &Env) -> Result<(), QuickexError> {
+    if is_pending(env) || schema_version(env) < STORAGE_SCHEMA_VERSION {
+        return Err(QuickexError::MigrationPending);
+    }
+    Ok(())
+}
+
+/// Fail if storage is not in a state the next upgrade can build on.
+///
+/// # Errors
+/// - [`QuickexError::MigrationPending`] – the previous upgrade has not been migrated.
+/// - [`QuickexError::SchemaDowngrade`] – storage was written by a newer schema.
+pub fn require_upgradable(env: &Env) -> Result<(), QuickexError> {
+    if is_pending(env) {
+        return Err(QuickexError::MigrationPending);
+    }
+    if schema_version(env) > STORAGE_SCHEMA_VERSION {
+        return Err(QuickexError::SchemaDowngrade);
+    }
+    Ok(())
+}
+
+/// Switch to `wasm_hash` and block normal operations until [`migrate`] runs.
+///
+/// Authorization is the caller's responsibility.
+pub(crate) fn upgrade(env: &Env, wasm_hash: BytesN<32>, version: u32, executor: &Address) {
+    let record = UpgradeRecord {
+        wasm_hash: wasm_hash.clone(),
+        version,
+        upgraded_at: env.ledger().timestamp(),
+    };
+    storage::set_pending_upgrade(env, Some(&record));
+    let old_hash = storage::get_wasm_hash(env);
+    storage::set_wasm_hash(env, &wasm_hash);
+
+    env.deployer()
+        .update_current_contract_wasm(wasm_hash.clone());
+
+    publish_upgrade_executed(env, old_hash, wasm_hash, executor, version);
+}
+
+/// Complete an upgrade by migrating storage to [`STORAGE_SCHEMA_VERSION`].
+///
+/// Callable by anyone, once per upgrade. Also migrates deployments upgraded before
+/// upgrades were recorded, if their schema is behind. Schema versions only move forward.
+///
+/// # Errors
+/// - [`QuickexError::SchemaDowngrade`] – storage was written by a newer schema.
+/// - [`QuickexError::NothingToMigrate`] – no upgrade is pending and storage is current.
+/// - [`QuickexError::UpgradeVersionMismatch`] – the pending upgrade targets a different
+///   code version than the one running.
+pub fn migrate(env: &Env) -> Result<u32, QuickexError> {
+    let pending = storage::get_pending_upgrade(env);
+    let from = schema_version(env);
+    if from > STORAGE_SCHEMA_VERSION {
+        return Err(QuickexError::SchemaDowngrade);
+    }
+    if pending.is_none() && from >= STORAGE_SCHEMA_VERSION {
+        return Err(QuickexError::NothingToMigrate);
+    }
+    if let Some(record) = &pending {
+        if record.version != CONTRACT_VERSION {
+            return Err(QuickexError::UpgradeVersionMismatch);
+        }
+    }
+
+    if from < 3 {
+        move_flags_into_config(env);
+    }
+    if from < 4 {
+        move_admin_to_instance(env);
+    }
+    if from < 5 {
+        backfill_known_locked_totals(env);
+    }
+
+    storage::set_schema_version(env, STORAGE_SCHEMA_VERSION);
+    storage::set_pending_upgrade(env, None);
+    publish_storage_migrated(env, from, STORAGE_SCHEMA_VERSION, CONTRACT_VERSION);
+
+    Ok(STORAGE_SCHEMA_VERSION)
+}
+
+/// v1/v2 → v3: store the config, which picks up the legacy `Paused`, `PausedOps`,
+/// `WithdrawalFeeBps`, and `GlobalCap` values, then drop those keys.
+fn move_flags_into_config(env: &Env) {
+    storage::set_config(env, &config::get(env));
+    let persistent = env.storage().persistent();
+    persistent.remove(&DataKey::Paused);
+    persistent.remove(&DataKey::PausedOps);
+    persistent.remove(&DataKey::WithdrawalFeeBps);
+    persistent.remove(&DataKey::GlobalCap);
+}
+
+/// v3 → v4: move the admin address from persistent to instance storage.
+fn move_admin_to_instance(env: &Env) {
+    let persistent = env.storage().persistent();
+    if let Some(admin) = persistent.get::<_, Address>(&DataKey::Admin) {
+        storage::set_admin(env, &admin);
+        persistent.remove(&DataKey::Admin);
+    }
+}
+
+/// v4 → v5: backfill the locked total of every token that has had an escrow since
+/// stats were recorded. Tokens whose balance cannot be read are skipped.
+fn backfill_known_locked_totals(env: &Env) {
+    for token in storage::get_escrow_stats(env).tokens.keys() {
+        let _ = raise_total_locked(env, &token);
+    }
+}
+
+/// Raise the locked total of `token` to its balance less accrued fees and relayer
+/// stakes, if that is higher. Returns the new total.
+fn raise_total_locked(env: &Env, token: &Address) -> Result<i128, QuickexError> {
+    let balance = tokens::balance(env, token)?;
+    let (locked, accrued_fees, staked) = solvency::liabilities(env, token);
+    let owed_elsewhere = accrued_fees.saturating_add(staked).max(0);
+    let backfilled = locked.max(balance.saturating_sub(owed_elsewhere)).max(0);
+    if backfilled != locked {
+        storage::set_total_locked(env, token, backfilled);
+        publish_total_locked_backfilled(env, token.clone(), locked, backfilled);
+    }
+    Ok(backfilled)
+}
+
+/// Backfill the locked total of `token` as the v5 migration does (**admin only**).
+/// Returns the new total.
+///
+/// For tokens deposited only before escrow stats were recorded, which `migrate`
+/// cannot find. Never lowers a total.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – `caller` is not the admin.
+/// - [`QuickexError::InvalidToken`] – `token` is not a token contract.
+pub fn backfill_total_locked(
+    env: &Env,
+    caller: Address,
+    token: Address,
+) -> Result<i128, QuickexError> {
+    admin::require_admin(env, &caller)?;
+    raise_total_locked(env, &token)
+}
+
+/// Move escrows stored under the legacy `("escrow", commitment)` tuple key into
+/// [`DataKey::Escrow`]. Returns how many were moved.
+///
+/// Per-entry keys cannot be enumerated on-chain, so the admin passes the commitments
+/// (from past deposit events). Commitments with no legacy entry, or already present
+/// under the new key, are skipped. Pending escrows are added to the token's locked
+/// total and the owner's claimable index, as a deposit would.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – `caller` is not the admin.
+pub fn migrate_legacy_escrows(
+    env: &Env,
+    caller: Address,
+    commitments: Vec<BytesN<32>>,
+) -> Result<u32, QuickexError> {
+    admin::require_admin(env, &caller)?;
+
+    let mut migrated = 0;
+    for commitment in commitments.iter() {
+        if storage::has_escrow(env, &commitment.clone().into()) {
+            continue;
+        }
+        let Some(entry) = storage::take_legacy_escrow(env, &commitment) else {
+            continue;
+        };
+        storage::put_escrow(env, &commitment.clone().into(), &entry);
+        if entry.status == EscrowStatus::Pending {
+            storage::adjust_total_locked(env, &entry.token, entry.amount)?;
+            privacy::index_commitment(env, &entry.owner, &commitment);
+        }
+        migrated += 1;
+    }
+
+    publish_legacy_escrows_migrated(env, caller, migrated);
+    Ok(migrated)
+}
@@ -0,0 +1,553 @@
+//! Differential tests against a reference model of the escrow state machine.
+//!
+//! [`Model`] re-implements deposits, withdrawals, refunds, pausing and the protocol
+//! withdrawal fee in plain Rust, with no storage layout, commitments or overflow-safe
+//! arithmetic. The driver replays the same operation sequence against the model and
+//! the contract, requires each call to succeed or fail with the same error, and diffs
+//! the two states after every step. A storage or fee refactor that changes observable
+//! behaviour shows up as a diff.
+
+extern crate std;
+
+use std::vec::Vec;
+
+use proptest::prelude::*;
+use soroban_sdk::{testutils::Ledger as _, token, Bytes, BytesN};
+
+use crate::{
+    admin::{PAUSE_DEPOSITS, PAUSE_REFUNDS, PAUSE_WITHDRAWALS},
+    errors::QuickexError,
+    escrow_fixture::{config, Fixture},
+    types::{EscrowStatus, TokenStats},
+};
+
+const USERS: usize = 3;
+const MINTED: i128 = 10_000;
+/// Includes a zero amount and one no user can afford.
+const AMOUNTS: [i128; 5] = [0, 99, 2_500, 4_001, MINTED + 1];
+/// Includes one above the 10% cap.
+const FEES_BPS: [u32; 6] = [0, 1, 37, 250, 1_000, 1_001];
+const FEE_WITHDRAWALS: [i128; 4] = [0, 1, 7, 50];
+
+#[derive(Clone, Debug)]
+enum Op {
+    Deposit {
+        user: usize,
+        amount: usize,
+        salt: u8,
+        timeout: u64,
+    },
+    Withdraw {
+        escrow: usize,
+        wrong_amount: bool,
+    },
+    Refund {
+        escrow: usize,
+        user: usize,
+    },
+    Advance(u64),
+    Pause {
+        ops: u32,
+        paused: bool,
+    },
+    SetFee(usize),
+    SetExempt {
+        user: usize,
+        exempt: bool,
+    },
+    WithdrawFees(usize),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        4 => (0..USERS, 0..AMOUNTS.len(), 0..4u8, prop_oneof![Just(0u64), 10..100u64]).prop_map(
+            |(user, amount, salt, timeout)| Op::Deposit {
+                user,
+                amount,
+                salt,
+                timeout,
+            }
+        ),
+        4 => (any::<usize>(), prop::bool::weighted(0.2)).prop_map(|(escrow, wrong_amount)| {
+            Op::Withdraw {
+                escrow,
+                wrong_amount,
+            }
+        }),
+        2 => (any::<usize>(), 0..USERS).prop_map(|(escrow, user)| Op::Refund { escrow, user }),
+        1 => (1..120u64).prop_map(Op::Advance),
+        1 => (1..8u32, any::<bool>()).prop_map(|(ops, paused)| Op::Pause { ops, paused }),
+        1 => (0..FEES_BPS.len()).prop_map(Op::SetFee),
+        1 => (0..USERS, any::<bool>()).prop_map(|(user, exempt)| Op::SetExempt { user, exempt }),
+        1 => (0..FEE_WITHDRAWALS.len()).prop_map(Op::WithdrawFees),
+    ]
+}
+
+/// Observable state, as read from the model or from the contract.
+#[derive(Debug, PartialEq)]
+struct Snapshot {
+    balances: [i128; USERS],
+    admin_balance: i128,
+    held: i128,
+    locked: i128,
+    fees: i128,
+    paused_ops: u32,
+    fee_bps: u32,
+    /// `(status, amount, expires_at)` of every escrow, in deposit order.
+    escrows: Vec<(EscrowStatus, i128, u64)>,
+    /// Pending, spent and refunded escrow counts.
+    counts: (u64, u64, u64),
+    /// Deposited, withdrawn and refunded volumes.
+    volumes: (i128, i128, i128),
+}
+
+struct ModelEscrow {
+    user: usize,
+    amount: i128,
+    salt: u8,
+    expires_at: u64,
+    status: EscrowStatus,
+}
+
+/// Reference escrow state machine for one token.
+///
+/// Checks run in the order the contract runs them, so both fail with the same error
+/// when several apply. Escrows are keyed by `(user, amount, salt)` rather than by
+/// commitment hash.
+struct Model {
+    now: u64,
+    paused_ops: u32,
+    fee_bps: u32,
+    exempt: [bool; USERS],
+    balances: [i128; USERS],
+    admin_balance: i128,
+    held: i128,
+    locked: i128,
+    fees: i128,
+    escrows: Vec<ModelEscrow>,
+    counts: (u64, u64, u64),
+    volumes: (i128, i128, i128),
+}
+
+impl Model {
+    fn new() -> Self {
+        Model {
+            now: 0,
+            paused_ops: 0,
+            fee_bps: 0,
+            exempt: [false; USERS],
+            balances: [MINTED; USERS],
+            admin_balance: 0,
+            held: 0,
+            locked: 0,
+            fees: 0,
+            escrows: Vec::new(),
+            counts: (0, 0, 0),
+            volumes: (0, 0, 0),
+        }
+    }
+
+    fn require_enabled(&self, op: u32) -> Result<(), QuickexError> {
+        if self.paused_ops & op != 0 {
+            return Err(QuickexError::ContractPaused);
+        }
+        Ok(())
+    }
+
+    fn is_expired(&self, escrow: &ModelEscrow) -> bool {
+        escrow.expires_at > 0 && self.now >= escrow.expires_at
+    }
+
+    fn deposit(
+        &mut self,
+        user: usize,
+        amount: i128,
+        salt: u8,
+        timeout: u64,
+    ) -> Result<(), QuickexError> {
+        self.require_enabled(PAUSE_DEPOSITS)?;
+        if amount <= 0 {
+            return Err(QuickexError::InvalidAmount);
+        }
+        if self
+            .escrows
+            .iter()
+            .any(|e| (e.user, e.amount, e.salt) == (user, amount, salt))
+        {
+            return Err(QuickexError::CommitmentAlreadyExists);
+        }
+        if self.balances[user] < amount {
+            return Err(QuickexError::TransferFailed);
+        }
+
+        self.balances[user] -= amount;
+        self.held += amount;
+        self.locked += amount;
+        self.escrows.push(ModelEscrow {
+            user,
+            amount,
+            salt,
+            expires_at: if timeout == 0 { 0 } else { self.now + timeout },
+            status: EscrowStatus::Pending,
+        });
+        self.counts.0 += 1;
+        self.volumes.0 += amount;
+        Ok(())
+    }
+
+    fn withdraw(&mut self, index: usize, wrong_amount: bool) -> Result<(), QuickexError> {
+        self.require_enabled(PAUSE_WITHDRAWALS)?;
+        if wrong_amount {
+            return Err(QuickexError::CommitmentMismatch);
+        }
+        let escrow = &self.escrows[index];
+        if escrow.status != EscrowStatus::Pending {
+            return Err(QuickexError::AlreadySpent);
+        }
+        if self.is_expired(escrow) {
+            return Err(QuickexError::EscrowExpired);
+        }
+
+        let (user, amount) = (escrow.user, escrow.amount);
+        let fee = if self.exempt[user] {
+            0
+        } else {
+            amount * self.fee_bps as i128 / 10_000
+        };
+        self.balances[user] += amount - fee;
+        self.held -= amount - fee;
+        self.fees += fee;
+        self.locked -= amount;
+        self.escrows[index].status = EscrowStatus::Spent;
+        self.counts.0 -= 1;
+        self.counts.1 += 1;
+        self.volumes.1 += amount;
+        Ok(())
+    }
+
+    fn refund(&mut self, index: usize, caller: usize) -> Result<(), QuickexError> {
+        self.require_enabled(PAUSE_REFUNDS)?;
+        let escrow = &self.escrows[index];
+        if escrow.status != EscrowStatus::Pending {
+            return Err(QuickexError::AlreadySpent);
+        }
+        if !self.is_expired(escrow) {
+            return Err(QuickexError::EscrowNotExpired);
+        }
+        if escrow.user != caller {
+            return Err(QuickexError::InvalidOwner);
+        }
+
+        let amount = escrow.amount;
+        self.balances[caller] += amount;
+        self.held -= amount;
+        self.locked -= amount;
+        self.escrows[index].status = EscrowStatus::Refunded;
+        self.counts.0 -= 1;
+        self.counts.2 += 1;
+        self.volumes.2 += amount;
+        Ok(())
+    }
+
+    fn set_paused_ops(&mut self, ops: u32, paused: bool) -> Result<(), QuickexError> {
+        if paused {
+            self.paused_ops |= ops;
+        } else {
+            self.paused_ops &= !ops;
+        }
+        Ok(())
+    }
+
+    fn set_fee(&mut self, fee_bps: u32) -> Result<(), QuickexError> {
+        if fee_bps > 1_000 {
+            return Err(QuickexError::InvalidFee);
+        }
+        self.fee_bps = fee_bps;
+        Ok(())
+    }
+
+    fn set_exempt(&mut self, user: usize, exempt: bool) -> Result<(), QuickexError> {
+        self.exempt[user] = exempt;
+        Ok(())
+    }
+
+    fn withdraw_fees(&mut self, amount: i128) -> Result<(), QuickexError> {
+        if amount <= 0 {
+            return Err(QuickexError::InvalidAmount);
+        }
+        if amount > self.fees {
+            return Err(QuickexError::InsufficientFees);
+        }
+        self.fees -= amount;
+        self.held -= amount;
+        self.admin_balance += amount;
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            balances: self.balances,
+            admin_balance: self.admin_balance,
+            held: self.held,
+            locked: self.locked,
+            fees: self.fees,
+            paused_ops: self.paused_ops,
+            fee_bps: self.fee_bps,
+            escrows: self
+                .escrows
+                .iter()
+                .map(|e| (e.status, e.amount, e.expires_at))
+                .collect(),
+            counts: self.counts,
+            volumes: self.volumes,
+        }
+    }
+}
+
+/// Reduce a `try_` client result to the contract's error, failing on a trap.
+fn outcome<T, C: core::fmt::Debug, I: core::fmt::Debug>(
+    result: Result<Result<T, C>, Result<QuickexError, I>>,
+) -> Result<(), QuickexError> {
+    match result {
+        Ok(Ok(_)) => Ok(()),
+        Err(Ok(error)) => Err(error),
+        Ok(Err(error)) => panic!("unexpected return value: {error:?}"),
+        Err(Err(error)) => panic!("contract trapped: {error:?}"),
+    }
+}
+
+/// Runs each operation against both the contract and the [`Model`].
+struct Harness<'a> {
+    fx: Fixture<'a, USERS>,
+    /// Commitment of each model escrow, by index.
+    commitments: Vec<BytesN<32>>,
+    model: Model,
+}
+
+impl Harness<'_> {
+    fn new() -> Self {
+        Harness {
+            fx: Fixture::new(MINTED),
+            commitments: Vec::new(),
+            model: Model::new(),
+        }
+    }
+
+    fn salt(&self, salt: u8) -> Bytes {
+        Bytes::from_array(&self.fx.env, &[salt; 16])
+    }
+
+    fn pick(&self, escrow: usize) -> Option<usize> {
+        let len = self.model.escrows.len();
+        (len > 0).then(|| escrow % len)
+    }
+
+    fn apply(&mut self, op: &Op) {
+        let (actual, expected) = match *op {
+            Op::Deposit {
+                user,
+                amount,
+                salt,
+                timeout,
+            } => {
+                let amount = AMOUNTS[amount];
+                let result = self.fx.client.try_deposit(
+                    &self.fx.token,
+                    &amount,
+                    &self.fx.users[user],
+                    &self.salt(salt),
+                    &timeout,
+                );
+                if let Ok(Ok(commitment)) = &result {
+                    self.commitments.push(commitment.clone());
+                }
+                (
+                    outcome(result),
+                    self.model.deposit(user, amount, salt, timeout),
+                )
+            }
+            Op::Withdraw {
+                escrow,
+                wrong_amount,
+            } => {
+                let Some(index) = self.pick(escrow) else {
+                    return;
+                };
+                let e = &self.model.escrows[index];
+                let result = self.fx.client.try_withdraw(
+                    &self.fx.token,
+                    &(e.amount + wrong_amount as i128),
+                    &self.commitments[index],
+                    &self.fx.users[e.user],
+                    &self.salt(e.salt),
+                );
+                (outcome(result), self.model.withdraw(index, wrong_amount))
+            }
+            Op::Refund { escrow, user } => {
+                let Some(index) = self.pick(escrow) else {
+                    return;
+                };
+                let result = self
+                    .fx
+                    .client
+                    .try_refund(&self.commitments[index], &self.fx.users[user]);
+                (outcome(result), self.model.refund(index, user))
+            }
+            Op::Advance(secs) => {
+                self.model.now += secs;
+                self.fx.env.ledger().set_timestamp(self.model.now);
+                (Ok(()), Ok(()))
+            }
+            Op::Pause { ops, paused } => {
+                let result = self
+                    .fx
+                    .client
+                    .try_set_paused_ops(&self.fx.admin, &ops, &paused);
+                (outcome(result), self.model.set_paused_ops(ops, paused))
+            }
+            Op::SetFee(fee) => {
+                let fee_bps = FEES_BPS[fee];
+                let result = self
+                    .fx
+                    .client
+                    .try_set_withdrawal_fee(&self.fx.admin, &fee_bps);
+                (outcome(result), self.model.set_fee(fee_bps))
+            }
+            Op::SetExempt { user, exempt } => {
+                let result = self.fx.client.try_set_fee_exempt(
+                    &self.fx.admin,
+                    &self.fx.users[user],
+                    &exempt,
+                );
+                (outcome(result), self.model.set_exempt(user, exempt))
+            }
+            Op::WithdrawFees(amount) => {
+                let amount = FEE_WITHDRAWALS[amount];
+                let result = self.fx.client.try_withdraw_fees(
+                    &self.fx.admin,
+                    &self.fx.token,
+                    &self.fx.admin,
+                    &amount,
+                );
+                (outcome(result), self.model.withdraw_fees(amount))
+            }
+        };
+        assert_eq!(actual, expected, "result of {op:?}");
+        assert_eq!(self.snapshot(), self.model.snapshot(), "state after {op:?}");
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        let token = token::Client::new(&self.fx.env, &self.fx.token);
+        let (held, locked) = self.fx.client.proof_of_reserves(&self.fx.token);
+        let stats = self.fx.client.stats();
+        let volume = stats
+            .tokens
+            .get(self.fx.token.clone())
+            .unwrap_or(TokenStats {
+                deposited: 0,
+                withdrawn: 0,
+                refunded: 0,
+            });
+        let escrows = self
+            .commitments
+            .iter()
+            .zip(&self.model.escrows)
+            .map(|(commitment, e)| {
+                let view = self
+                    .fx
+                    .client
+                    .get_escrow_details(commitment, &self.fx.users[e.user])
+                    .expect("escrow exists");
+                (view.status, view.amount.unwrap(), view.expires_at)
+            })
+            .collect();
+        Snapshot {
+            balances: core::array::from_fn(|i| token.balance(&self.fx.users[i])),
+            admin_balance: token.balance(&self.fx.admin),
+            held,
+            locked,
+            fees: self.fx.client.treasury_balance(&self.fx.token),
+            paused_ops: self.fx.client.paused_ops(),
+            fee_bps: self.fx.client.get_withdrawal_fee(),
+            escrows,
+            counts: (stats.pending, stats.spent, stats.refunded),
+            volumes: (volume.deposited, volume.withdrawn, volume.refunded),
+        }
+    }
+}
+
+#[test]
+fn test_model_matches_contract_on_fee_scenario() {
+    let mut harness = Harness::new();
+    let ops = [
+        Op::SetFee(3),
+        Op::Deposit {
+            user: 0,
+            amount: 1,
+            salt: 0,
+            timeout: 0,
+        },
+        Op::Deposit {
+            user: 1,
+            amount: 3,
+            salt: 0,
+            timeout: 50,
+        },
+        Op::Deposit {
+            user: 2,
+            amount: 2,
+            salt: 1,
+            timeout: 0,
+        },
+        Op::Withdraw {
+            escrow: 0,
+            wrong_amount: false,
+        },
+        Op::SetExempt {
+            user: 2,
+            exempt: true,
+        },
+        Op::Withdraw {
+            escrow: 2,
+            wrong_amount: false,
+        },
+        Op::Refund { escrow: 1, user: 1 },
+        Op::Advance(60),
+        Op::Withdraw {
+            escrow: 1,
+            wrong_amount: false,
+        },
+        Op::Refund { escrow: 1, user: 0 },
+        Op::Refund { escrow: 1, user: 1 },
+        Op::WithdrawFees(3),
+        Op::WithdrawFees(1),
+        Op::Pause {
+            ops: PAUSE_DEPOSITS,
+            paused: true,
+        },
+        Op::Deposit {
+            user: 0,
+            amount: 2,
+            salt: 2,
+            timeout: 0,
+        },
+    ];
+    for op in &ops {
+        harness.apply(op);
+    }
+    // 99 at 2.5% rounds the fee down to 2; the exempt withdrawal pays none.
+    assert_eq!(harness.model.fees, 1);
+    assert_eq!(harness.model.admin_balance, 1);
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn prop_contract_matches_model(ops in prop::collection::vec(op(), 1..40)) {
+        let mut harness = Harness::new();
+        for op in &ops {
+            harness.apply(op);
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! # Pedersen (Hiding) Amount Commitments
+//!
+//! Alternative to the SHA-256 scheme in [`crate::commitment`] where the amount is
+//! never stored in cleartext. Built on the BLS12-381 G1 host functions.
+//!
+//! ## Construction
+//!
+//! ```text
+//! C   = amount·G + r·H          (96-byte uncompressed G1 point)
+//! key = SHA256(XDR(to) || C)    (32-byte escrow storage key)
+//! ```
+//!
+//! where:
+//! - `G`, `H` are independent generators derived with `hash_to_g1` under the
+//!   [`PEDERSEN_DST`] domain tag, so nobody knows `log_G(H)`
+//! - `r` is a 32-byte blinding scalar chosen by the note creator, reduced mod the
+//!   BLS12-381 scalar field order
+//!
+//! ## Properties
+//!
+//! 1. **Hiding**: `C` reveals nothing about `amount` without `r` (perfectly hiding).
+//! 2. **Binding**: opening `C` to a different amount requires computing `log_G(H)`.
+//! 3. **Deposit soundness**: the depositor supplies `R = r·H` and the contract adds
+//!    `amount·G` itself. `R` comes in a [`BlindingProof`] of knowledge of `r`
+//!    with respect to `H`. Without it a depositor could send `R = k·G + r·H` and later
+//!    withdraw `amount + k`; since nobody knows `log_H(G)`, such a point has no proof.
+//!    So `C` always commits to the amount actually transferred in.
+//!
+//! ## Blinding proof
+//!
+//! ```text
+//! T = k·H                                   (random nonce k)
+//! c = SHA256(DST || key || R || T) mod q    (key = escrow storage key)
+//! s = k + c·r
+//! ```
+//!
+//! The contract accepts when `s·H = T + c·R`. Binding `key` into `c` stops a proof from
+//! being replayed for another escrow.
+//!
+//! ## Limitations
+//!
+//! - The amount is still visible in the token transfer at deposit and in the
+//!   opening at withdrawal; only the persisted escrow state is amount-free.
+//! - Withdrawal reveals `r`, which lets observers recompute `C` after the fact.
+//!   Full unlinkability requires zero-knowledge proofs.
+
+use crate::{bls, errors::QuickexError, types::BlindingProof};
+use soroban_sdk::{
+    crypto::bls12_381::{Fr, G1Affine},
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env,
+};
+
+/// Domain separation tag for deriving the generators.
+pub const PEDERSEN_DST: &[u8] = b"QUICKEX-V1-PEDERSEN-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+
+/// Domain separation tag for the blinding-proof challenge.
+const BLINDING_PROOF_DST: &[u8] = b"QUICKEX-V1-PEDERSEN-BLINDING-POK";
+
+/// Message hashed to obtain the value generator `G`.
+const GENERATOR_G_MSG: &[u8] = b"quickex/pedersen/G";
+
+/// Message hashed to obtain the blinding generator `H`.
+const GENERATOR_H_MSG: &[u8] = b"quickex/pedersen/H";
+
+fn generator(env: &Env, msg: &[u8]) -> G1Affine {
+    env.crypto().bls12_381().hash_to_g1(
+        &Bytes::from_slice(env, msg),
+        &Bytes::from_slice(env, PEDERSEN_DST),
+    )
+}
+
+/// Value generator `G`.
+pub fn generator_g(env: &Env) -> G1Affine {
+    generator(env, GENERATOR_G_MSG)
+}
+
+/// Blinding generator `H`.
+pub fn generator_h(env: &Env) -> G1Affine {
+    generator(env, GENERATOR_H_MSG)
+}
+
+/// Convert a non-negative amount into a field scalar.
+fn amount_scalar(env: &Env, amount: i128) -> Result<Fr, QuickexError> {
+    if amount < 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+    Ok(bls::fr_from_u128(env, amount as u128))
+}
+
+/// Parse a caller-supplied blinding point.
+///
+/// Rejects the identity (`r = 0`, which would make `C = amount·G` brute-forceable)
+/// and bytes that are malformed, off the curve, or outside the prime-order subgroup.
+pub fn parse_point(env: &Env, bytes: BytesN<96>) -> Result<G1Affine, QuickexError> {
+    if bytes.to_array() == bls::G1_IDENTITY {
+        return Err(QuickexError::InvalidPoint);
+    }
+    bls::g1_checked(env, &bytes).ok_or(QuickexError::InvalidPoint)
+}
+
+/// Fiat–Shamir challenge `c` for a blinding proof on the escrow `key`.
+fn blinding_challenge(
+    env: &Env,
+    key: &BytesN<32>,
+    blinding_point: &G1Affine,
+    nonce_point: &BytesN<96>,
+) -> Fr {
+    let mut payload = Bytes::from_slice(env, BLINDING_PROOF_DST);
+    payload.append(&key.clone().into());
+    payload.append(&blinding_point.to_bytes().into());
+    payload.append(&nonce_point.clone().into());
+    bls::fr_from_bytes(env, &env.crypto().sha256(&payload).into())
+}
+
+/// Check that `proof` shows knowledge of `r` with `blinding_point = r·H`.
+///
+/// `blinding_point` is `proof.blinding_point`, already parsed by the caller.
+///
+/// # Errors
+/// - [`QuickexError::InvalidProof`] – the nonce point is not a valid G1 point or
+///   `s·H ≠ T + c·R`.
+pub fn verify_blinding_proof(
+    env: &Env,
+    key: &BytesN<32>,
+    blinding_point: &G1Affine,
+    proof: &BlindingProof,
+) -> Result<(), QuickexError> {
+    let nonce_point = bls::g1_checked(env, &proof.nonce_point).ok_or(QuickexError::InvalidProof)?;
+    let bls = env.crypto().bls12_381();
+    let challenge = blinding_challenge(env, key, blinding_point, &proof.nonce_point);
+    let lhs = bls.g1_mul(&generator_h(env), &bls::fr_from_bytes(env, &proof.response));
+    let rhs = bls.g1_add(&nonce_point, &bls.g1_mul(blinding_point, &challenge));
+    if lhs.to_bytes() != rhs.to_bytes() {
+        return Err(QuickexError::InvalidProof);
+    }
+    Ok(())
+}
+
+/// Prove knowledge of `blinding` for the escrow `key`, with nonce scalar `nonce`.
+///
+/// The nonce must be fresh and secret; reusing it across two proofs reveals `r`.
+#[cfg(test)]
+pub fn prove_blinding(
+    env: &Env,
+    key: &BytesN<32>,
+    blinding: &BytesN<32>,
+    nonce: &BytesN<32>,
+) -> BlindingProof {
+    let bls = env.crypto().bls12_381();
+    let r = bls::fr_from_bytes(env, blinding);
+    let k = bls::fr_from_bytes(env, nonce);
+    let blinding_point = bls.g1_mul(&generator_h(env), &r);
+    let nonce_point = bls.g1_mul(&generator_h(env), &k).to_bytes();
+    let challenge = blinding_challenge(env, key, &blinding_point, &nonce_point);
+    BlindingProof {
+        blinding_point: blinding_point.to_bytes(),
+        nonce_point,
+        response: bls.fr_add(&k, &bls.fr_mul(&challenge, &r)).to_bytes(),
+    }
+}
+
+/// Compute `C = amount·G + R` from a blinding point `R = r·H`.
+pub fn commit_with_blinding_point(
+    env: &Env,
+    amount: i128,
+    blinding_point: &G1Affine,
+) -> Result<BytesN<96>, QuickexError> {
+    let bls = env.crypto().bls12_381();
+    let value = bls.g1_mul(&generator_g(env), &amount_scalar(env, amount)?);
+    Ok(bls.g1_add(&value, blinding_point).to_bytes())
+}
+
+/// Compute `C = amount·G + r·H` from the full opening.
+///
+/// # Errors
+/// - [`QuickexError::InvalidAmount`] – amount is negative.
+pub fn create_pedersen_commitment(
+    env: &Env,
+    amount: i128,
+    blinding: BytesN<32>,
+) -> Result<BytesN<96>, QuickexError> {
+    let bls = env.crypto().bls12_381();
+    let blinding_point = bls.g1_mul(&generator_h(env), &bls::fr_from_bytes(env, &blinding));
+    commit_with_blinding_point(env, amount, &blinding_point)
+}
+
+/// Derive the escrow storage key `SHA256(XDR(to) || C)` binding a commitment to its recipient.
+pub fn hidden_escrow_key(env: &Env, to: &Address, amount_commitment: &BytesN<96>) -> BytesN<32> {
+    let mut payload = Bytes::new(env);
+    payload.append(&to.clone().to_xdr(env));
+    payload.append(&amount_commitment.clone().into());
+    env.crypto().sha256(&payload).into()
+}
+
+/// Check that (`amount`, `blinding`) opens `amount_commitment`.
+pub fn verify_opening(
+    env: &Env,
+    amount_commitment: &BytesN<96>,
+    amount: i128,
+    blinding: BytesN<32>,
+) -> bool {
+    match create_pedersen_commitment(env, amount, blinding) {
+        Ok(c) => c == *amount_commitment,
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,348 @@
+//! Pedersen (hiding) commitment tests.
+//!
+//! Covers the scheme invariants documented in `pedersen.rs` and the hidden-amount
+//! escrow flow (`deposit_hidden` → `withdraw_hidden` / `refund_hidden`).
+
+use crate::{
+    bls,
+    errors::QuickexError,
+    pedersen,
+    storage::get_hidden_escrow,
+    test_fixture::{create_token, deploy},
+    types::{BlindingProof, EscrowStatus},
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, BytesN, Env,
+};
+
+fn blinding(env: &Env, seed: u8) -> BytesN<32> {
+    let mut bytes = [0u8; 32];
+    bytes[31] = seed;
+    bytes[0] = 0x11;
+    BytesN::from_array(env, &bytes)
+}
+
+/// `r·H` with its proof for the escrow `key`, as a depositor passes to `deposit_hidden`.
+fn blinding_proof(env: &Env, key: &BytesN<32>, r: &BytesN<32>) -> BlindingProof {
+    let mut nonce = r.to_array();
+    nonce[1] ^= 0x5a;
+    pedersen::prove_blinding(env, key, r, &BytesN::from_array(env, &nonce))
+}
+
+// ============================================================================
+// Scheme invariants
+// ============================================================================
+
+#[test]
+fn test_pedersen_commitment_deterministic() {
+    let (env, client) = deploy();
+    let r = blinding(&env, 1);
+
+    let c1 = client.create_pedersen_commitment(&1_000, &r);
+    let c2 = client.create_pedersen_commitment(&1_000, &r);
+    assert_eq!(c1, c2);
+}
+
+#[test]
+fn test_pedersen_commitment_hides_amount_behind_blinding() {
+    let (env, client) = deploy();
+
+    let same_amount_a = client.create_pedersen_commitment(&1_000, &blinding(&env, 1));
+    let same_amount_b = client.create_pedersen_commitment(&1_000, &blinding(&env, 2));
+    assert_ne!(same_amount_a, same_amount_b);
+
+    let other_amount = client.create_pedersen_commitment(&2_000, &blinding(&env, 1));
+    assert_ne!(same_amount_a, other_amount);
+}
+
+#[test]
+fn test_pedersen_commitment_is_additively_homomorphic() {
+    let (env, client) = deploy();
+    let mut r1 = [0u8; 32];
+    r1[31] = 5;
+    let mut r2 = [0u8; 32];
+    r2[31] = 7;
+    let mut r_sum = [0u8; 32];
+    r_sum[31] = 12;
+
+    let c1 = client.create_pedersen_commitment(&300, &BytesN::from_array(&env, &r1));
+    let c2 = client.create_pedersen_commitment(&700, &BytesN::from_array(&env, &r2));
+    let c_sum = client.create_pedersen_commitment(&1_000, &BytesN::from_array(&env, &r_sum));
+
+    let bls = env.crypto().bls12_381();
+    let added = bls.g1_add(
+        &soroban_sdk::crypto::bls12_381::G1Affine::from_bytes(c1),
+        &soroban_sdk::crypto::bls12_381::G1Affine::from_bytes(c2),
+    );
+    assert_eq!(added.to_bytes(), c_sum);
+}
+
+#[test]
+fn test_pedersen_commitment_negative_amount_rejected() {
+    let (env, client) = deploy();
+    let result = client.try_create_pedersen_commitment(&-1, &blinding(&env, 1));
+    assert_eq!(result, Err(Ok(QuickexError::InvalidAmount)));
+}
+
+// ============================================================================
+// Hidden-amount escrow flow
+// ============================================================================
+
+#[test]
+fn test_hidden_deposit_and_withdraw() {
+    let (env, client) = deploy();
+    let token = create_token(&env);
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let amount: i128 = 1_500;
+    let r = blinding(&env, 3);
+
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &amount);
+
+    let amount_commitment = client.create_pedersen_commitment(&amount, &r);
+    let key = pedersen::hidden_escrow_key(&env, &recipient, &amount_commitment);
+
+    client.deposit_hidden(
+        &depositor,
+        &token,
+        &amount,
+        &key,
+        &blinding_proof(&env, &key, &r),
+        &0,
+    );
+
+    // Only the commitment is persisted, and it commits to the transferred amount.
+    let stored = env
+        .as_contract(&client.address, || get_hidden_escrow(&env, &key))
+        .unwrap();
+    assert_eq!(stored.amount_commitment, amount_commitment);
+    assert_eq!(stored.status, EscrowStatus::Pending);
+
+    assert!(client.withdraw_hidden(&recipient, &amount, &r));
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&recipient), amount);
+    assert_eq!(token_client.balance(&client.address), 0);
+
+    let stored = env
+        .as_contract(&client.address, || get_hidden_escrow(&env, &key))
+        .unwrap();
+    assert_eq!(stored.status, EscrowStatus::Spent);
+
+    let again = client.try_withdraw_hidden(&recipient, &amount, &r);
+    assert_eq!(again, Err(Ok(QuickexError::AlreadySpent)));
+}
+
+#[test]
+fn test_prune_spent_hidden_escrow() {
+    let (env, client) = deploy();
+    let token = create_token(&env);
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let r = blinding(&env, 9);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &2_000);
+
+    let amount_commitment = client.create_pedersen_commitment(&1_000, &r);
+    let key = pedersen::hidden_escrow_key(&env, &recipient, &amount_commitment);
+    let proof = blinding_proof(&env, &key, &r);
+    client.deposit_hidden(&depositor, &token, &1_000, &key, &proof, &0);
+
+    // Pending escrows are left alone.
+    assert_eq!(client.prune_spent(&vec![&env, key.clone()]), 0);
+
+    client.withdraw_hidden(&recipient, &1_000, &r);
+    assert_eq!(client.prune_spent(&vec![&env, key.clone()]), 1);
+    assert_eq!(client.prune_spent(&vec![&env, key.clone()]), 0);
+    assert!(env
+        .as_contract(&client.address, || get_hidden_escrow(&env, &key))
+        .is_none());
+
+    assert_eq!(
+        client.try_withdraw_hidden(&recipient, &1_000, &r),
+        Err(Ok(QuickexError::AlreadySpent))
+    );
+    assert_eq!(
+        client.try_deposit_hidden(&depositor, &token, &1_000, &key, &proof, &0),
+        Err(Ok(QuickexError::CommitmentAlreadyExists))
+    );
+}
+
+#[test]
+fn test_hidden_withdraw_wrong_opening_fails() {
+    let (env, client) = deploy();
+    let token = create_token(&env);
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let amount: i128 = 1_000;
+    let r = blinding(&env, 4);
+
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &amount);
+    let key = pedersen::hidden_escrow_key(
+        &env,
+        &recipient,
+        &client.create_pedersen_commitment(&amount, &r),
+    );
+    client.deposit_hidden(
+        &depositor,
+        &token,
+        &amount,
+        &key,
+        &blinding_proof(&env, &key, &r),
+        &0,
+    );
+
+    // Claiming more than was committed cannot open the commitment.
+    let wrong_amount = client.try_withdraw_hidden(&recipient, &(amount * 2), &r);
+    assert_eq!(wrong_amount, Err(Ok(QuickexError::CommitmentNotFound)));
+
+    let wrong_blinding = client.try_withdraw_hidden(&recipient, &amount, &blinding(&env, 5));
+    assert_eq!(wrong_blinding, Err(Ok(QuickexError::CommitmentNotFound)));
+
+    let stranger = Address::generate(&env);
+    let wrong_recipient = client.try_withdraw_hidden(&stranger, &amount, &r);
+    assert_eq!(wrong_recipient, Err(Ok(QuickexError::CommitmentNotFound)));
+}
+
+#[test]
+fn test_hidden_deposit_identity_blinding_point_rejected() {
+    let (env, client) = deploy();
+    let token = create_token(&env);
+    let depositor = Address::generate(&env);
+    let mut identity = [0u8; 96];
+    identity[0] = 0x40;
+
+    let key = BytesN::from_array(&env, &[8u8; 32]);
+    let proof = BlindingProof {
+        blinding_point: BytesN::from_array(&env, &identity),
+        ..blinding_proof(&env, &key, &blinding(&env, 1))
+    };
+
+    let result = client.try_deposit_hidden(&depositor, &token, &1_000, &key, &proof, &0);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidPoint)));
+}
+
+#[test]
+fn test_hidden_deposit_rejects_blinding_point_with_value_component() {
+    let (env, client) = deploy();
+    let token = create_token(&env);
+    let victim = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let token_client = token::Client::new(&env, &token);
+    let asset = token::StellarAssetClient::new(&env, &token);
+    asset.mint(&victim, &1_000);
+    asset.mint(&attacker, &100);
+
+    let victim_r = blinding(&env, 7);
+    let victim_key = pedersen::hidden_escrow_key(
+        &env,
+        &Address::generate(&env),
+        &client.create_pedersen_commitment(&1_000, &victim_r),
+    );
+    client.deposit_hidden(
+        &victim,
+        &token,
+        &1_000,
+        &victim_key,
+        &blinding_proof(&env, &victim_key, &victim_r),
+        &0,
+    );
+
+    // R = 900·G + r·H turns a 100-unit deposit into a commitment that opens to 1 000.
+    let r = blinding(&env, 8);
+    let bls_ops = env.crypto().bls12_381();
+    let tampered = bls_ops.g1_add(
+        &bls_ops.g1_mul(&pedersen::generator_g(&env), &bls::fr_from_u128(&env, 900)),
+        &bls_ops.g1_mul(&pedersen::generator_h(&env), &bls::fr_from_bytes(&env, &r)),
+    );
+    assert_eq!(
+        tampered.to_bytes(),
+        client.create_pedersen_commitment(&900, &r)
+    );
+    let key = pedersen::hidden_escrow_key(
+        &env,
+        &attacker,
+        &client.create_pedersen_commitment(&1_000, &r),
+    );
+
+    // Neither a proof for `r·H` nor a forged response verifies against the tampered point.
+    let honest = BlindingProof {
+        blinding_point: tampered.to_bytes(),
+        ..blinding_proof(&env, &key, &r)
+    };
+    let forged = BlindingProof {
+        response: blinding(&env, 9),
+        ..honest.clone()
+    };
+    for proof in [honest, forged] {
+        let result = client.try_deposit_hidden(&attacker, &token, &100, &key, &proof, &0);
+        assert_eq!(result, Err(Ok(QuickexError::InvalidProof)));
+    }
+
+    assert_eq!(token_client.balance(&attacker), 100);
+    assert_eq!(token_client.balance(&client.address), 1_000);
+    let drain = client.try_withdraw_hidden(&attacker, &1_000, &r);
+    assert_eq!(drain, Err(Ok(QuickexError::CommitmentNotFound)));
+}
+
+#[test]
+fn test_hidden_deposit_rejects_proof_for_another_escrow() {
+    let (env, client) = deploy();
+    let token = create_token(&env);
+    let depositor = Address::generate(&env);
+    let r = blinding(&env, 10);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1_000);
+
+    let commitment = client.create_pedersen_commitment(&1_000, &r);
+    let key = pedersen::hidden_escrow_key(&env, &Address::generate(&env), &commitment);
+    let other_key = pedersen::hidden_escrow_key(&env, &Address::generate(&env), &commitment);
+
+    let result = client.try_deposit_hidden(
+        &depositor,
+        &token,
+        &1_000,
+        &key,
+        &blinding_proof(&env, &other_key, &r),
+        &0,
+    );
+    assert_eq!(result, Err(Ok(QuickexError::InvalidProof)));
+}
+
+#[test]
+fn test_hidden_refund_after_expiry() {
+    let (env, client) = deploy();
+    let token = create_token(&env);
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let amount: i128 = 800;
+    let r = blinding(&env, 6);
+
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &amount);
+    let key = pedersen::hidden_escrow_key(
+        &env,
+        &recipient,
+        &client.create_pedersen_commitment(&amount, &r),
+    );
+    client.deposit_hidden(
+        &depositor,
+        &token,
+        &amount,
+        &key,
+        &blinding_proof(&env, &key, &r),
+        &100,
+    );
+
+    let early = client.try_refund_hidden(&key, &depositor, &amount, &r);
+    assert_eq!(early, Err(Ok(QuickexError::EscrowNotExpired)));
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 100);
+
+    let bad_opening = client.try_refund_hidden(&key, &depositor, &(amount + 1), &r);
+    assert_eq!(bad_opening, Err(Ok(QuickexError::CommitmentMismatch)));
+
+    client.refund_hidden(&key, &depositor, &amount, &r);
+    assert_eq!(token::Client::new(&env, &token).balance(&depositor), amount);
+
+    let expired_withdraw = client.try_withdraw_hidden(&recipient, &amount, &r);
+    assert_eq!(expired_withdraw, Err(Ok(QuickexError::AlreadySpent)));
+}
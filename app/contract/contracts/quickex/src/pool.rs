@@ -0,0 +1,218 @@
+//! Fixed-denomination deposit pools.
+//!
+//! The admin registers a set of allowed denominations per token (e.g. 10 / 100 / 1000
+//! units). [`deposit_to_pool`] only accepts an exact registered denomination, so every
+//! note in a pool has the same amount and withdrawals cannot be linked to deposits by
+//! value. Pool notes never expire: a per-note expiry would act as a fingerprint.
+//!
+//! Pool notes are ordinary commitment escrows (`SHA256(owner || amount || salt)`), so
+//! they are claimed through the regular `withdraw` entrypoint, which calls
+//! [`record_spend`] to keep the pool statistics current.
+//!
+//! [`pool_stats`] lets wallets warn users when a pool's anonymity set is too small.
+//!
+//! Outside pools, the admin can also restrict ordinary escrow deposits to a list of
+//! approved amount buckets per token with [`set_amount_bucket`], so no deposit carries
+//! a unique "fingerprint" amount. Registered denominations always count as approved.
+
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{admin, errors::QuickexError, events, storage};
+#[cfg(feature = "privacy-pool")]
+use crate::{
+    escrow,
+    types::{EscrowEntry, PoolStats},
+};
+#[cfg(feature = "privacy-pool")]
+use soroban_sdk::BytesN;
+
+#[cfg(feature = "privacy-pool")]
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[cfg(feature = "privacy-pool")]
+/// Enable or disable a denomination for `token` (**admin only**).
+///
+/// Enabling an already-enabled denomination (or disabling an absent one) is a no-op.
+/// Disabling only stops new deposits; existing notes remain withdrawable.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::InvalidAmount`] – denomination ≤ 0.
+pub fn set_denomination(
+    env: &Env,
+    caller: Address,
+    token: Address,
+    denomination: i128,
+    enabled: bool,
+) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+
+    if denomination <= 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+
+    let mut denominations = storage::get_denominations(env, &token);
+    let position = denominations.first_index_of(denomination);
+    match (position, enabled) {
+        (None, true) => denominations.push_back(denomination),
+        (Some(index), false) => {
+            denominations.remove(index);
+        }
+        _ => return Ok(()),
+    }
+    storage::set_denominations(env, &token, &denominations);
+
+    events::publish_denomination_updated(env, token, denomination, enabled);
+
+    Ok(())
+}
+
+#[cfg(feature = "privacy-pool")]
+/// Registered denominations for `token`, in registration order.
+pub fn get_denominations(env: &Env, token: &Address) -> Vec<i128> {
+    storage::get_denominations(env, token)
+}
+
+/// Returns `true` if `denomination` is currently accepted for `token`.
+pub fn is_denomination(env: &Env, token: &Address, denomination: i128) -> bool {
+    storage::get_denominations(env, token).contains(denomination)
+}
+
+/// Approve or withdraw approval of a deposit `amount` for `token` (**admin only**).
+///
+/// While at least one amount is approved, escrow deposits of `token` must use an
+/// approved amount or a pool denomination. Removing the last amount lifts the policy.
+/// Existing escrows are unaffected.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::InvalidAmount`] – amount ≤ 0.
+pub fn set_amount_bucket(
+    env: &Env,
+    caller: Address,
+    token: Address,
+    amount: i128,
+    enabled: bool,
+) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+
+    if amount <= 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+
+    let mut buckets = storage::get_amount_buckets(env, &token);
+    let position = buckets.first_index_of(amount);
+    match (position, enabled) {
+        (None, true) => buckets.push_back(amount),
+        (Some(index), false) => {
+            buckets.remove(index);
+        }
+        _ => return Ok(()),
+    }
+    storage::set_amount_buckets(env, &token, &buckets);
+
+    events::publish_amount_bucket_updated(env, token, amount, enabled);
+
+    Ok(())
+}
+
+/// Approved deposit amounts for `token`, in approval order.
+pub fn get_amount_buckets(env: &Env, token: &Address) -> Vec<i128> {
+    storage::get_amount_buckets(env, token)
+}
+
+/// Check a deposit `amount` of `token` against its approved buckets.
+///
+/// # Errors
+/// - [`QuickexError::UnapprovedAmount`] – buckets are set for `token` and `amount` is
+///   neither one of them nor a pool denomination.
+pub fn require_bucketed(env: &Env, token: &Address, amount: i128) -> Result<(), QuickexError> {
+    let buckets = storage::get_amount_buckets(env, token);
+    if buckets.is_empty() || buckets.contains(amount) || is_denomination(env, token, amount) {
+        return Ok(());
+    }
+    Err(QuickexError::UnapprovedAmount)
+}
+
+#[cfg(feature = "privacy-pool")]
+/// Deposit exactly one `denomination` of `token` into its pool.
+///
+/// # Errors
+/// - [`QuickexError::InvalidDenomination`] – `denomination` is not registered for `token`.
+/// - Any error from [`escrow::deposit_with_commitment`].
+pub fn deposit_to_pool(
+    env: &Env,
+    from: Address,
+    token: Address,
+    denomination: i128,
+    commitment: BytesN<32>,
+) -> Result<(), QuickexError> {
+    if !is_denomination(env, &token, denomination) {
+        return Err(QuickexError::InvalidDenomination);
+    }
+
+    escrow::deposit_with_commitment(
+        env,
+        from.into(),
+        token.clone(),
+        denomination,
+        commitment.clone(),
+        0,
+    )?;
+    storage::mark_pool_note(env, &commitment);
+
+    let day = env.ledger().timestamp() / SECONDS_PER_DAY;
+    let mut state = storage::get_pool_state(env, &token, denomination);
+    state.deposit_count += 1;
+    let pending = state.pending_by_day.get(day).unwrap_or(0);
+    state.pending_by_day.set(day, pending + 1);
+    storage::set_pool_state(env, &token, denomination, &state);
+
+    Ok(())
+}
+
+#[cfg(feature = "privacy-pool")]
+/// Update pool statistics after `entry` (stored under `commitment`) was withdrawn.
+///
+/// No-op for commitments that were not deposited through a pool.
+pub fn record_spend(env: &Env, commitment: &BytesN<32>, entry: &EscrowEntry) {
+    if !storage::is_pool_note(env, commitment) {
+        return;
+    }
+
+    let day = entry.created_at / SECONDS_PER_DAY;
+    let mut state = storage::get_pool_state(env, &entry.token, entry.amount);
+    state.spent_count += 1;
+    match state.pending_by_day.get(day).unwrap_or(0) {
+        0 | 1 => {
+            state.pending_by_day.remove(day);
+        }
+        pending => state.pending_by_day.set(day, pending - 1),
+    }
+    storage::set_pool_state(env, &entry.token, entry.amount, &state);
+}
+
+#[cfg(feature = "privacy-pool")]
+/// Anonymity-set statistics for the (`token`, `denomination`) pool.
+pub fn pool_stats(env: &Env, token: &Address, denomination: i128) -> PoolStats {
+    let state = storage::get_pool_state(env, token, denomination);
+    let today = env.ledger().timestamp() / SECONDS_PER_DAY;
+
+    let mut stats = PoolStats {
+        pending: state.deposit_count - state.spent_count,
+        spent: state.spent_count,
+        age_under_1d: 0,
+        age_1d_to_7d: 0,
+        age_7d_to_30d: 0,
+        age_over_30d: 0,
+    };
+    for (day, count) in state.pending_by_day.iter() {
+        match today.saturating_sub(day) {
+            0 => stats.age_under_1d += count,
+            1..=6 => stats.age_1d_to_7d += count,
+            7..=29 => stats.age_7d_to_30d += count,
+            _ => stats.age_over_30d += count,
+        }
+    }
+    stats
+}
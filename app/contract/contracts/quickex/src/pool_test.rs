@@ -0,0 +1,212 @@
+//! Fixed-denomination pool tests.
+
+use crate::{
+    errors::QuickexError,
+    test_fixture::{create_token, deploy_initialized},
+    EscrowStatus,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Bytes, BytesN,
+};
+
+#[test]
+fn test_set_denomination_by_admin() {
+    let (env, client, admin, token) = deploy_initialized();
+
+    client.set_denomination(&admin, &token, &100, &true);
+    client.set_denomination(&admin, &token, &1_000, &true);
+    // Re-enabling is a no-op.
+    client.set_denomination(&admin, &token, &100, &true);
+    assert_eq!(client.get_denominations(&token), vec![&env, 100, 1_000]);
+
+    client.set_denomination(&admin, &token, &100, &false);
+    assert_eq!(client.get_denominations(&token), vec![&env, 1_000]);
+}
+
+#[test]
+fn test_set_denomination_by_non_admin_fails() {
+    let (env, client, _admin, token) = deploy_initialized();
+    let stranger = Address::generate(&env);
+
+    let result = client.try_set_denomination(&stranger, &token, &100, &true);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+}
+
+#[test]
+fn test_set_denomination_non_positive_fails() {
+    let (_env, client, admin, token) = deploy_initialized();
+
+    let result = client.try_set_denomination(&admin, &token, &0, &true);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidAmount)));
+}
+
+#[test]
+fn test_pool_deposit_and_withdraw() {
+    let (env, client, admin, token) = deploy_initialized();
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let denomination: i128 = 100;
+    let salt = Bytes::from_slice(&env, b"pool_note_salt");
+
+    client.set_denomination(&admin, &token, &denomination, &true);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &denomination);
+
+    let commitment = client.create_amount_commitment(&recipient, &denomination, &salt);
+    client.deposit_to_pool(&depositor, &token, &denomination, &commitment);
+
+    assert_eq!(client.pool_stats(&token, &denomination).pending, 1);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+
+    client.withdraw(&token, &denomination, &commitment, &recipient, &salt);
+    assert_eq!(
+        token::Client::new(&env, &token).balance(&recipient),
+        denomination
+    );
+}
+
+#[test]
+fn test_pool_deposit_rejects_unregistered_amount() {
+    let (env, client, admin, token) = deploy_initialized();
+    let depositor = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[3u8; 32]);
+
+    client.set_denomination(&admin, &token, &100, &true);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1_000);
+
+    let result = client.try_deposit_to_pool(&depositor, &token, &150, &commitment);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidDenomination)));
+
+    // Denominations are per token.
+    let other_token = create_token(&env);
+    let result = client.try_deposit_to_pool(&depositor, &other_token, &100, &commitment);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidDenomination)));
+}
+
+#[test]
+fn test_pool_deposit_rejected_after_denomination_disabled() {
+    let (env, client, admin, token) = deploy_initialized();
+    let depositor = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[4u8; 32]);
+
+    client.set_denomination(&admin, &token, &100, &true);
+    client.set_denomination(&admin, &token, &100, &false);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &100);
+
+    let result = client.try_deposit_to_pool(&depositor, &token, &100, &commitment);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidDenomination)));
+}
+
+#[test]
+fn test_pool_stats_tracks_pending_spent_and_age() {
+    let (env, client, admin, token) = deploy_initialized();
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let denomination: i128 = 100;
+    const DAY: u64 = 86_400;
+
+    client.set_denomination(&admin, &token, &denomination, &true);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &(denomination * 3));
+
+    // Day 10: two notes, one of which will be spent.
+    env.ledger().set_timestamp(10 * DAY);
+    let spent_salt = Bytes::from_slice(&env, b"stats_spent");
+    let spent_note = client.create_amount_commitment(&recipient, &denomination, &spent_salt);
+    client.deposit_to_pool(&depositor, &token, &denomination, &spent_note);
+    client.deposit_to_pool(
+        &depositor,
+        &token,
+        &denomination,
+        &BytesN::from_array(&env, &[5u8; 32]),
+    );
+
+    // Day 18: one more note.
+    env.ledger().set_timestamp(18 * DAY);
+    client.deposit_to_pool(
+        &depositor,
+        &token,
+        &denomination,
+        &BytesN::from_array(&env, &[6u8; 32]),
+    );
+
+    let stats = client.pool_stats(&token, &denomination);
+    assert_eq!(stats.pending, 3);
+    assert_eq!(stats.spent, 0);
+    assert_eq!(stats.age_under_1d, 1);
+    assert_eq!(stats.age_7d_to_30d, 2);
+
+    client.withdraw(&token, &denomination, &spent_note, &recipient, &spent_salt);
+
+    let stats = client.pool_stats(&token, &denomination);
+    assert_eq!(stats.pending, 2);
+    assert_eq!(stats.spent, 1);
+    assert_eq!(stats.age_under_1d, 1);
+    assert_eq!(stats.age_1d_to_7d, 0);
+    assert_eq!(stats.age_7d_to_30d, 1);
+    assert_eq!(stats.age_over_30d, 0);
+
+    // Ages roll forward with the ledger clock.
+    env.ledger().set_timestamp(60 * DAY);
+    let stats = client.pool_stats(&token, &denomination);
+    assert_eq!(stats.age_over_30d, 2);
+}
+
+#[test]
+fn test_pool_stats_ignores_non_pool_withdrawals() {
+    let (env, client, admin, token) = deploy_initialized();
+    let owner = Address::generate(&env);
+    let denomination: i128 = 100;
+    let salt = Bytes::from_slice(&env, b"regular_escrow");
+
+    client.set_denomination(&admin, &token, &denomination, &true);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &denomination);
+
+    // Same amount as the pool denomination, but deposited outside the pool.
+    let commitment = client.deposit(&token, &denomination, &owner, &salt, &0);
+    client.withdraw(&token, &denomination, &commitment, &owner, &salt);
+
+    let stats = client.pool_stats(&token, &denomination);
+    assert_eq!(stats.pending, 0);
+    assert_eq!(stats.spent, 0);
+}
+
+#[test]
+fn test_amount_buckets_reject_fingerprint_amounts() {
+    let (env, client, admin, token) = deploy_initialized();
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &10_000);
+
+    client.set_amount_bucket(&admin, &token, &1_000, &true);
+    client.set_denomination(&admin, &token, &500, &true);
+    assert_eq!(client.get_amount_buckets(&token), vec![&env, 1_000]);
+
+    let salt = Bytes::from_slice(&env, b"bucket_salt");
+    let result = client.try_deposit(&token, &1_234, &owner, &salt, &0);
+    assert_eq!(result, Err(Ok(QuickexError::UnapprovedAmount)));
+
+    let commitment = BytesN::from_array(&env, &[3u8; 32]);
+    let result = client.try_deposit_with_commitment(&owner, &token, &1_234, &commitment, &0);
+    assert_eq!(result, Err(Ok(QuickexError::UnapprovedAmount)));
+
+    // Approved buckets and pool denominations are accepted.
+    client.deposit(&token, &1_000, &owner, &salt, &0);
+    client.deposit_with_commitment(&owner, &token, &500, &commitment, &0);
+
+    // Removing the last bucket lifts the policy.
+    client.set_amount_bucket(&admin, &token, &1_000, &false);
+    client.deposit(&token, &1_234, &owner, &salt, &0);
+}
+
+#[test]
+fn test_set_amount_bucket_validation() {
+    let (env, client, admin, token) = deploy_initialized();
+
+    let result = client.try_set_amount_bucket(&Address::generate(&env), &token, &100, &true);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+
+    let result = client.try_set_amount_bucket(&admin, &token, &0, &true);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidAmount)));
+}
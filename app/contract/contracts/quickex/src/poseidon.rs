@@ -0,0 +1,286 @@
+//! # Poseidon Hash
+//!
+//! SNARK-friendly alternative to SHA-256 for note commitments and Merkle leaves.
+//! A SHA-256 compression costs ~25k R1CS constraints while a Poseidon permutation
+//! costs a few hundred, which is what makes withdrawal circuits practical.
+//!
+//! ## Construction
+//!
+//! The permutation follows the reference HADES design over a prime field `F`:
+//!
+//! ```text
+//! for each of R_F/2 full, R_P partial, R_F/2 full rounds:
+//!     state += round_constants[round]
+//!     state  = S(state)            full: x^5 on every element, partial: on state[0]
+//!     state  = MDS · state
+//! ```
+//!
+//! Two-to-one hashing uses the circomlib layout:
+//!
+//! ```text
+//! hash2(left, right) = permute([0, left, right])[0]
+//! ```
+//!
+//! Inputs and outputs are canonical field elements encoded as 32 big-endian bytes.
+//! Inputs `≥` the field modulus are rejected rather than reduced, so every
+//! element has exactly one encoding.
+//!
+//! ## Fields
+//!
+//! Parameters for both the BLS12-381 and BN254 scalar fields live in
+//! [`crate::poseidon_params`]. Field arithmetic is done in software (4×64-bit
+//! Montgomery form) because this protocol version has no BN254 host functions;
+//! the same code serves BLS12-381 so both fields behave identically.
+
+use soroban_sdk::{BytesN, Env};
+
+use crate::{
+    errors::QuickexError,
+    poseidon_params::{POSEIDON_BLS12_381, POSEIDON_BN254},
+    types::PoseidonField,
+};
+
+/// Parse `0x` followed by 64 hex digits into little-endian 64-bit limbs.
+pub(crate) const fn hex(s: &str) -> [u64; 4] {
+    let bytes = s.as_bytes();
+    assert!(bytes.len() == 66 && bytes[0] == b'0' && bytes[1] == b'x');
+    let mut limbs = [0u64; 4];
+    let mut i = 0;
+    while i < 64 {
+        let c = bytes[2 + i];
+        let digit = match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            _ => panic!("invalid hex digit"),
+        };
+        let limb = 3 - i / 16;
+        limbs[limb] = (limbs[limb] << 4) | digit as u64;
+        i += 1;
+    }
+    limbs
+}
+
+/// A prime field below `2^255`, with elements kept in Montgomery form (`R = 2^256`).
+pub struct PrimeField {
+    /// Field modulus `p`.
+    modulus: [u64; 4],
+    /// `R^2 mod p`, used to convert into Montgomery form.
+    r2: [u64; 4],
+    /// `-p^-1 mod 2^64`.
+    inv: u64,
+}
+
+/// BLS12-381 scalar field.
+pub const BLS12_381_FR: PrimeField = PrimeField {
+    modulus: hex("0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001"),
+    r2: hex("0x0748d9d99f59ff1105d314967254398f2b6cedcb87925c23c999e990f3f29c6d"),
+    inv: 0xfffffffeffffffff,
+};
+
+/// BN254 scalar field.
+pub const BN254_FR: PrimeField = PrimeField {
+    modulus: hex("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001"),
+    r2: hex("0x0216d0b17f4e44a58c49833d53bb808553fe3ab1e35c59e31bb8e645ae216da7"),
+    inv: 0xc2e1f593efffffff,
+};
+
+/// Field element in Montgomery form.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Element([u64; 4]);
+
+impl PrimeField {
+    /// The additive identity.
+    pub const fn zero(&self) -> Element {
+        Element([0; 4])
+    }
+
+    /// Element with canonical limbs `limbs`, or `None` if `limbs ≥ p`.
+    pub fn element(&self, limbs: [u64; 4]) -> Option<Element> {
+        if !less_than(&limbs, &self.modulus) {
+            return None;
+        }
+        Some(self.mul(&Element(limbs), &Element(self.r2)))
+    }
+
+    /// Decode a canonical big-endian element, rejecting values `≥ p`.
+    pub fn decode(&self, bytes: &[u8; 32]) -> Option<Element> {
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let start = (3 - i) * 8;
+            let mut chunk = [0u8; 8];
+            chunk.copy_from_slice(&bytes[start..start + 8]);
+            *limb = u64::from_be_bytes(chunk);
+        }
+        self.element(limbs)
+    }
+
+    /// Encode an element as canonical big-endian bytes.
+    pub fn encode(&self, element: &Element) -> [u8; 32] {
+        let canonical = self.mul(element, &Element([1, 0, 0, 0]));
+        let mut bytes = [0u8; 32];
+        for (i, limb) in canonical.0.iter().enumerate() {
+            let start = (3 - i) * 8;
+            bytes[start..start + 8].copy_from_slice(&limb.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// `a + b mod p`.
+    pub fn add(&self, a: &Element, b: &Element) -> Element {
+        let mut sum = [0u64; 4];
+        let mut carry = 0u128;
+        for (i, limb) in sum.iter_mut().enumerate() {
+            let acc = a.0[i] as u128 + b.0[i] as u128 + carry;
+            *limb = acc as u64;
+            carry = acc >> 64;
+        }
+        // Both inputs are below p < 2^255, so the sum fits in 256 bits.
+        Element(self.reduce_once(sum))
+    }
+
+    /// `a · b · R^-1 mod p` (Montgomery multiplication, CIOS).
+    pub fn mul(&self, a: &Element, b: &Element) -> Element {
+        let p = &self.modulus;
+        let mut t = [0u64; 6];
+        for i in 0..4 {
+            let mut carry = 0u64;
+            for (j, a_j) in a.0.iter().enumerate() {
+                let acc = t[j] as u128 + *a_j as u128 * b.0[i] as u128 + carry as u128;
+                t[j] = acc as u64;
+                carry = (acc >> 64) as u64;
+            }
+            let acc = t[4] as u128 + carry as u128;
+            t[4] = acc as u64;
+            t[5] = (acc >> 64) as u64;
+
+            let m = t[0].wrapping_mul(self.inv);
+            let acc = t[0] as u128 + m as u128 * p[0] as u128;
+            let mut carry = (acc >> 64) as u64;
+            for (j, p_j) in p.iter().enumerate().skip(1) {
+                let acc = t[j] as u128 + m as u128 * *p_j as u128 + carry as u128;
+                t[j - 1] = acc as u64;
+                carry = (acc >> 64) as u64;
+            }
+            let acc = t[4] as u128 + carry as u128;
+            t[3] = acc as u64;
+            t[4] = t[5] + (acc >> 64) as u64;
+        }
+        // The result is below 2p < 2^256, so t[4] is always zero here.
+        Element(self.reduce_once([t[0], t[1], t[2], t[3]]))
+    }
+
+    /// Subtract `p` once if `value ≥ p`.
+    fn reduce_once(&self, value: [u64; 4]) -> [u64; 4] {
+        if less_than(&value, &self.modulus) {
+            return value;
+        }
+        let mut out = [0u64; 4];
+        let mut borrow = 0u64;
+        for (i, limb) in out.iter_mut().enumerate() {
+            let (diff, b1) = value[i].overflowing_sub(self.modulus[i]);
+            let (diff, b2) = diff.overflowing_sub(borrow);
+            *limb = diff;
+            borrow = (b1 | b2) as u64;
+        }
+        out
+    }
+}
+
+/// `a < b` for little-endian limbs.
+fn less_than(a: &[u64; 4], b: &[u64; 4]) -> bool {
+    for i in (0..4).rev() {
+        if a[i] != b[i] {
+            return a[i] < b[i];
+        }
+    }
+    false
+}
+
+/// Poseidon instance of width `T` with the `x^5` S-box.
+pub struct PoseidonParams<const T: usize> {
+    pub field: &'static PrimeField,
+    /// Total full rounds, split evenly before and after the partial rounds.
+    pub full_rounds: usize,
+    pub partial_rounds: usize,
+    /// MDS matrix, canonical limbs.
+    pub mds: &'static [[[u64; 4]; T]; T],
+    /// One row of `T` constants per round, canonical limbs.
+    pub round_constants: &'static [[[u64; 4]; T]],
+}
+
+impl<const T: usize> PoseidonParams<T> {
+    /// Apply the Poseidon permutation to `state`.
+    pub fn permute(&self, mut state: [Element; T]) -> [Element; T] {
+        let field = self.field;
+        let mds = self.mds.map(|row| row.map(|c| Self::constant(field, c)));
+
+        let half_full = self.full_rounds / 2;
+        let rounds = self.full_rounds + self.partial_rounds;
+        for round in 0..rounds {
+            for (element, c) in state.iter_mut().zip(self.round_constants[round].iter()) {
+                *element = field.add(element, &Self::constant(field, *c));
+            }
+
+            if round < half_full || round >= half_full + self.partial_rounds {
+                for element in state.iter_mut() {
+                    *element = sbox(field, element);
+                }
+            } else {
+                state[0] = sbox(field, &state[0]);
+            }
+
+            let mut mixed = [field.zero(); T];
+            for (out, row) in mixed.iter_mut().zip(mds.iter()) {
+                for (m, element) in row.iter().zip(state.iter()) {
+                    *out = field.add(out, &field.mul(m, element));
+                }
+            }
+            state = mixed;
+        }
+        state
+    }
+
+    fn constant(field: &PrimeField, limbs: [u64; 4]) -> Element {
+        field
+            .element(limbs)
+            .expect("Poseidon constants are canonical")
+    }
+}
+
+/// `x^5`.
+fn sbox(field: &PrimeField, x: &Element) -> Element {
+    let x2 = field.mul(x, x);
+    let x4 = field.mul(&x2, &x2);
+    field.mul(&x4, x)
+}
+
+/// Width-3 Poseidon instance for `field`.
+pub fn params(field: PoseidonField) -> &'static PoseidonParams<3> {
+    match field {
+        PoseidonField::Bls12381 => &POSEIDON_BLS12_381,
+        PoseidonField::Bn254 => &POSEIDON_BN254,
+    }
+}
+
+/// Two-to-one Poseidon hash `permute([0, left, right])[0]` over `field`.
+///
+/// # Errors
+/// - [`QuickexError::InvalidFieldElement`] – an input is not a canonical element of `field`.
+pub fn hash2(
+    env: &Env,
+    field: PoseidonField,
+    left: &BytesN<32>,
+    right: &BytesN<32>,
+) -> Result<BytesN<32>, QuickexError> {
+    let params = params(field);
+    let f = params.field;
+    let left = f
+        .decode(&left.to_array())
+        .ok_or(QuickexError::InvalidFieldElement)?;
+    let right = f
+        .decode(&right.to_array())
+        .ok_or(QuickexError::InvalidFieldElement)?;
+
+    let out = params.permute([f.zero(), left, right]);
+    Ok(BytesN::from_array(env, &f.encode(&out[0])))
+}
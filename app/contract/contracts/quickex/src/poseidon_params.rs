@@ -0,0 +1,708 @@
+//! Poseidon permutation parameters (`t = 3`, `α = 5`, 8 full and 56 partial rounds).
+//!
+//! Copied verbatim from the HorizenLabs reference instances
+//! (<https://github.com/HorizenLabs/poseidon2>, `poseidon_instance_bls12.rs` and
+//! `poseidon_instance_bn254.rs`), which the Soroban host also uses as test vectors.
+//! Values are canonical (non-Montgomery) field elements in big-endian hex.
+
+use crate::poseidon::{hex, PoseidonParams, BLS12_381_FR, BN254_FR};
+
+/// Poseidon over the BLS12-381 scalar field.
+pub const POSEIDON_BLS12_381: PoseidonParams<3> = PoseidonParams {
+    field: &BLS12_381_FR,
+    full_rounds: 8,
+    partial_rounds: 56,
+    mds: &BLS12_381_MDS,
+    round_constants: &BLS12_381_ROUND_CONSTANTS,
+};
+
+/// Poseidon over the BN254 scalar field.
+pub const POSEIDON_BN254: PoseidonParams<3> = PoseidonParams {
+    field: &BN254_FR,
+    full_rounds: 8,
+    partial_rounds: 56,
+    mds: &BN254_MDS,
+    round_constants: &BN254_ROUND_CONSTANTS,
+};
+
+const BLS12_381_MDS: [[[u64; 4]; 3]; 3] = [
+    [
+        hex("0x211d0c9c28920044b34c2ed4a4a2b4e3139485ee7cf3598c291cdcf51b48ba21"),
+        hex("0x0b27c81f25198de28b7637b4c371222a703fdeee65998039126851dc77d585c2"),
+        hex("0x04603216569f6eeaecf37f66ce250c1bbfd638e9209014c39a833d3757a46bc7"),
+    ],
+    [
+        hex("0x1f23916bea4ffb4a28f2f0d759325de7f90f2e137b5482853f50fde61859b73a"),
+        hex("0x5c1e7a8011313fa573f806471815824b70fbfbe4bc3e81819042b6b4efad659e"),
+        hex("0x4d349bb595829ac30788875eaba85d488cbcf4f2c370cdb424be193afc109e7b"),
+    ],
+    [
+        hex("0x59db7377f026a6f02f7c90535b93db9cfc82bbaefa1deba36f1087880e6dc2c9"),
+        hex("0x4a83f6ddbf04a70f99e13e1fd72e4d4e0a9ca509c1c30bdae07508503cbc8ce9"),
+        hex("0x65d256a96817f3ab2a30d19785500fe898df9589cb8af15ec7c4a2e33aaaa87b"),
+    ],
+];
+
+const BLS12_381_ROUND_CONSTANTS: [[[u64; 4]; 3]; 64] = [
+    [
+        hex("0x452088f7ec90c80818a1b5665f38ea30116456becd6709977cd8a2e0a2b38b62"),
+        hex("0x21512346b8ece60d5951c1505089c2b4220707ca56373bb9d828fa33bbfd2a31"),
+        hex("0x2b3a40252c69e83e92c548e199bbbeba4291e0d7fc3b4810193606753da588c8"),
+    ],
+    [
+        hex("0x2a1a778e3f303c4187c082ea4475734596fb10bd2954843e12be80e8c1c0d464"),
+        hex("0x400df3a9dd4631e354222e1b1c2ab7092feb19a96eef425270e2016e2348fb96"),
+        hex("0x667e2e3deaf0278725697f5acc7222fd572e22944d04d7fd2f86c5c1c0cea988"),
+    ],
+    [
+        hex("0x224d2355fc17ee0b5e46455d2ef3a85cfaa88b08689b0d0e4c111094fd780093"),
+        hex("0x6d82b530a685ea27a3f7d47626bb7997b59cf2c6ec2ac14ff28ff0562a95e22a"),
+        hex("0x2241de16388cdd7ffda42e0838b5d59bc2182f14bbef622fa633d8b87250a740"),
+    ],
+    [
+        hex("0x6b9fc67a95a7a01f9034b58ac9130de47ec903c21c77cbd05258b011e904b80e"),
+        hex("0x325f11e96905193f6836e6fa2c727dd0261ea083fedde9873f1e7b9d90419833"),
+        hex("0x6e39fe041e18592c01cb0ae2e7cf51f57797c498be313f3485de24974129f48f"),
+    ],
+    [
+        hex("0x51693257ab2fa82eb0e8040a6866fe4d121c59b8a22ff9b17c72ad78e4dcb42a"),
+        hex("0x48805d48952999e8f52884e543f775f6bdac8da75cf37bad19754415183c6516"),
+        hex("0x1cc541b9ed19280c216f6b90876cbe83d07ba14fcc6f2af068e1dda739f5acb9"),
+    ],
+    [
+        hex("0x0f6f3f6703c0dcd136b24ddb8766fbcd69bbc9cb3bb20a1da2f7130c4ba62664"),
+        hex("0x352376600a75802c6e6c6da69001e0376328848bc7ada465176d571ba029a20b"),
+        hex("0x2b1708d59adc4ba04a6bcb2dd264c1b014e0b7bae9bb3af916eb276ee3a34565"),
+    ],
+    [
+        hex("0x66c5bbb78c64b8369b845ed0a25af2d05e3a4ce053ff4fd056a5c8a059e2cb3c"),
+        hex("0x5e80c1b3b2e5b0b2cc4385a07f85777bf8d59ee2358ce694376f35c844d300f0"),
+        hex("0x6c2ae56c365577b154b04b7683b8910dd66808b89858e03cf627b50057425c6c"),
+    ],
+    [
+        hex("0x65720f82218ce2bd09c504216f1fe44967596b1720ab267749537206c0ddf03f"),
+        hex("0x71a7333c7e07b20015f2c1f030adc0137e4d7453d795616eeda38b3a25e709b8"),
+        hex("0x161c8a77adcd1a5f8dad71c3b044ce64bab9de792195da91a0b0acca4f8b4568"),
+    ],
+    [
+        hex("0x5b7f6f2e59eecbf4802a456ac633c38ba8895b41cdde35c1caa5dd1ff4bdef7e"),
+        hex("0x5a2569754178df0732e843339b2b7d55519590d8fe6f4f4bf9f35bcd8c589b4d"),
+        hex("0x5bf5026511b12bbfbde38a5394aa7dd9a8bc9a5055fadbcbaa541f3b523925e9"),
+    ],
+    [
+        hex("0x1d53e37d6ddf6dd88beb25c0870b2d0af2a51efb6ada05c4ac7e5099a71499e0"),
+        hex("0x65e488523b0b3430d5f91e25b4b96ee6d628fbe0529c2868749657d7f7aa3f95"),
+        hex("0x0b6d7adb7b72cfaee0184354accbff821a14efb48b46405b397c037a5e15f095"),
+    ],
+    [
+        hex("0x2444c70bc898765b95c5438156c28671cfd20569a8d31b3f08cfa60d2bb18d6e"),
+        hex("0x6979d5cf3da00fcb59a9832188456fbe515261a9be5cf052ab8e50c874a07ff8"),
+        hex("0x1eca31224b0d4ae965b179fd952d958de48a5de147348ca5dd00790d5c76fb2f"),
+    ],
+    [
+        hex("0x5e1c8f87eccb7e8cd338500800ca62b6bed6fc390597c5a7f21eb7e80cecad80"),
+        hex("0x0c3f882f7a3bd8ae1eb328e026f6419db30a5026c279df1219499333ef8caa06"),
+        hex("0x38011264a16e7cf3e96f029dbfe344e778314b1e2e9d8a2f8f8f76ff5795430d"),
+    ],
+    [
+        hex("0x3119da354a6f450bf8f700b89b8319a6f57d6278bfb0bbf9d8e37d55c9f3133d"),
+        hex("0x0bbe0649314a68a31d5e8222bfec7b1298fc5bc1e6ea098675c94695aa3aa221"),
+        hex("0x4dfb208e602b401ba661e37ff5a5fd971874a1082cfb00ebefc6405c2d0a73d3"),
+    ],
+    [
+        hex("0x2238971cffd7a12e565e591c0b28c8e76a4582d57892d3db5c8be394a60ba3e9"),
+        hex("0x4c466e3153daea54ee62f719c6f947207e7234dbd7257489b4a8a385be7645ba"),
+        hex("0x1badbb1e2e9734afa09ff6c92e98038b9e329e0c5d0bac9a7d7996392f5caf78"),
+    ],
+    [
+        hex("0x37dbb6c5059651ae1362eba3195c08716dab6a61ce8476b5cebd09274a53413c"),
+        hex("0x7154aa15ad1736497d7902f9e74828b3e65f43295cc20bf69ba2c622592c224e"),
+        hex("0x3ca22f38e795e433ae9a8d2d5f1d535bbe1a3c3fdadcd549a718e72cd257bb09"),
+    ],
+    [
+        hex("0x0854ff209558742b8d9b0cbe9db7767f33668f1f8888458bc954025ffe84b7da"),
+        hex("0x377699a38c21b41939a5098a8202ed55b4d3472e01f185f4336f4c6c879051ad"),
+        hex("0x3492b7170200764635922920ad5e3c8761fbbcbaeaa2fc08a59f9ddac49a59ed"),
+    ],
+    [
+        hex("0x372c88a6b45c1593f353789be4cdce85dd2ea1adfca9d8444c83b7a990921a25"),
+        hex("0x3d0996734441d7d414e14d72e46eb6cb3d403a822ce642357e1319d169f4ce6f"),
+        hex("0x026362f8c8a86b5afadc22b1cc0aa53e9da137607812778e47d5f86740722a52"),
+    ],
+    [
+        hex("0x195b6294bdf65bb0c07569f6c6193de7e5e9ea5b17b5adc39179ff15280fda29"),
+        hex("0x440a155e09c263aa0c3fdfc62d5b6f6c759735d1b73efd60c5afd4deddbd7bf8"),
+        hex("0x5f0135b9b6a8ba9cc5d83221ba344778dfc00b1f4ac578455dd5d71dca2bb64e"),
+    ],
+    [
+        hex("0x5d108be88e31ef2e03e46662075177281cdb28e77c120d158c89ce3850fd93d7"),
+        hex("0x11dfbb5f5e48ea973c6ef2ece89463c5316bc767896b67b88be18a1d858d6f52"),
+        hex("0x071180314ab2e242cc0552b728495b97e0c2e073970a264c43397356d6ef6c99"),
+    ],
+    [
+        hex("0x13c1cc0a221c29fde3183f7dc644004d3f4dd341fe7626996ce68c69d73204c5"),
+        hex("0x06fc0f5f038d0ab20f4815ba721b366824bf534980265836b224fecfccb6fcaf"),
+        hex("0x122e97658c701fcb7b25d8ce0629f2942dd8f07d6ac06a91320a6f1f4421fd59"),
+    ],
+    [
+        hex("0x3a19573d57741adba1942d72016391d115a1971af158cfe2a776cb506d714272"),
+        hex("0x4037de356b6cb97e4b73d9604342a7b3e5bf44dd44e4318cdc116edde917fffe"),
+        hex("0x3b34dfdced639990194ad27c8d3bebd9e6657350c0c522a2b65f2ace44dd16cf"),
+    ],
+    [
+        hex("0x546b1eed6e680434955e6259f20ba428460181982eed9832898c945ce343f9cb"),
+        hex("0x4d144b9647ff9822cb76f838802bd249bc58c2782587d470f5cd906f860a79d6"),
+        hex("0x2d39cfe678f0816cf3bc7a0476517c070d9db6b0f20aa849fc9746e4be5bdf80"),
+    ],
+    [
+        hex("0x69f92e5361b806c4e082661a39cf8ead1431c49536eaf8b8450804c99d9c8899"),
+        hex("0x1c2e919d0e061629fd6b5416898dc1d5a5cd0e130531151d18480906ea3d9cc5"),
+        hex("0x3dae30c784fd66c4a551a6b0a9551747fc1cae54522bb25238f06a7a3e4490cb"),
+    ],
+    [
+        hex("0x0264c23f67c44aa792f1c731655e1c9eefc4b4b808913f6bb3806ee56caf9c8b"),
+        hex("0x0fa6d7c32c55e7621d72604c5abc1d970e7569dbf1475f989816be1ac248f889"),
+        hex("0x2516928d25d3fe4ca89ff71d5958f4f256d86457b58215dc8c1b02454314ff19"),
+    ],
+    [
+        hex("0x10842e1683519bc44c3b3de92cf860e9185c5ed67b20662ae8c4f50008de4780"),
+        hex("0x6b7e2c013a40a2c5f5a992a0cd4bf198a64171617af313332c244edffcdf45be"),
+        hex("0x036c65d30abc46a63c4b26ea1e17c5325181354f800fa4c4f207ed1849bb8b3c"),
+    ],
+    [
+        hex("0x568d2f7f0f8fb4fc28ef1bc673f2db513427dbab162b7c444a1070cd85f22397"),
+        hex("0x53a9fd8aefc965366ddcf373f85ce62ee77f1ef0bdd18243842c2eb313db9989"),
+        hex("0x1577e14026128fcbe30d7fe646e0cfcf5a91052f2cadc41553e10aa4ea94eb81"),
+    ],
+    [
+        hex("0x38814490cf1681f17c23adf62ea2988d48fdce37b3a2fc259b090391d72be770"),
+        hex("0x3c24dd5b9460893f28e95b9cbaaba0e1b6af9c00d8182b66ec771ca957b4cb8d"),
+        hex("0x367180fac58037ec0d86a1932aabe6a5e353feaf64b1acaaf208a79f711fe35f"),
+    ],
+    [
+        hex("0x28ad8a1ecc6d58bf0cbff23e654b824abecae09905278fffa65e9b0d634d9205"),
+        hex("0x56b8a175a8e0031f33f342e53f2b565f098aed0baff9a26e6981f7129b2a7b1f"),
+        hex("0x5b68b63c523f4fb76fcb5c63ee048575a221119373427cd74c5f32f542cd2895"),
+    ],
+    [
+        hex("0x046fe9ca53b25a411a48d539b09984e2e04bbfe5bfdbb3d390e7489c2cecc4bf"),
+        hex("0x5cede9acf639ee099c37c71a78146aac713b1c9b2e13d8a587a46db424e19826"),
+        hex("0x5d77d40ebd612b82e63f42cdf351c96834cd60319d3fb55b812e263a211cdb80"),
+    ],
+    [
+        hex("0x1b6869c3d4333173de4546f09cee0dea3ae707dfa5e1bcebf9d7671cf766aac0"),
+        hex("0x51fe61e53e88cc98192802e1df6a5eec1d095d00fad564f9013366bf9c02819d"),
+        hex("0x6c16983737754b8011c12b3ba9204894b7e8ed476998df08a4d2b5ba29133b7d"),
+    ],
+    [
+        hex("0x1ac8cb354ad46130e09e72c559d322a6daf108002e5f1040c0db23e21ba7b12d"),
+        hex("0x16ff5289ab75696ff12f1a2207ee96d0824574cedc0bb3558f4a6ae6e674c446"),
+        hex("0x5a77dca1291f77077d5e6b6ff22accbc500a7065274df2a35a2c6b1fa82e6f04"),
+    ],
+    [
+        hex("0x6b0e33788a959d17f135432898a7d6e610f28c9dbd60b9c49a6dc490c34ef98d"),
+        hex("0x6cf72c65df522e4266f8423fa1659ad66b2578b7f993ea6507d0721f28810f83"),
+        hex("0x5a8a16f926e64e5728140473f651e594593ffba73c8e0b3222dff49feaba351f"),
+    ],
+    [
+        hex("0x718a33863594a8cae0d8034df7b5e9d5a695444b5df36b17f729ecf82ecf72db"),
+        hex("0x3b7f271e38ba8cde9b5fabbe82eae1b848b78a32022516d4cfa423062a3632a1"),
+        hex("0x23c4be170342843749dac4e883d34ef66ac377a41c53edfa136f0ba50d0fe5a3"),
+    ],
+    [
+        hex("0x5c26d0a3e700edf5bf5aaa952903efa479f77e84294998cf64f10f7acc5e6574"),
+        hex("0x41d401cfcb2049b7ea928444a8683aa7c9f036572c25b1fc9eb5bf88a5a4e7c4"),
+        hex("0x5cfab88543d96c375141236314bc8f0b66a9bd70dfff1f9e5458c6ca7f2fd8e7"),
+    ],
+    [
+        hex("0x366862ca8fd49db9b624d9a1e33b393cf761a181813955f48ff1d8d16d7d7d73"),
+        hex("0x2b12d66066df3e3446f8fcc6bc74aeb1dadb4f06f5d1b49646c52708c447cd14"),
+        hex("0x4c5c782c8c1541ffa5d1cc2d72f4ca7e0d269be6334d8694643a0fd716b02d9e"),
+    ],
+    [
+        hex("0x0a88d5bde48f9b752b856a2646a793cf285473c38870624240b143758c0b5289"),
+        hex("0x5e3fe7b3d5cfb69f87cd0c2d9189ecc88d0f104bacb79bd8b7f53c5ed87ab646"),
+        hex("0x4c1c38bc77690ebcf900b9cd0b753e227339ba81624dc85fa9539f2dc626b2e6"),
+    ],
+    [
+        hex("0x1cfea95345ce89e544d0447eb5a655610588ac2097f3008389897701d404c98f"),
+        hex("0x3a9ec343c1c6a122f3897126da51f92d0187ce6e0221cb23bb1797fe4ba72e1d"),
+        hex("0x3bf940ada3cf20415d3b0b12dc1e0ecededea4dc1e5d7d1587edb6b4c79342f6"),
+    ],
+    [
+        hex("0x008621ee09a0ef69dee34dcea9261a2adfa38e8304e461a9635bf49c0f36d4be"),
+        hex("0x60acd6ad5bbf46cb3d215c5bcc362349d08cdf5b01f90a2c1b945b316df4c40e"),
+        hex("0x704f5c935383a8f4d020c56284fa5a83cbb9115d409754640a6e1c4fb97c7a88"),
+    ],
+    [
+        hex("0x580b48c347475fd5bd329eb4f4e98576fe31ce7f8a406325441c039e5421656b"),
+        hex("0x209cd0df3e50c56186a5db349b595bad4395036a310c098ecea3d041576725fd"),
+        hex("0x6e55adc04c723d3fbc8da51643c004b68d40a93bf745c04ab3f8daa77de33fa5"),
+    ],
+    [
+        hex("0x648b107f3892d7a2bcb36612fae09615c67f008fb4b9a9f168e2c1258632a813"),
+        hex("0x25b9a4649aa7d962e12ba088d37d5b582f5f5c160c8d28c03294c26a52447f18"),
+        hex("0x05ec12eb5698ef5afe1a8e225a299afc37b923dfb5094870f4ad979aa416fbed"),
+    ],
+    [
+        hex("0x58a840d7a870788599df218210d12b95f2e0aae7ffe8cea8d03e093281286551"),
+        hex("0x3e682fbf59f4cca0b6d50829d76c246e65b7f78d939191d570c977e4c5257d0b"),
+        hex("0x0b850ec39f210fbf8797cd35565461378546c06a342edc84dc7831ce36614009"),
+    ],
+    [
+        hex("0x68401ae3bc2fabff21b207f6d74201400cc3e8d984495e70b6afdaaa61e268e0"),
+        hex("0x290b9a83a1cb831f478bd70ebb8930d0cc23f44443b3d1e61dd60f41b6a49274"),
+        hex("0x4b69d520ecca8cea66882f338bcc0ef9ac7f361be13252d0b8a11d03edaf1831"),
+    ],
+    [
+        hex("0x57b39cf1f0ea46850b019be2fec197260da68cb15b0675c41a7fa1530421210a"),
+        hex("0x4546c73d0c7f354b94a40aa83e09d75d87c9f11c865ee8cbe627eba08a3be38f"),
+        hex("0x62bf47c581239d648e958a838021caa7826a1513c1e71253a6fa98624d0a2a27"),
+    ],
+    [
+        hex("0x4eebe4ae39389914984e52e9828e8ac485c4a0abb937767aa9127906588fd189"),
+        hex("0x04bc379725cf05684871ffeed475d2d2bba63620f86306bb445ab768ba2aa185"),
+        hex("0x440b5661c3d15e65143a090d6bd2b84b8b716e6ebd1b7d05771e91e7cde024ec"),
+    ],
+    [
+        hex("0x5c77b598f3b97fba6d9973d25bdec455cbebac77d56c6acb1a60ee894409f8d8"),
+        hex("0x2328f4080eceecfef5ccd20da1be4bcd31ae5cc77aef7ae71c51e18c59c3f1ed"),
+        hex("0x302caec20a4e995e4ccc8565a77af10e49b3f7754b9a5967157944c30743b1bb"),
+    ],
+    [
+        hex("0x10741146ca5ef1a2a085754a2ed15a71fb495875bb63c5438ee5bf9d10e8c058"),
+        hex("0x465a65f1aa261fda6a821a89632f22cf2dbdf84f37f2f8903afbae58dcdac5b6"),
+        hex("0x1a371581be47518396a1748d6538e2f4ff683d3405f7f5f73d6b9c52d1b99d9d"),
+    ],
+    [
+        hex("0x049f92746cde1b4280a5d7e8da69b7eefe1a2c4905d2846a69c11f4c22e06bf8"),
+        hex("0x5c9bd12889f8cf15ae721e85a75821cb532e53ce79def307da6291f7e34f27dd"),
+        hex("0x7240300cf133215b386ab97daf5fda13dc874ff0253eef1cf9992070e26e2d12"),
+    ],
+    [
+        hex("0x3b8e5dca75c3cfb9c2579fb74cae071c8dc339208ca47deec9065e78dc881ba7"),
+        hex("0x51e62a2d38bd6bed7480a0b4852d8f09ed0e339ec2073e5b0c513600cd67285a"),
+        hex("0x411a4c8134237ff4ad00bbd25479906ec6ef6dad629b117246003c31765f7fc2"),
+    ],
+    [
+        hex("0x11a05a9592274f3cfd9b83b849c774f3c53dd187a65fdd807765337ffdb6ebbe"),
+        hex("0x32d4144bd5e6a92072e23ab63c252bf1bdcff45c185f2b14bec92202465278b4"),
+        hex("0x3d8a49dc1f8ff51cb94645a994a818a1483f37fe38c0a863610ae6d1f59f4566"),
+    ],
+    [
+        hex("0x494206f22970f333d7d7efb2a7c1cb29e92ec199470aaf035a421fd005f9fbe2"),
+        hex("0x60ed9fa9211099f40edfe6b86e5435a8064297696b72ede5c0710adb400629d8"),
+        hex("0x399070ea6f6b8ec5569408200d7e3c9f1935e00ac893af9a766efb21166bfe36"),
+    ],
+    [
+        hex("0x5fca75e89233230e5b85bc65f21bb5987c7ca77a090e03876982e0d444672368"),
+        hex("0x3242e136a19a866cc85712dcf62918bce13bdda1dfe67c24a4cabf36e365bc0a"),
+        hex("0x0f46bb63560d481ebfe015bc67dd4e51bb7463ddc546d3d436e5cb688bde224d"),
+    ],
+    [
+        hex("0x0fcac22e2ed86b92c2bce7a64d6fe20785f8a64fc3cd44b7791798d6ffd2cb5f"),
+        hex("0x466defed67622325544f9da06f261b5adf6bc1fcdee0768d6afec8495c6b6d05"),
+        hex("0x56ee5fc88c2592e273326dd0196f088cd1d36758a83517355f7abd397c81efc9"),
+    ],
+    [
+        hex("0x627635a23a4642ef2efcc5f71a6e48bfc6d2aa646bf1a78e03408b431493fc92"),
+        hex("0x0db2a01d98799909617028dbbcd9ca49a27accfd4b5aaaf03050d78ae0c4e707"),
+        hex("0x343c446f363b07a7baf45a6fa516f2f5234c9da12da24c5c950ecf9d3698cffd"),
+    ],
+    [
+        hex("0x6ad60300202131f4765c2f822c59111b3e7170a91df9d9ccd3f4dc866fa2656a"),
+        hex("0x35520e3e9547534a00c2ef16d42ae1fbb4f63896a4e261f571f2071ff0a434e8"),
+        hex("0x18e2bc16686fde3f4d65ab127982160400583faec949be8a14c0be267273e24e"),
+    ],
+    [
+        hex("0x4ea4ba1b3cf0122acf9cfd809ca7c2b331ba0397badf4704cc26719d98cb2569"),
+        hex("0x5aca1effb98ee8ae39c14f75f232f6b9f8ea43b015df9e6f9aed75198a2959f3"),
+        hex("0x47d6fb839a2eeceba8624f1a8e0aa57036f6538dfa236b18a0d970f5433337ec"),
+    ],
+    [
+        hex("0x49ee5bfcea8ab9d70b1092b5381c8a149b398741e38ccf1a62b27af2a6bbda9b"),
+        hex("0x1a9d7fdef949af9f8f4d7be3d6df2fb11fbcfa26bfdc1ea7eb9dec8a5b018f09"),
+        hex("0x0e6dde8951c3906c528a73f7390da5a327c51bee762e85a649794366139967fb"),
+    ],
+    [
+        hex("0x59cc9e4eecf44ec21f62b2ca73f46209b80d92a3c3afd34d00d74660d41eb3ed"),
+        hex("0x1224677711f1c8c1e7bf1cba520c187a6b0ce732fd15fec589a191ba2531e924"),
+        hex("0x5386031dcb90735ae19a680eeef02d9a48b0da8d5c2163dd9d3b8f79ccf72c71"),
+    ],
+    [
+        hex("0x6869310f5c66f2ec7bfcd948da4fe0251097e84bf2a56d4d04cb759bae6c54d8"),
+        hex("0x5415058859a780ada562ce9e1a16c874846051fa2e89be4b716d38d90422f6ca"),
+        hex("0x00923f9b8d8bf4e6da72c660dcf6c73323ab8464c1be4165d7f9c825b6e378da"),
+    ],
+    [
+        hex("0x3121dbf9153b81e93bafe8f7fbdbe1ca51d08ab1954e1d81e103727126c9f997"),
+        hex("0x72a3e5fa7527fe237cb87699f34b6823e67d054f9e042009e64b8c6b430b685f"),
+        hex("0x5962fc48f13bfed03d82e939c18c6dcea60dd65ec04f12c64f306b52da3909cb"),
+    ],
+    [
+        hex("0x55f21d1759cb5a531f8ce6738a259224a20fdf087bc91c6457bd172b2cc66af2"),
+        hex("0x725a78363a24dc7828224d5970c70cefd62c6894a293acd68a85b64bcd9da6a6"),
+        hex("0x53037f1a24c6e43891eb1ffa3e3fa01fa6fdfc692cd079e136f0aea007d11516"),
+    ],
+    [
+        hex("0x32bdfa8034123b28726459bf81a351107bb51072303742efac53c5fdf86a7259"),
+        hex("0x591049f0c57009233aa0d64a5bd3692f26dcfd71569a093f88741fdc1e403e7c"),
+        hex("0x67c7e61d817e9eec5eed0be358a7022a063425741b41e3d7b2c8a59a10ea3c81"),
+    ],
+    [
+        hex("0x39fc1a25f3aa12efcc435280b2661c3d86a3f3c7c23648ea11e43b22c0e2e5cc"),
+        hex("0x4cd7811037cfd93c871802e6cc2f9411a65c38baaeff2e7b1a4edab7a69bfbc4"),
+        hex("0x498be8a014709891e4cb7b925209d59af7bd0c371c58578b36e534176a85e379"),
+    ],
+    [
+        hex("0x434a51d9e320ba7b35e2f6007a22aa187a98132df1268c80696cf69155f15493"),
+        hex("0x37eaa43631c3bd82683a32d73e7776604fd3c46f9d0cbeac6cd0e71ce7c3c5c7"),
+        hex("0x12cde892a12a2ad78f3eb3031e103ddc74329294cf77b60e4ba15af6f0604b75"),
+    ],
+    [
+        hex("0x6c07a97aa2b56da4694ce3b2dbca8465273de26db67e914b7b43c278adfbc854"),
+        hex("0x19205f51def8e3d739cde771d326347828b25da3abcb32f4b1e42fe327eadba9"),
+        hex("0x69f1206c9e4c4aa974488a83f1ae73efee515f52f0b2b7c1ab4bde43a93fabc5"),
+    ],
+];
+
+const BN254_MDS: [[[u64; 4]; 3]; 3] = [
+    [
+        hex("0x0bfff43619e8a576c8772f52d1e0adde3356bd099fba04b007144c032cff4f57"),
+        hex("0x016bedd74d82ed159be3305d15b9a1ddc4b6568cd4bd7603f598a8c1f4f84169"),
+        hex("0x24531cf9b6d1796f5d0d53e18d89a4d2f796881a8c7c03ddadcab041fba63ba6"),
+    ],
+    [
+        hex("0x22516a119963325e27eab2e0eeb4d89942bdca493c42db2e8555e59215ca3eb5"),
+        hex("0x0d33432f40458dff4e89ca4fd82a2d22ac2bd4b71373cb2c2fe49758725bdcfb"),
+        hex("0x017544a2a98d2374a892722c24d0385b16d6012b152c33c09d9fad7ca08d6196"),
+    ],
+    [
+        hex("0x063ba2a4b56138708ede69ddd539987eb6adabd205c13c9f7ad1cc6c6fdb4729"),
+        hex("0x1e4d9afac7c0783882b65d845dd2b2c2c5e0d992174d69603ee352a913b217c1"),
+        hex("0x1ba8a85fdc68e9ce16729f88138ad006d070f438bc131d1444d02fbec4b4d231"),
+    ],
+];
+
+const BN254_ROUND_CONSTANTS: [[[u64; 4]; 3]; 64] = [
+    [
+        hex("0x2c4c51fd1bb9567c27e99f5712b49e0574178b41b6f0a476cddc41d242cf2b43"),
+        hex("0x1c5f8d18acb9c61ec6fcbfcda5356f1b3fdee7dc22c99a5b73a2750e5b054104"),
+        hex("0x2d3c1988b4541e4c045595b8d574e98a7c2820314a82e67a4e380f1c4541ba90"),
+    ],
+    [
+        hex("0x052547dc9e6d936cab6680372f1734c39f490d0cb970e2077c82f7e4172943d3"),
+        hex("0x29d967f4002adcbb5a6037d644d36db91f591b088f69d9b4257694f5f9456bc2"),
+        hex("0x0350084b8305b91c426c25aeeecafc83fc5feec44b9636cb3b17d2121ec5b88a"),
+    ],
+    [
+        hex("0x1815d1e52a8196127530cc1e79f07a0ccd815fb5d94d070631f89f6c724d4cbe"),
+        hex("0x17b5ba882530af5d70466e2b434b0ccb15b7a8c0138d64455281e7724a066272"),
+        hex("0x1c859b60226b443767b73cd1b08823620de310bc49ea48662626014cea449aee"),
+    ],
+    [
+        hex("0x1b26e7f0ac7dd8b64c2f7a1904c958bb48d2635478a90d926f5ff2364effab37"),
+        hex("0x2da7f36850e6c377bdcdd380efd9e7c419555d3062b0997952dfbe5c54b1a22e"),
+        hex("0x17803c56450e74bc6c7ff97275390c017f682db11f3f4ca6e1f714efdfb9bd66"),
+    ],
+    [
+        hex("0x15ce7e5ae220e8623a40b3a3b22d441eff0c9be1ae1d32f1b777af84eea7e38c"),
+        hex("0x1bf60ac8bfff0f631983c93e218ca0d4a4059c254b4299b1d9984a07edccfaf0"),
+        hex("0x0fab0c9387cb2bec9dc11b2951088b9e1e1d2978542fc131f74a8f8fdac95b40"),
+    ],
+    [
+        hex("0x07d085a48750738019784663bccd460656dc62c1b18964a0d27a5bd0c27ee453"),
+        hex("0x10d57b1fad99da9d3fe16cf7f5dae05be844f67b2e7db3472a2e96e167578bc4"),
+        hex("0x0c36c40f7bd1934b7d5525031467aa39aeaea461996a70eda5a2a704e1733bb0"),
+    ],
+    [
+        hex("0x0e4b65a0f3e1f9d3166a2145063c999bd08a4679676d765f4d11f97ed5c080ae"),
+        hex("0x1ce5561061120d5c7ea09da2528c4c041b9ad0f05d655f38b10d79878b69f29d"),
+        hex("0x2d323f651c3da8f0e0754391a10fa111b25dfa00471edf5493c44dfc3f28add6"),
+    ],
+    [
+        hex("0x05a0741ee5bdc3e099fd6bdad9a0865bc9ceecd13ea4e702e536dd370b8f1953"),
+        hex("0x176a2ec4746fc0e0eca9e5e11d6facaee05524a92e5785c8b8161780a4435136"),
+        hex("0x0691faf0f42a9ed97629b1ae0dc7f1b019c06dd852cb6efe57f7eeb1aa865aef"),
+    ],
+    [
+        hex("0x0e46cf138dad09d61b9a7cab95a23b5c8cb276874f3715598bacb55d5ad271de"),
+        hex("0x0f18c3d95bac1ac424160d240cdffc2c44f7b6315ba65ed3ff2eff5b3e48b4f2"),
+        hex("0x2eea6af14b592ec45a4119ac1e6e6f0312ecd090a096e340d472283e543ddff7"),
+    ],
+    [
+        hex("0x06b0d7a8f4ce97d049ae994139f5f71dca4899d4f1cd3dd83a32a89a58c0a8e6"),
+        hex("0x019df0b9828eed5892dd55c1ad6408196f6293d600ef4491703a1b37e119ba8e"),
+        hex("0x08ca5e3c93817cdb1c2b2a12d02c779d74c1bb12b6668f3ab3ddd7837f3a4a00"),
+    ],
+    [
+        hex("0x28382d747e3fd6cb2e0d8e8edd79c5313eed307a3517c11046245b1476e4f701"),
+        hex("0x0ca89aecd5675b77c8271765da98cfcb6875b3053d4742c9ff502861bd16ad28"),
+        hex("0x19046bc0b03ca90802ec83f212001e7ffd7f9224cfffae523451deb52eab3787"),
+    ],
+    [
+        hex("0x036fd7dfa1c05110b3428e6abcc43e1de9abba915320c4a600f843bfb676ca51"),
+        hex("0x08f0a7abcb1a2f6595a9b7380c5028e3999db4fe5cb21892e5bb5cb11a7757ba"),
+        hex("0x0b614acc1ce3fbe9048f8385e4ee24c3843deea186bacea3c904c9f6340ad8cb"),
+    ],
+    [
+        hex("0x00b2d98c5d988f9b41f2c98e017fc954a6ae423b2261575941f8eac8835d985c"),
+        hex("0x1457f18555b7973ba5b311d57ec5d77e936980b97f5973875f1f7cc765a4fc95"),
+        hex("0x002b453debc1bee525cb751bc10641a6b86f847d696418cf1144950982591bfa"),
+    ],
+    [
+        hex("0x0c2af1abcc6ece77218315d2af445ccbfc6647b7af2510682882cc792c6bb8cf"),
+        hex("0x0e2825d9eb84b59902a1adb49ac0c2c291dee7c45d2e8c30369a4d595039e8ad"),
+        hex("0x297e2e86a8c672d39f3343b8dfce7a6f20f3571bfd5c8a28e3905aa2dcfeca44"),
+    ],
+    [
+        hex("0x00d397281d902e49ec6504ba9186e806db9ad4fc8f86e7277aa7f1467eb6f9de"),
+        hex("0x2fb7c89c372d7e2050e7377ed471000c73544a2b9fd66557f3577c09cac98b4b"),
+        hex("0x16125247be4387a8c3e62490167f0cffdba02eda4f018d0b40639a13bb0cfef9"),
+    ],
+    [
+        hex("0x2291fd9d442f2d9b97ab22f7d4d52c2a82e41f852cf620b144612650a39e26e8"),
+        hex("0x1eec61f16a275ae238540feaeeadfec56d32171b1cc393729d06f37f476fde71"),
+        hex("0x259ce871ba5dacbb48d8aed3d8513eef51558dc0b360f28c1a15dbfc5e7f6ca2"),
+    ],
+    [
+        hex("0x2d3376a14ddbf95587e2f7567ff04fe13a3c7cb17363c8b9c5dd1d9262a210cb"),
+        hex("0x13b843d9f65f4cddd7ce10d9cad9b8b99ac5e9a8c4269288173a91c0f3c3b084"),
+        hex("0x0b52e9b2f1aa9fd204e4a42c481cc76c704783e34114b8e93e026a50fa9764e8"),
+    ],
+    [
+        hex("0x1fd083229276c7f27d3ad941476b394ff37bd44d3a1e9caca1400d9077a2056c"),
+        hex("0x22743c328a6283f3ba7379af22c684c498568fd7ad9fad5151368c913197cbd9"),
+        hex("0x043007aefd9741070d95caaaba0c1b070e4eec8eef8c1e512c8e579c6ed64f76"),
+    ],
+    [
+        hex("0x17ab175144f64bc843074f6b3a0c57c5dd2c954af8723c029ee642539496a7b3"),
+        hex("0x2befcad3d53fba5eeef8cae9668fed5c1e9e596a46e8458e218f7a665fddf4eb"),
+        hex("0x15151c4116d97de74bfa6ca3178f73c8fe8fe612c70c6f85a7a1551942cb71cc"),
+    ],
+    [
+        hex("0x2ac40bf6c3176300a6835d5fc7cc4fd5e5d299fb1baa86487268ec1b9eedfa97"),
+        hex("0x0f151de1f01b4e24ffe04279318f0a68efabb485188f191e37e6915ff6059f6e"),
+        hex("0x2e43dffc34537535182aebac1ad7bf0a5533b88f65f9652f0ad584e2ffc4dd1f"),
+    ],
+    [
+        hex("0x2ebabc2c37ef53d8b13b24a2a2b729d536735f58956125a3876da0664c2442d7"),
+        hex("0x0dc3beceb34e49f5ad7226dd202c5cf879dffcc9a6dd32a300e8f2a4b59edf03"),
+        hex("0x2f1ddeccce83adf68779c53b639871a8f81d4d00aefe1e812efce8ec999d457d"),
+    ],
+    [
+        hex("0x1f63e41280ff5c021715d52b19780298ed8bd3d5eb506316b527e24149d4d4f1"),
+        hex("0x1b8c1252a5888f8cb2672effb5df49c633d3fd7183271488a1c40d0f88e7636e"),
+        hex("0x0f45697130f5498e2940568ef0d5e9e16b1095a6cdbb6411df20a973c605e70b"),
+    ],
+    [
+        hex("0x0780ccc403cdd68983acbd34cda41cacfb2cf911a93076bc25587b4b0aed4929"),
+        hex("0x238d26ca97c691591e929f32199a643550f325f23a85d420080b289d7cecc9d4"),
+        hex("0x25672a14b5d085e31a30a7e1d5675ebfab034fb04dc2ec5e544887523f98dede"),
+    ],
+    [
+        hex("0x0cf702434b891e1b2f1d71883506d68cdb1be36fa125674a3019647b3a98accd"),
+        hex("0x1837e75235ff5d112a5eddf7a4939448748339e7b5f2de683cf0c0ae98bdfbb3"),
+        hex("0x1cd8a14cff3a61f04197a083c6485581a7d836941f6832704837a24b2d15613a"),
+    ],
+    [
+        hex("0x266f6d85be0cef2ece525ba6a54b647ff789785069882772e6cac8131eecc1e4"),
+        hex("0x0538fde2183c3f5833ecd9e07edf30fe977d28dd6f246d7960889d9928b506b3"),
+        hex("0x07a0693ff41476abb4664f3442596aa8399fdccf245d65882fce9a37c268aa04"),
+    ],
+    [
+        hex("0x11eb49b07d33de2bd60ea68e7f652beda15644ed7855ee5a45763b576d216e8e"),
+        hex("0x08f8887da6ce51a8c06041f64e22697895f34bacb8c0a39ec12bf597f7c67cfc"),
+        hex("0x2a912ec610191eb7662f86a52cc64c0122bd5ba762e1db8da79b5949fdd38092"),
+    ],
+    [
+        hex("0x2031d7fd91b80857aa1fef64e23cfad9a9ba8fe8c8d09de92b1edb592a44c290"),
+        hex("0x0f81ebce43c47711751fa64d6c007221016d485641c28c507d04fd3dc7fba1d2"),
+        hex("0x1895b132a3b4f63a890e7601f503e47bfdc628dd4b8cca01235a911a07cf9a2c"),
+    ],
+    [
+        hex("0x0f4cc60a74968fb6cd9dfa2dfbc57566bbc14e83c4526cd49b73e00a98a579e3"),
+        hex("0x18ad9810bf5492aa178f4f98caacd49b09d65e8acacaded9a44d4ef635cbd6e4"),
+        hex("0x2fb86e1c59d3e3c8d68f7a6e1bdb8be0c415943769425a2664b9faeff11c08e7"),
+    ],
+    [
+        hex("0x21a2e70e3aeb0a9753b0bc45c4617b5ea35933800d5625a7c3f587c0fd46225b"),
+        hex("0x1e56b7129334869a077ba9ca1ff25d652f38a8fc491a3313196e0f2687325cf6"),
+        hex("0x0d158e4e51d1141c3392b38c56576e055530ba135ea13d7de98bfbe34d3ae076"),
+    ],
+    [
+        hex("0x22a9bd68e514c0e62df12ba41922ce3ed0721f36f4ac0eed02315060a6eb136f"),
+        hex("0x121353809a89b1e2226ac1bf9ec3b52d615fcfe16a017c0a3815dc7dea77ab2d"),
+        hex("0x12f508bf199c0f08e5a4abe3f7c3aff9958077d67e241f02ffc7a03878015935"),
+    ],
+    [
+        hex("0x1823c63a24cf440cdd790ef1d4658d1f527fb87b9114bcf979286453de741db1"),
+        hex("0x05993ec9bf1729a1fd607be2f32c176d2a3eb6d9b5a8a4bcfc907bad2e961d90"),
+        hex("0x1426db8c925f3b18285df0c8f4a4eebdc14b3f9dbcd13fa858a6141d754e21cf"),
+    ],
+    [
+        hex("0x156d2369ef6646f3c41e8b4fa08db801564dd205f98f560a033d5347f210a542"),
+        hex("0x162bdc3da8fa6d6408d30ba48b8ee9de16775e0d5adbe4c61d8e1744746eee75"),
+        hex("0x064cdd0b4261ecafa482ad6aa0382df4b2817209531e2d0fa99785170593989d"),
+    ],
+    [
+        hex("0x2e055038d66b07373afed9c641b7504c4d68e8c21b50a825f3dd08799252b4c2"),
+        hex("0x0801a52ec8cfd81e14847a115d6d33122ccc936b5a43d2a1147e040026e29697"),
+        hex("0x178768fc8ec4846dd5bf245d78c2cf4838ed2d8023fcc2151d29896924f74e5d"),
+    ],
+    [
+        hex("0x2797831f66a3c1fa974a44f8c2ccb9e039cd151ee075ba02ca63fb0cd329985b"),
+        hex("0x14387cd44915795eb1fbedebbfdb93f63f02a2cd276bb67fbd5c873eec9065f2"),
+        hex("0x1da345bd89bd33183e4d7093140eeccffe8d1bc41a1e177df9fd5467d0e7d7b6"),
+    ],
+    [
+        hex("0x0ba87f92d95adc8e79e1728694388c593dfc1fd016a588a93591eb3c3e60dd76"),
+        hex("0x2cfbf314cedb2fe474f22346e0dd45c3aec0f33fa5dbcd5cbc57a60500a715cb"),
+        hex("0x1b364bbadc072df4f13872ef9771b90ad59bd74cec44a761ee745850c6633dc6"),
+    ],
+    [
+        hex("0x17cff638b87d9810bfb46210bfbbb036158152f931ad83ecc382fe65aae696b8"),
+        hex("0x073488c84affc730638584a226b5ad1fe791077b7a0ac57164c80221a087b76f"),
+        hex("0x1e0eda30cf0396542488e1515438ba49f9e6eab63560d0c1a78964f687e44c0a"),
+    ],
+    [
+        hex("0x1e42662c197192cd8c04ef4909cfcd371405f4c23221783ba811befc0adef0b5"),
+        hex("0x09dc7e99f0a7ff4cb6cde1ac8059e8e599efa23e87d5e1b1aa051ec1b8673ee0"),
+        hex("0x0208a5c313767dcd083af4f4b0077465aa8bf51ca766a52b701318265b36bdf0"),
+    ],
+    [
+        hex("0x2b3a13ff0e76f109add82bfb3d8da4eb10b0498732f110ef533bcd1bd214fbda"),
+        hex("0x169f313650451e8755f73ff6a4a049287958f167aa56325191e51f4c911eb333"),
+        hex("0x2a3a61c8338d913ca3d4b15635d6592b43f5eff803de5343e060d651bb081b5f"),
+    ],
+    [
+        hex("0x263e40919b33e3e38cbedcbfc3759568a76a3ab971090dec155207b5a4aaee99"),
+        hex("0x15c193fd18079eedd899b8961ed7ed3a6c1e65d40d5bef5a0ba7fce61316166b"),
+        hex("0x1b305d273cb349967eea11e115c8d82b1a7c6c02c7a1605a75093c90308681b4"),
+    ],
+    [
+        hex("0x221363a451b9679b3a8b6da9d9bf298b9e749d7e1993ca72dc96125a4446673b"),
+        hex("0x098c99ee98eda1ed7e794827b72eb58366ab9d3dde34a682617bdbf6d90c543e"),
+        hex("0x28384acbea0706585c9d4b23dd47de166e8a9b5ebc2a5f2c8ee2f5c8456c6604"),
+    ],
+    [
+        hex("0x0cb7bee531d5074fd51e4d1ad633f3636731b9b130c159c6fd07657a688de6cb"),
+        hex("0x2a8421c4ae026946e5a5fd8c228e9da35845eba29beb3756c0de87c17a1bf36f"),
+        hex("0x04fabca66fb4576aa98ddea1874005b091272cd9f0135b6d9bd4b74486c1dda4"),
+    ],
+    [
+        hex("0x140fa9c0ceb18fc1309fa24fcb6befad9b9506fbb2304528e1178e2a156024e1"),
+        hex("0x0e55bba52f294d8d4b63f8655e25c06d1c22f04347757f866c38dd2112fbea80"),
+        hex("0x0168938c381904c988edb76fa65846cca5a6e528e68c3a021739e194911a118c"),
+    ],
+    [
+        hex("0x119a0fe5af6bed80c13dec6ef3bf01ded7078a607d3a675079bcfc3d2f2c8b63"),
+        hex("0x2715e1a3557ea2593e6365e7dad136c46688f9fd73d13d1f6e1b76ac34625020"),
+        hex("0x01455ea995894243e2fba16d6c53b0f4b3a1a0adf4f1219e432e97775b7e943b"),
+    ],
+    [
+        hex("0x07e37b7a4276c471bd94b3206532bbe2ce0949f05dea0e37d22d244e51bb1476"),
+        hex("0x0076527a3458ab318d769d7aca309290d4d300f201beb7ae690d1c27b6fd37ef"),
+        hex("0x0866d13b35f28acd32b0cdd1240fbbd55e46a767cf7e7f1eb5ee4d81a07b4270"),
+    ],
+    [
+        hex("0x080ba85ae5fe184cd6613b573a00146898d1f13f12c709f5d8d7aae2b61163ed"),
+        hex("0x146177a1f732ddf4ce2a6e68f07c81f18163ec5f79777825c98af60e810ad7cf"),
+        hex("0x1301327374bf094a1ae0c5e6015b01826871bda162e98ebe24d5ec8b1e953e27"),
+    ],
+    [
+        hex("0x00b6a90037dc3ba2988374daf11659814cdbc172ec54f6cfe3b2a7894d3cb10a"),
+        hex("0x0f95d96cab4c663b590f81d2622c408ff04b446c1ec387f4202bdd1dab9a5253"),
+        hex("0x2cc3466fdf581e61a0c0f3d80af78a544db848ad07ff07765c4b8e378dc7cf9c"),
+    ],
+    [
+        hex("0x2cd50209809a87951695bef1441b46b50b92d9095e0b206f75c7dbf5b56f0be7"),
+        hex("0x03a838c99298c583f2ec916459f901bd12a55d1d0bc905eff1bbffa9f67b324c"),
+        hex("0x0d1a41d85414026e49e9eb8b295b225b4590383752fbfb26a2f4ddcecb7b0551"),
+    ],
+    [
+        hex("0x15b2e5cf31397844f17c4e962fa08ec7b63f5ed6f183b2cf0c102012d248d463"),
+        hex("0x2db14f8dd7f05774f3b74e08e46bdc541747f354fc06438891718434548fff22"),
+        hex("0x21ff30ef7d57d5510cc53eb96e8df272f6dafff9fb01c718f0f148208d301ee2"),
+    ],
+    [
+        hex("0x2acb22e3a439d4bcb157e9d5113da76bd8ab76d5c5551b6b87250b70dc77f0fa"),
+        hex("0x1ae5ec3bef630782f745bc17d9c5af74062d30e7410a8c29764de2c67f6ddf25"),
+        hex("0x296180deac9cc3a506c20409307e509aab62f8939db075ea0b86a6d47a466c07"),
+    ],
+    [
+        hex("0x0b818d8dbd96b5a65db2d7490dc7a539fef138ba219a0f87e7b12154b59d66bb"),
+        hex("0x2bbcb71354c134d81f459e15b6d37b385546e4d476770229d59933d2df2e4889"),
+        hex("0x237376a5dc4be2bfd24a8d50607732464ab0fac97725d32604c9e278a80f1b63"),
+    ],
+    [
+        hex("0x20c2c8241dc9c5b01f038295f616c61a01571a0c5bfda85d51741fc5662a5849"),
+        hex("0x272b0f38b3a9e03cbef637d66a194ec70b326b82539e68b98b6c16f99059777c"),
+        hex("0x2cefa41818d6d44c1facd62f1c1d8e2d49d4df4d95893b28453fade3256f790c"),
+    ],
+    [
+        hex("0x077fe3662b5afabd93b294c7e687b90d2f704c8ec344ee966107667f0058e0ee"),
+        hex("0x2af4041a82ea2f300f00f29a19558634347d75f9123309e2f088e06c3bb9bd09"),
+        hex("0x1b2436cc7b5a5f481082fd81356862e63ba8c94252933ecc5dd8632bc73c39ab"),
+    ],
+    [
+        hex("0x05b86e958b85092efd6a61f8e108ec0d69e3931aa04273ae6d0643bd80d75544"),
+        hex("0x2180b22145d8052ebc24124aeba7d5e277d8be1fd0b1451ac866173816e1874a"),
+        hex("0x222929ecb0627dfc46ce748294f4e5df6542b3fca0c2acb994ec39436be08dae"),
+    ],
+    [
+        hex("0x10a5613d17f5899c1f9abe34e7c808845d7db54ea0361f64f791d9d860ff1e3a"),
+        hex("0x187c1d0f3389d3b520be8314b0c99c1275b2e3bc7198845535855e140f4197df"),
+        hex("0x1c387b065ec4130c93bc6afe20905270c529d627fbd65bf8dfb91be0fbb0c110"),
+    ],
+    [
+        hex("0x1cd5e0fa7c635fe564ad8fc40b9526debc659aba951b1d13ae1a57b020731a8a"),
+        hex("0x237813d540a15c5217b34afa84343cba7af5adf5a8de983f21d89687b860aee5"),
+        hex("0x1507d35ed7c120fa091ecc3bba06945d686805c0ffa078d74051fc5003a10c53"),
+    ],
+    [
+        hex("0x03a454b022ed70ebb889a9829933f2ba76ffa284ae65c56a74145470c0fd61cf"),
+        hex("0x2fff39c19a99cf5dff2258d8ebd3174652ebde16229e1c34bfa201c6e0ad3dca"),
+        hex("0x30077ea39cffd94fc40eded4e644f8c1bcae61a69b34a653476bdc8d10598c0a"),
+    ],
+    [
+        hex("0x0420d44e7a70ef517032a8e4b0c195b011781f4f663353c4e847e5034f5162cd"),
+        hex("0x11a8e3cf79594bff6647ba0e0fa0939881da87564a050bb14bdeecb46b68816b"),
+        hex("0x0e750e70034ce9d61cbfb67885e065ba1bf11e0b26616a15095ee630d84283b0"),
+    ],
+    [
+        hex("0x137c5f17f844bb82b74a94f76a244cd8fba41ac3dbaf8431b9b72d3f2184f30f"),
+        hex("0x2dcbcf7fe405956e0169ad617833f89064a2dd939bfe41094570d1a0f1cb5560"),
+        hex("0x28fd1ad9f8a0b21b5069de7671f6722b2eade816c68b755286e86d9a2851e42c"),
+    ],
+    [
+        hex("0x270fb925857f7a32b893b2f18097046f96969dbee3647decb4eb1c4d053f2aef"),
+        hex("0x0be2cc7ba258dc14f37fbf2c75eee26f3b446e66ad6a304529bbd1734156151b"),
+        hex("0x0aac30b0f866e18e40ce911e16aed6139d2677e3c62fe63db82d2dfb83c9c433"),
+    ],
+    [
+        hex("0x1d8f552747d2943f67f00d77189df46d2ea9d0b41ff7f9263771a4a7f0925a97"),
+        hex("0x0f89d051618b173bbbedaf897d34ba25e7451004c83826659b630b90fec84140"),
+        hex("0x1def8952f3409a8968a6ab88d5cf2faf1cdf54d5fc589e8bd5a4d60ca66c2773"),
+    ],
+    [
+        hex("0x1cb8958c959ea40aef51dfc4b6fd2b82fcc2f1710c7903094d5076c89da0778b"),
+        hex("0x1b8d491e14d40444d6a55dc387f49b5ff3189bb5688fef99cc358851017f9812"),
+        hex("0x040ae35d3d711016dd4be1d648f2aff2075e96d9e27c0a0cb77c646e3600bbff"),
+    ],
+    [
+        hex("0x08535646cdcc4053038be2973d97012bafc70407f1340fbcc1722c5f1fc83684"),
+        hex("0x079aec758ee618d8f9cb7ff8a111897f939d10252cc2b8777c7265b184eb7403"),
+        hex("0x2e01efc8df69089a62995832e321be5bace92b321202de25a01c5df8d26108d6"),
+    ],
+    [
+        hex("0x1ccd6bd7c5c5ddbad822cc2155a55a633bf9cc04b510e6c38dc2d99ae2b4532d"),
+        hex("0x0dab1668d6f3339ca545f6fb560ddb88c8dbbed8ff5ba99dca9345212bd0d84b"),
+        hex("0x1a8f8263346fa919c687811c83a30f299bcf03b69df110a6e72477c077545552"),
+    ],
+    [
+        hex("0x24939b943e9841db4d9af6f20abf17834073732e899558703357e9b65be53283"),
+        hex("0x1f9607d0121aa68b9bc38aed6f78d8ac700f7ced5a47e9690529ee1b52a21972"),
+        hex("0x088e9dfa8d0617d6f53073e7a8a6ea2b8af78820b9cfb17b3672c7cfc18560e9"),
+    ],
+];
@@ -0,0 +1,126 @@
+//! Poseidon permutation and hash tests.
+//!
+//! Known-answer vectors are the HorizenLabs reference outputs for input `[0, 1, 2]`.
+
+use crate::{
+    bls,
+    errors::QuickexError,
+    poseidon::{hex, Element, PrimeField, BLS12_381_FR, BN254_FR},
+    poseidon_params::{POSEIDON_BLS12_381, POSEIDON_BN254},
+    test_fixture::deploy,
+    types::PoseidonField,
+};
+use soroban_sdk::{BytesN, Env, U256};
+
+fn element(field: &PrimeField, value: u64) -> Element {
+    field.element([value, 0, 0, 0]).unwrap()
+}
+
+fn expected(field: &PrimeField, values: [&str; 3]) -> [Element; 3] {
+    values.map(|value| field.element(hex(value)).unwrap())
+}
+
+#[test]
+fn test_poseidon_bls12_381_known_answer() {
+    let f = &BLS12_381_FR;
+    let out = POSEIDON_BLS12_381.permute([element(f, 0), element(f, 1), element(f, 2)]);
+
+    assert_eq!(
+        out,
+        expected(
+            f,
+            [
+                "0x200e6982ac00df8fa65cef1fde9f21373fdbbfd98f2df1eb5fa04f3302ab0397",
+                "0x2233c9a40d91c1f643b700f836a1ac231c3f3a8d438ad1609355e1b7317a47e5",
+                "0x2eae6736db3c086ad29938869dedbf969dd9804a58aa228ec467b7d5a08dc765",
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_poseidon_bn254_known_answer() {
+    let f = &BN254_FR;
+    let out = POSEIDON_BN254.permute([element(f, 0), element(f, 1), element(f, 2)]);
+
+    assert_eq!(
+        out,
+        expected(
+            f,
+            [
+                "0x2677d68d9cfa91f197bf5148b50afac461b6b8340ff119a5217794770baade5f",
+                "0x21ae9d716173496b62c76ad7deb4654961f64334441bcf77e17a047155a3239f",
+                "0x008f8e7c73ff20b6a141c48cef73215860acc749b14f0a7887f74950215169c6",
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_bls12_381_field_matches_host_arithmetic() {
+    let env = Env::default();
+    let f = &BLS12_381_FR;
+    // Both values are below the modulus.
+    let mut a = [0xAB; 32];
+    a[0] = 0x1b;
+    let mut b = [0x5C; 32];
+    b[0] = 0x12;
+
+    let host_a = bls::fr_from_bytes(&env, &BytesN::from_array(&env, &a));
+    let host_b = bls::fr_from_bytes(&env, &BytesN::from_array(&env, &b));
+    let sw_a = f.decode(&a).unwrap();
+    let sw_b = f.decode(&b).unwrap();
+
+    let bls12 = env.crypto().bls12_381();
+    let host_product = bls12.fr_mul(&host_a, &host_b).to_u256();
+    let host_sum = bls12.fr_add(&host_a, &host_b).to_u256();
+    let as_u256 = |e: Element| {
+        let bytes = BytesN::from_array(&env, &f.encode(&e));
+        U256::from_be_bytes(&env, &bytes.into())
+    };
+
+    assert_eq!(as_u256(f.mul(&sw_a, &sw_b)), host_product);
+    assert_eq!(as_u256(f.add(&sw_a, &sw_b)), host_sum);
+}
+
+#[test]
+fn test_field_rejects_non_canonical_values() {
+    assert!(BLS12_381_FR.decode(&bls::FR_MODULUS_BE).is_none());
+    assert!(BN254_FR.decode(&[0xFF; 32]).is_none());
+}
+
+#[test]
+fn test_poseidon_hash_entrypoint() {
+    let (env, client) = deploy();
+    let f = &BN254_FR;
+    let left = element(f, 1);
+    let right = element(f, 2);
+    let expected = POSEIDON_BN254.permute([f.zero(), left, right])[0];
+
+    let hash = client.poseidon_hash(
+        &PoseidonField::Bn254,
+        &BytesN::from_array(&env, &f.encode(&left)),
+        &BytesN::from_array(&env, &f.encode(&right)),
+    );
+    assert_eq!(hash.to_array(), f.encode(&expected));
+
+    // The same inputs hash differently over the other field.
+    let other = client.poseidon_hash(
+        &PoseidonField::Bls12381,
+        &BytesN::from_array(&env, &f.encode(&left)),
+        &BytesN::from_array(&env, &f.encode(&right)),
+    );
+    assert_ne!(hash, other);
+}
+
+#[test]
+fn test_poseidon_hash_rejects_non_canonical_input() {
+    let (env, client) = deploy();
+    let zero = BytesN::from_array(&env, &[0; 32]);
+    let modulus = BytesN::from_array(&env, &bls::FR_MODULUS_BE);
+
+    let result = client.try_poseidon_hash(&PoseidonField::Bls12381, &modulus, &zero);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidFieldElement)));
+    let result = client.try_poseidon_hash(&PoseidonField::Bn254, &zero, &modulus);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidFieldElement)));
+}
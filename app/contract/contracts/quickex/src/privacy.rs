@@ -1,7 +1,141 @@
+//! Per-account privacy settings.
+//!
+//! Two APIs coexist: a numeric level (`enable_privacy`) and a boolean toggle
+//! (`set_privacy`). Both feed [`effective_level`], which views and escrow events use
+//! to decide what to disclose about an account's escrows to third parties:
+//!
+//! | Level | Name                       | Amount | Owner  | Status via `get_commitment_state` |
+//! |-------|----------------------------|--------|--------|-----------------------------------|
+//! | 0     | [`LEVEL_PUBLIC`]           | shown  | shown  | shown                             |
+//! | 1     | [`LEVEL_HIDE_AMOUNT`]      | hidden | shown  | shown                             |
+//! | 2     | [`LEVEL_HIDE_ALL`]         | hidden | hidden | hidden                            |
+//!
+//! Escrow events follow the same table, except that at [`LEVEL_HIDE_AMOUNT`] they carry
+//! the amount's decimal order of magnitude in place of the amount.
+//!
+//! The boolean toggle, when on, is equivalent to [`LEVEL_HIDE_ALL`]. The owner and
+//! holders of [`Role::Auditor`] always see full details through [`escrow_view`]. Amounts remain visible in the
+//! underlying token transfers; levels only control what this contract discloses.
+//!
+//! While an account is below [`LEVEL_HIDE_ALL`], its pending escrows are indexed so
+//! wallets can find them with [`list_claimable`]. Raising the level to
+//! [`LEVEL_HIDE_ALL`] (or turning the toggle on) drops the index and stops indexing.
+//! The index is stored in buckets of
+//! [`OWNER_BUCKET_SIZE`](storage::OWNER_BUCKET_SIZE) commitments, so an owner with
+//! thousands of escrows never outgrows a storage entry; [`list_claimable_page`] reads
+//! it a bucket at a time.
+//!
+//! An account can also cap how many withdrawals it performs per time window with
+//! [`set_withdraw_rate_limit`]. This slows down sweeps that try to link or drain an
+//! account's escrows, at the cost of the account's own throughput.
+
+use crate::admin;
 use crate::errors::QuickexError;
 use crate::events::publish_privacy_toggled;
-use crate::storage::PRIVACY_ENABLED_KEY;
-use soroban_sdk::{Address, Env, Symbol};
+use crate::storage;
+use crate::types::{
+    ClaimablePage, EscrowEntry, PrivacyAwareEscrowView, PrivacyHistoryEntry, PrivacyHistoryPage,
+    Role, WithdrawRateLimit, WithdrawWindow,
+};
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+/// Everything is disclosed.
+pub const LEVEL_PUBLIC: u32 = 0;
+
+/// Amounts are hidden from views and event payloads.
+pub const LEVEL_HIDE_AMOUNT: u32 = 1;
+
+/// Amounts and owner are hidden, and anonymous status lookups return nothing.
+pub const LEVEL_HIDE_ALL: u32 = 2;
+
+/// Set the numeric privacy level for an account and append it to its history.
+///
+/// # Errors
+/// - [`QuickexError::InvalidPrivacyLevel`] – level > [`LEVEL_HIDE_ALL`].
+pub fn set_privacy_level(env: &Env, account: Address, level: u32) -> Result<(), QuickexError> {
+    if level > LEVEL_HIDE_ALL {
+        return Err(QuickexError::InvalidPrivacyLevel);
+    }
+
+    account.require_auth();
+
+    storage::set_privacy_level(env, &account, level);
+    storage::add_privacy_history(env, &account, level);
+    if level >= LEVEL_HIDE_ALL {
+        storage::clear_owner_index(env, &account);
+    }
+    Ok(())
+}
+
+/// Largest page returned by [`privacy_history_page`].
+pub const MAX_HISTORY_PAGE: u32 = 20;
+
+/// Levels of `account`'s privacy history, newest first (at most
+/// [`PRIVACY_HISTORY_CAP`](storage::PRIVACY_HISTORY_CAP) entries).
+pub fn privacy_history(env: &Env, account: &Address) -> Vec<u32> {
+    let mut levels = Vec::new(env);
+    for entry in storage::get_privacy_history(env, account).iter() {
+        levels.push_back(entry.level);
+    }
+    levels
+}
+
+/// A page of `account`'s privacy history.
+///
+/// `cursor` is the number of newer entries to skip (start at 0); `limit` is clamped
+/// to [`MAX_HISTORY_PAGE`].
+pub fn privacy_history_page(
+    env: &Env,
+    account: &Address,
+    cursor: u32,
+    limit: u32,
+) -> PrivacyHistoryPage {
+    let history = storage::get_privacy_history(env, account);
+    let start = cursor.min(history.len());
+    let end = start
+        .saturating_add(limit.min(MAX_HISTORY_PAGE))
+        .min(history.len());
+    let entries: Vec<PrivacyHistoryEntry> = history.slice(start..end);
+    PrivacyHistoryPage {
+        entries,
+        next_cursor: (end < history.len()).then_some(end),
+    }
+}
+
+/// The level that applies to `account`: the stricter of its numeric level and the
+/// boolean toggle (on = [`LEVEL_HIDE_ALL`]).
+pub fn effective_level(env: &Env, account: &Address) -> u32 {
+    let level = storage::get_privacy_level(env, account).unwrap_or(LEVEL_PUBLIC);
+    if get_privacy(env, account.clone()) {
+        LEVEL_HIDE_ALL
+    } else {
+        level
+    }
+}
+
+/// View of `entry` for `caller`, redacted by the owner's [`effective_level`].
+///
+/// The owner and holders of [`Role::Auditor`] see every field, but must authorize
+/// when that lifts a redaction, so naming someone else as `caller` reveals nothing.
+pub fn escrow_view(env: &Env, entry: EscrowEntry, caller: &Address) -> PrivacyAwareEscrowView {
+    let mut level = effective_level(env, &entry.owner);
+    if level > LEVEL_PUBLIC
+        && (*caller == entry.owner || admin::has_role(env, Role::Auditor, caller))
+    {
+        caller.require_auth();
+        level = LEVEL_PUBLIC;
+    }
+
+    PrivacyAwareEscrowView {
+        token: entry.token,
+        amount: (level < LEVEL_HIDE_AMOUNT).then_some(entry.amount),
+        owner: (level < LEVEL_HIDE_ALL).then_some(entry.owner),
+        status: entry.status,
+        created_at: entry.created_at,
+        expires_at: entry.expires_at,
+        redacted: level > LEVEL_PUBLIC,
+    }
+}
 
 /// Enable or disable privacy for an account.
 ///
@@ -11,18 +145,14 @@ use soroban_sdk::{Address, Env, Symbol};
 pub fn set_privacy(env: &Env, owner: Address, enabled: bool) -> Result<(), QuickexError> {
     owner.require_auth();
 
-    let key = Symbol::new(env, PRIVACY_ENABLED_KEY);
-    let storage_key = (key.clone(), owner.clone());
-    let current: bool = env
-        .storage()
-        .persistent()
-        .get(&storage_key)
-        .unwrap_or(false);
-    if current == enabled {
+    if storage::get_privacy_enabled(env, &owner) == enabled {
         return Err(QuickexError::PrivacyAlreadySet);
     }
 
-    env.storage().persistent().set(&storage_key, &enabled);
+    storage::set_privacy_enabled(env, &owner, enabled);
+    if enabled {
+        storage::clear_owner_index(env, &owner);
+    }
 
     publish_privacy_toggled(env, owner, enabled);
     Ok(())
@@ -32,9 +162,100 @@ pub fn set_privacy(env: &Env, owner: Address, enabled: bool) -> Result<(), Quick
 ///
 /// Defaults to `false` if never set.
 pub fn get_privacy(env: &Env, owner: Address) -> bool {
-    let key = Symbol::new(env, PRIVACY_ENABLED_KEY);
-    env.storage()
-        .persistent()
-        .get(&(key, owner))
-        .unwrap_or(false)
+    storage::get_privacy_enabled(env, &owner)
+}
+
+/// Add a new escrow to `owner`'s claimable index, unless `owner` is at
+/// [`LEVEL_HIDE_ALL`].
+pub fn index_commitment(env: &Env, owner: &Address, commitment: &BytesN<32>) {
+    if effective_level(env, owner) < LEVEL_HIDE_ALL {
+        storage::add_to_owner_index(env, owner, commitment);
+    }
+}
+
+/// Pending escrows of `owner`, oldest first.
+///
+/// Empty for owners at [`LEVEL_HIDE_ALL`]. Escrows created while the owner was hidden
+/// are never listed, even after the level is lowered again.
+pub fn list_claimable(env: &Env, owner: &Address) -> Vec<BytesN<32>> {
+    if effective_level(env, owner) >= LEVEL_HIDE_ALL {
+        return Vec::new(env);
+    }
+    storage::get_owner_index(env, owner)
+}
+
+/// One page of [`list_claimable`], starting at `cursor` (0 for the first page).
+///
+/// Each page holds at most [`OWNER_BUCKET_SIZE`](storage::OWNER_BUCKET_SIZE)
+/// escrows, except the first page of owners indexed before buckets were introduced.
+/// A page can be empty while `next_cursor` is set, after a long run of spent escrows.
+pub fn list_claimable_page(env: &Env, owner: &Address, cursor: u32) -> ClaimablePage {
+    if effective_level(env, owner) >= LEVEL_HIDE_ALL {
+        return ClaimablePage {
+            commitments: Vec::new(env),
+            next_cursor: None,
+        };
+    }
+    let (commitments, next_cursor) = storage::get_owner_page(env, owner, cursor);
+    ClaimablePage {
+        commitments,
+        next_cursor,
+    }
+}
+
+/// Limit `account` to `max_withdrawals` withdrawals per `window_secs` seconds.
+///
+/// Windows are fixed: the first withdrawal after a window closes opens a new one.
+/// Passing `max_withdrawals = 0` removes the limit.
+///
+/// # Errors
+/// - [`QuickexError::InvalidRateLimit`] – `max_withdrawals > 0` with `window_secs = 0`.
+pub fn set_withdraw_rate_limit(
+    env: &Env,
+    account: Address,
+    max_withdrawals: u32,
+    window_secs: u64,
+) -> Result<(), QuickexError> {
+    if max_withdrawals > 0 && window_secs == 0 {
+        return Err(QuickexError::InvalidRateLimit);
+    }
+
+    account.require_auth();
+
+    if max_withdrawals == 0 {
+        storage::remove_withdraw_rate_limit(env, &account);
+    } else {
+        let limit = WithdrawRateLimit {
+            max_withdrawals,
+            window_secs,
+        };
+        storage::set_withdraw_rate_limit(env, &account, &limit);
+    }
+    Ok(())
+}
+
+/// Count a withdrawal by `account` against its rate limit, if it has one.
+///
+/// # Errors
+/// - [`QuickexError::WithdrawRateLimited`] – the current window is already full.
+pub fn record_withdrawal(env: &Env, account: &Address) -> Result<(), QuickexError> {
+    let Some(limit) = storage::get_withdraw_rate_limit(env, account) else {
+        return Ok(());
+    };
+
+    let now = env.ledger().timestamp();
+    let mut window = match storage::get_withdraw_window(env, account) {
+        Some(w) if now < w.start.saturating_add(limit.window_secs) => w,
+        _ => WithdrawWindow {
+            start: now,
+            count: 0,
+        },
+    };
+    if window.count >= limit.max_withdrawals {
+        return Err(QuickexError::WithdrawRateLimited);
+    }
+
+    window.count += 1;
+    storage::set_withdraw_window(env, account, &window);
+    Ok(())
 }
@@ -0,0 +1,240 @@
+//! Relayer registry with staking and slashing.
+//!
+//! Relayers submit [`withdraw_via_relayer`](crate::escrow::withdraw_via_relayer)
+//! transactions for recipients that hold no XLM. To be eligible a relayer must lock at
+//! least `min_stake` of the admin-configured stake token. The admin can slash stake
+//! from relayers that censor or grief users; slashed funds go to the admin.
+//!
+//! ```text
+//! stake() ──► Active (stake >= min_stake)
+//! unstake() ──► Unbonding (inactive, still slashable) ──[unbonding_period]──► withdraw_stake()
+//! ```
+//!
+//! Staking again while unbonding cancels the unbonding.
+
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+use crate::{
+    admin,
+    errors::QuickexError,
+    events, storage, tokens,
+    types::{RelayerConfig, RelayerInfo, RelayerPage},
+};
+
+/// Configure the relayer stake requirements (**admin only**).
+///
+/// The stake token is fixed by the first call; later calls may only change
+/// `min_stake` and `unbonding_period`. Raising `min_stake` does not deactivate
+/// relayers that are already active.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::InvalidRelayerConfig`] – `min_stake` ≤ 0, or `stake_token`
+///   differs from the configured one.
+pub fn set_relayer_config(
+    env: &Env,
+    caller: Address,
+    stake_token: Address,
+    min_stake: i128,
+    unbonding_period: u64,
+) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+
+    if min_stake <= 0 {
+        return Err(QuickexError::InvalidRelayerConfig);
+    }
+    if let Some(existing) = storage::get_relayer_config(env) {
+        if existing.stake_token != stake_token {
+            return Err(QuickexError::InvalidRelayerConfig);
+        }
+    }
+
+    storage::set_relayer_config(
+        env,
+        &RelayerConfig {
+            stake_token,
+            min_stake,
+            unbonding_period,
+        },
+    );
+
+    Ok(())
+}
+
+/// Current relayer configuration, if set.
+pub fn get_relayer_config(env: &Env) -> Option<RelayerConfig> {
+    storage::get_relayer_config(env)
+}
+
+/// Lock `amount` of the stake token for `relayer`.
+///
+/// The relayer becomes active once its total stake reaches `min_stake`.
+///
+/// # Errors
+/// - [`QuickexError::RelayerConfigNotSet`] – staking has not been configured.
+/// - [`QuickexError::InvalidAmount`] – amount ≤ 0.
+pub fn stake(env: &Env, relayer: Address, amount: i128) -> Result<(), QuickexError> {
+    let config = storage::get_relayer_config(env).ok_or(QuickexError::RelayerConfigNotSet)?;
+    if amount <= 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+
+    relayer.require_auth();
+
+    tokens::collect(env, &config.stake_token, &relayer, amount)?;
+    storage::adjust_total_staked(env, amount)?;
+
+    let mut info = storage::get_relayer(env, &relayer).unwrap_or(RelayerInfo {
+        stake: 0,
+        active: false,
+        unlock_at: 0,
+        slashed: 0,
+    });
+    info.stake = info
+        .stake
+        .checked_add(amount)
+        .ok_or(QuickexError::ArithmeticOverflow)?;
+    info.unlock_at = 0;
+    if !info.active && info.stake >= config.min_stake {
+        info.active = true;
+        storage::add_active_relayer(env, &relayer);
+    }
+    storage::set_relayer(env, &relayer, &info);
+
+    events::publish_relayer_staked(env, relayer, amount, info.stake);
+
+    Ok(())
+}
+
+/// Deactivate `relayer` and start the unbonding period.
+///
+/// Calling again while already unbonding is a no-op.
+///
+/// # Errors
+/// - [`QuickexError::RelayerNotRegistered`] – `relayer` has no stake.
+pub fn unstake(env: &Env, relayer: Address) -> Result<(), QuickexError> {
+    relayer.require_auth();
+
+    let mut info = storage::get_relayer(env, &relayer).ok_or(QuickexError::RelayerNotRegistered)?;
+    if info.unlock_at > 0 {
+        return Ok(());
+    }
+
+    let config = storage::get_relayer_config(env).ok_or(QuickexError::RelayerConfigNotSet)?;
+    if info.active {
+        info.active = false;
+        storage::remove_active_relayer(env, &relayer);
+    }
+    // `unlock_at == 0` means bonded, so never store 0 here.
+    info.unlock_at = env
+        .ledger()
+        .timestamp()
+        .saturating_add(config.unbonding_period)
+        .max(1);
+    storage::set_relayer(env, &relayer, &info);
+
+    events::publish_relayer_unstaked(env, relayer, info.unlock_at);
+
+    Ok(())
+}
+
+/// Return the remaining stake to `relayer` after its unbonding period.
+///
+/// # Errors
+/// - [`QuickexError::RelayerNotRegistered`] – `relayer` has no stake.
+/// - [`QuickexError::StakeLocked`] – `unstake` was not called or the unbonding period
+///   has not elapsed.
+pub fn withdraw_stake(env: &Env, relayer: Address) -> Result<i128, QuickexError> {
+    relayer.require_auth();
+
+    let info = storage::get_relayer(env, &relayer).ok_or(QuickexError::RelayerNotRegistered)?;
+    if info.unlock_at == 0 || env.ledger().timestamp() < info.unlock_at {
+        return Err(QuickexError::StakeLocked);
+    }
+
+    let config = storage::get_relayer_config(env).ok_or(QuickexError::RelayerConfigNotSet)?;
+    storage::remove_relayer(env, &relayer);
+    storage::adjust_total_staked(env, -info.stake)?;
+
+    if info.stake > 0 {
+        tokens::pay(env, &config.stake_token, &relayer, info.stake)?;
+    }
+
+    events::publish_relayer_stake_withdrawn(env, relayer, info.stake);
+
+    Ok(info.stake)
+}
+
+/// Slash `amount` of `relayer`'s stake to the admin (**admin only**).
+///
+/// Works on active and unbonding relayers. A relayer whose stake drops below
+/// `min_stake` is deactivated.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::RelayerNotRegistered`] – `relayer` has no stake.
+/// - [`QuickexError::InvalidAmount`] – amount ≤ 0 or exceeds the stake.
+pub fn slash(
+    env: &Env,
+    caller: Address,
+    relayer: Address,
+    amount: i128,
+    reason: Symbol,
+) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+
+    let mut info = storage::get_relayer(env, &relayer).ok_or(QuickexError::RelayerNotRegistered)?;
+    if amount <= 0 || amount > info.stake {
+        return Err(QuickexError::InvalidAmount);
+    }
+
+    let config = storage::get_relayer_config(env).ok_or(QuickexError::RelayerConfigNotSet)?;
+    info.stake -= amount;
+    info.slashed = info.slashed.saturating_add(amount);
+    if info.active && info.stake < config.min_stake {
+        info.active = false;
+        storage::remove_active_relayer(env, &relayer);
+    }
+    storage::set_relayer(env, &relayer, &info);
+    storage::adjust_total_staked(env, -amount)?;
+
+    tokens::pay(env, &config.stake_token, &caller, amount)?;
+
+    events::publish_relayer_slashed(env, relayer, amount, reason, info.stake);
+
+    Ok(())
+}
+
+/// Stake record for `relayer`, if any.
+pub fn get_relayer(env: &Env, relayer: &Address) -> Option<RelayerInfo> {
+    storage::get_relayer(env, relayer)
+}
+
+/// Relayers currently eligible to submit relayed withdrawals, in activation order.
+pub fn get_active_relayers(env: &Env) -> Vec<Address> {
+    storage::get_active_relayers(env)
+}
+
+/// One page of [`get_active_relayers`], starting at `cursor` (0 for the first page).
+///
+/// Each page holds at most [`RELAYER_BUCKET_SIZE`](storage::RELAYER_BUCKET_SIZE)
+/// relayers, except the first page of relayers activated before buckets were
+/// introduced. A page can be empty while `next_cursor` is set.
+pub fn get_active_relayers_page(env: &Env, cursor: u32) -> RelayerPage {
+    let (relayers, next_cursor) = storage::get_active_relayer_page(env, cursor);
+    RelayerPage {
+        relayers,
+        next_cursor,
+    }
+}
+
+/// Require that `relayer` is active.
+///
+/// # Errors
+/// - [`QuickexError::RelayerNotActive`] – relayer is unknown, unbonding, or under-staked.
+pub fn require_active(env: &Env, relayer: &Address) -> Result<(), QuickexError> {
+    match storage::get_relayer(env, relayer) {
+        Some(info) if info.active => Ok(()),
+        _ => Err(QuickexError::RelayerNotActive),
+    }
+}
@@ -0,0 +1,414 @@
+//! Relayer registry and relayer-submitted withdrawal tests.
+
+use crate::{
+    errors::QuickexError,
+    storage::{self, DataKey},
+    test_fixture::{create_token, deploy, deploy_initialized},
+    types::RelayerPage,
+    EscrowStatus, QuickexContractClient,
+};
+use soroban_sdk::{
+    testutils::{Address as _, AuthorizedFunction, Ledger},
+    token, vec, Address, Bytes, Env, IntoVal, Symbol,
+};
+
+const AMOUNT: i128 = 1_000;
+const MIN_STAKE: i128 = 500;
+const UNBONDING: u64 = 7 * 86_400;
+
+struct Setup<'a> {
+    env: Env,
+    client: QuickexContractClient<'a>,
+    admin: Address,
+    token: Address,
+    stake_token: Address,
+    relayer: Address,
+    to: Address,
+    salt: Bytes,
+}
+
+/// Deposit `AMOUNT` into an escrow claimable by a fresh recipient and register one
+/// active relayer staking `MIN_STAKE`.
+fn setup<'a>() -> Setup<'a> {
+    let (env, client, admin, token) = deploy_initialized();
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &AMOUNT);
+
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"relayed_salt");
+    let commitment = client.create_amount_commitment(&to, &AMOUNT, &salt);
+    client.deposit_with_commitment(&depositor, &token, &AMOUNT, &commitment, &0);
+
+    let stake_token = create_token(&env);
+    client.set_relayer_config(&admin, &stake_token, &MIN_STAKE, &UNBONDING);
+    let relayer = staked_relayer(&env, &client, &stake_token, MIN_STAKE);
+
+    Setup {
+        env,
+        client,
+        admin,
+        token,
+        stake_token,
+        relayer,
+        to,
+        salt,
+    }
+}
+
+fn staked_relayer(
+    env: &Env,
+    client: &QuickexContractClient,
+    stake_token: &Address,
+    amount: i128,
+) -> Address {
+    let relayer = Address::generate(env);
+    token::StellarAssetClient::new(env, stake_token).mint(&relayer, &amount);
+    client.stake_relayer(&relayer, &amount);
+    relayer
+}
+
+#[test]
+fn test_withdraw_via_relayer_splits_fee() {
+    let s = setup();
+    let relayer = s.relayer.clone();
+
+    let result = s
+        .client
+        .withdraw_via_relayer(&relayer, &s.token, &s.to, &AMOUNT, &s.salt, &25);
+    assert!(result);
+
+    let token_client = token::Client::new(&s.env, &s.token);
+    assert_eq!(token_client.balance(&s.to), AMOUNT - 25);
+    assert_eq!(token_client.balance(&relayer), 25);
+    assert_eq!(token_client.balance(&s.client.address), 0);
+
+    let commitment = s.client.create_amount_commitment(&s.to, &AMOUNT, &s.salt);
+    assert_eq!(
+        s.client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+#[test]
+fn test_withdraw_via_relayer_requires_auth_over_fee() {
+    let s = setup();
+    let relayer = s.relayer.clone();
+
+    s.client
+        .withdraw_via_relayer(&relayer, &s.token, &s.to, &AMOUNT, &s.salt, &25);
+
+    let auths = s.env.auths();
+    let (signer, invocation) = auths
+        .iter()
+        .find(|(address, _)| *address == s.to)
+        .expect("recipient must authorize");
+    assert_eq!(*signer, s.to);
+    assert_eq!(
+        invocation.function,
+        AuthorizedFunction::Contract((
+            s.client.address.clone(),
+            Symbol::new(&s.env, "withdraw_via_relayer"),
+            (
+                relayer.clone(),
+                s.token.clone(),
+                s.to.clone(),
+                AMOUNT,
+                s.salt.clone(),
+                25_i128
+            )
+                .into_val(&s.env),
+        ))
+    );
+    assert!(auths.iter().any(|(address, _)| *address == relayer));
+}
+
+#[test]
+fn test_withdraw_via_relayer_zero_and_full_fee() {
+    let s = setup();
+    let relayer = s.relayer.clone();
+    s.client
+        .withdraw_via_relayer(&relayer, &s.token, &s.to, &AMOUNT, &s.salt, &AMOUNT);
+
+    let token_client = token::Client::new(&s.env, &s.token);
+    assert_eq!(token_client.balance(&s.to), 0);
+    assert_eq!(token_client.balance(&relayer), AMOUNT);
+
+    let s = setup();
+    let relayer = s.relayer.clone();
+    s.client
+        .withdraw_via_relayer(&relayer, &s.token, &s.to, &AMOUNT, &s.salt, &0);
+
+    let token_client = token::Client::new(&s.env, &s.token);
+    assert_eq!(token_client.balance(&s.to), AMOUNT);
+    assert_eq!(token_client.balance(&relayer), 0);
+}
+
+#[test]
+fn test_withdraw_via_relayer_invalid_fee_fails() {
+    let s = setup();
+    let relayer = s.relayer.clone();
+
+    let too_high = s.client.try_withdraw_via_relayer(
+        &relayer,
+        &s.token,
+        &s.to,
+        &AMOUNT,
+        &s.salt,
+        &(AMOUNT + 1),
+    );
+    assert_eq!(too_high, Err(Ok(QuickexError::InvalidFee)));
+
+    let negative = s
+        .client
+        .try_withdraw_via_relayer(&relayer, &s.token, &s.to, &AMOUNT, &s.salt, &-1);
+    assert_eq!(negative, Err(Ok(QuickexError::InvalidFee)));
+}
+
+#[test]
+fn test_withdraw_via_relayer_double_spend_fails() {
+    let s = setup();
+    let relayer = s.relayer.clone();
+
+    s.client
+        .withdraw_via_relayer(&relayer, &s.token, &s.to, &AMOUNT, &s.salt, &25);
+    let result = s.client.try_withdraw(
+        &s.token,
+        &AMOUNT,
+        &s.client.create_amount_commitment(&s.to, &AMOUNT, &s.salt),
+        &s.to,
+        &s.salt,
+    );
+    assert_eq!(result, Err(Ok(QuickexError::AlreadySpent)));
+}
+
+#[test]
+fn test_withdraw_via_relayer_when_paused_fails() {
+    let s = setup();
+    let relayer = s.relayer.clone();
+    s.client.set_paused(&s.admin, &true);
+
+    let result = s
+        .client
+        .try_withdraw_via_relayer(&relayer, &s.token, &s.to, &AMOUNT, &s.salt, &25);
+    assert_eq!(result, Err(Ok(QuickexError::ContractPaused)));
+}
+
+#[test]
+fn test_withdraw_via_unstaked_relayer_fails() {
+    let s = setup();
+    let outsider = Address::generate(&s.env);
+
+    let result = s
+        .client
+        .try_withdraw_via_relayer(&outsider, &s.token, &s.to, &AMOUNT, &s.salt, &25);
+    assert_eq!(result, Err(Ok(QuickexError::RelayerNotActive)));
+}
+
+#[test]
+fn test_stake_below_minimum_is_inactive() {
+    let s = setup();
+    let relayer = staked_relayer(&s.env, &s.client, &s.stake_token, MIN_STAKE - 1);
+
+    let info = s.client.get_relayer(&relayer).unwrap();
+    assert_eq!(info.stake, MIN_STAKE - 1);
+    assert!(!info.active);
+    assert_eq!(
+        s.client.get_active_relayers(),
+        vec![&s.env, s.relayer.clone()]
+    );
+
+    token::StellarAssetClient::new(&s.env, &s.stake_token).mint(&relayer, &1);
+    s.client.stake_relayer(&relayer, &1);
+    assert!(s.client.get_relayer(&relayer).unwrap().active);
+    assert_eq!(
+        s.client.get_active_relayers(),
+        vec![&s.env, s.relayer.clone(), relayer]
+    );
+}
+
+#[test]
+fn test_active_relayers_are_paged_in_buckets() {
+    let s = setup();
+    let mut relayers = vec![&s.env, s.relayer.clone()];
+    for _ in 0..40 {
+        relayers.push_back(staked_relayer(&s.env, &s.client, &s.stake_token, MIN_STAKE));
+    }
+
+    let first = s.client.get_active_relayers_page(&0);
+    assert_eq!(first.relayers, relayers.slice(0..32));
+    assert_eq!(first.next_cursor, Some(2));
+    let second = s.client.get_active_relayers_page(&2);
+    assert_eq!(second.relayers, relayers.slice(32..41));
+    assert_eq!(second.next_cursor, None);
+
+    // Emptying the first bucket moves the index past it.
+    for relayer in relayers.slice(0..32).iter() {
+        s.client.unstake_relayer(&relayer);
+    }
+    let first = s.client.get_active_relayers_page(&0);
+    assert_eq!(first.relayers, relayers.slice(32..41));
+    assert_eq!(first.next_cursor, None);
+    assert_eq!(s.client.get_active_relayers(), relayers.slice(32..41));
+}
+
+#[test]
+fn test_legacy_active_relayer_list_is_read_and_drained() {
+    let s = setup();
+    let legacy = staked_relayer(&s.env, &s.client, &s.stake_token, MIN_STAKE);
+    // Move `legacy` to the list kept before active relayers were bucketed.
+    s.env.as_contract(&s.client.address, || {
+        storage::remove_active_relayer(&s.env, &legacy);
+        s.env
+            .storage()
+            .persistent()
+            .set(&DataKey::ActiveRelayers, &vec![&s.env, legacy.clone()]);
+    });
+
+    let page = s.client.get_active_relayers_page(&0);
+    assert_eq!(page.relayers, vec![&s.env, legacy.clone()]);
+    assert_eq!(
+        s.client
+            .get_active_relayers_page(&page.next_cursor.unwrap()),
+        RelayerPage {
+            relayers: vec![&s.env, s.relayer.clone()],
+            next_cursor: None,
+        }
+    );
+
+    s.client.unstake_relayer(&legacy);
+    assert_eq!(
+        s.client.get_active_relayers(),
+        vec![&s.env, s.relayer.clone()]
+    );
+    s.env.as_contract(&s.client.address, || {
+        assert!(!s.env.storage().persistent().has(&DataKey::ActiveRelayers));
+    });
+}
+
+#[test]
+fn test_stake_without_config_fails() {
+    let (env, client) = deploy();
+
+    let result = client.try_stake_relayer(&Address::generate(&env), &MIN_STAKE);
+    assert_eq!(result, Err(Ok(QuickexError::RelayerConfigNotSet)));
+}
+
+#[test]
+fn test_set_relayer_config_cannot_change_stake_token() {
+    let s = setup();
+    let other_token = Address::generate(&s.env);
+
+    let result = s
+        .client
+        .try_set_relayer_config(&s.admin, &other_token, &MIN_STAKE, &UNBONDING);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidRelayerConfig)));
+
+    s.client
+        .set_relayer_config(&s.admin, &s.stake_token, &(MIN_STAKE * 2), &0);
+    assert_eq!(
+        s.client.get_relayer_config().unwrap().min_stake,
+        MIN_STAKE * 2
+    );
+}
+
+#[test]
+fn test_unstake_and_withdraw_after_unbonding() {
+    let s = setup();
+    s.client.unstake_relayer(&s.relayer);
+
+    let info = s.client.get_relayer(&s.relayer).unwrap();
+    assert!(!info.active);
+    assert_eq!(info.unlock_at, s.env.ledger().timestamp() + UNBONDING);
+    assert_eq!(s.client.get_active_relayers().len(), 0);
+
+    let early = s.client.try_withdraw_relayer_stake(&s.relayer);
+    assert_eq!(early, Err(Ok(QuickexError::StakeLocked)));
+
+    s.env.ledger().with_mut(|li| li.timestamp += UNBONDING);
+    assert_eq!(s.client.withdraw_relayer_stake(&s.relayer), MIN_STAKE);
+    assert_eq!(
+        token::Client::new(&s.env, &s.stake_token).balance(&s.relayer),
+        MIN_STAKE
+    );
+    assert_eq!(s.client.get_relayer(&s.relayer), None);
+}
+
+#[test]
+fn test_withdraw_stake_while_bonded_fails() {
+    let s = setup();
+
+    let result = s.client.try_withdraw_relayer_stake(&s.relayer);
+    assert_eq!(result, Err(Ok(QuickexError::StakeLocked)));
+}
+
+#[test]
+fn test_restake_cancels_unbonding() {
+    let s = setup();
+    s.client.unstake_relayer(&s.relayer);
+
+    token::StellarAssetClient::new(&s.env, &s.stake_token).mint(&s.relayer, &1);
+    s.client.stake_relayer(&s.relayer, &1);
+
+    let info = s.client.get_relayer(&s.relayer).unwrap();
+    assert!(info.active);
+    assert_eq!(info.unlock_at, 0);
+    assert_eq!(info.stake, MIN_STAKE + 1);
+}
+
+#[test]
+fn test_slash_deactivates_under_staked_relayer() {
+    let s = setup();
+
+    s.client.slash_relayer(
+        &s.admin,
+        &s.relayer,
+        &100,
+        &Symbol::new(&s.env, "censorship"),
+    );
+
+    let info = s.client.get_relayer(&s.relayer).unwrap();
+    assert_eq!(info.stake, MIN_STAKE - 100);
+    assert_eq!(info.slashed, 100);
+    assert!(!info.active);
+    assert_eq!(s.client.get_active_relayers().len(), 0);
+    assert_eq!(
+        token::Client::new(&s.env, &s.stake_token).balance(&s.admin),
+        100
+    );
+}
+
+#[test]
+fn test_slash_during_unbonding() {
+    let s = setup();
+    s.client.unstake_relayer(&s.relayer);
+
+    s.client.slash_relayer(
+        &s.admin,
+        &s.relayer,
+        &MIN_STAKE,
+        &Symbol::new(&s.env, "grief"),
+    );
+
+    s.env.ledger().with_mut(|li| li.timestamp += UNBONDING);
+    assert_eq!(s.client.withdraw_relayer_stake(&s.relayer), 0);
+}
+
+#[test]
+fn test_slash_invalid_amount_and_non_admin_fail() {
+    let s = setup();
+    let reason = Symbol::new(&s.env, "grief");
+
+    let too_much = s
+        .client
+        .try_slash_relayer(&s.admin, &s.relayer, &(MIN_STAKE + 1), &reason);
+    assert_eq!(too_much, Err(Ok(QuickexError::InvalidAmount)));
+
+    let stranger = Address::generate(&s.env);
+    let unauthorized = s
+        .client
+        .try_slash_relayer(&stranger, &s.relayer, &1, &reason);
+    assert_eq!(unauthorized, Err(Ok(QuickexError::Unauthorized)));
+
+    let unknown = s.client.try_slash_relayer(&s.admin, &stranger, &1, &reason);
+    assert_eq!(unknown, Err(Ok(QuickexError::RelayerNotRegistered)));
+}
@@ -0,0 +1,231 @@
+//! Linkable ring signatures over pool notes.
+//!
+//! A lighter-weight alternative to [`crate::shielded`]: a ring note is a
+//! fixed-denomination deposit identified by a one-time public key `P = x·G` (BLS12-381
+//! G1). To withdraw, the owner signs with an LSAG (linkable spontaneous anonymous
+//! group) signature over a ring of `N` registered notes of the same pool, proving it
+//! knows the secret key of *one* of them without revealing which.
+//!
+//! ## Verification
+//!
+//! ```text
+//! Hp(P)   = hash_to_g1(P, RING_DST)
+//! I       = x·Hp(P_π)                          (key image, published)
+//! for i in 0..N:
+//!     L_i = s_i·G + c_i·P_i
+//!     R_i = s_i·Hp(P_i) + c_i·I
+//!     c_{i+1} = SHA256(m || L_i || R_i) mod r
+//! accept iff c_N == c_0
+//! ```
+//!
+//! `m` binds the pool, recipient, and ring (see [`signing_message`]). The key image
+//! depends only on `x`, so each note can be withdrawn once: spent key images are
+//! recorded and a second signature with the same key fails with `AlreadySpent`.
+//!
+//! The anonymity set is the ring, so privacy is limited to `N ≤ MAX_RING_SIZE` notes.
+
+use soroban_sdk::{
+    crypto::bls12_381::{Fr, G1Affine},
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, Vec,
+};
+
+use crate::{
+    bls, compliance,
+    errors::QuickexError,
+    events, pedersen, pool, storage, tokens,
+    types::{RingNote, RingSignature},
+};
+
+/// Smallest accepted ring.
+pub const MIN_RING_SIZE: u32 = 2;
+
+/// Largest accepted ring; bounds verification cost.
+pub const MAX_RING_SIZE: u32 = 16;
+
+/// Domain separation tag for hashing public keys to G1.
+pub const RING_DST: &[u8] = b"QUICKEX-V1-RING-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+
+/// Message hashed to obtain the ring base point `G`.
+const GENERATOR_MSG: &[u8] = b"quickex/ring/G";
+
+/// Prefix of the signed message.
+const MESSAGE_TAG: &[u8] = b"quickex/ring/v1";
+
+/// Ring base point `G`.
+pub fn generator(env: &Env) -> G1Affine {
+    hash_to_point(env, &Bytes::from_slice(env, GENERATOR_MSG))
+}
+
+/// `Hp(P)`: hash a public key to a G1 point.
+pub fn hash_public_key(env: &Env, public_key: &BytesN<96>) -> G1Affine {
+    hash_to_point(env, &public_key.clone().into())
+}
+
+fn hash_to_point(env: &Env, msg: &Bytes) -> G1Affine {
+    env.crypto()
+        .bls12_381()
+        .hash_to_g1(msg, &Bytes::from_slice(env, RING_DST))
+}
+
+/// Storage id of a ring note: `SHA256(P)`.
+pub fn note_id(env: &Env, public_key: &BytesN<96>) -> BytesN<32> {
+    env.crypto().sha256(&public_key.clone().into()).into()
+}
+
+/// The message `m` every ring member's challenge is bound to.
+///
+/// `SHA256("quickex/ring/v1" || XDR(token) || BE(denomination) || XDR(recipient) || P_0 || … || P_{N-1})`
+pub fn signing_message(
+    env: &Env,
+    token: &Address,
+    denomination: i128,
+    recipient: &Address,
+    ring: &Vec<BytesN<96>>,
+) -> BytesN<32> {
+    let mut payload = Bytes::from_slice(env, MESSAGE_TAG);
+    payload.append(&token.clone().to_xdr(env));
+    payload.append(&Bytes::from_array(env, &denomination.to_be_bytes()));
+    payload.append(&recipient.clone().to_xdr(env));
+    for member in ring.iter() {
+        payload.append(&member.into());
+    }
+    env.crypto().sha256(&payload).into()
+}
+
+/// `SHA256(m || L || R) mod r`.
+pub fn challenge(env: &Env, message: &BytesN<32>, l: &G1Affine, r: &G1Affine) -> Fr {
+    let mut payload = Bytes::from_array(env, &message.to_array());
+    payload.append(&l.to_bytes().into());
+    payload.append(&r.to_bytes().into());
+    bls::fr_from_bytes(env, &env.crypto().sha256(&payload).into())
+}
+
+/// Deposit one `denomination` of `token` as a ring note owned by `public_key`.
+///
+/// # Errors
+/// - [`QuickexError::InvalidDenomination`] – `denomination` is not registered for `token`.
+/// - [`QuickexError::InvalidPoint`] – `public_key` is the identity or not a subgroup point.
+/// - [`QuickexError::CommitmentAlreadyExists`] – the key is already registered.
+pub fn deposit_ring_note(
+    env: &Env,
+    from: Address,
+    token: Address,
+    denomination: i128,
+    public_key: BytesN<96>,
+) -> Result<(), QuickexError> {
+    tokens::require_deposit_allowed(env, &token, denomination)?;
+    compliance::require_not_blocked(env, &from)?;
+    if !pool::is_denomination(env, &token, denomination) {
+        return Err(QuickexError::InvalidDenomination);
+    }
+    pedersen::parse_point(env, public_key.clone())?;
+
+    from.require_auth();
+
+    let id = note_id(env, &public_key);
+    if storage::get_ring_note(env, &id).is_some() {
+        return Err(QuickexError::CommitmentAlreadyExists);
+    }
+
+    tokens::collect(env, &token, &from, denomination)?;
+    storage::adjust_total_locked(env, &token, denomination)?;
+
+    storage::set_ring_note(
+        env,
+        &id,
+        &RingNote {
+            token: token.clone(),
+            denomination,
+            created_at: env.ledger().timestamp(),
+        },
+    );
+
+    events::publish_ring_note_deposited(env, token, denomination, public_key);
+
+    Ok(())
+}
+
+/// Withdraw one ring note of the (`token`, `denomination`) pool to `recipient`.
+///
+/// Anyone may submit: the signature binds `recipient`.
+///
+/// # Errors
+/// - [`QuickexError::InvalidRing`] – ring size out of bounds, response count mismatch,
+///   or a member is not a ring note of this pool.
+/// - [`QuickexError::InvalidPoint`] – key image is the identity or not a subgroup point.
+/// - [`QuickexError::AlreadySpent`] – the key image was already used.
+/// - [`QuickexError::InvalidSignature`] – the ring signature does not verify.
+pub fn withdraw_with_ring_signature(
+    env: &Env,
+    token: Address,
+    denomination: i128,
+    recipient: Address,
+    ring: Vec<BytesN<96>>,
+    signature: RingSignature,
+) -> Result<(), QuickexError> {
+    tokens::require_withdraw_allowed(env, &token)?;
+    tokens::record_withdrawal_volume(env, &token, denomination)?;
+    compliance::require_not_blocked(env, &recipient)?;
+    let size = ring.len();
+    if !(MIN_RING_SIZE..=MAX_RING_SIZE).contains(&size) || signature.responses.len() != size {
+        return Err(QuickexError::InvalidRing);
+    }
+    for member in ring.iter() {
+        match storage::get_ring_note(env, &note_id(env, &member)) {
+            Some(note) if note.token == token && note.denomination == denomination => {}
+            _ => return Err(QuickexError::InvalidRing),
+        }
+    }
+
+    let key_image = pedersen::parse_point(env, signature.key_image.clone())?;
+    let key_image_id: BytesN<32> = env
+        .crypto()
+        .sha256(&signature.key_image.clone().into())
+        .into();
+    if storage::is_key_image_spent(env, &key_image_id) {
+        return Err(QuickexError::AlreadySpent);
+    }
+
+    let message = signing_message(env, &token, denomination, &recipient, &ring);
+    if !verify(env, &message, &ring, &key_image, &signature) {
+        return Err(QuickexError::InvalidSignature);
+    }
+
+    storage::mark_key_image(env, &key_image_id);
+    events::publish_nullifier_spent(env, key_image_id);
+    storage::adjust_total_locked(env, &token, -denomination)?;
+
+    tokens::pay(env, &token, &recipient, denomination)?;
+
+    events::publish_ring_withdrawn(env, signature.key_image, recipient, token, denomination);
+
+    Ok(())
+}
+
+/// Check an LSAG signature over `ring` for `message`.
+fn verify(
+    env: &Env,
+    message: &BytesN<32>,
+    ring: &Vec<BytesN<96>>,
+    key_image: &G1Affine,
+    signature: &RingSignature,
+) -> bool {
+    let bls12 = env.crypto().bls12_381();
+    let g = generator(env);
+    let c0 = bls::fr_from_bytes(env, &signature.challenge);
+
+    let mut c = c0.clone();
+    for (member, response) in ring.iter().zip(signature.responses.iter()) {
+        let s = bls::fr_from_bytes(env, &response);
+        let p = G1Affine::from_bytes(member.clone());
+        let l = bls12.g1_add(&bls12.g1_mul(&g, &s), &bls12.g1_mul(&p, &c));
+        let r = bls12.g1_add(
+            &bls12.g1_mul(&hash_public_key(env, &member), &s),
+            &bls12.g1_mul(key_image, &c),
+        );
+        c = challenge(env, message, &l, &r);
+    }
+
+    c.to_u256() == c0.to_u256()
+}
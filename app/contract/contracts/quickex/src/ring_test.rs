@@ -0,0 +1,255 @@
+//! Ring-signature withdrawal tests.
+//!
+//! Signatures are produced by [`sign`], a straightforward LSAG signer mirroring the
+//! verifier in [`crate::ring`].
+
+use crate::{
+    bls, errors::QuickexError, ring, test_fixture::deploy_pool, types::RingSignature,
+    QuickexContractClient,
+};
+use soroban_sdk::{
+    crypto::bls12_381::{Fr, G1Affine},
+    testutils::Address as _,
+    token, vec, Address, BytesN, Env, Vec,
+};
+
+const DENOMINATION: i128 = 100;
+
+fn fr(env: &Env, v: u128) -> Fr {
+    bls::fr_from_u128(env, v)
+}
+
+fn fr_bytes(fr: &Fr) -> BytesN<32> {
+    fr.to_u256().to_be_bytes().try_into().unwrap()
+}
+
+fn public_key(env: &Env, secret: u128) -> BytesN<96> {
+    env.crypto()
+        .bls12_381()
+        .g1_mul(&ring::generator(env), &fr(env, secret))
+        .to_bytes()
+}
+
+/// LSAG-sign for `ring[index]` whose secret key is `secret`. `nonce` seeds the
+/// commitment and the decoy responses.
+fn sign(
+    env: &Env,
+    token: &Address,
+    recipient: &Address,
+    ring: &Vec<BytesN<96>>,
+    index: u32,
+    secret: u128,
+    nonce: u128,
+) -> RingSignature {
+    let bls12 = env.crypto().bls12_381();
+    let g = ring::generator(env);
+    let n = ring.len();
+    let message = ring::signing_message(env, token, DENOMINATION, recipient, ring);
+
+    let signer_hp = ring::hash_public_key(env, &ring.get_unchecked(index));
+    let key_image = bls12.g1_mul(&signer_hp, &fr(env, secret));
+
+    let alpha = fr(env, nonce);
+    let mut challenges: [Option<Fr>; 16] = Default::default();
+    let mut responses: [Option<Fr>; 16] = Default::default();
+
+    let mut i = (index + 1) % n;
+    challenges[i as usize] = Some(ring::challenge(
+        env,
+        &message,
+        &bls12.g1_mul(&g, &alpha),
+        &bls12.g1_mul(&signer_hp, &alpha),
+    ));
+    while i != index {
+        let s = fr(env, nonce + 1 + i as u128);
+        let c = challenges[i as usize].clone().unwrap();
+        let member = ring.get_unchecked(i);
+        let p = G1Affine::from_bytes(member.clone());
+        let l = bls12.g1_add(&bls12.g1_mul(&g, &s), &bls12.g1_mul(&p, &c));
+        let r = bls12.g1_add(
+            &bls12.g1_mul(&ring::hash_public_key(env, &member), &s),
+            &bls12.g1_mul(&key_image, &c),
+        );
+        responses[i as usize] = Some(s);
+        i = (i + 1) % n;
+        challenges[i as usize] = Some(ring::challenge(env, &message, &l, &r));
+    }
+    let c_signer = challenges[index as usize].clone().unwrap();
+    responses[index as usize] =
+        Some(bls12.fr_sub(&alpha, &bls12.fr_mul(&c_signer, &fr(env, secret))));
+
+    let mut response_bytes = Vec::new(env);
+    for response in responses.iter().take(n as usize) {
+        response_bytes.push_back(fr_bytes(response.as_ref().unwrap()));
+    }
+    RingSignature {
+        key_image: key_image.to_bytes(),
+        challenge: fr_bytes(challenges[0].as_ref().unwrap()),
+        responses: response_bytes,
+    }
+}
+
+/// Deposit ring notes for each secret and return their public keys.
+fn deposit_ring(
+    env: &Env,
+    client: &QuickexContractClient,
+    token: &Address,
+    secrets: &[u128],
+) -> Vec<BytesN<96>> {
+    let mut ring = Vec::new(env);
+    for secret in secrets {
+        let depositor = Address::generate(env);
+        token::StellarAssetClient::new(env, token).mint(&depositor, &DENOMINATION);
+        let pk = public_key(env, *secret);
+        client.deposit_ring_note(&depositor, token, &DENOMINATION, &pk);
+        ring.push_back(pk);
+    }
+    ring
+}
+
+#[test]
+fn test_ring_withdrawal_success_and_double_spend() {
+    let (env, client, _admin, token) = deploy_pool(DENOMINATION);
+    let ring = deposit_ring(&env, &client, &token, &[1_001, 1_002, 1_003]);
+    let recipient = Address::generate(&env);
+
+    let signature = sign(&env, &token, &recipient, &ring, 1, 1_002, 77);
+    client.withdraw_with_ring_signature(&token, &DENOMINATION, &recipient, &ring, &signature);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&recipient), DENOMINATION);
+    assert_eq!(token_client.balance(&client.address), 2 * DENOMINATION);
+
+    // Same key, different ring and nonce: the key image links the two signatures.
+    let other_ring = vec![&env, ring.get_unchecked(1), ring.get_unchecked(2)];
+    let again = sign(&env, &token, &recipient, &other_ring, 0, 1_002, 99);
+    let result = client.try_withdraw_with_ring_signature(
+        &token,
+        &DENOMINATION,
+        &recipient,
+        &other_ring,
+        &again,
+    );
+    assert_eq!(result, Err(Ok(QuickexError::AlreadySpent)));
+}
+
+#[test]
+fn test_ring_withdrawal_wrong_recipient_fails() {
+    let (env, client, _admin, token) = deploy_pool(DENOMINATION);
+    let ring = deposit_ring(&env, &client, &token, &[1_001, 1_002]);
+    let recipient = Address::generate(&env);
+
+    let signature = sign(&env, &token, &recipient, &ring, 0, 1_001, 77);
+    let thief = Address::generate(&env);
+    let result =
+        client.try_withdraw_with_ring_signature(&token, &DENOMINATION, &thief, &ring, &signature);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidSignature)));
+}
+
+#[test]
+fn test_ring_withdrawal_without_member_secret_fails() {
+    let (env, client, _admin, token) = deploy_pool(DENOMINATION);
+    let ring = deposit_ring(&env, &client, &token, &[1_001, 1_002]);
+    let recipient = Address::generate(&env);
+
+    let forged = sign(&env, &token, &recipient, &ring, 0, 4_242, 77);
+    let result =
+        client.try_withdraw_with_ring_signature(&token, &DENOMINATION, &recipient, &ring, &forged);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidSignature)));
+}
+
+#[test]
+fn test_ring_with_unregistered_member_fails() {
+    let (env, client, _admin, token) = deploy_pool(DENOMINATION);
+    let mut ring = deposit_ring(&env, &client, &token, &[1_001]);
+    ring.push_back(public_key(&env, 5_555));
+    let recipient = Address::generate(&env);
+
+    let signature = sign(&env, &token, &recipient, &ring, 0, 1_001, 77);
+    let result = client.try_withdraw_with_ring_signature(
+        &token,
+        &DENOMINATION,
+        &recipient,
+        &ring,
+        &signature,
+    );
+    assert_eq!(result, Err(Ok(QuickexError::InvalidRing)));
+}
+
+#[test]
+fn test_ring_with_member_of_other_pool_fails() {
+    let (env, client, admin, token) = deploy_pool(DENOMINATION);
+    client.set_denomination(&admin, &token, &(DENOMINATION * 10), &true);
+    let ring = deposit_ring(&env, &client, &token, &[1_001]);
+
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &(DENOMINATION * 10));
+    let other_pk = public_key(&env, 1_002);
+    client.deposit_ring_note(&depositor, &token, &(DENOMINATION * 10), &other_pk);
+
+    let mixed = vec![&env, ring.get_unchecked(0), other_pk];
+    let recipient = Address::generate(&env);
+    let signature = sign(&env, &token, &recipient, &mixed, 0, 1_001, 77);
+    let result = client.try_withdraw_with_ring_signature(
+        &token,
+        &DENOMINATION,
+        &recipient,
+        &mixed,
+        &signature,
+    );
+    assert_eq!(result, Err(Ok(QuickexError::InvalidRing)));
+}
+
+#[test]
+fn test_ring_size_and_response_count_checked() {
+    let (env, client, _admin, token) = deploy_pool(DENOMINATION);
+    let ring = deposit_ring(&env, &client, &token, &[1_001, 1_002]);
+    let recipient = Address::generate(&env);
+
+    let single = vec![&env, ring.get_unchecked(0)];
+    let signature = sign(&env, &token, &recipient, &single, 0, 1_001, 77);
+    let result = client.try_withdraw_with_ring_signature(
+        &token,
+        &DENOMINATION,
+        &recipient,
+        &single,
+        &signature,
+    );
+    assert_eq!(result, Err(Ok(QuickexError::InvalidRing)));
+
+    let mut signature = sign(&env, &token, &recipient, &ring, 0, 1_001, 77);
+    signature.responses.pop_back();
+    let result = client.try_withdraw_with_ring_signature(
+        &token,
+        &DENOMINATION,
+        &recipient,
+        &ring,
+        &signature,
+    );
+    assert_eq!(result, Err(Ok(QuickexError::InvalidRing)));
+}
+
+#[test]
+fn test_deposit_ring_note_rejects_duplicate_and_identity_keys() {
+    let (env, client, _admin, token) = deploy_pool(DENOMINATION);
+    let ring = deposit_ring(&env, &client, &token, &[1_001]);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &DENOMINATION);
+
+    let duplicate =
+        client.try_deposit_ring_note(&depositor, &token, &DENOMINATION, &ring.get_unchecked(0));
+    assert_eq!(duplicate, Err(Ok(QuickexError::CommitmentAlreadyExists)));
+
+    let identity = BytesN::from_array(&env, &bls::G1_IDENTITY);
+    let result = client.try_deposit_ring_note(&depositor, &token, &DENOMINATION, &identity);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidPoint)));
+}
+
+#[test]
+fn test_deposit_ring_note_unregistered_denomination_fails() {
+    let (env, client, _admin, token) = deploy_pool(DENOMINATION);
+    let depositor = Address::generate(&env);
+
+    let result = client.try_deposit_ring_note(&depositor, &token, &7, &public_key(&env, 1));
+    assert_eq!(result, Err(Ok(QuickexError::InvalidDenomination)));
+}
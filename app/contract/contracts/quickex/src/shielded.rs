@@ -0,0 +1,144 @@
+//! Shielded pool notes withdrawn with zero-knowledge proofs.
+//!
+//! A shielded note is a leaf `SHA256(nullifier || secret)` (computed off-chain) in
+//! the Merkle tree of a fixed-denomination pool (see [`crate::merkle`]). Depositing
+//! reveals only the leaf. Withdrawing reveals neither the leaf nor the depositor:
+//! [`withdraw_with_proof`] checks a Groth16 proof (see [`crate::groth16`]) that the
+//! caller knows the opening of *some* leaf under a recent root, plus the note's
+//! nullifier hash, which is recorded to prevent a second withdrawal.
+//!
+//! Shielded notes are kept separate from the commitment-based notes of
+//! [`crate::pool::deposit_to_pool`]; a note can only be spent through the path it was
+//! deposited on.
+
+use soroban_sdk::{vec, xdr::ToXdr, Address, BytesN, Env, Vec};
+
+use crate::{
+    bls, compliance,
+    errors::QuickexError,
+    events, groth16, merkle, pool, storage, tokens,
+    types::{Groth16Proof, WithdrawPublicInputs},
+    verifier,
+};
+
+/// Deposit one `denomination` of `token` as a shielded note with leaf `leaf`.
+///
+/// Returns the leaf index, which the depositor needs to build the Merkle path.
+///
+/// # Errors
+/// - [`QuickexError::InvalidDenomination`] – `denomination` is not registered for `token`.
+/// - [`QuickexError::MerkleTreeFull`] – the pool tree is full.
+pub fn deposit_shielded(
+    env: &Env,
+    from: Address,
+    token: Address,
+    denomination: i128,
+    leaf: BytesN<32>,
+) -> Result<u32, QuickexError> {
+    tokens::require_deposit_allowed(env, &token, denomination)?;
+    compliance::require_not_blocked(env, &from)?;
+    if !pool::is_denomination(env, &token, denomination) {
+        return Err(QuickexError::InvalidDenomination);
+    }
+
+    from.require_auth();
+
+    let mut tree = storage::get_pool_tree(env, &token, denomination)
+        .unwrap_or_else(|| merkle::empty_tree(env));
+    let leaf_index = merkle::insert(env, &mut tree, leaf.clone())?;
+    storage::set_pool_tree(env, &token, denomination, &tree);
+
+    tokens::collect(env, &token, &from, denomination)?;
+    storage::adjust_total_locked(env, &token, denomination)?;
+
+    events::publish_shielded_deposited(env, token, denomination, leaf, leaf_index, tree.root);
+
+    Ok(leaf_index)
+}
+
+/// Withdraw one shielded note to `public_inputs.recipient` by proving membership.
+///
+/// The recipient does not need to authorize: the proof binds the recipient, so a
+/// relayer can submit the transaction without learning anything it could redirect.
+///
+/// # Errors
+/// - [`QuickexError::InvalidRoot`] – `root` is not a recent root of the pool.
+/// - [`QuickexError::AlreadySpent`] – the nullifier hash was already used.
+/// - [`QuickexError::VerifyingKeyNotSet`] – no verifying key registered (see [`crate::verifier`]).
+/// - [`QuickexError::InvalidProof`] – the proof does not verify, or `nullifier_hash`
+///   is not a canonical scalar.
+pub fn withdraw_with_proof(
+    env: &Env,
+    proof: Groth16Proof,
+    public_inputs: WithdrawPublicInputs,
+) -> Result<(), QuickexError> {
+    let WithdrawPublicInputs {
+        token,
+        denomination,
+        root,
+        nullifier_hash,
+        recipient,
+    } = public_inputs;
+    tokens::require_withdraw_allowed(env, &token)?;
+    tokens::record_withdrawal_volume(env, &token, denomination)?;
+    compliance::require_not_blocked(env, &recipient)?;
+
+    let tree =
+        storage::get_pool_tree(env, &token, denomination).ok_or(QuickexError::InvalidRoot)?;
+    if !merkle::is_known_root(&tree, &root) {
+        return Err(QuickexError::InvalidRoot);
+    }
+
+    if storage::is_nullifier_spent(env, &nullifier_hash) {
+        return Err(QuickexError::AlreadySpent);
+    }
+
+    let vk = verifier::current_verifying_key(env)?;
+
+    let nullifier_scalar =
+        bls::fr_from_canonical_bytes(env, &nullifier_hash).ok_or(QuickexError::InvalidProof)?;
+    let recipient_hash: BytesN<32> = env.crypto().sha256(&recipient.clone().to_xdr(env)).into();
+    let scalars = vec![
+        env,
+        bls::fr_from_bytes(env, &root),
+        nullifier_scalar,
+        bls::fr_from_bytes(env, &recipient_hash),
+    ];
+
+    if !groth16::verify(env, &vk, &proof, &scalars)? {
+        return Err(QuickexError::InvalidProof);
+    }
+
+    storage::mark_nullifier(env, &nullifier_hash);
+    events::publish_nullifier_spent(env, nullifier_hash.clone());
+    storage::adjust_total_locked(env, &token, -denomination)?;
+
+    tokens::pay(env, &token, &recipient, denomination)?;
+
+    events::publish_shielded_withdrawn(env, nullifier_hash, recipient, token, denomination);
+
+    Ok(())
+}
+
+/// Current Merkle root of the (`token`, `denomination`) shielded pool.
+///
+/// Returns the empty-tree root if nothing has been deposited yet.
+pub fn get_pool_root(env: &Env, token: &Address, denomination: i128) -> BytesN<32> {
+    storage::get_pool_tree(env, token, denomination)
+        .unwrap_or_else(|| merkle::empty_tree(env))
+        .root
+}
+
+/// Roots a withdrawal proof may currently target, oldest first.
+///
+/// Holds the last [`merkle::ROOT_HISTORY_SIZE`] roots; the last entry is the current root.
+pub fn get_known_roots(env: &Env, token: &Address, denomination: i128) -> Vec<BytesN<32>> {
+    storage::get_pool_tree(env, token, denomination)
+        .unwrap_or_else(|| merkle::empty_tree(env))
+        .recent_roots
+}
+
+/// Number of shielded notes ever deposited into the pool.
+pub fn get_pool_size(env: &Env, token: &Address, denomination: i128) -> u32 {
+    storage::get_pool_tree(env, token, denomination).map_or(0, |tree| tree.next_index)
+}
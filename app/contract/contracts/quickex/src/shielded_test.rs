@@ -0,0 +1,442 @@
+//! Shielded pool and Groth16 withdrawal tests.
+//!
+//! Proofs are forged with a known trapdoor: with `α = a·G1`, `β = b·G2`, `γ = G2`,
+//! `δ = d·G2`, `IC_i = k_i·G1`, `B = G2` and `C = c·G1`, the proof
+//! `A = (a·b + L + c·d)·G1` satisfies the verification equation. This exercises the
+//! on-chain verifier without a real circuit.
+
+use crate::{
+    bls,
+    errors::QuickexError,
+    merkle,
+    test_fixture::deploy_pool,
+    types::{Groth16Proof, MerklePath, VerifyingKey, WithdrawPublicInputs},
+    QuickexContractClient,
+};
+use soroban_sdk::{
+    crypto::bls12_381::{Fr, G1Affine, G2Affine},
+    testutils::Address as _,
+    token,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, Vec,
+};
+
+pub(crate) const DENOMINATION: i128 = 100;
+
+struct Trapdoor {
+    a: u128,
+    b: u128,
+    c: u128,
+    d: u128,
+    k: [u128; 4],
+}
+
+const TRAPDOOR: Trapdoor = Trapdoor {
+    a: 11,
+    b: 13,
+    c: 17,
+    d: 19,
+    k: [23, 29, 31, 37],
+};
+
+fn g1(env: &Env) -> G1Affine {
+    env.crypto().bls12_381().hash_to_g1(
+        &Bytes::from_slice(env, b"test/g1"),
+        &Bytes::from_slice(env, b"QUICKEX-TEST-G1"),
+    )
+}
+
+fn g2(env: &Env) -> G2Affine {
+    env.crypto().bls12_381().hash_to_g2(
+        &Bytes::from_slice(env, b"test/g2"),
+        &Bytes::from_slice(env, b"QUICKEX-TEST-G2"),
+    )
+}
+
+pub(crate) fn g1_mul(env: &Env, k: u128) -> BytesN<96> {
+    env.crypto()
+        .bls12_381()
+        .g1_mul(&g1(env), &bls::fr_from_u128(env, k))
+        .to_bytes()
+}
+
+pub(crate) fn g2_mul(env: &Env, k: u128) -> BytesN<192> {
+    env.crypto()
+        .bls12_381()
+        .g2_mul(&g2(env), &bls::fr_from_u128(env, k))
+        .to_bytes()
+}
+
+pub(crate) fn test_vk(env: &Env) -> VerifyingKey {
+    let mut ic = Vec::new(env);
+    for k in TRAPDOOR.k {
+        ic.push_back(g1_mul(env, k));
+    }
+    VerifyingKey {
+        alpha: g1_mul(env, TRAPDOOR.a),
+        beta: g2_mul(env, TRAPDOOR.b),
+        gamma: g2_mul(env, 1),
+        delta: g2_mul(env, TRAPDOOR.d),
+        ic,
+    }
+}
+
+/// Scalars the contract derives from `inputs`, in circuit order.
+fn input_scalars(env: &Env, inputs: &WithdrawPublicInputs) -> [Fr; 3] {
+    let recipient_hash: BytesN<32> = env
+        .crypto()
+        .sha256(&inputs.recipient.clone().to_xdr(env))
+        .into();
+    [
+        bls::fr_from_bytes(env, &inputs.root),
+        bls::fr_from_bytes(env, &inputs.nullifier_hash),
+        bls::fr_from_bytes(env, &recipient_hash),
+    ]
+}
+
+pub(crate) fn forge_proof(env: &Env, inputs: &WithdrawPublicInputs) -> Groth16Proof {
+    let bls12 = env.crypto().bls12_381();
+    let fr = |v: u128| bls::fr_from_u128(env, v);
+
+    let mut l = fr(TRAPDOOR.k[0]);
+    for (x, k) in input_scalars(env, inputs).iter().zip(&TRAPDOOR.k[1..]) {
+        l = bls12.fr_add(&l, &bls12.fr_mul(x, &fr(*k)));
+    }
+    let ab = bls12.fr_mul(&fr(TRAPDOOR.a), &fr(TRAPDOOR.b));
+    let cd = bls12.fr_mul(&fr(TRAPDOOR.c), &fr(TRAPDOOR.d));
+    let s = bls12.fr_add(&bls12.fr_add(&ab, &l), &cd);
+
+    Groth16Proof {
+        a: bls12.g1_mul(&g1(env), &s).to_bytes(),
+        b: g2_mul(env, 1),
+        c: g1_mul(env, TRAPDOOR.c),
+    }
+}
+
+pub(crate) fn deposit_note(
+    env: &Env,
+    client: &QuickexContractClient,
+    token: &Address,
+    seed: u8,
+) -> BytesN<32> {
+    let depositor = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&depositor, &DENOMINATION);
+    let leaf = BytesN::from_array(env, &[seed; 32]);
+    client.deposit_shielded(&depositor, token, &DENOMINATION, &leaf);
+    leaf
+}
+
+pub(crate) fn inputs_for(
+    env: &Env,
+    client: &QuickexContractClient,
+    token: &Address,
+    nullifier_seed: u8,
+    recipient: &Address,
+) -> WithdrawPublicInputs {
+    let mut nullifier = [nullifier_seed; 32];
+    nullifier[0] = 0;
+    WithdrawPublicInputs {
+        token: token.clone(),
+        denomination: DENOMINATION,
+        root: client.get_pool_root(token, &DENOMINATION),
+        nullifier_hash: BytesN::from_array(env, &nullifier),
+        recipient: recipient.clone(),
+    }
+}
+
+#[test]
+fn test_merkle_root_matches_manual_computation() {
+    let env = Env::default();
+    let zeros = merkle::zero_hashes(&env);
+    let mut tree = merkle::empty_tree(&env);
+    assert_eq!(tree.root, zeros.get_unchecked(merkle::TREE_DEPTH));
+
+    let leaf0 = BytesN::from_array(&env, &[1; 32]);
+    let leaf1 = BytesN::from_array(&env, &[2; 32]);
+    assert_eq!(merkle::insert(&env, &mut tree, leaf0.clone()), Ok(0));
+    assert_eq!(merkle::insert(&env, &mut tree, leaf1.clone()), Ok(1));
+
+    let mut expected = merkle::hash_pair(&env, &leaf0, &leaf1);
+    for level in 1..merkle::TREE_DEPTH {
+        expected = merkle::hash_pair(&env, &expected, &zeros.get_unchecked(level));
+    }
+    assert_eq!(tree.root, expected);
+    assert_eq!(tree.next_index, 2);
+    assert_eq!(tree.recent_roots.len(), 3);
+}
+
+#[test]
+fn test_merkle_root_history_is_bounded() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let mut tree = merkle::empty_tree(&env);
+    let first_root = tree.root.clone();
+
+    for i in 0..merkle::ROOT_HISTORY_SIZE {
+        merkle::insert(&env, &mut tree, BytesN::from_array(&env, &[i as u8; 32])).unwrap();
+    }
+
+    assert_eq!(tree.recent_roots.len(), merkle::ROOT_HISTORY_SIZE);
+    assert!(!merkle::is_known_root(&tree, &first_root));
+    assert!(merkle::is_known_root(&tree, &tree.root));
+}
+
+#[test]
+fn test_verify_merkle_proof() {
+    let (env, client, _, token) = deploy_pool(DENOMINATION);
+    let leaf0 = deposit_note(&env, &client, &token, 1);
+    let leaf1 = deposit_note(&env, &client, &token, 2);
+    let leaf2 = deposit_note(&env, &client, &token, 3);
+    let root = client.get_pool_root(&token, &DENOMINATION);
+
+    // Leaf 2: its right neighbour is empty, its parent's left sibling is H(leaf0, leaf1).
+    let zeros = merkle::zero_hashes(&env);
+    let mut siblings = Vec::new(&env);
+    siblings.push_back(zeros.get_unchecked(0));
+    siblings.push_back(merkle::hash_pair(&env, &leaf0, &leaf1));
+    for level in 2..merkle::TREE_DEPTH {
+        siblings.push_back(zeros.get_unchecked(level));
+    }
+    let path = MerklePath { index: 2, siblings };
+
+    assert!(client.verify_merkle_proof(&leaf2, &path, &root));
+    assert!(!client.verify_merkle_proof(&leaf1, &path, &root));
+
+    let mut wrong_index = path.clone();
+    wrong_index.index = 3;
+    assert!(!client.verify_merkle_proof(&leaf2, &wrong_index, &root));
+
+    let mut short = path.clone();
+    short.siblings.pop_back();
+    assert!(!client.verify_merkle_proof(&leaf2, &short, &root));
+
+    let mut out_of_range = path;
+    out_of_range.index = 1 << merkle::TREE_DEPTH;
+    assert!(!client.verify_merkle_proof(&leaf2, &out_of_range, &root));
+}
+
+#[test]
+fn test_g1_neg_cancels_point() {
+    let env = Env::default();
+    let p = g1(&env);
+    let sum = env.crypto().bls12_381().g1_add(&p, &bls::g1_neg(&env, &p));
+    assert_eq!(sum.to_bytes().to_array(), bls::G1_IDENTITY);
+}
+
+#[test]
+fn test_curve_checks_reject_malformed_points() {
+    let env = Env::default();
+    let p = g1(&env).to_bytes().to_array();
+    assert!(bls::g1_is_on_curve(&p));
+    assert!(bls::g1_is_on_curve(&bls::G1_IDENTITY));
+
+    let mut off_curve = p;
+    off_curve[95] ^= 1;
+    assert!(!bls::g1_is_on_curve(&off_curve));
+    let mut flagged = p;
+    flagged[0] |= 0x80;
+    assert!(!bls::g1_is_on_curve(&flagged));
+    assert!(!bls::g1_is_on_curve(&[0xff; 96]));
+    assert!(bls::g1_checked(&env, &BytesN::from_array(&env, &off_curve)).is_none());
+
+    let q = g2(&env).to_bytes().to_array();
+    assert!(bls::g2_is_on_curve(&q));
+    let mut off_curve = q;
+    off_curve[191] ^= 1;
+    assert!(!bls::g2_is_on_curve(&off_curve));
+    assert!(bls::g2_checked(&env, &BytesN::from_array(&env, &off_curve)).is_none());
+}
+
+#[test]
+fn test_fr_from_canonical_bytes_rejects_modulus() {
+    let env = Env::default();
+    let modulus = BytesN::from_array(&env, &bls::FR_MODULUS_BE);
+    assert!(bls::fr_from_canonical_bytes(&env, &modulus).is_none());
+    assert!(bls::fr_from_canonical_bytes(&env, &BytesN::from_array(&env, &[0; 32])).is_some());
+}
+
+#[test]
+fn test_deposit_shielded_updates_tree() {
+    let (env, client, _admin, token) = deploy_pool(DENOMINATION);
+    let empty_root = client.get_pool_root(&token, &DENOMINATION);
+
+    deposit_note(&env, &client, &token, 1);
+    deposit_note(&env, &client, &token, 2);
+
+    assert_eq!(client.get_pool_size(&token, &DENOMINATION), 2);
+    assert_ne!(client.get_pool_root(&token, &DENOMINATION), empty_root);
+    assert_eq!(
+        token::Client::new(&env, &token).balance(&client.address),
+        2 * DENOMINATION
+    );
+}
+
+#[test]
+fn test_deposit_shielded_unregistered_denomination_fails() {
+    let (env, client, _admin, token) = deploy_pool(DENOMINATION);
+    let depositor = Address::generate(&env);
+    let leaf = BytesN::from_array(&env, &[1; 32]);
+
+    let result = client.try_deposit_shielded(&depositor, &token, &50, &leaf);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidDenomination)));
+}
+
+#[test]
+fn test_withdraw_with_proof_success_and_double_spend() {
+    let (env, client, admin, token) = deploy_pool(DENOMINATION);
+    client.set_verifying_key(&admin, &test_vk(&env));
+    deposit_note(&env, &client, &token, 1);
+
+    let recipient = Address::generate(&env);
+    let inputs = inputs_for(&env, &client, &token, 7, &recipient);
+    let proof = forge_proof(&env, &inputs);
+
+    client.withdraw_with_proof(&proof, &inputs);
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&recipient), DENOMINATION);
+    assert_eq!(token_client.balance(&client.address), 0);
+
+    let result = client.try_withdraw_with_proof(&proof, &inputs);
+    assert_eq!(result, Err(Ok(QuickexError::AlreadySpent)));
+}
+
+#[test]
+fn test_withdraw_with_proof_rejects_off_curve_points() {
+    let (env, client, admin, token) = deploy_pool(DENOMINATION);
+    client.set_verifying_key(&admin, &test_vk(&env));
+    deposit_note(&env, &client, &token, 1);
+
+    let inputs = inputs_for(&env, &client, &token, 7, &Address::generate(&env));
+    let mut proof = forge_proof(&env, &inputs);
+    proof.a = BytesN::from_array(&env, &[1; 96]);
+
+    let result = client.try_withdraw_with_proof(&proof, &inputs);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidProof)));
+}
+
+#[test]
+fn test_withdraw_with_proof_accepts_recent_root() {
+    let (env, client, admin, token) = deploy_pool(DENOMINATION);
+    client.set_verifying_key(&admin, &test_vk(&env));
+    deposit_note(&env, &client, &token, 1);
+
+    let recipient = Address::generate(&env);
+    let inputs = inputs_for(&env, &client, &token, 7, &recipient);
+    let proof = forge_proof(&env, &inputs);
+
+    // Another deposit lands before the withdrawal is submitted.
+    deposit_note(&env, &client, &token, 2);
+    assert_ne!(client.get_pool_root(&token, &DENOMINATION), inputs.root);
+
+    client.withdraw_with_proof(&proof, &inputs);
+    assert_eq!(
+        token::Client::new(&env, &token).balance(&recipient),
+        DENOMINATION
+    );
+}
+
+#[test]
+fn test_withdraw_with_proof_evicted_root_fails() {
+    let (env, client, admin, token) = deploy_pool(DENOMINATION);
+    env.cost_estimate().budget().reset_unlimited();
+    client.set_verifying_key(&admin, &test_vk(&env));
+    deposit_note(&env, &client, &token, 1);
+
+    let recipient = Address::generate(&env);
+    let inputs = inputs_for(&env, &client, &token, 7, &recipient);
+    let proof = forge_proof(&env, &inputs);
+
+    // The root is still accepted after ROOT_HISTORY_SIZE - 1 further deposits...
+    for i in 1..merkle::ROOT_HISTORY_SIZE {
+        deposit_note(&env, &client, &token, i as u8 + 1);
+    }
+    let known = client.get_known_roots(&token, &DENOMINATION);
+    assert_eq!(known.len(), merkle::ROOT_HISTORY_SIZE);
+    assert_eq!(known.first(), Some(inputs.root.clone()));
+    assert_eq!(
+        known.last(),
+        Some(client.get_pool_root(&token, &DENOMINATION))
+    );
+
+    // ...and rejected once one more deposit pushes it out of the history.
+    deposit_note(&env, &client, &token, 100);
+    assert!(!client
+        .get_known_roots(&token, &DENOMINATION)
+        .contains(&inputs.root));
+    let result = client.try_withdraw_with_proof(&proof, &inputs);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidRoot)));
+}
+
+#[test]
+fn test_withdraw_with_proof_wrong_recipient_fails() {
+    let (env, client, admin, token) = deploy_pool(DENOMINATION);
+    client.set_verifying_key(&admin, &test_vk(&env));
+    deposit_note(&env, &client, &token, 1);
+
+    let recipient = Address::generate(&env);
+    let inputs = inputs_for(&env, &client, &token, 7, &recipient);
+    let proof = forge_proof(&env, &inputs);
+
+    let mut redirected = inputs.clone();
+    redirected.recipient = Address::generate(&env);
+    let result = client.try_withdraw_with_proof(&proof, &redirected);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidProof)));
+}
+
+#[test]
+fn test_withdraw_with_proof_unknown_root_fails() {
+    let (env, client, admin, token) = deploy_pool(DENOMINATION);
+    client.set_verifying_key(&admin, &test_vk(&env));
+    deposit_note(&env, &client, &token, 1);
+
+    let recipient = Address::generate(&env);
+    let mut inputs = inputs_for(&env, &client, &token, 7, &recipient);
+    inputs.root = BytesN::from_array(&env, &[9; 32]);
+    let proof = forge_proof(&env, &inputs);
+
+    let result = client.try_withdraw_with_proof(&proof, &inputs);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidRoot)));
+}
+
+#[test]
+fn test_withdraw_with_proof_non_canonical_nullifier_fails() {
+    let (env, client, admin, token) = deploy_pool(DENOMINATION);
+    client.set_verifying_key(&admin, &test_vk(&env));
+    deposit_note(&env, &client, &token, 1);
+
+    let recipient = Address::generate(&env);
+    let mut inputs = inputs_for(&env, &client, &token, 7, &recipient);
+    inputs.nullifier_hash = BytesN::from_array(&env, &[0xff; 32]);
+    let proof = forge_proof(&env, &inputs);
+
+    let result = client.try_withdraw_with_proof(&proof, &inputs);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidProof)));
+}
+
+#[test]
+fn test_withdraw_with_proof_without_verifying_key_fails() {
+    let (env, client, _admin, token) = deploy_pool(DENOMINATION);
+    deposit_note(&env, &client, &token, 1);
+
+    let recipient = Address::generate(&env);
+    let inputs = inputs_for(&env, &client, &token, 7, &recipient);
+    let proof = forge_proof(&env, &inputs);
+
+    let result = client.try_withdraw_with_proof(&proof, &inputs);
+    assert_eq!(result, Err(Ok(QuickexError::VerifyingKeyNotSet)));
+}
+
+#[test]
+fn test_withdraw_with_proof_when_paused_fails() {
+    let (env, client, admin, token) = deploy_pool(DENOMINATION);
+    client.set_verifying_key(&admin, &test_vk(&env));
+    deposit_note(&env, &client, &token, 1);
+
+    let recipient = Address::generate(&env);
+    let inputs = inputs_for(&env, &client, &token, 7, &recipient);
+    let proof = forge_proof(&env, &inputs);
+
+    client.set_paused(&admin, &true);
+    let result = client.try_withdraw_with_proof(&proof, &inputs);
+    assert_eq!(result, Err(Ok(QuickexError::ContractPaused)));
+}
@@ -0,0 +1,88 @@
+//! # Solvency
+//!
+//! The contract must always hold at least what it owes for each token: the locked
+//! total of pending escrows and pool notes, the accrued protocol fees, and (for the
+//! relayer stake token) relayer stakes. [`check_invariants`] compares the contract's
+//! balance against those counters so monitors can detect a shortfall, e.g. from a
+//! token that charges transfer fees or a bug that paid out twice.
+//!
+//! The check is permissionless. When the admin has turned on auto-pause, a check that
+//! finds a shortfall also pauses every operation, so no further funds leave until the
+//! admin investigates and unpauses.
+
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    admin::{self, PAUSE_ALL},
+    errors::QuickexError,
+    events::{publish_invariant_auto_pause_updated, publish_invariant_violated},
+    storage, tokens,
+    types::InvariantReport,
+};
+
+/// Amount of `token` the contract owes: locked escrows and pool notes, accrued fees,
+/// and relayer stakes if `token` is the stake token.
+pub(crate) fn liabilities(env: &Env, token: &Address) -> (i128, i128, i128) {
+    let staked = match storage::get_relayer_config(env) {
+        Some(config) if config.stake_token == *token => storage::get_total_staked(env),
+        _ => 0,
+    };
+    (
+        storage::get_total_locked(env, token),
+        storage::get_accrued_fees(env, token),
+        staked,
+    )
+}
+
+/// Compare the contract's balance of `token` against what it owes.
+///
+/// If the balance falls short and auto-pause is on, pauses every operation (unless
+/// already fully paused) and reports `auto_paused`. Emits `InvariantViolated` on a
+/// shortfall.
+///
+/// # Errors
+/// - [`QuickexError::InvalidToken`] – `token` is not a token contract.
+pub fn check_invariants(env: &Env, token: Address) -> Result<InvariantReport, QuickexError> {
+    let contract = env.current_contract_address();
+    let balance = tokens::balance(env, &token)?;
+    let (locked, accrued_fees, staked) = liabilities(env, &token);
+    let owed = locked.saturating_add(accrued_fees).saturating_add(staked);
+    let shortfall = owed.saturating_sub(balance).max(0);
+
+    let auto_paused = shortfall > 0
+        && storage::is_invariant_auto_pause(env)
+        && storage::get_paused_ops(env) != PAUSE_ALL;
+    if auto_paused {
+        admin::update_paused_ops(env, &contract, PAUSE_ALL, true);
+    }
+    if shortfall > 0 {
+        publish_invariant_violated(env, token.clone(), balance, owed, auto_paused);
+    }
+
+    Ok(InvariantReport {
+        token,
+        balance,
+        locked,
+        accrued_fees,
+        staked,
+        shortfall,
+        solvent: shortfall == 0,
+        auto_paused,
+    })
+}
+
+/// Turn auto-pause on a failed [`check_invariants`] on or off (**admin only**).
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+pub fn set_auto_pause(env: &Env, caller: Address, enabled: bool) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+    storage::set_invariant_auto_pause(env, enabled);
+    publish_invariant_auto_pause_updated(env, enabled, caller);
+    Ok(())
+}
+
+/// Whether a failed [`check_invariants`] pauses the contract.
+pub fn auto_pause(env: &Env) -> bool {
+    storage::is_invariant_auto_pause(env)
+}
@@ -0,0 +1,57 @@
+//! Solvency invariant check tests.
+
+use crate::{
+    admin::PAUSE_ALL, errors::QuickexError, storage, QuickexContract, QuickexContractClient,
+};
+use soroban_sdk::{testutils::Address as _, token, Address, Bytes, Env};
+
+#[test]
+fn test_check_invariants_reports_and_auto_pauses_on_shortfall() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = QuickexContractClient::new(&env, &env.register(QuickexContract, ()));
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &1_000);
+    client.deposit(
+        &token,
+        &1_000,
+        &owner,
+        &Bytes::from_slice(&env, b"solvent"),
+        &0,
+    );
+
+    let report = client.check_invariants(&token);
+    assert!(report.solvent && !report.auto_paused);
+    assert_eq!(
+        (report.balance, report.locked, report.shortfall),
+        (1_000, 1_000, 0)
+    );
+
+    // Simulate an accounting bug: more owed than the contract holds.
+    env.as_contract(&client.address, || {
+        storage::adjust_total_locked(&env, &token, 500).unwrap();
+    });
+    let report = client.check_invariants(&token);
+    assert!(!report.solvent && !report.auto_paused);
+    assert_eq!(report.shortfall, 500);
+    assert_eq!(client.paused_ops(), 0);
+
+    assert_eq!(
+        client.try_set_invariant_auto_pause(&owner, &true),
+        Err(Ok(QuickexError::Unauthorized))
+    );
+    client.set_invariant_auto_pause(&admin, &true);
+    assert!(client.get_invariant_auto_pause());
+
+    let report = client.check_invariants(&token);
+    assert!(report.auto_paused);
+    assert_eq!(client.paused_ops(), PAUSE_ALL);
+
+    // Already paused: nothing more to do.
+    assert!(!client.check_invariants(&token).auto_paused);
+}
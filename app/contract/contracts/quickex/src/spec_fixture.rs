@@ -0,0 +1,47 @@
+//! The contract spec of the release WASM, for tests that check every entrypoint.
+//!
+//! Native test builds carry no contract spec, so these tests read the one embedded in
+//! `target/wasm32-unknown-unknown/release/quickex.wasm`. CI builds the WASM before
+//! `cargo test`; locally the checks are skipped until it has been built.
+
+extern crate std;
+
+use soroban_sdk::xdr::ScSpecEntry;
+use std::{collections::BTreeSet, path::PathBuf, string::String, vec::Vec};
+
+/// Path of the release WASM, honouring `CARGO_TARGET_DIR`.
+fn wasm_path() -> PathBuf {
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target"))
+        .join("wasm32-unknown-unknown/release/quickex.wasm")
+}
+
+/// Entries of the release WASM's contract spec, or `None` if it has not been built.
+pub(crate) fn contract_spec() -> Option<Vec<ScSpecEntry>> {
+    let path = wasm_path();
+    let Ok(wasm) = std::fs::read(&path) else {
+        std::eprintln!("skipping contract spec check: {} not built", path.display());
+        return None;
+    };
+    Some(soroban_spec::read::from_wasm(&wasm).expect("the release WASM has a valid contract spec"))
+}
+
+/// Names of the entrypoints in the release WASM's contract spec.
+///
+/// `None` if it has not been built, or if this test build's features differ from the
+/// defaults the WASM is built with, since its interface would not match.
+pub(crate) fn entrypoints() -> Option<BTreeSet<String>> {
+    if !cfg!(all(feature = "privacy-pool", feature = "zk-verifier")) {
+        std::eprintln!("skipping contract spec check: not a default-features build");
+        return None;
+    }
+    let names = contract_spec()?
+        .iter()
+        .filter_map(|entry| match entry {
+            ScSpecEntry::FunctionV0(function) => Some(function.name.to_utf8_string_lossy()),
+            _ => None,
+        })
+        .collect();
+    Some(names)
+}
@@ -0,0 +1,61 @@
+//! # Escrow Statistics
+//!
+//! Counters of escrows by status and per-token volumes, updated at every escrow
+//! transition so dashboards can read basic health metrics with [`stats`] instead of
+//! replaying events. Hidden-amount and ephemeral escrows are included; pool and ring
+//! notes are not (see `pool_stats`).
+//!
+//! Counting started with the contract version that introduced it: escrows created
+//! earlier are not counted as pending, and `pending` never drops below zero when they
+//! are spent or refunded. Volumes saturate at
+//! `i128::MAX` rather than fail, so a metric can never block a deposit or withdrawal.
+
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    storage,
+    types::{ContractStats, TokenStats},
+};
+
+/// Get the escrow counters and per-token volumes.
+pub fn stats(env: &Env) -> ContractStats {
+    storage::get_escrow_stats(env)
+}
+
+/// Count a new pending escrow of `amount` of `token`.
+pub(crate) fn record_deposit(env: &Env, token: &Address, amount: i128) {
+    update(env, token, |stats, volume| {
+        stats.pending += 1;
+        volume.deposited = volume.deposited.saturating_add(amount);
+    });
+}
+
+/// Count a pending escrow of `amount` of `token` as withdrawn.
+pub(crate) fn record_withdrawal(env: &Env, token: &Address, amount: i128) {
+    update(env, token, |stats, volume| {
+        stats.pending = stats.pending.saturating_sub(1);
+        stats.spent += 1;
+        volume.withdrawn = volume.withdrawn.saturating_add(amount);
+    });
+}
+
+/// Count a pending escrow of `amount` of `token` as refunded.
+pub(crate) fn record_refund(env: &Env, token: &Address, amount: i128) {
+    update(env, token, |stats, volume| {
+        stats.pending = stats.pending.saturating_sub(1);
+        stats.refunded += 1;
+        volume.refunded = volume.refunded.saturating_add(amount);
+    });
+}
+
+fn update(env: &Env, token: &Address, f: impl FnOnce(&mut ContractStats, &mut TokenStats)) {
+    let mut stats = storage::get_escrow_stats(env);
+    let mut volume = stats.tokens.get(token.clone()).unwrap_or(TokenStats {
+        deposited: 0,
+        withdrawn: 0,
+        refunded: 0,
+    });
+    f(&mut stats, &mut volume);
+    stats.tokens.set(token.clone(), volume);
+    storage::set_escrow_stats(env, &stats);
+}
@@ -8,27 +8,113 @@
 //!
 //! | Key Variant            | Value Type     | Description |
 //! |------------------------|----------------|-------------|
-//! | [`Escrow`](DataKey::Escrow) | `EscrowEntry`  | Escrow entry keyed by commitment hash (32 bytes). One entry per unique deposit. |
+//! | [`Escrow`](DataKey::Escrow) | `PackedEscrowEntry` | Escrow entry keyed by commitment hash (32 bytes). One entry per unique deposit. |
+//! | [`SpentStatus`](DataKey::SpentStatus) | `EscrowStatus` | Terminal status of a spent or refunded escrow, overriding the entry's own. Blocks reuse of the commitment. |
+//! | [`ArchivedEscrow`](DataKey::ArchivedEscrow) | `PackedEscrowEntry` | **Temporary storage.** Spent/refunded ephemeral escrow kept for [`ESCROW_ARCHIVE_TTL`] ledgers for indexers. |
+//! | [`TotalLocked`](DataKey::TotalLocked) | `i128` | Sum of pending escrow and pool note amounts per token (the contract's liabilities). |
+//! | [`TotalStaked`](DataKey::TotalStaked) | `i128` | Sum of relayer stakes held in the stake token. |
 //! | [`EscrowCounter`](DataKey::EscrowCounter) | `u64`       | Global monotonic counter for escrow creation. |
-//! | [`Admin`](DataKey::Admin) | `Address`     | Contract admin address. Set during initialisation, transferable by admin. |
-//! | [`Paused`](DataKey::Paused) | `bool`       | Global pause flag. When true, critical operations may be blocked. |
-//! | [`PrivacyLevel`](DataKey::PrivacyLevel) | `u32`  | Numeric privacy level per account (0 = off). Used by `enable_privacy`. |
-//! | [`PrivacyHistory`](DataKey::PrivacyHistory) | `Vec<u32>` | Per-account history of privacy level changes (chronological). |
+//! | [`Admin`](DataKey::Admin) | `Address`     | **Instance storage** (persistent at schema ≤ 3). Contract admin address. Set during initialisation, transferable by admin. |
+//! | [`Paused`](DataKey::Paused) | `bool`       | *Legacy*, no longer written. Global pause flag, read only if `PausedOps` is unset. |
+//! | [`PausedOps`](DataKey::PausedOps) | `u32` | *Legacy* (schema ≤ 2), moved into [`Config`](DataKey::Config). Bitmask of paused operations. |
+//! | [`SchemaVersion`](DataKey::SchemaVersion) | `u32` | Storage schema version (see [`crate::migration`]). Unset means 1. |
+//! | [`PendingUpgrade`](DataKey::PendingUpgrade) | `UpgradeRecord` | Upgrade awaiting `migrate`; blocks normal operations while set. |
+//! | [`ScheduledUpgrade`](DataKey::ScheduledUpgrade) | `u64` | Queued action id of the upgrade announced by `schedule_upgrade`. |
+//! | [`RecoveryAddress`](DataKey::RecoveryAddress) | `Address` | Backup key that can claim admin after [`crate::admin::RECOVERY_INACTIVITY_SECS`]. |
+//! | [`AdminLastActive`](DataKey::AdminLastActive) | `u64` | Timestamp of the admin's last authorized admin call. |
+//! | [`WithdrawalFeeBps`](DataKey::WithdrawalFeeBps) | `u32` | *Legacy* (schema ≤ 2), moved into [`Config`](DataKey::Config). Protocol withdrawal fee in basis points. |
+//! | [`FeeExempt`](DataKey::FeeExempt) | `bool` | Present while the address is exempt from the withdrawal fee. |
+//! | [`AccruedFees`](DataKey::AccruedFees) | `i128` | Protocol fees held by the contract per token. |
+//! | [`TokenAllowlistEnabled`](DataKey::TokenAllowlistEnabled) | `bool` | Set once the deposit allowlist is in force (see [`crate::tokens`]). |
+//! | [`AllowedToken`](DataKey::AllowedToken) | `bool` | Present while the token is on the deposit allowlist. |
+//! | [`TokenBlock`](DataKey::TokenBlock) | `bool` | Present while the token is blocked; `true` if withdrawals are frozen too. |
+//! | [`TokenCap`](DataKey::TokenCap) | `i128` | Cap on the token's locked total; absent means uncapped. |
+//! | [`GlobalCap`](DataKey::GlobalCap) | `i128` | *Legacy* (schema ≤ 2), moved into [`Config`](DataKey::Config). Cap on the value locked across all tokens. |
+//! | [`TokenValues`](DataKey::TokenValues) | `Map<Address, i128>` | Admin-set value per whole token unit for the global cap. |
+//! | [`WithdrawVolumeLimit`](DataKey::WithdrawVolumeLimit) | `WithdrawVolumeLimit` | Cap on the token amount withdrawn per ledger window. |
+//! | [`VolumeWindow`](DataKey::VolumeWindow) | `VolumeWindow` | Start ledger and volume of the token's current window. |
+//! | [`BlockedAccount`](DataKey::BlockedAccount) | `bool` | Present while the account is blocked from depositing and receiving withdrawals. |
+//! | [`Config`](DataKey::Config) | `Config` | **Instance storage.** Paused operations, withdrawal fee, global cap, and timing parameters (see [`crate::config`]). |
+//! | [`DisabledFunction`](DataKey::DisabledFunction) | `bool` | Present while the entrypoint is switched off (see [`crate::admin::KILLABLE_FUNCTIONS`]). |
+//! | [`EventSequence`](DataKey::EventSequence) | `u64` | **Instance storage.** Sequence number of the last event emitted. |
+//! | [`WasmHash`](DataKey::WasmHash) | `BytesN<32>` | WASM installed by the last `upgrade`; absent until the first one. |
+//! | [`PrivacyEnabled`](DataKey::PrivacyEnabled) | `bool` | Boolean privacy toggle per account. Used by `set_privacy` / `get_privacy`. |
+//! | [`EphemeralEscrow`](DataKey::EphemeralEscrow) | `PackedEscrowEntry` | **Temporary storage.** Escrow from `deposit_ephemeral`, live for its claim window plus [`EPHEMERAL_REFUND_LEDGERS`]. |
+//! | [`EphemeralClaim`](DataKey::EphemeralClaim) | `PackedEscrowEntry` | Copy of a pending ephemeral escrow, kept until it is withdrawn or refunded so its funds stay refundable after the temporary entry expires. |
+//! | [`WithdrawTelemetry`](DataKey::WithdrawTelemetry) | `bool` | Present while `attempt_withdraw` records rejected withdrawals (see [`crate::telemetry`]). |
+//! | [`RejectedWithdrawals`](DataKey::RejectedWithdrawals) | `u64` | Rejected withdrawals recorded per [`WithdrawRejection`] class. |
+//! | [`HiddenSpentStatus`](DataKey::HiddenSpentStatus) | `EscrowStatus` | Terminal status of a spent or refunded hidden escrow, overriding the entry's own. Blocks reuse of the key. |
+//! | [`GcItem`](DataKey::GcItem) | `BytesN<32>` | Commitment of a spent or refunded escrow that still holds storage, queued for `gc`. |
+//! | [`GcHead`](DataKey::GcHead) / [`GcTail`](DataKey::GcTail) | `u64` | **Instance storage.** Queue positions of the next item to clean and the next free slot. |
+//! | [`GcBounty`](DataKey::GcBounty) | `(Address, i128)` | Token and amount paid from the treasury per entry cleaned by `gc`. |
+//! | [`OwnerBucket`](DataKey::OwnerBucket) | `Vec<BytesN<32>>` | Page of at most [`OWNER_BUCKET_SIZE`] pending escrow commitments per (owner, bucket number), oldest first. Not kept for owners at privacy level 2. |
+//! | [`OwnerBuckets`](DataKey::OwnerBuckets) | `(u32, u32)` | First live and last (appended-to) bucket number per owner. |
+//! | [`OwnerSlot`](DataKey::OwnerSlot) | `u32` | Bucket number holding a commitment, so it can be removed without a scan. |
+//! | [`WithdrawTicket`](DataKey::WithdrawTicket) | `WithdrawTicket` | **Temporary storage.** Two-phase withdrawal awaiting `commit_withdraw` or `abort_withdraw`. |
+//! | [`EscrowReservation`](DataKey::EscrowReservation) | `BytesN<32>` | **Temporary storage.** Ticket reserving an escrow; blocks other withdrawals and refunds while it lives. |
+//! | [`Receipt`](DataKey::Receipt) | `WithdrawReceipt` | **Temporary storage.** Payout details of a withdrawn escrow, kept for [`ESCROW_ARCHIVE_TTL`] ledgers. |
+//! | [`InvariantAutoPause`](DataKey::InvariantAutoPause) | `bool` | Present while `check_invariants` pauses the contract on a shortfall (see [`crate::solvency`]). |
+//! | [`EscrowStats`](DataKey::EscrowStats) | `ContractStats` | Escrow counters and per-token volumes returned by `stats`. |
+//! | [`Guardian`](DataKey::Guardian) | `Address` | Hot key allowed only to pause the contract. |
+//! | [`AdminCouncil`](DataKey::AdminCouncil) | `AdminCouncil` | Council members and approval threshold. Replaces `Admin` in multisig mode. |
+//! | [`AdminActionCounter`](DataKey::AdminActionCounter) | `u64` | Id of the next council action. |
+//! | [`AdminAction`](DataKey::AdminAction) | `PendingAdminAction` | Council action awaiting approvals, removed once executed. |
+//! | [`Role`](DataKey::Role) | `bool` | Present while the address holds the role. |
+//! | [`RenounceScheduled`](DataKey::RenounceScheduled) | `u32` | Ledger at which the admin scheduled renouncing. |
+//! | [`AdminRenounced`](DataKey::AdminRenounced) | `bool` | Set once the admin is renounced; blocks all admin powers and re-initialization. |
+//! | [`TimelockDelay`](DataKey::TimelockDelay) | `u64` | Seconds a queued admin action must wait before execution (0 = no timelock). |
+//! | [`QueuedAction`](DataKey::QueuedAction) | `QueuedAdminAction` | Timelocked admin action by id, removed once executed or cancelled. |
+//! | [`OwnerIndex`](DataKey::OwnerIndex) | `Vec<BytesN<32>>` | *Legacy*, no longer appended to. Pending escrow commitments per owner indexed before `OwnerBucket`; drained as they are spent. |
+//! | [`SaltPolicy`](DataKey::SaltPolicy) | `SaltPolicy` | Admin-set minimum salt length and when it took effect. |
+//! | [`PrivacyLevel`](DataKey::PrivacyLevel) | `u32`  | Numeric privacy level per account (0–2, see [`crate::privacy`]). Used by `enable_privacy`. |
+//! | [`PrivacyHistory`](DataKey::PrivacyHistory) | `Vec<u32>` | *Legacy*, no longer written. Unbounded level history without timestamps. |
+//! | [`Memo`](DataKey::Memo) | `Bytes` | Opaque encrypted recipient memo attached at deposit. |
+//! | [`WithdrawAnnouncement`](DataKey::WithdrawAnnouncement) | `u32` | Ledger of a pending commit-reveal withdrawal announcement. |
+//! | [`WithdrawRateLimit`](DataKey::WithdrawRateLimit) | `WithdrawRateLimit` | Per-account cap on withdrawals per window, set by the account. |
+//! | [`WithdrawWindow`](DataKey::WithdrawWindow) | `WithdrawWindow` | Start and withdrawal count of the account's current window. |
+//! | [`PrivacyLog`](DataKey::PrivacyLog) | `Vec<PrivacyHistoryEntry>` | Per-account privacy level changes with timestamps, newest first, capped at [`PRIVACY_HISTORY_CAP`]. |
+//! | [`HiddenEscrow`](DataKey::HiddenEscrow) | `HiddenEscrowEntry` | Hidden-amount escrow keyed by `SHA256(XDR(to) \|\| C)` where `C` is a Pedersen commitment. |
+//! | [`Denominations`](DataKey::Denominations) | `Vec<i128>` | Admin-registered pool denominations per token. |
+//! | [`AmountBuckets`](DataKey::AmountBuckets) | `Vec<i128>` | Approved escrow deposit amounts per token. Empty means any amount. |
+//! | [`PoolState`](DataKey::PoolState) | `PoolState` | Per-(token, denomination) pool counters. |
+//! | [`PoolNote`](DataKey::PoolNote) | `bool` | Marks a commitment as a pool note so withdrawals update pool stats. |
+//! | [`PoolTree`](DataKey::PoolTree) | `MerkleTree` | Shielded-note Merkle tree per (token, denomination). |
+//! | [`Nullifier`](DataKey::Nullifier) | `bool` | Marks a shielded-note nullifier hash as spent. |
+//! | [`VerifyingKey`](DataKey::VerifyingKey) | `VerifyingKeyRecord` | Groth16 verifying key for shielded withdrawals, per version. |
+//! | [`VerifyingKeyVersion`](DataKey::VerifyingKeyVersion) | `u32` | Current verifying-key version (0 = none registered). |
+//! | [`RelayerConfig`](DataKey::RelayerConfig) | `RelayerConfig` | Relayer stake token, minimum stake, and unbonding period. |
+//! | [`Relayer`](DataKey::Relayer) | `RelayerInfo` | Stake record per relayer. |
+//! | [`ActiveRelayers`](DataKey::ActiveRelayers) | `Vec<Address>` | *Legacy*, no longer appended to. Relayers activated before `RelayerBucket`; drained as they deactivate. |
+//! | [`RelayerBucket`](DataKey::RelayerBucket) | `Vec<Address>` | Page of at most [`RELAYER_BUCKET_SIZE`] active relayers per bucket number, in activation order. |
+//! | [`RelayerBuckets`](DataKey::RelayerBuckets) | `(u32, u32)` | First live and last (appended-to) relayer bucket number. |
+//! | [`RelayerSlot`](DataKey::RelayerSlot) | `u32` | Bucket number holding an active relayer, so it can be removed without a scan. |
+//! | [`RingNote`](DataKey::RingNote) | `RingNote` | Ring note keyed by `SHA256(public_key)`. |
+//! | [`KeyImage`](DataKey::KeyImage) | `bool` | Marks a ring-signature key image (by `SHA256`) as spent. |
 //!
-//! ## Related Keys (outside `DataKey`)
+//! ## Legacy Keys (outside `DataKey`)
 //!
 //! | Key                    | Format                    | Value Type | Description |
 //! |------------------------|---------------------------|------------|-------------|
-//! | `privacy_enabled`      | `(Symbol, Address)`       | `bool`     | Boolean privacy on/off per account. Used by `set_privacy` / `get_privacy`. |
+//! | `privacy_enabled`      | `(Symbol, Address)`       | `bool`     | *Legacy*, no longer written. Read only if `PrivacyEnabled` is unset, and removed on the account's next `set_privacy`. |
+//! | `escrow`               | `(Symbol, BytesN<32>)`    | `EscrowEntry` | *Legacy*, no longer read. Moved to `Escrow` by `migrate_storage`. |
 //!
 //! ## Relations
 //!
 //! - **Escrow ↔ Commitment**: Each `Escrow(Bytes)` key is derived from a 32-byte commitment hash
 //!   (`SHA256(owner || amount || salt)`). The stored [`EscrowEntry`] contains token, amount, owner,
 //!   status, and created_at.
-//! - **Admin ↔ Paused**: Admin can set the paused flag. Both are singleton keys.
-//! - **PrivacyLevel ↔ PrivacyHistory**: Same account may have both; level is current, history is append-only.
-//! - **PrivacyLevel / PrivacyHistory ↔ privacy_enabled**: Separate APIs; level-based vs boolean. Both persist per `Address`.
+//! - **Escrow → SpentStatus**: When an escrow is spent or refunded, [`close_escrow`] writes
+//!   only its status, so the withdrawal does not rewrite the full entry. The entry stays
+//!   readable until `prune_spent` or `gc` removes it, leaving the status as the only
+//!   long-term record, and the double-spend check ([`is_closed`]) reads only this cell.
+//!   Ephemeral escrows get the same status, and their entry moves from
+//!   `EphemeralEscrow` / `EphemeralClaim` to `ArchivedEscrow` for indexers.
+//! - **Admin ↔ Config**: Admin (or a pauser) sets the paused-operation mask in the config. Both are singleton keys.
+//! - **Admin ↔ AdminCouncil**: Mutually exclusive. `initialize_multisig` removes `Admin`, after
+//!   which privileged actions go through `AdminAction` proposals.
+//! - **PrivacyLevel ↔ PrivacyLog**: Same account may have both; level is current, the log keeps the
+//!   most recent changes.
+//! - **PrivacyLevel / PrivacyLog ↔ PrivacyEnabled**: Separate APIs; level-based vs boolean. Both persist per `Address`.
 //!
 //! ## Backwards Compatibility
 //!
@@ -37,19 +123,39 @@
 //! - **Add** new variants for new keys; they will not collide with existing ones.
 //! - **Value layout**: Changing `EscrowEntry` fields may require migration logic; adding optional
 //!   fields can be done carefully with defaults.
+//! - **Escrow encoding**: Escrows are stored as [`PackedEscrowEntry`]; entries written before
+//!   packing are full `EscrowEntry` maps and are still read. A new packed layout must bump
+//!   `ESCROW_ENCODING_VERSION` and keep decoding the old one.
 
-use soroban_sdk::{contracttype, Address, Bytes, Env, Vec};
+use crate::admin::PAUSE_ALL;
+use crate::errors::QuickexError;
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, Map, Symbol, TryFromVal, Val, Vec};
 
-use crate::types::EscrowEntry;
+use crate::types::{
+    AdminCouncil, Config, ContractStats, EscrowEntry, EscrowStatus, HiddenEscrowEntry,
+    PackedEscrowEntry, PendingAdminAction, PrivacyHistoryEntry, QueuedAdminAction, RelayerConfig,
+    RelayerInfo, Role, SaltPolicy, UpgradeRecord, VolumeWindow, WithdrawRateLimit, WithdrawReceipt,
+    WithdrawRejection, WithdrawTicket, WithdrawVolumeLimit, WithdrawWindow,
+};
+#[cfg(feature = "zk-verifier")]
+use crate::types::{MerkleTree, VerifyingKeyRecord};
+#[cfg(feature = "privacy-pool")]
+use crate::types::{PoolState, RingNote};
 
 // -----------------------------------------------------------------------------
-// Key constants (for keys not using DataKey)
+// Key constants
 // -----------------------------------------------------------------------------
 
-/// Symbol string for the boolean privacy-enabled flag.
-/// Used as `(Symbol::new(env, PRIVACY_ENABLED_KEY), Address)` in persistent storage.
-/// See [`crate::privacy`] module.
-pub const PRIVACY_ENABLED_KEY: &str = "privacy_enabled";
+/// Symbol string of the legacy boolean privacy key, `(Symbol, Address)`, written
+/// before the flag moved to [`DataKey::PrivacyEnabled`].
+const LEGACY_PRIVACY_ENABLED_KEY: &str = "privacy_enabled";
+
+/// Symbol string of the legacy escrow key, `(Symbol, BytesN<32>)`, moved to
+/// [`DataKey::Escrow`] by [`crate::migration::migrate_legacy_escrows`].
+const LEGACY_ESCROW_KEY: &str = "escrow";
+
+/// Maximum number of entries kept in an account's privacy history.
+pub const PRIVACY_HISTORY_CAP: u32 = 32;
 
 // -----------------------------------------------------------------------------
 // DataKey enum – central key derivation
@@ -60,7 +166,10 @@ pub const PRIVACY_ENABLED_KEY: &str = "privacy_enabled";
 /// All persistent storage access should go through the helpers in this module.
 /// Each variant maps to a distinct namespace; the Soroban runtime serialises
 /// the enum discriminant and payload into the actual storage key.
-#[contracttype]
+///
+/// Keys are internal, not part of the contract interface, so the type is left out of
+/// the spec (which would also cap it at 50 cases).
+#[contracttype(export = false)]
 #[derive(Clone)]
 pub enum DataKey {
     /// Escrow entry keyed by commitment hash (`Bytes`, typically 32 bytes).
@@ -73,38 +182,519 @@ pub enum DataKey {
     Paused,
     /// Numeric privacy level per account.
     PrivacyLevel(Address),
-    /// Privacy level change history per account.
+    /// Legacy unbounded privacy level history per account (superseded by `PrivacyLog`).
     PrivacyHistory(Address),
+    /// Hidden-amount escrow entry keyed by recipient-bound Pedersen commitment hash.
+    HiddenEscrow(BytesN<32>),
+    /// Registered pool denominations per token.
+    Denominations(Address),
+    /// Pool state per (token, denomination).
+    PoolState(Address, i128),
+    /// Marker for commitments deposited via a pool.
+    PoolNote(BytesN<32>),
+    /// Shielded-note Merkle tree per (token, denomination).
+    PoolTree(Address, i128),
+    /// Spent marker per shielded-note nullifier hash.
+    Nullifier(BytesN<32>),
+    /// Groth16 verifying key per version.
+    VerifyingKey(u32),
+    /// Current verifying-key version (singleton).
+    VerifyingKeyVersion,
+    /// Relayer staking configuration (singleton).
+    RelayerConfig,
+    /// Stake record per relayer.
+    Relayer(Address),
+    /// Legacy active relayer list (singleton); now kept in [`RelayerBucket`](DataKey::RelayerBucket)s.
+    ActiveRelayers,
+    /// Ring note keyed by public key hash.
+    RingNote(BytesN<32>),
+    /// Spent marker per ring-signature key image hash.
+    KeyImage(BytesN<32>),
+    /// Capped, timestamped privacy level history per account.
+    PrivacyLog(Address),
+    /// Encrypted recipient memo per commitment.
+    Memo(BytesN<32>),
+    /// Ledger sequence at which a withdrawal hash was announced.
+    WithdrawAnnouncement(BytesN<32>),
+    /// Terminal status of a spent or refunded escrow.
+    SpentStatus(Bytes),
+    /// Full entry of a spent or refunded ephemeral escrow (temporary storage).
+    ArchivedEscrow(Bytes),
+    /// Sum of pending escrow and pool note amounts per token.
+    TotalLocked(Address),
+    /// Withdrawal rate limit configured by an account.
+    WithdrawRateLimit(Address),
+    /// Current withdrawal rate-limit window per account.
+    WithdrawWindow(Address),
+    /// Minimum salt length policy (singleton).
+    SaltPolicy,
+    /// Legacy unbounded list of pending escrow commitments per owner (superseded by
+    /// `OwnerBucket`).
+    OwnerIndex(Address),
+    /// Admin-approved escrow deposit amounts per token.
+    AmountBuckets(Address),
+    /// Admin council (singleton).
+    AdminCouncil,
+    /// Next council action id (singleton).
+    AdminActionCounter,
+    /// Pending council action by id.
+    AdminAction(u64),
+    /// Role membership per (role, address).
+    Role(Role, Address),
+    /// Timelock delay in seconds (singleton).
+    TimelockDelay,
+    /// Queued timelocked admin action by id.
+    QueuedAction(u64),
+    /// Ledger at which an admin renounce was scheduled (singleton).
+    RenounceScheduled,
+    /// Permanent marker that the admin was renounced (singleton).
+    AdminRenounced,
+    /// Sum of relayer stakes (singleton).
+    TotalStaked,
+    /// Legacy bitmask of paused operations (singleton); now part of [`Config`].
+    PausedOps,
+    /// Pause-only guardian address (singleton).
+    Guardian,
+    /// Storage schema version (singleton).
+    SchemaVersion,
+    /// Upgrade awaiting migration (singleton).
+    PendingUpgrade,
+    /// Queued action id of the scheduled upgrade (singleton).
+    ScheduledUpgrade,
+    /// Backup address allowed to claim admin after inactivity (singleton).
+    RecoveryAddress,
+    /// Ledger timestamp of the admin's last authorized admin call (singleton).
+    AdminLastActive,
+    /// Legacy protocol withdrawal fee (singleton); now part of [`Config`].
+    WithdrawalFeeBps,
+    /// Present while the address is exempt from the withdrawal fee.
+    FeeExempt(Address),
+    /// Protocol fees kept by the contract, per token.
+    AccruedFees(Address),
+    /// Set once the deposit token allowlist is in force (singleton).
+    TokenAllowlistEnabled,
+    /// Present while the token is on the deposit allowlist.
+    AllowedToken(Address),
+    /// Block on a token; the value is whether withdrawals are frozen too.
+    TokenBlock(Address),
+    /// Deposit cap on the locked total per token.
+    TokenCap(Address),
+    /// Legacy global cap (singleton); now part of [`Config`].
+    GlobalCap,
+    /// Token values for the global cap (singleton).
+    TokenValues,
+    /// Withdrawal volume cap per token.
+    WithdrawVolumeLimit(Address),
+    /// Current withdrawal volume window per token.
+    VolumeWindow(Address),
+    /// Accounts on the compliance blocklist.
+    BlockedAccount(Address),
+    /// Deployment config.
+    Config,
+    /// Entrypoints disabled by the kill switch.
+    DisabledFunction(Symbol),
+    /// Sequence number of the last event emitted.
+    EventSequence,
+    /// WASM hash installed by the last upgrade.
+    WasmHash,
+    /// Boolean privacy toggle per account.
+    PrivacyEnabled(Address),
+    /// Ephemeral escrow entry keyed by commitment hash (temporary storage).
+    EphemeralEscrow(Bytes),
+    /// Terminal status of a spent or refunded hidden-amount escrow.
+    HiddenSpentStatus(BytesN<32>),
+    /// Commitment awaiting garbage collection, by queue position.
+    GcItem(u64),
+    /// Queue position of the next item `gc` cleans (singleton, instance storage).
+    GcHead,
+    /// Queue position the next terminal escrow is added at (singleton, instance storage).
+    GcTail,
+    /// Bounty token and amount paid per entry cleaned by `gc` (singleton).
+    GcBounty,
+    /// Escrow counters and per-token volumes (singleton).
+    EscrowStats,
+    /// Page of pending escrow commitments per (owner, bucket number).
+    OwnerBucket(Address, u32),
+    /// First live and last bucket number of an owner's index.
+    OwnerBuckets(Address),
+    /// Bucket number holding an indexed commitment.
+    OwnerSlot(BytesN<32>),
+    /// Prepared two-phase withdrawal by ticket id (temporary storage).
+    WithdrawTicket(BytesN<32>),
+    /// Ticket id reserving an escrow, by commitment (temporary storage).
+    EscrowReservation(BytesN<32>),
+    /// Set while a failed solvency check pauses the contract (singleton).
+    InvariantAutoPause,
+    /// Payout details of a withdrawn escrow, by commitment (temporary storage).
+    Receipt(BytesN<32>),
+    /// Persistent copy of a pending ephemeral escrow, by commitment.
+    EphemeralClaim(Bytes),
+    /// Set while rejected withdrawals are recorded (singleton).
+    WithdrawTelemetry,
+    /// Count of recorded rejected withdrawals per class.
+    RejectedWithdrawals(WithdrawRejection),
+    /// Page of active relayers per bucket number.
+    RelayerBucket(u32),
+    /// First live and last bucket number of the active relayer index (singleton).
+    RelayerBuckets,
+    /// Bucket number holding an active relayer.
+    RelayerSlot(Address),
 }
 
 // -----------------------------------------------------------------------------
 // Escrow helpers
 // -----------------------------------------------------------------------------
 
+/// Version of the [`PackedEscrowEntry`] encoding, stored above the status code.
+const ESCROW_ENCODING_VERSION: u32 = 1;
+
+fn pack_escrow(entry: &EscrowEntry) -> PackedEscrowEntry {
+    let status = match entry.status {
+        EscrowStatus::Pending => 0,
+        EscrowStatus::Spent => 1,
+        EscrowStatus::Expired => 2,
+        EscrowStatus::Refunded => 3,
+    };
+    PackedEscrowEntry(
+        entry.token.clone(),
+        entry.amount,
+        entry.owner.clone(),
+        ESCROW_ENCODING_VERSION << 8 | status,
+        entry.created_at,
+        entry.expires_at,
+    )
+}
+
+/// Decode an escrow stored either packed or as a full `EscrowEntry` map (written
+/// before packing).
+fn unpack_escrow(env: &Env, value: Val) -> Option<EscrowEntry> {
+    let Ok(packed) = PackedEscrowEntry::try_from_val(env, &value) else {
+        return EscrowEntry::try_from_val(env, &value).ok();
+    };
+    let PackedEscrowEntry(token, amount, owner, packed_status, created_at, expires_at) = packed;
+    let status = match packed_status & 0xff {
+        0 => EscrowStatus::Pending,
+        1 => EscrowStatus::Spent,
+        2 => EscrowStatus::Expired,
+        3 => EscrowStatus::Refunded,
+        _ => return None,
+    };
+    Some(EscrowEntry {
+        token,
+        amount,
+        owner,
+        status,
+        created_at,
+        expires_at,
+    })
+}
+
 /// Put an escrow entry into storage.
 ///
 /// **Contract**: Overwrites any existing entry for the same commitment.
 /// The commitment should be the 32-byte `SHA256(owner || amount || salt)` hash.
+/// Stored as a [`PackedEscrowEntry`], as are ephemeral and archived escrows.
 pub fn put_escrow(env: &Env, commitment: &Bytes, entry: &EscrowEntry) {
     let key = DataKey::Escrow(commitment.clone());
-    env.storage().persistent().set(&key, entry);
+    env.storage().persistent().set(&key, &pack_escrow(entry));
+}
+
+/// Remove and return an escrow entry stored under the legacy `("escrow", commitment)` key.
+pub fn take_legacy_escrow(env: &Env, commitment: &BytesN<32>) -> Option<EscrowEntry> {
+    let key = (Symbol::new(env, LEGACY_ESCROW_KEY), commitment.clone());
+    let entry = env.storage().persistent().get(&key);
+    if entry.is_some() {
+        env.storage().persistent().remove(&key);
+    }
+    entry
+}
+
+/// Raw stored value of the escrow for `commitment`, looked up under
+/// [`DataKey::Escrow`], then the ephemeral, claim and archived escrow keys, then the legacy
+/// `("escrow", commitment)` key.
+///
+/// **Contract**: Returns `None` if none of those keys holds a value. The value is not
+/// decoded, so it is returned in whatever format it was written.
+pub fn get_raw_escrow(env: &Env, commitment: &BytesN<32>) -> Option<Val> {
+    let key: Bytes = commitment.clone().into();
+    let persistent = env.storage().persistent();
+    let temporary = env.storage().temporary();
+    persistent
+        .get(&DataKey::Escrow(key.clone()))
+        .or_else(|| temporary.get(&DataKey::EphemeralEscrow(key.clone())))
+        .or_else(|| persistent.get(&DataKey::EphemeralClaim(key.clone())))
+        .or_else(|| temporary.get(&DataKey::ArchivedEscrow(key)))
+        .or_else(|| {
+            let legacy = (Symbol::new(env, LEGACY_ESCROW_KEY), commitment.clone());
+            persistent.get(&legacy)
+        })
 }
 
-/// Get an escrow entry from storage.
+/// Get an escrow entry from storage, with the status recorded by [`close_escrow`].
 ///
-/// **Contract**: Returns `None` if no escrow exists for the commitment.
+/// Falls back to ephemeral escrows, then to the temporary archive for spent or
+/// refunded ephemeral escrows (and escrows archived before statuses were split out).
+///
+/// **Contract**: Returns `None` if no escrow exists for the commitment, if its entry
+/// was pruned, or if it was ephemeral and archived more than [`ESCROW_ARCHIVE_TTL`]
+/// ledgers ago.
 pub fn get_escrow(env: &Env, commitment: &Bytes) -> Option<EscrowEntry> {
     let key = DataKey::Escrow(commitment.clone());
-    env.storage().persistent().get(&key)
+    let persistent = env.storage().persistent();
+    persistent
+        .get(&key)
+        .and_then(|value| unpack_escrow(env, value))
+        .map(|mut entry| {
+            if let Some(status) = persistent.get(&DataKey::SpentStatus(commitment.clone())) {
+                entry.status = status;
+            }
+            entry
+        })
+        .or_else(|| get_ephemeral_escrow(env, commitment))
+        .or_else(|| {
+            let archived = DataKey::ArchivedEscrow(commitment.clone());
+            env.storage()
+                .temporary()
+                .get(&archived)
+                .and_then(|value| unpack_escrow(env, value))
+        })
 }
 
-/// Check if an escrow entry exists in storage.
-#[allow(dead_code)]
+/// Check if an escrow exists, or ever existed, for the commitment.
+///
+/// **Contract**: Stays `true` after the escrow is closed and pruned, so a spent
+/// commitment can never be reused. Ephemeral escrows count through their persistent
+/// claim while pending and their status once closed, even after their temporary
+/// entries expire. Ephemeral escrows closed before claims were kept count only while
+/// their archive lives.
 pub fn has_escrow(env: &Env, commitment: &Bytes) -> bool {
     let key = DataKey::Escrow(commitment.clone());
+    let spent = DataKey::SpentStatus(commitment.clone());
+    let claim = DataKey::EphemeralClaim(commitment.clone());
+    let ephemeral = DataKey::EphemeralEscrow(commitment.clone());
+    let archived = DataKey::ArchivedEscrow(commitment.clone());
+    env.storage().persistent().has(&key)
+        || env.storage().persistent().has(&spent)
+        || env.storage().persistent().has(&claim)
+        || env.storage().temporary().has(&ephemeral)
+        || env.storage().temporary().has(&archived)
+}
+
+/// Check if a persistent escrow was spent or refunded.
+///
+/// **Contract**: Reads only the status cell, so it stays cheap after the entry is
+/// pruned or its TTL lapsed.
+pub fn is_closed(env: &Env, commitment: &Bytes) -> bool {
+    let key = DataKey::SpentStatus(commitment.clone());
     env.storage().persistent().has(&key)
 }
 
+/// Ledgers an archived escrow stays readable in temporary storage (~7 days at 5s ledgers).
+pub const ESCROW_ARCHIVE_TTL: u32 = 120_960;
+
+/// Close an escrow with a terminal `status` (`Spent` or `Refunded`).
+///
+/// Only the status is written, to its own cell; the entry is left as is and
+/// [`get_escrow`] reports the new status until [`prune_escrow`] removes it. An
+/// ephemeral escrow's entry and claim are dropped instead, and the entry moves to
+/// temporary storage for [`ESCROW_ARCHIVE_TTL`] ledgers.
+pub fn close_escrow(env: &Env, commitment: &Bytes, entry: &EscrowEntry, status: EscrowStatus) {
+    let persistent = env.storage().persistent();
+    let temporary = env.storage().temporary();
+    let key = DataKey::SpentStatus(commitment.clone());
+    persistent.set(&key, &status);
+
+    let ephemeral = DataKey::EphemeralEscrow(commitment.clone());
+    let claim = DataKey::EphemeralClaim(commitment.clone());
+    if !temporary.has(&ephemeral) && !persistent.has(&claim) {
+        return;
+    }
+
+    temporary.remove(&ephemeral);
+    persistent.remove(&claim);
+    let archived = DataKey::ArchivedEscrow(commitment.clone());
+    let mut entry = entry.clone();
+    entry.status = status;
+    temporary.set(&archived, &pack_escrow(&entry));
+    temporary.extend_ttl(&archived, ESCROW_ARCHIVE_TTL, ESCROW_ARCHIVE_TTL);
+}
+
+/// Store the receipt of a withdrawal for [`ESCROW_ARCHIVE_TTL`] ledgers.
+pub fn put_receipt(env: &Env, receipt: &WithdrawReceipt) {
+    let key = DataKey::Receipt(receipt.commitment.clone());
+    let temporary = env.storage().temporary();
+    temporary.set(&key, receipt);
+    temporary.extend_ttl(&key, ESCROW_ARCHIVE_TTL, ESCROW_ARCHIVE_TTL);
+}
+
+/// Get the receipt of the withdrawal of `commitment`.
+///
+/// **Contract**: Returns `None` if the escrow was not withdrawn (e.g. refunded or still
+/// pending), or if it was more than [`ESCROW_ARCHIVE_TTL`] ledgers ago.
+pub fn get_receipt(env: &Env, commitment: &BytesN<32>) -> Option<WithdrawReceipt> {
+    let key = DataKey::Receipt(commitment.clone());
+    env.storage().temporary().get(&key)
+}
+
+/// Extend a pending escrow to the maximum TTL and return the last ledger it lives to.
+///
+/// **Contract**: Returns `None` if no pending escrow exists for the commitment.
+pub fn extend_escrow_ttl(env: &Env, commitment: &Bytes) -> Option<u32> {
+    let max_ttl = env.storage().max_ttl();
+    bump_escrow_ttl(env, commitment, max_ttl).then(|| env.ledger().sequence() + max_ttl)
+}
+
+/// Extend a stored escrow so it lives at least `extend_to` more ledgers. Entries that
+/// already do are left unchanged.
+///
+/// **Contract**: Returns `false` if no pending escrow entry exists for the commitment.
+/// `extend_to` must not exceed the maximum TTL.
+pub fn bump_escrow_ttl(env: &Env, commitment: &Bytes, extend_to: u32) -> bool {
+    let key = DataKey::Escrow(commitment.clone());
+    let ephemeral = DataKey::EphemeralEscrow(commitment.clone());
+    let claim = DataKey::EphemeralClaim(commitment.clone());
+    let persistent = env.storage().persistent();
+    let temporary = env.storage().temporary();
+    if is_closed(env, commitment) {
+        return false;
+    } else if persistent.has(&key) {
+        persistent.extend_ttl(&key, extend_to, extend_to);
+    } else if persistent.has(&claim) || temporary.has(&ephemeral) {
+        if persistent.has(&claim) {
+            persistent.extend_ttl(&claim, extend_to, extend_to);
+        }
+        if temporary.has(&ephemeral) {
+            temporary.extend_ttl(&ephemeral, extend_to, extend_to);
+        }
+    } else {
+        return false;
+    }
+    true
+}
+
+/// Extend a pending escrow, and the entries its withdrawal reads (memo and owner-index
+/// slot and bucket), to the maximum TTL. Returns the last ledger the escrow lives to.
+///
+/// Called after an archived escrow is restored, so the claim that follows cannot fail
+/// on an entry that is still archived or about to be.
+///
+/// **Contract**: Returns `None` if no pending escrow exists for the commitment.
+pub fn restore_escrow_ttl(env: &Env, commitment: &BytesN<32>) -> Option<u32> {
+    let key: Bytes = commitment.clone().into();
+    let owner = get_escrow(env, &key)?.owner;
+    let max_ttl = env.storage().max_ttl();
+    if !bump_escrow_ttl(env, &key, max_ttl) {
+        return None;
+    }
+
+    let persistent = env.storage().persistent();
+    let mut related = Vec::from_array(
+        env,
+        [
+            DataKey::Memo(commitment.clone()),
+            DataKey::OwnerSlot(commitment.clone()),
+            DataKey::OwnerBuckets(owner.clone()),
+            DataKey::OwnerIndex(owner.clone()),
+        ],
+    );
+    if let Some(number) = persistent.get::<_, u32>(&DataKey::OwnerSlot(commitment.clone())) {
+        related.push_back(DataKey::OwnerBucket(owner, number));
+    }
+    for related_key in related.iter() {
+        if persistent.has(&related_key) {
+            persistent.extend_ttl(&related_key, max_ttl, max_ttl);
+        }
+    }
+    Some(env.ledger().sequence() + max_ttl)
+}
+
+/// Remove the entry of a spent or refunded escrow, keeping only its status.
+///
+/// **Contract**: Returns `false`, changing nothing, unless a persistent entry exists
+/// and the escrow was closed (or the entry itself holds a terminal status, as written
+/// before statuses were split out).
+pub fn prune_escrow(env: &Env, commitment: &Bytes) -> bool {
+    let key = DataKey::Escrow(commitment.clone());
+    let spent = DataKey::SpentStatus(commitment.clone());
+    let persistent = env.storage().persistent();
+    let Some(entry) = persistent
+        .get(&key)
+        .and_then(|value| unpack_escrow(env, value))
+    else {
+        return false;
+    };
+    let status = persistent.get(&spent).unwrap_or(entry.status);
+    if status == EscrowStatus::Pending {
+        return false;
+    }
+    persistent.remove(&key);
+    persistent.set(&spent, &status);
+    true
+}
+
+/// Ledgers an expired ephemeral escrow stays refundable (~1 day at 5s ledgers).
+pub const EPHEMERAL_REFUND_LEDGERS: u32 = 17_280;
+
+/// Put an ephemeral escrow entry into temporary storage, live for `live_ledgers`, and
+/// its claim into persistent storage.
+pub fn put_ephemeral_escrow(env: &Env, commitment: &Bytes, entry: &EscrowEntry, live_ledgers: u32) {
+    let key = DataKey::EphemeralEscrow(commitment.clone());
+    let temporary = env.storage().temporary();
+    let packed = pack_escrow(entry);
+    temporary.set(&key, &packed);
+    temporary.extend_ttl(&key, live_ledgers, live_ledgers);
+    let claim = DataKey::EphemeralClaim(commitment.clone());
+    env.storage().persistent().set(&claim, &packed);
+}
+
+/// Get a pending ephemeral escrow entry.
+///
+/// **Contract**: Reads the temporary entry while it lives, then the persistent claim,
+/// so an escrow whose entry expired unclaimed can still be refunded.
+pub fn get_ephemeral_escrow(env: &Env, commitment: &Bytes) -> Option<EscrowEntry> {
+    let key = DataKey::EphemeralEscrow(commitment.clone());
+    let claim = DataKey::EphemeralClaim(commitment.clone());
+    env.storage()
+        .temporary()
+        .get(&key)
+        .or_else(|| env.storage().persistent().get(&claim))
+        .and_then(|value| unpack_escrow(env, value))
+}
+
+/// Extend the contract instance to the maximum TTL and return the last ledger it lives to.
+pub fn extend_instance_ttl(env: &Env) -> u32 {
+    let max_ttl = env.storage().max_ttl();
+    env.storage().instance().extend_ttl(max_ttl, max_ttl);
+    env.ledger().sequence() + max_ttl
+}
+
+/// Instance TTL (~7 days of ledgers) below which [`bump_instance_ttl`] extends it.
+pub const INSTANCE_BUMP_THRESHOLD: u32 = 120_960;
+
+/// TTL (~30 days of ledgers) [`bump_instance_ttl`] extends the instance to.
+pub const INSTANCE_BUMP_TO: u32 = 518_400;
+
+/// Extend the contract instance to [`INSTANCE_BUMP_TO`] ledgers (capped at the maximum
+/// TTL) if it has fewer than [`INSTANCE_BUMP_THRESHOLD`] left.
+///
+/// Called on every deposit, withdrawal, and refund, so an instance in use never
+/// approaches archival; the check costs nothing extra when the TTL is high enough.
+pub fn bump_instance_ttl(env: &Env) {
+    let extend_to = INSTANCE_BUMP_TO.min(env.storage().max_ttl());
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_BUMP_THRESHOLD.min(extend_to), extend_to);
+}
+
+/// Extend the contract instance so it lives at least `extend_to` more ledgers and
+/// return that ledger. An instance that already does is left unchanged.
+///
+/// **Contract**: `extend_to` must not exceed the maximum TTL.
+pub fn bump_instance_to(env: &Env, extend_to: u32) -> u32 {
+    env.storage().instance().extend_ttl(extend_to, extend_to);
+    env.ledger().sequence() + extend_to
+}
+
 /// Get the next escrow counter value.
 ///
 /// **Contract**: Returns 0 if never set. Counter is used for `create_escrow`.
@@ -125,76 +715,1441 @@ pub fn increment_escrow_counter(env: &Env) -> u64 {
     count
 }
 
-// -----------------------------------------------------------------------------
-// Admin helpers
-// -----------------------------------------------------------------------------
+/// Get the amount of `token` owed to pending escrows and pool notes.
+///
+/// **Contract**: Returns 0 if never set.
+pub fn get_total_locked(env: &Env, token: &Address) -> i128 {
+    let key = DataKey::TotalLocked(token.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
 
-/// Set admin address.
-#[allow(dead_code)]
-pub fn set_admin(env: &Env, admin: &Address) {
-    let key = DataKey::Admin;
-    env.storage().persistent().set(&key, admin);
+/// Set the locked total for `token`.
+pub fn set_total_locked(env: &Env, token: &Address, total: i128) {
+    let key = DataKey::TotalLocked(token.clone());
+    env.storage().persistent().set(&key, &total);
 }
 
-/// Get admin address.
-#[allow(dead_code)]
-pub fn get_admin(env: &Env) -> Option<Address> {
-    let key = DataKey::Admin;
-    env.storage().persistent().get(&key)
+/// Add `delta` (negative when funds are paid out) to the locked total for `token`.
+///
+/// # Errors
+/// - [`QuickexError::ArithmeticOverflow`] – the total would overflow `i128`.
+/// - [`QuickexError::LockedTotalUnderflow`] – the total would go below zero, i.e. the
+///   funds paid out were deposited before totals were tracked and never backfilled.
+pub fn adjust_total_locked(env: &Env, token: &Address, delta: i128) -> Result<(), QuickexError> {
+    let total = get_total_locked(env, token)
+        .checked_add(delta)
+        .ok_or(QuickexError::ArithmeticOverflow)?;
+    if total < 0 {
+        return Err(QuickexError::LockedTotalUnderflow);
+    }
+    set_total_locked(env, token, total);
+    Ok(())
 }
 
-/// Set paused state.
-#[allow(dead_code)]
-pub fn set_paused(env: &Env, paused: bool) {
-    let key = DataKey::Paused;
-    env.storage().persistent().set(&key, &paused);
+/// Set the protocol withdrawal fee in basis points.
+pub fn set_withdrawal_fee_bps(env: &Env, fee_bps: u32) {
+    update_config(env, |config| config.withdrawal_fee_bps = fee_bps);
 }
 
-/// Get paused state.
-#[allow(dead_code)]
-pub fn is_paused(env: &Env) -> bool {
-    let key = DataKey::Paused;
-    env.storage().persistent().get(&key).unwrap_or(false)
+/// Get the protocol withdrawal fee in basis points (0 if never set).
+pub fn get_withdrawal_fee_bps(env: &Env) -> u32 {
+    crate::config::get(env).withdrawal_fee_bps
 }
 
-// -----------------------------------------------------------------------------
-// Privacy helpers (level-based API)
-// -----------------------------------------------------------------------------
+/// Get the withdrawal fee stored before it moved into the config (schema ≤ 2).
+pub fn get_legacy_withdrawal_fee_bps(env: &Env) -> u32 {
+    let key = DataKey::WithdrawalFeeBps;
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
 
-/// Set privacy level for an account.
-pub fn set_privacy_level(env: &Env, account: &Address, level: u32) {
-    let key = DataKey::PrivacyLevel(account.clone());
-    env.storage().persistent().set(&key, &level);
+/// Exempt `account` from the withdrawal fee, or remove the exemption.
+pub fn set_fee_exempt(env: &Env, account: &Address, exempt: bool) {
+    let key = DataKey::FeeExempt(account.clone());
+    if exempt {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
 }
 
-/// Get privacy level for an account.
-pub fn get_privacy_level(env: &Env, account: &Address) -> Option<u32> {
-    let key = DataKey::PrivacyLevel(account.clone());
-    env.storage().persistent().get(&key)
+/// Whether `account` is exempt from the withdrawal fee.
+pub fn is_fee_exempt(env: &Env, account: &Address) -> bool {
+    let key = DataKey::FeeExempt(account.clone());
+    env.storage().persistent().has(&key)
 }
 
-/// Add to privacy history for an account.
+/// Get the protocol fees of `token` held by the contract.
 ///
-/// **Contract**: Pushes `level` to the front of the history (newest first).
-/// History is unbounded; consider capping in future if needed.
-pub fn add_privacy_history(env: &Env, account: &Address, level: u32) {
-    let key = DataKey::PrivacyHistory(account.clone());
-    let mut history: Vec<u32> = env
-        .storage()
+/// **Contract**: Returns 0 if never set.
+pub fn get_accrued_fees(env: &Env, token: &Address) -> i128 {
+    let key = DataKey::AccruedFees(token.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Add `delta` (negative when fees are paid out) to the accrued fees for `token`.
+///
+/// # Errors
+/// - [`QuickexError::ArithmeticOverflow`] – the total would overflow `i128`.
+pub fn adjust_accrued_fees(env: &Env, token: &Address, delta: i128) -> Result<(), QuickexError> {
+    let key = DataKey::AccruedFees(token.clone());
+    let total = get_accrued_fees(env, token)
+        .checked_add(delta)
+        .ok_or(QuickexError::ArithmeticOverflow)?;
+    env.storage().persistent().set(&key, &total);
+    Ok(())
+}
+
+/// Put the deposit token allowlist in force.
+pub fn set_token_allowlist_enabled(env: &Env) {
+    let key = DataKey::TokenAllowlistEnabled;
+    env.storage().persistent().set(&key, &true);
+}
+
+/// Whether the deposit token allowlist is in force.
+pub fn is_token_allowlist_enabled(env: &Env) -> bool {
+    let key = DataKey::TokenAllowlistEnabled;
+    env.storage().persistent().has(&key)
+}
+
+/// Turn auto-pause on a failed solvency check on or off.
+pub fn set_invariant_auto_pause(env: &Env, enabled: bool) {
+    let key = DataKey::InvariantAutoPause;
+    if enabled {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Whether a failed solvency check pauses the contract.
+pub fn is_invariant_auto_pause(env: &Env) -> bool {
+    let key = DataKey::InvariantAutoPause;
+    env.storage().persistent().has(&key)
+}
+
+/// Turn recording of rejected withdrawals on or off.
+pub fn set_withdraw_telemetry(env: &Env, enabled: bool) {
+    let key = DataKey::WithdrawTelemetry;
+    if enabled {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Whether rejected withdrawals are recorded.
+pub fn is_withdraw_telemetry(env: &Env) -> bool {
+    let key = DataKey::WithdrawTelemetry;
+    env.storage().persistent().has(&key)
+}
+
+/// Number of rejected withdrawals recorded for `class`.
+pub fn get_rejected_withdrawals(env: &Env, class: WithdrawRejection) -> u64 {
+    let key = DataKey::RejectedWithdrawals(class);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Count one more rejected withdrawal of `class` and return the new count.
+pub fn increment_rejected_withdrawals(env: &Env, class: WithdrawRejection) -> u64 {
+    let count = get_rejected_withdrawals(env, class).saturating_add(1);
+    env.storage()
+        .persistent()
+        .set(&DataKey::RejectedWithdrawals(class), &count);
+    count
+}
+
+/// Add `token` to, or remove it from, the deposit allowlist.
+pub fn set_token_allowed(env: &Env, token: &Address, allowed: bool) {
+    let key = DataKey::AllowedToken(token.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Whether `token` is on the deposit allowlist.
+pub fn is_token_allowed(env: &Env, token: &Address) -> bool {
+    let key = DataKey::AllowedToken(token.clone());
+    env.storage().persistent().has(&key)
+}
+
+/// Block `token` (`Some(freeze_withdrawals)`) or lift its block (`None`).
+pub fn set_token_block(env: &Env, token: &Address, freeze_withdrawals: Option<bool>) {
+    let key = DataKey::TokenBlock(token.clone());
+    match freeze_withdrawals {
+        Some(freeze) => env.storage().persistent().set(&key, &freeze),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Get the block on `token`: `Some(freeze_withdrawals)` while blocked.
+pub fn get_token_block(env: &Env, token: &Address) -> Option<bool> {
+    let key = DataKey::TokenBlock(token.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Set the deposit cap for `token` (`0` removes it).
+pub fn set_token_cap(env: &Env, token: &Address, cap: i128) {
+    let key = DataKey::TokenCap(token.clone());
+    if cap > 0 {
+        env.storage().persistent().set(&key, &cap);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Get the deposit cap for `token` (0 if uncapped).
+pub fn get_token_cap(env: &Env, token: &Address) -> i128 {
+    let key = DataKey::TokenCap(token.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Set the global cap in value units (`0` removes it).
+pub fn set_global_cap(env: &Env, cap: i128) {
+    update_config(env, |config| config.global_cap = cap);
+}
+
+/// Get the global cap in value units (0 if uncapped).
+pub fn get_global_cap(env: &Env) -> i128 {
+    crate::config::get(env).global_cap
+}
+
+/// Get the global cap stored before it moved into the config (schema ≤ 2).
+pub fn get_legacy_global_cap(env: &Env) -> i128 {
+    let key = DataKey::GlobalCap;
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Store the token values used by the global cap.
+pub fn set_token_values(env: &Env, values: &Map<Address, i128>) {
+    let key = DataKey::TokenValues;
+    env.storage().persistent().set(&key, values);
+}
+
+/// Get the token values used by the global cap (empty if none set).
+pub fn get_token_values(env: &Env) -> Map<Address, i128> {
+    let key = DataKey::TokenValues;
+    env.storage()
         .persistent()
         .get(&key)
-        .unwrap_or(Vec::new(env));
-    history.push_front(level);
-    env.storage().persistent().set(&key, &history);
+        .unwrap_or(Map::new(env))
 }
 
-/// Get privacy history for an account.
+/// Set the withdrawal volume limit for `token`.
+pub fn set_withdraw_volume_limit(env: &Env, token: &Address, limit: &WithdrawVolumeLimit) {
+    let key = DataKey::WithdrawVolumeLimit(token.clone());
+    env.storage().persistent().set(&key, limit);
+}
+
+/// Get the withdrawal volume limit for `token`.
+pub fn get_withdraw_volume_limit(env: &Env, token: &Address) -> Option<WithdrawVolumeLimit> {
+    let key = DataKey::WithdrawVolumeLimit(token.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Remove the withdrawal volume limit and current window for `token`.
+pub fn remove_withdraw_volume_limit(env: &Env, token: &Address) {
+    let persistent = env.storage().persistent();
+    persistent.remove(&DataKey::WithdrawVolumeLimit(token.clone()));
+    persistent.remove(&DataKey::VolumeWindow(token.clone()));
+}
+
+/// Set the current withdrawal volume window for `token`.
+pub fn set_volume_window(env: &Env, token: &Address, window: &VolumeWindow) {
+    let key = DataKey::VolumeWindow(token.clone());
+    env.storage().persistent().set(&key, window);
+}
+
+/// Get the current withdrawal volume window for `token`.
+pub fn get_volume_window(env: &Env, token: &Address) -> Option<VolumeWindow> {
+    let key = DataKey::VolumeWindow(token.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Add `account` to, or remove it from, the compliance blocklist.
+pub fn set_account_blocked(env: &Env, account: &Address, blocked: bool) {
+    let key = DataKey::BlockedAccount(account.clone());
+    if blocked {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Whether `account` is on the compliance blocklist.
+pub fn is_account_blocked(env: &Env, account: &Address) -> bool {
+    let key = DataKey::BlockedAccount(account.clone());
+    env.storage().persistent().has(&key)
+}
+
+/// Set the deployment config.
+pub fn set_config(env: &Env, config: &Config) {
+    env.storage().instance().set(&DataKey::Config, config);
+}
+
+/// Get the stored deployment config, if any has been written.
+pub fn get_config(env: &Env) -> Option<Config> {
+    env.storage().instance().get(&DataKey::Config)
+}
+
+/// Apply `update` to the current config and store it.
+fn update_config(env: &Env, update: impl FnOnce(&mut Config)) {
+    let mut config = crate::config::get(env);
+    update(&mut config);
+    set_config(env, &config);
+}
+
+/// Disable or re-enable the entrypoint `name`.
+pub fn set_function_disabled(env: &Env, name: &Symbol, disabled: bool) {
+    let key = DataKey::DisabledFunction(name.clone());
+    if disabled {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Whether the entrypoint `name` is disabled.
+pub fn is_function_disabled(env: &Env, name: &Symbol) -> bool {
+    let key = DataKey::DisabledFunction(name.clone());
+    env.storage().persistent().has(&key)
+}
+
+/// Sequence number of the last event emitted (0 before the first).
+pub fn get_event_sequence(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::EventSequence)
+        .unwrap_or(0)
+}
+
+/// Advance the event sequence and return the new number.
+pub fn next_event_sequence(env: &Env) -> u64 {
+    let sequence = get_event_sequence(env) + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::EventSequence, &sequence);
+    sequence
+}
+
+/// Queue `commitment` for garbage collection.
+pub fn push_gc(env: &Env, commitment: &BytesN<32>) {
+    let instance = env.storage().instance();
+    let tail: u64 = instance.get(&DataKey::GcTail).unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&DataKey::GcItem(tail), commitment);
+    instance.set(&DataKey::GcTail, &(tail + 1));
+}
+
+/// Remove and return the oldest queued commitment.
 ///
-/// **Contract**: Returns empty vec if never set. Order is newest-first.
-pub fn get_privacy_history(env: &Env, account: &Address) -> Vec<u32> {
-    let key = DataKey::PrivacyHistory(account.clone());
+/// **Contract**: Returns `None` if the queue is empty.
+pub fn pop_gc(env: &Env) -> Option<BytesN<32>> {
+    let instance = env.storage().instance();
+    let head: u64 = instance.get(&DataKey::GcHead).unwrap_or(0);
+    let key = DataKey::GcItem(head);
+    let commitment = env.storage().persistent().get(&key)?;
+    env.storage().persistent().remove(&key);
+    instance.set(&DataKey::GcHead, &(head + 1));
+    Some(commitment)
+}
+
+/// Number of commitments waiting in the garbage-collection queue.
+pub fn gc_queue_len(env: &Env) -> u64 {
+    let instance = env.storage().instance();
+    let head: u64 = instance.get(&DataKey::GcHead).unwrap_or(0);
+    let tail: u64 = instance.get(&DataKey::GcTail).unwrap_or(0);
+    tail - head
+}
+
+/// Set or remove (`None`) the bounty paid per entry cleaned by `gc`.
+pub fn set_gc_bounty(env: &Env, bounty: Option<&(Address, i128)>) {
+    let key = DataKey::GcBounty;
+    match bounty {
+        Some(bounty) => env.storage().persistent().set(&key, bounty),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Get the `gc` bounty token and amount, if set.
+pub fn get_gc_bounty(env: &Env) -> Option<(Address, i128)> {
+    let key = DataKey::GcBounty;
+    env.storage().persistent().get(&key)
+}
+
+/// Store the escrow counters and per-token volumes.
+pub fn set_escrow_stats(env: &Env, stats: &ContractStats) {
+    let key = DataKey::EscrowStats;
+    env.storage().persistent().set(&key, stats);
+}
+
+/// Get the escrow counters and per-token volumes.
+///
+/// **Contract**: Returns zeroed counters if never set.
+pub fn get_escrow_stats(env: &Env) -> ContractStats {
+    let key = DataKey::EscrowStats;
     env.storage()
         .persistent()
         .get(&key)
-        .unwrap_or(Vec::new(env))
+        .unwrap_or(ContractStats {
+            pending: 0,
+            spent: 0,
+            refunded: 0,
+            tokens: Map::new(env),
+        })
+}
+
+/// Get the sum of relayer stakes held by the contract.
+///
+/// **Contract**: Returns 0 if never set.
+pub fn get_total_staked(env: &Env) -> i128 {
+    let key = DataKey::TotalStaked;
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Add `delta` (negative when stake leaves the contract) to the staked total.
+///
+/// # Errors
+/// - [`QuickexError::ArithmeticOverflow`] – the total would overflow `i128`.
+pub fn adjust_total_staked(env: &Env, delta: i128) -> Result<(), QuickexError> {
+    let key = DataKey::TotalStaked;
+    let total = get_total_staked(env)
+        .checked_add(delta)
+        .ok_or(QuickexError::ArithmeticOverflow)?;
+    env.storage().persistent().set(&key, &total);
+    Ok(())
+}
+
+/// Store the encrypted memo for a commitment.
+pub fn put_memo(env: &Env, commitment: &BytesN<32>, memo: &Bytes) {
+    let key = DataKey::Memo(commitment.clone());
+    env.storage().persistent().set(&key, memo);
+}
+
+/// Get the encrypted memo for a commitment.
+///
+/// **Contract**: Returns `None` if the deposit had no memo.
+pub fn get_memo(env: &Env, commitment: &BytesN<32>) -> Option<Bytes> {
+    let key = DataKey::Memo(commitment.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Remove the encrypted memo for a commitment. Returns whether one existed.
+pub fn remove_memo(env: &Env, commitment: &BytesN<32>) -> bool {
+    let key = DataKey::Memo(commitment.clone());
+    let persistent = env.storage().persistent();
+    let existed = persistent.has(&key);
+    if existed {
+        persistent.remove(&key);
+    }
+    existed
+}
+
+/// Record the ledger at which a withdrawal hash was announced.
+pub fn put_withdraw_announcement(env: &Env, announcement: &BytesN<32>, ledger: u32) {
+    let key = DataKey::WithdrawAnnouncement(announcement.clone());
+    env.storage().persistent().set(&key, &ledger);
+}
+
+/// Store a prepared withdrawal and reserve its escrow, both for `live_ledgers`.
+pub fn put_withdraw_ticket(env: &Env, id: &BytesN<32>, ticket: &WithdrawTicket, live_ledgers: u32) {
+    let temporary = env.storage().temporary();
+    let key = DataKey::WithdrawTicket(id.clone());
+    let reservation = DataKey::EscrowReservation(ticket.commitment.clone());
+    temporary.set(&key, ticket);
+    temporary.extend_ttl(&key, live_ledgers, live_ledgers);
+    temporary.set(&reservation, id);
+    temporary.extend_ttl(&reservation, live_ledgers, live_ledgers);
+}
+
+/// Get a prepared withdrawal, if it is still live.
+pub fn get_withdraw_ticket(env: &Env, id: &BytesN<32>) -> Option<WithdrawTicket> {
+    let key = DataKey::WithdrawTicket(id.clone());
+    env.storage().temporary().get(&key)
+}
+
+/// Remove a prepared withdrawal and release its escrow.
+pub fn remove_withdraw_ticket(env: &Env, id: &BytesN<32>, ticket: &WithdrawTicket) {
+    let temporary = env.storage().temporary();
+    temporary.remove(&DataKey::WithdrawTicket(id.clone()));
+    temporary.remove(&DataKey::EscrowReservation(ticket.commitment.clone()));
+}
+
+/// Check if a live withdrawal ticket reserves the escrow under `commitment`.
+pub fn is_reserved(env: &Env, commitment: &BytesN<32>) -> bool {
+    let key = DataKey::EscrowReservation(commitment.clone());
+    env.storage().temporary().has(&key)
+}
+
+/// Get the ledger at which a withdrawal hash was announced.
+pub fn get_withdraw_announcement(env: &Env, announcement: &BytesN<32>) -> Option<u32> {
+    let key = DataKey::WithdrawAnnouncement(announcement.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Remove a withdrawal announcement once it has been finalized.
+pub fn remove_withdraw_announcement(env: &Env, announcement: &BytesN<32>) {
+    let key = DataKey::WithdrawAnnouncement(announcement.clone());
+    env.storage().persistent().remove(&key);
+}
+
+// -----------------------------------------------------------------------------
+// Hidden-amount escrow helpers
+// -----------------------------------------------------------------------------
+
+/// Put a hidden-amount escrow entry into storage.
+///
+/// **Contract**: Overwrites any existing entry for the same key.
+pub fn put_hidden_escrow(env: &Env, key: &BytesN<32>, entry: &HiddenEscrowEntry) {
+    let key = DataKey::HiddenEscrow(key.clone());
+    env.storage().persistent().set(&key, entry);
+}
+
+/// Get a hidden-amount escrow entry from storage, with the status recorded by
+/// [`close_hidden_escrow`].
+///
+/// **Contract**: Returns `None` if no entry exists for the key.
+pub fn get_hidden_escrow(env: &Env, key: &BytesN<32>) -> Option<HiddenEscrowEntry> {
+    let persistent = env.storage().persistent();
+    let mut entry: HiddenEscrowEntry = persistent.get(&DataKey::HiddenEscrow(key.clone()))?;
+    if let Some(status) = persistent.get(&DataKey::HiddenSpentStatus(key.clone())) {
+        entry.status = status;
+    }
+    Some(entry)
+}
+
+/// Close a hidden-amount escrow with a terminal `status` (`Spent` or `Refunded`).
+///
+/// Only the status is written, to its own cell; the entry is left as is until
+/// [`prune_hidden_escrow`] removes it.
+pub fn close_hidden_escrow(env: &Env, key: &BytesN<32>, status: EscrowStatus) {
+    let key = DataKey::HiddenSpentStatus(key.clone());
+    env.storage().persistent().set(&key, &status);
+}
+
+/// Check if a hidden-amount escrow exists, or ever existed, for the key.
+///
+/// **Contract**: Stays `true` after the escrow is closed and pruned.
+pub fn has_hidden_escrow(env: &Env, key: &BytesN<32>) -> bool {
+    let persistent = env.storage().persistent();
+    persistent.has(&DataKey::HiddenEscrow(key.clone()))
+        || persistent.has(&DataKey::HiddenSpentStatus(key.clone()))
+}
+
+/// Check if a hidden-amount escrow was spent or refunded.
+pub fn is_hidden_closed(env: &Env, key: &BytesN<32>) -> bool {
+    let key = DataKey::HiddenSpentStatus(key.clone());
+    env.storage().persistent().has(&key)
+}
+
+/// Remove the entry of a spent or refunded hidden-amount escrow, keeping only its
+/// status.
+///
+/// **Contract**: Returns `false`, changing nothing, unless the entry exists and the
+/// escrow was closed (or the entry itself holds a terminal status, as written before
+/// statuses were split out).
+pub fn prune_hidden_escrow(env: &Env, key: &BytesN<32>) -> bool {
+    let entry_key = DataKey::HiddenEscrow(key.clone());
+    let spent = DataKey::HiddenSpentStatus(key.clone());
+    let persistent = env.storage().persistent();
+    let Some(entry) = persistent.get::<_, HiddenEscrowEntry>(&entry_key) else {
+        return false;
+    };
+    let status = persistent.get(&spent).unwrap_or(entry.status);
+    if status == EscrowStatus::Pending {
+        return false;
+    }
+    persistent.remove(&entry_key);
+    persistent.set(&spent, &status);
+    true
+}
+
+// -----------------------------------------------------------------------------
+// Pool helpers
+// -----------------------------------------------------------------------------
+
+#[cfg(feature = "privacy-pool")]
+/// Set the registered denominations for a token.
+pub fn set_denominations(env: &Env, token: &Address, denominations: &Vec<i128>) {
+    let key = DataKey::Denominations(token.clone());
+    env.storage().persistent().set(&key, denominations);
+}
+
+/// Get the registered denominations for a token.
+///
+/// **Contract**: Returns empty vec if none registered.
+pub fn get_denominations(env: &Env, token: &Address) -> Vec<i128> {
+    let key = DataKey::Denominations(token.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Set the approved deposit amounts for a token.
+pub fn set_amount_buckets(env: &Env, token: &Address, buckets: &Vec<i128>) {
+    let key = DataKey::AmountBuckets(token.clone());
+    env.storage().persistent().set(&key, buckets);
+}
+
+/// Get the approved deposit amounts for a token.
+///
+/// **Contract**: Returns empty vec if none approved.
+pub fn get_amount_buckets(env: &Env, token: &Address) -> Vec<i128> {
+    let key = DataKey::AmountBuckets(token.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+#[cfg(feature = "privacy-pool")]
+/// Set the state of a (token, denomination) pool.
+pub fn set_pool_state(env: &Env, token: &Address, denomination: i128, state: &PoolState) {
+    let key = DataKey::PoolState(token.clone(), denomination);
+    env.storage().persistent().set(&key, state);
+}
+
+#[cfg(feature = "privacy-pool")]
+/// Get the state of a (token, denomination) pool.
+///
+/// **Contract**: Returns a zeroed state if the pool has never received a deposit.
+pub fn get_pool_state(env: &Env, token: &Address, denomination: i128) -> PoolState {
+    let key = DataKey::PoolState(token.clone(), denomination);
+    env.storage().persistent().get(&key).unwrap_or(PoolState {
+        deposit_count: 0,
+        spent_count: 0,
+        pending_by_day: Map::new(env),
+    })
+}
+
+#[cfg(feature = "privacy-pool")]
+/// Mark a commitment as a pool note.
+pub fn mark_pool_note(env: &Env, commitment: &BytesN<32>) {
+    let key = DataKey::PoolNote(commitment.clone());
+    env.storage().persistent().set(&key, &true);
+}
+
+#[cfg(feature = "privacy-pool")]
+/// Check whether a commitment was deposited via a pool.
+pub fn is_pool_note(env: &Env, commitment: &BytesN<32>) -> bool {
+    let key = DataKey::PoolNote(commitment.clone());
+    env.storage().persistent().has(&key)
+}
+
+// -----------------------------------------------------------------------------
+// Shielded pool helpers
+// -----------------------------------------------------------------------------
+
+#[cfg(feature = "zk-verifier")]
+/// Set the Merkle tree of a (token, denomination) pool.
+pub fn set_pool_tree(env: &Env, token: &Address, denomination: i128, tree: &MerkleTree) {
+    let key = DataKey::PoolTree(token.clone(), denomination);
+    env.storage().persistent().set(&key, tree);
+}
+
+#[cfg(feature = "zk-verifier")]
+/// Get the Merkle tree of a (token, denomination) pool.
+///
+/// **Contract**: Returns `None` if no shielded note was ever deposited into the pool.
+pub fn get_pool_tree(env: &Env, token: &Address, denomination: i128) -> Option<MerkleTree> {
+    let key = DataKey::PoolTree(token.clone(), denomination);
+    env.storage().persistent().get(&key)
+}
+
+#[cfg(feature = "zk-verifier")]
+/// Mark a nullifier hash as spent.
+pub fn mark_nullifier(env: &Env, nullifier_hash: &BytesN<32>) {
+    let key = DataKey::Nullifier(nullifier_hash.clone());
+    env.storage().persistent().set(&key, &true);
+}
+
+#[cfg(feature = "zk-verifier")]
+/// Check whether a nullifier hash has been spent.
+pub fn is_nullifier_spent(env: &Env, nullifier_hash: &BytesN<32>) -> bool {
+    let key = DataKey::Nullifier(nullifier_hash.clone());
+    env.storage().persistent().has(&key)
+}
+
+#[cfg(feature = "zk-verifier")]
+/// Store a verifying key under its version.
+pub fn set_verifying_key(env: &Env, version: u32, record: &VerifyingKeyRecord) {
+    let key = DataKey::VerifyingKey(version);
+    env.storage().persistent().set(&key, record);
+}
+
+#[cfg(feature = "zk-verifier")]
+/// Get the verifying key registered as `version`.
+///
+/// **Contract**: Returns `None` if the version does not exist.
+pub fn get_verifying_key(env: &Env, version: u32) -> Option<VerifyingKeyRecord> {
+    let key = DataKey::VerifyingKey(version);
+    env.storage().persistent().get(&key)
+}
+
+#[cfg(feature = "zk-verifier")]
+/// Set the current verifying-key version.
+pub fn set_verifying_key_version(env: &Env, version: u32) {
+    let key = DataKey::VerifyingKeyVersion;
+    env.storage().persistent().set(&key, &version);
+}
+
+#[cfg(feature = "zk-verifier")]
+/// Get the current verifying-key version.
+///
+/// **Contract**: Returns 0 if no key has been registered.
+pub fn get_verifying_key_version(env: &Env) -> u32 {
+    let key = DataKey::VerifyingKeyVersion;
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+// -----------------------------------------------------------------------------
+// Ring note helpers
+// -----------------------------------------------------------------------------
+
+#[cfg(feature = "privacy-pool")]
+/// Put a ring note.
+pub fn set_ring_note(env: &Env, id: &BytesN<32>, note: &RingNote) {
+    let key = DataKey::RingNote(id.clone());
+    env.storage().persistent().set(&key, note);
+}
+
+#[cfg(feature = "privacy-pool")]
+/// Get a ring note.
+///
+/// **Contract**: Returns `None` if no note is registered under `id`.
+pub fn get_ring_note(env: &Env, id: &BytesN<32>) -> Option<RingNote> {
+    let key = DataKey::RingNote(id.clone());
+    env.storage().persistent().get(&key)
+}
+
+#[cfg(feature = "privacy-pool")]
+/// Mark a key image as spent.
+pub fn mark_key_image(env: &Env, key_image_id: &BytesN<32>) {
+    let key = DataKey::KeyImage(key_image_id.clone());
+    env.storage().persistent().set(&key, &true);
+}
+
+#[cfg(feature = "privacy-pool")]
+/// Check whether a key image has been spent.
+pub fn is_key_image_spent(env: &Env, key_image_id: &BytesN<32>) -> bool {
+    let key = DataKey::KeyImage(key_image_id.clone());
+    env.storage().persistent().has(&key)
+}
+
+// -----------------------------------------------------------------------------
+// Relayer helpers
+// -----------------------------------------------------------------------------
+
+/// Set the relayer staking configuration.
+pub fn set_relayer_config(env: &Env, config: &RelayerConfig) {
+    let key = DataKey::RelayerConfig;
+    env.storage().persistent().set(&key, config);
+}
+
+/// Get the relayer staking configuration.
+///
+/// **Contract**: Returns `None` if staking has not been configured.
+pub fn get_relayer_config(env: &Env) -> Option<RelayerConfig> {
+    let key = DataKey::RelayerConfig;
+    env.storage().persistent().get(&key)
+}
+
+/// Put a relayer stake record.
+pub fn set_relayer(env: &Env, relayer: &Address, info: &RelayerInfo) {
+    let key = DataKey::Relayer(relayer.clone());
+    env.storage().persistent().set(&key, info);
+}
+
+/// Get a relayer stake record.
+///
+/// **Contract**: Returns `None` if the relayer has never staked or has withdrawn.
+pub fn get_relayer(env: &Env, relayer: &Address) -> Option<RelayerInfo> {
+    let key = DataKey::Relayer(relayer.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Remove a relayer stake record.
+pub fn remove_relayer(env: &Env, relayer: &Address) {
+    let key = DataKey::Relayer(relayer.clone());
+    env.storage().persistent().remove(&key);
+}
+
+/// Most relayers stored in one [`RelayerBucket`](DataKey::RelayerBucket).
+pub const RELAYER_BUCKET_SIZE: u32 = 32;
+
+/// Empty buckets a page read skips before returning an empty page with a cursor.
+const RELAYER_PAGE_SCAN: u32 = 16;
+
+/// First live and last bucket number of the active relayer index. Buckets are
+/// numbered from 1; page 0 is the legacy [`ActiveRelayers`](DataKey::ActiveRelayers) list.
+fn get_relayer_buckets(env: &Env) -> (u32, u32) {
+    let key = DataKey::RelayerBuckets;
+    env.storage().persistent().get(&key).unwrap_or((1, 1))
+}
+
+fn get_relayer_bucket(env: &Env, bucket: u32) -> Vec<Address> {
+    let key = DataKey::RelayerBucket(bucket);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// One page of active relayers, in activation order, and the cursor of the next page.
+///
+/// Page 0 holds the relayers activated before buckets existed, and each later page one
+/// bucket. Runs of emptied buckets are skipped, [`RELAYER_PAGE_SCAN`] at a time.
+///
+/// **Contract**: Returns an empty page and `None` past the last bucket.
+pub fn get_active_relayer_page(env: &Env, cursor: u32) -> (Vec<Address>, Option<u32>) {
+    let (head, tail) = get_relayer_buckets(env);
+    if cursor == 0 {
+        let legacy: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRelayers)
+            .unwrap_or(Vec::new(env));
+        if !legacy.is_empty() {
+            return (legacy, Some(head));
+        }
+    }
+
+    let mut bucket = cursor.max(head);
+    for _ in 0..RELAYER_PAGE_SCAN {
+        if bucket > tail {
+            return (Vec::new(env), None);
+        }
+        let page = get_relayer_bucket(env, bucket);
+        if !page.is_empty() {
+            return (page, (bucket < tail).then_some(bucket + 1));
+        }
+        bucket += 1;
+    }
+    (Vec::new(env), (bucket <= tail).then_some(bucket))
+}
+
+/// Every active relayer, in activation order.
+///
+/// **Contract**: Returns empty vec if no relayer is active.
+pub fn get_active_relayers(env: &Env) -> Vec<Address> {
+    let mut relayers = Vec::new(env);
+    let mut cursor = Some(0);
+    while let Some(page) = cursor {
+        let (active, next) = get_active_relayer_page(env, page);
+        relayers.append(&active);
+        cursor = next;
+    }
+    relayers
+}
+
+/// Append `relayer` to the last bucket, starting a new one when it is full.
+pub fn add_active_relayer(env: &Env, relayer: &Address) {
+    let persistent = env.storage().persistent();
+    let (head, mut tail) = get_relayer_buckets(env);
+    let mut bucket = get_relayer_bucket(env, tail);
+    if bucket.len() >= RELAYER_BUCKET_SIZE {
+        tail += 1;
+        bucket = Vec::new(env);
+        persistent.set(&DataKey::RelayerBuckets, &(head, tail));
+    }
+    bucket.push_back(relayer.clone());
+    persistent.set(&DataKey::RelayerBucket(tail), &bucket);
+    persistent.set(&DataKey::RelayerSlot(relayer.clone()), &tail);
+}
+
+/// Remove `relayer` from the active index, if present.
+///
+/// **Contract**: Relayers without a slot are looked up in the legacy list.
+pub fn remove_active_relayer(env: &Env, relayer: &Address) {
+    let persistent = env.storage().persistent();
+    let slot = DataKey::RelayerSlot(relayer.clone());
+    let Some(number) = persistent.get::<_, u32>(&slot) else {
+        remove_legacy_active_relayer(env, relayer);
+        return;
+    };
+    persistent.remove(&slot);
+
+    let (mut head, tail) = get_relayer_buckets(env);
+    let key = DataKey::RelayerBucket(number);
+    let mut bucket = get_relayer_bucket(env, number);
+    let Some(i) = bucket.first_index_of(relayer) else {
+        return;
+    };
+    bucket.remove(i);
+    if !bucket.is_empty() || number == tail {
+        persistent.set(&key, &bucket);
+        return;
+    }
+    persistent.remove(&key);
+    if number == head {
+        while head < tail && !persistent.has(&DataKey::RelayerBucket(head)) {
+            head += 1;
+        }
+        persistent.set(&DataKey::RelayerBuckets, &(head, tail));
+    }
+}
+
+fn remove_legacy_active_relayer(env: &Env, relayer: &Address) {
+    let key = DataKey::ActiveRelayers;
+    let persistent = env.storage().persistent();
+    let Some(mut active) = persistent.get::<_, Vec<Address>>(&key) else {
+        return;
+    };
+    if let Some(i) = active.first_index_of(relayer) {
+        active.remove(i);
+        if active.is_empty() {
+            persistent.remove(&key);
+        } else {
+            persistent.set(&key, &active);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Admin helpers
+// -----------------------------------------------------------------------------
+
+/// Set admin address.
+#[allow(dead_code)]
+pub fn set_admin(env: &Env, admin: &Address) {
+    let key = DataKey::Admin;
+    env.storage().instance().set(&key, admin);
+}
+
+/// Get admin address.
+///
+/// **Contract**: Falls back to the persistent entry written before schema 4, until
+/// `migrate` moves it to instance storage.
+#[allow(dead_code)]
+pub fn get_admin(env: &Env) -> Option<Address> {
+    let key = DataKey::Admin;
+    env.storage()
+        .instance()
+        .get(&key)
+        .or_else(|| env.storage().persistent().get(&key))
+}
+
+/// Remove the admin address.
+pub fn remove_admin(env: &Env) {
+    let key = DataKey::Admin;
+    env.storage().instance().remove(&key);
+    env.storage().persistent().remove(&key);
+}
+
+/// Set the storage schema version.
+pub fn set_schema_version(env: &Env, version: u32) {
+    let key = DataKey::SchemaVersion;
+    env.storage().persistent().set(&key, &version);
+}
+
+/// Get the storage schema version, if one was ever recorded.
+pub fn get_schema_version(env: &Env) -> Option<u32> {
+    let key = DataKey::SchemaVersion;
+    env.storage().persistent().get(&key)
+}
+
+/// Record or clear the upgrade awaiting migration.
+pub fn set_pending_upgrade(env: &Env, record: Option<&UpgradeRecord>) {
+    let key = DataKey::PendingUpgrade;
+    match record {
+        Some(record) => env.storage().persistent().set(&key, record),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Record the WASM hash installed by an upgrade.
+pub fn set_wasm_hash(env: &Env, wasm_hash: &BytesN<32>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::WasmHash, wasm_hash);
+}
+
+/// Get the WASM hash installed by the last upgrade. `None` until the first upgrade,
+/// since a contract cannot read its own code hash.
+pub fn get_wasm_hash(env: &Env) -> Option<BytesN<32>> {
+    env.storage().persistent().get(&DataKey::WasmHash)
+}
+
+/// Get the upgrade awaiting migration, if any.
+pub fn get_pending_upgrade(env: &Env) -> Option<UpgradeRecord> {
+    let key = DataKey::PendingUpgrade;
+    env.storage().persistent().get(&key)
+}
+
+/// Set or clear the recovery address.
+pub fn set_recovery_address(env: &Env, recovery: Option<&Address>) {
+    let key = DataKey::RecoveryAddress;
+    match recovery {
+        Some(recovery) => env.storage().persistent().set(&key, recovery),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Get the recovery address, if one is set.
+pub fn get_recovery_address(env: &Env) -> Option<Address> {
+    let key = DataKey::RecoveryAddress;
+    env.storage().persistent().get(&key)
+}
+
+/// Record when the admin last authorized an admin call.
+pub fn set_admin_last_active(env: &Env, timestamp: u64) {
+    let key = DataKey::AdminLastActive;
+    env.storage().persistent().set(&key, &timestamp);
+}
+
+/// Get when the admin last authorized an admin call (0 if never recorded).
+pub fn get_admin_last_active(env: &Env) -> u64 {
+    let key = DataKey::AdminLastActive;
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Set or clear the guardian address.
+pub fn set_guardian(env: &Env, guardian: Option<&Address>) {
+    let key = DataKey::Guardian;
+    match guardian {
+        Some(guardian) => env.storage().persistent().set(&key, guardian),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Get the guardian address, if one is set.
+pub fn get_guardian(env: &Env) -> Option<Address> {
+    let key = DataKey::Guardian;
+    env.storage().persistent().get(&key)
+}
+
+/// Record or clear the ledger at which an admin renounce was scheduled.
+pub fn set_renounce_scheduled(env: &Env, ledger: Option<u32>) {
+    let key = DataKey::RenounceScheduled;
+    match ledger {
+        Some(ledger) => env.storage().persistent().set(&key, &ledger),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Ledger at which an admin renounce was scheduled, if any.
+pub fn get_renounce_scheduled(env: &Env) -> Option<u32> {
+    let key = DataKey::RenounceScheduled;
+    env.storage().persistent().get(&key)
+}
+
+/// Permanently mark the admin as renounced.
+pub fn set_admin_renounced(env: &Env) {
+    let key = DataKey::AdminRenounced;
+    env.storage().persistent().set(&key, &true);
+}
+
+/// Whether the admin has been renounced.
+pub fn is_admin_renounced(env: &Env) -> bool {
+    let key = DataKey::AdminRenounced;
+    env.storage().persistent().has(&key)
+}
+
+/// Set the admin council.
+pub fn set_admin_council(env: &Env, council: &AdminCouncil) {
+    let key = DataKey::AdminCouncil;
+    env.storage().persistent().set(&key, council);
+}
+
+/// Get the admin council, if multisig mode is on.
+pub fn get_admin_council(env: &Env) -> Option<AdminCouncil> {
+    let key = DataKey::AdminCouncil;
+    env.storage().persistent().get(&key)
+}
+
+/// Remove the admin council, leaving multisig mode.
+pub fn remove_admin_council(env: &Env) {
+    let key = DataKey::AdminCouncil;
+    env.storage().persistent().remove(&key);
+}
+
+/// Allocate the next council action id.
+pub fn next_admin_action_id(env: &Env) -> u64 {
+    let key = DataKey::AdminActionCounter;
+    let id: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().set(&key, &(id + 1));
+    id
+}
+
+/// Store a pending council action.
+pub fn put_admin_action(env: &Env, id: u64, action: &PendingAdminAction) {
+    let key = DataKey::AdminAction(id);
+    env.storage().persistent().set(&key, action);
+}
+
+/// Get a pending council action.
+pub fn get_admin_action(env: &Env, id: u64) -> Option<PendingAdminAction> {
+    let key = DataKey::AdminAction(id);
+    env.storage().persistent().get(&key)
+}
+
+/// Remove a council action once executed.
+pub fn remove_admin_action(env: &Env, id: u64) {
+    let key = DataKey::AdminAction(id);
+    env.storage().persistent().remove(&key);
+}
+
+/// Set the timelock delay in seconds.
+pub fn set_timelock_delay(env: &Env, delay: u64) {
+    let key = DataKey::TimelockDelay;
+    env.storage().persistent().set(&key, &delay);
+}
+
+/// Get the timelock delay in seconds (0 if never set).
+pub fn get_timelock_delay(env: &Env) -> u64 {
+    let key = DataKey::TimelockDelay;
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Store a queued timelocked action.
+pub fn put_queued_action(env: &Env, id: u64, action: &QueuedAdminAction) {
+    let key = DataKey::QueuedAction(id);
+    env.storage().persistent().set(&key, action);
+}
+
+/// Get a queued timelocked action.
+pub fn get_queued_action(env: &Env, id: u64) -> Option<QueuedAdminAction> {
+    let key = DataKey::QueuedAction(id);
+    env.storage().persistent().get(&key)
+}
+
+/// Remove a queued action once executed or cancelled.
+pub fn remove_queued_action(env: &Env, id: u64) {
+    let key = DataKey::QueuedAction(id);
+    env.storage().persistent().remove(&key);
+}
+
+/// Set or clear the queued action id of the scheduled upgrade.
+pub fn set_scheduled_upgrade(env: &Env, id: Option<u64>) {
+    let key = DataKey::ScheduledUpgrade;
+    match id {
+        Some(id) => env.storage().persistent().set(&key, &id),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Get the queued action id of the scheduled upgrade, if any.
+pub fn get_scheduled_upgrade(env: &Env) -> Option<u64> {
+    let key = DataKey::ScheduledUpgrade;
+    env.storage().persistent().get(&key)
+}
+
+/// Grant or revoke `role` for `account`.
+pub fn set_role(env: &Env, role: Role, account: &Address, granted: bool) {
+    let key = DataKey::Role(role, account.clone());
+    if granted {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Whether `account` has been granted `role`.
+pub fn has_role(env: &Env, role: Role, account: &Address) -> bool {
+    let key = DataKey::Role(role, account.clone());
+    env.storage().persistent().has(&key)
+}
+
+/// Pause (`true`) or unpause (`false`) every operation.
+#[allow(dead_code)]
+pub fn set_paused(env: &Env, paused: bool) {
+    set_paused_ops(env, if paused { PAUSE_ALL } else { 0 });
+}
+
+/// Whether any operation is paused.
+#[allow(dead_code)]
+pub fn is_paused(env: &Env) -> bool {
+    get_paused_ops(env) != 0
+}
+
+/// Set the bitmask of paused operations.
+pub fn set_paused_ops(env: &Env, ops: u32) {
+    update_config(env, |config| config.paused_ops = ops);
+}
+
+/// Get the bitmask of paused operations.
+pub fn get_paused_ops(env: &Env) -> u32 {
+    crate::config::get(env).paused_ops
+}
+
+/// Get the paused-operation mask stored before it moved into the config (schema ≤ 2).
+///
+/// **Contract**: Falls back to the legacy `Paused` flag (all or nothing) if the mask
+/// was never written.
+pub fn get_legacy_paused_ops(env: &Env) -> u32 {
+    if let Some(ops) = env.storage().persistent().get(&DataKey::PausedOps) {
+        return ops;
+    }
+    let legacy: bool = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Paused)
+        .unwrap_or(false);
+    if legacy {
+        PAUSE_ALL
+    } else {
+        0
+    }
+}
+
+/// Most commitments stored in one [`OwnerBucket`](DataKey::OwnerBucket).
+pub const OWNER_BUCKET_SIZE: u32 = 32;
+
+/// Empty buckets a page read skips before returning an empty page with a cursor.
+const OWNER_PAGE_SCAN: u32 = 16;
+
+/// First live and last bucket number of `owner`'s index. Buckets are numbered from 1;
+/// page 0 is the legacy [`OwnerIndex`](DataKey::OwnerIndex) list.
+fn get_owner_buckets(env: &Env, owner: &Address) -> (u32, u32) {
+    let key = DataKey::OwnerBuckets(owner.clone());
+    env.storage().persistent().get(&key).unwrap_or((1, 1))
+}
+
+fn get_owner_bucket(env: &Env, owner: &Address, bucket: u32) -> Vec<BytesN<32>> {
+    let key = DataKey::OwnerBucket(owner.clone(), bucket);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// One page of `owner`'s index, oldest first, and the cursor of the next page.
+///
+/// Page 0 holds the commitments indexed before buckets existed, and each later page
+/// one bucket. Runs of emptied buckets are skipped, [`OWNER_PAGE_SCAN`] at a time.
+///
+/// **Contract**: Returns an empty page and `None` past the last bucket.
+pub fn get_owner_page(env: &Env, owner: &Address, cursor: u32) -> (Vec<BytesN<32>>, Option<u32>) {
+    let (head, tail) = get_owner_buckets(env, owner);
+    if cursor == 0 {
+        let legacy: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OwnerIndex(owner.clone()))
+            .unwrap_or(Vec::new(env));
+        if !legacy.is_empty() {
+            return (legacy, Some(head));
+        }
+    }
+
+    let mut bucket = cursor.max(head);
+    for _ in 0..OWNER_PAGE_SCAN {
+        if bucket > tail {
+            return (Vec::new(env), None);
+        }
+        let page = get_owner_bucket(env, owner, bucket);
+        if !page.is_empty() {
+            return (page, (bucket < tail).then_some(bucket + 1));
+        }
+        bucket += 1;
+    }
+    (Vec::new(env), (bucket <= tail).then_some(bucket))
+}
+
+/// Every commitment in `owner`'s index, oldest first.
+pub fn get_owner_index(env: &Env, owner: &Address) -> Vec<BytesN<32>> {
+    let mut index = Vec::new(env);
+    let mut cursor = Some(0);
+    while let Some(page) = cursor {
+        let (commitments, next) = get_owner_page(env, owner, page);
+        index.append(&commitments);
+        cursor = next;
+    }
+    index
+}
+
+/// Append `commitment` to `owner`'s last bucket, starting a new one when it is full.
+pub fn add_to_owner_index(env: &Env, owner: &Address, commitment: &BytesN<32>) {
+    let persistent = env.storage().persistent();
+    let (head, mut tail) = get_owner_buckets(env, owner);
+    let mut bucket = get_owner_bucket(env, owner, tail);
+    if bucket.len() >= OWNER_BUCKET_SIZE {
+        tail += 1;
+        bucket = Vec::new(env);
+        persistent.set(&DataKey::OwnerBuckets(owner.clone()), &(head, tail));
+    }
+    bucket.push_back(commitment.clone());
+    persistent.set(&DataKey::OwnerBucket(owner.clone(), tail), &bucket);
+    persistent.set(&DataKey::OwnerSlot(commitment.clone()), &tail);
+}
+
+/// Remove `commitment` from `owner`'s index, if present.
+///
+/// **Contract**: Commitments without a slot are looked up in the legacy list.
+pub fn remove_from_owner_index(env: &Env, owner: &Address, commitment: &BytesN<32>) {
+    let persistent = env.storage().persistent();
+    let slot = DataKey::OwnerSlot(commitment.clone());
+    let Some(number) = persistent.get::<_, u32>(&slot) else {
+        remove_from_legacy_owner_index(env, owner, commitment);
+        return;
+    };
+    persistent.remove(&slot);
+
+    let (mut head, tail) = get_owner_buckets(env, owner);
+    if number < head {
+        // The bucket was dropped by `clear_owner_index`.
+        return;
+    }
+    let key = DataKey::OwnerBucket(owner.clone(), number);
+    let mut bucket = get_owner_bucket(env, owner, number);
+    let Some(i) = bucket.first_index_of(commitment) else {
+        return;
+    };
+    bucket.remove(i);
+    if !bucket.is_empty() || number == tail {
+        persistent.set(&key, &bucket);
+        return;
+    }
+    persistent.remove(&key);
+    if number == head {
+        while head < tail && !persistent.has(&DataKey::OwnerBucket(owner.clone(), head)) {
+            head += 1;
+        }
+        persistent.set(&DataKey::OwnerBuckets(owner.clone()), &(head, tail));
+    }
+}
+
+fn remove_from_legacy_owner_index(env: &Env, owner: &Address, commitment: &BytesN<32>) {
+    let key = DataKey::OwnerIndex(owner.clone());
+    let persistent = env.storage().persistent();
+    let Some(mut index) = persistent.get::<_, Vec<BytesN<32>>>(&key) else {
+        return;
+    };
+    if let Some(i) = index.first_index_of(commitment) {
+        index.remove(i);
+        if index.is_empty() {
+            persistent.remove(&key);
+        } else {
+            persistent.set(&key, &index);
+        }
+    }
+}
+
+/// Drop `owner`'s whole index.
+///
+/// **Contract**: Constant cost: the bucket range moves past the last bucket, and the
+/// dropped buckets are left to expire with their TTL.
+pub fn clear_owner_index(env: &Env, owner: &Address) {
+    let persistent = env.storage().persistent();
+    let (_, tail) = get_owner_buckets(env, owner);
+    persistent.set(&DataKey::OwnerBuckets(owner.clone()), &(tail + 1, tail + 1));
+    persistent.remove(&DataKey::OwnerIndex(owner.clone()));
+}
+
+/// Set the minimum salt length policy.
+pub fn set_salt_policy(env: &Env, policy: &SaltPolicy) {
+    let key = DataKey::SaltPolicy;
+    env.storage().persistent().set(&key, policy);
+}
+
+/// Get the minimum salt length policy, if one has been set.
+pub fn get_salt_policy(env: &Env) -> Option<SaltPolicy> {
+    let key = DataKey::SaltPolicy;
+    env.storage().persistent().get(&key)
+}
+
+// -----------------------------------------------------------------------------
+// Privacy helpers (level-based API)
+// -----------------------------------------------------------------------------
+
+/// Set privacy level for an account.
+pub fn set_privacy_level(env: &Env, account: &Address, level: u32) {
+    let key = DataKey::PrivacyLevel(account.clone());
+    env.storage().persistent().set(&key, &level);
+}
+
+/// Get privacy level for an account.
+pub fn get_privacy_level(env: &Env, account: &Address) -> Option<u32> {
+    let key = DataKey::PrivacyLevel(account.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Set the boolean privacy toggle for an account.
+///
+/// **Contract**: Also removes the account's legacy `privacy_enabled` key.
+pub fn set_privacy_enabled(env: &Env, account: &Address, enabled: bool) {
+    let persistent = env.storage().persistent();
+    persistent.set(&DataKey::PrivacyEnabled(account.clone()), &enabled);
+    persistent.remove(&legacy_privacy_key(env, account));
+}
+
+/// Get the boolean privacy toggle for an account.
+///
+/// **Contract**: Falls back to the legacy `privacy_enabled` key, then `false`.
+pub fn get_privacy_enabled(env: &Env, account: &Address) -> bool {
+    let persistent = env.storage().persistent();
+    persistent
+        .get(&DataKey::PrivacyEnabled(account.clone()))
+        .or_else(|| persistent.get(&legacy_privacy_key(env, account)))
+        .unwrap_or(false)
+}
+
+fn legacy_privacy_key(env: &Env, account: &Address) -> (Symbol, Address) {
+    (
+        Symbol::new(env, LEGACY_PRIVACY_ENABLED_KEY),
+        account.clone(),
+    )
+}
+
+/// Add to privacy history for an account.
+///
+/// **Contract**: Pushes `level` with the current ledger timestamp to the front of the
+/// history (newest first). Once [`PRIVACY_HISTORY_CAP`] entries are stored, the oldest
+/// entry is dropped.
+pub fn add_privacy_history(env: &Env, account: &Address, level: u32) {
+    let key = DataKey::PrivacyLog(account.clone());
+    let mut history = get_privacy_history(env, account);
+    history.push_front(PrivacyHistoryEntry {
+        level,
+        timestamp: env.ledger().timestamp(),
+    });
+    if history.len() > PRIVACY_HISTORY_CAP {
+        history.pop_back();
+    }
+    env.storage().persistent().set(&key, &history);
+}
+
+/// Get privacy history for an account.
+///
+/// **Contract**: Returns empty vec if never set. Order is newest-first.
+pub fn get_privacy_history(env: &Env, account: &Address) -> Vec<PrivacyHistoryEntry> {
+    let key = DataKey::PrivacyLog(account.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Set the withdrawal rate limit for an account.
+pub fn set_withdraw_rate_limit(env: &Env, account: &Address, limit: &WithdrawRateLimit) {
+    let key = DataKey::WithdrawRateLimit(account.clone());
+    env.storage().persistent().set(&key, limit);
+}
+
+/// Get the withdrawal rate limit for an account.
+pub fn get_withdraw_rate_limit(env: &Env, account: &Address) -> Option<WithdrawRateLimit> {
+    let key = DataKey::WithdrawRateLimit(account.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Remove the withdrawal rate limit and current window for an account.
+pub fn remove_withdraw_rate_limit(env: &Env, account: &Address) {
+    let persistent = env.storage().persistent();
+    persistent.remove(&DataKey::WithdrawRateLimit(account.clone()));
+    persistent.remove(&DataKey::WithdrawWindow(account.clone()));
+}
+
+/// Set the current withdrawal window for an account.
+pub fn set_withdraw_window(env: &Env, account: &Address, window: &WithdrawWindow) {
+    let key = DataKey::WithdrawWindow(account.clone());
+    env.storage().persistent().set(&key, window);
+}
+
+/// Get the current withdrawal window for an account.
+pub fn get_withdraw_window(env: &Env, account: &Address) -> Option<WithdrawWindow> {
+    let key = DataKey::WithdrawWindow(account.clone());
+    env.storage().persistent().get(&key)
 }
@@ -1,10 +1,12 @@
-use soroban_sdk::{testutils::Address as _, Address, Bytes, Env};
+use soroban_sdk::{testutils::Address as _, xdr::ToXdr, Address, Bytes, Env};
 
 use crate::{
     storage::*,
-    types::{EscrowEntry, EscrowStatus},
+    types::{EscrowEntry, EscrowStatus, PackedEscrowEntry},
 };
 
+extern crate std;
+
 #[test]
 fn test_escrow_storage() {
     let env = Env::default();
@@ -47,6 +49,47 @@ fn test_escrow_storage() {
     });
 }
 
+#[test]
+fn test_packed_escrow_encoding_is_smaller_and_reads_legacy_entries() {
+    let env = Env::default();
+    let contract_id = env.register(crate::QuickexContract, ());
+    env.as_contract(&contract_id, || {
+        let commitment: Bytes = Bytes::from_array(&env, &[5u8; 32]);
+        let entry = EscrowEntry {
+            token: Address::generate(&env),
+            amount: 1_000_000,
+            owner: Address::generate(&env),
+            status: EscrowStatus::Refunded,
+            created_at: 1_700_000_000,
+            expires_at: 1_700_086_400,
+        };
+
+        put_escrow(&env, &commitment, &entry);
+        let stored: PackedEscrowEntry = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(commitment.clone()))
+            .unwrap();
+        let packed_len = stored.to_xdr(&env).len();
+        let map_len = entry.clone().to_xdr(&env).len();
+        std::println!("EscrowEntry XDR: {map_len} bytes as a map, {packed_len} packed");
+        assert!(packed_len * 10 <= map_len * 7);
+
+        let decoded = get_escrow(&env, &commitment).unwrap();
+        assert_eq!(decoded.status, EscrowStatus::Refunded);
+        assert_eq!(decoded.expires_at, entry.expires_at);
+
+        // Entries written before packing still decode.
+        let legacy: Bytes = Bytes::from_array(&env, &[6u8; 32]);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(legacy.clone()), &entry);
+        let decoded = get_escrow(&env, &legacy).unwrap();
+        assert_eq!(decoded.amount, entry.amount);
+        assert_eq!(decoded.status, EscrowStatus::Refunded);
+    });
+}
+
 #[test]
 fn test_escrow_status_update() {
     let env = Env::default();
@@ -141,6 +184,20 @@ fn test_paused_storage() {
     });
 }
 
+#[test]
+fn test_legacy_paused_flag_pauses_all_ops() {
+    let env = Env::default();
+    let contract_id = env.register(crate::QuickexContract, ());
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(&DataKey::Paused, &true);
+        assert_eq!(get_paused_ops(&env), crate::admin::PAUSE_ALL);
+
+        // Once written, the mask takes precedence over the legacy flag.
+        set_paused_ops(&env, 0);
+        assert!(!is_paused(&env));
+    });
+}
+
 #[test]
 fn test_privacy_storage() {
     let env = Env::default();
@@ -168,9 +225,9 @@ fn test_privacy_storage() {
 
         let history = get_privacy_history(&env, &account);
         assert_eq!(history.len(), 3);
-        assert_eq!(history.get(0).unwrap(), 25u32);
-        assert_eq!(history.get(1).unwrap(), 20u32);
-        assert_eq!(history.get(2).unwrap(), 15u32);
+        assert_eq!(history.get(0).unwrap().level, 25u32);
+        assert_eq!(history.get(1).unwrap().level, 20u32);
+        assert_eq!(history.get(2).unwrap().level, 15u32);
 
         // Test non-existent privacy level
         let non_existent_account = Address::generate(&env);
@@ -178,3 +235,20 @@ fn test_privacy_storage() {
         assert_eq!(get_privacy_history(&env, &non_existent_account).len(), 0);
     });
 }
+
+#[test]
+fn test_privacy_history_is_capped() {
+    let env = Env::default();
+    let contract_id = env.register(crate::QuickexContract, ());
+    env.as_contract(&contract_id, || {
+        let account = Address::generate(&env);
+        for level in 0..PRIVACY_HISTORY_CAP + 5 {
+            add_privacy_history(&env, &account, level);
+        }
+
+        let history = get_privacy_history(&env, &account);
+        assert_eq!(history.len(), PRIVACY_HISTORY_CAP);
+        assert_eq!(history.first().unwrap().level, PRIVACY_HISTORY_CAP + 4);
+        assert_eq!(history.last().unwrap().level, 5);
+    });
+}
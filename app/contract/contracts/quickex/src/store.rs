@@ -0,0 +1,58 @@
+//! Storage seam for escrow business logic.
+//!
+//! The withdrawal and refund rules (commitment lookup, status, reservation, expiry,
+//! amount, token, owner) only need a handful of reads. [`EscrowStore`] names them so
+//! that logic can run against the contract's Soroban storage, which `Env` implements,
+//! or against an in-memory double in unit tests, without registering the contract,
+//! minting tokens, or making deposits to reach each case.
+
+use soroban_sdk::{Bytes, BytesN, Env};
+
+use crate::{storage, types::EscrowEntry};
+
+/// Reads the escrow state machine depends on.
+pub(crate) trait EscrowStore {
+    /// Host environment, for hashing and host types.
+    fn env(&self) -> &Env;
+
+    /// The escrow stored under `commitment`, with its current status.
+    fn escrow(&self, commitment: &Bytes) -> Option<EscrowEntry>;
+
+    /// Whether an escrow exists, or ever existed, for `commitment`.
+    fn has_escrow(&self, commitment: &Bytes) -> bool;
+
+    /// Whether the escrow for `commitment` was spent or refunded.
+    fn is_closed(&self, commitment: &Bytes) -> bool;
+
+    /// Whether a two-phase withdrawal currently reserves the escrow.
+    fn is_reserved(&self, commitment: &BytesN<32>) -> bool;
+
+    /// Current ledger timestamp, in seconds.
+    fn now(&self) -> u64;
+}
+
+impl EscrowStore for Env {
+    fn env(&self) -> &Env {
+        self
+    }
+
+    fn escrow(&self, commitment: &Bytes) -> Option<EscrowEntry> {
+        storage::get_escrow(self, commitment)
+    }
+
+    fn has_escrow(&self, commitment: &Bytes) -> bool {
+        storage::has_escrow(self, commitment)
+    }
+
+    fn is_closed(&self, commitment: &Bytes) -> bool {
+        storage::is_closed(self, commitment)
+    }
+
+    fn is_reserved(&self, commitment: &BytesN<32>) -> bool {
+        storage::is_reserved(self, commitment)
+    }
+
+    fn now(&self) -> u64 {
+        self.ledger().timestamp()
+    }
+}
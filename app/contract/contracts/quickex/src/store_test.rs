@@ -0,0 +1,185 @@
+//! Escrow rules run against an in-memory [`EscrowStore`].
+//!
+//! The `Env` only provides host types and hashing: no contract is registered, so each
+//! case is set up by writing entries directly instead of making deposits.
+
+use soroban_sdk::{testutils::Address as _, Address, Bytes, BytesN, Env, Map, Vec};
+
+use crate::{
+    commitment,
+    errors::QuickexError,
+    escrow::{resolve_commitment, validate_refund, validate_withdrawal},
+    store::EscrowStore,
+    types::{EscrowEntry, EscrowStatus},
+};
+
+/// In-memory test double for the contract's escrow storage.
+struct MemoryStore {
+    env: Env,
+    escrows: Map<Bytes, EscrowEntry>,
+    closed: Vec<Bytes>,
+    reserved: Vec<BytesN<32>>,
+    now: u64,
+}
+
+impl MemoryStore {
+    fn new(env: &Env) -> Self {
+        MemoryStore {
+            env: env.clone(),
+            escrows: Map::new(env),
+            closed: Vec::new(env),
+            reserved: Vec::new(env),
+            now: 1_000,
+        }
+    }
+
+    fn put(&mut self, commitment: &BytesN<32>, entry: &EscrowEntry) {
+        self.escrows.set(commitment.clone().into(), entry.clone());
+    }
+}
+
+impl EscrowStore for MemoryStore {
+    fn env(&self) -> &Env {
+        &self.env
+    }
+
+    fn escrow(&self, commitment: &Bytes) -> Option<EscrowEntry> {
+        self.escrows.get(commitment.clone())
+    }
+
+    fn has_escrow(&self, commitment: &Bytes) -> bool {
+        self.escrows.contains_key(commitment.clone()) || self.closed.contains(commitment)
+    }
+
+    fn is_closed(&self, commitment: &Bytes) -> bool {
+        self.closed.contains(commitment)
+    }
+
+    fn is_reserved(&self, commitment: &BytesN<32>) -> bool {
+        self.reserved.contains(commitment)
+    }
+
+    fn now(&self) -> u64 {
+        self.now
+    }
+}
+
+fn pending(token: &Address, owner: &Address, amount: i128) -> EscrowEntry {
+    EscrowEntry {
+        token: token.clone(),
+        amount,
+        owner: owner.clone(),
+        status: EscrowStatus::Pending,
+        created_at: 0,
+        expires_at: 0,
+    }
+}
+
+#[test]
+fn test_resolve_commitment_prefers_newest_scheme() {
+    let env = Env::default();
+    let mut store = MemoryStore::new(&env);
+    let token = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"store_salt");
+    let entry = pending(&token, &owner, 100);
+
+    let v1 = commitment::create_amount_commitment(&env, owner.clone(), 100, salt.clone()).unwrap();
+    let v2 =
+        commitment::create_token_commitment(&env, token.clone(), owner.clone(), 100, salt.clone())
+            .unwrap();
+    let v3 =
+        commitment::create_fixed_commitment(&env, token.clone(), owner.clone(), 100, salt.clone())
+            .unwrap();
+
+    // Nothing stored: falls back to v1, which the caller then fails to load.
+    let resolve =
+        |store: &MemoryStore| resolve_commitment(store, &token, &owner, 100, salt.clone()).unwrap();
+    assert_eq!(resolve(&store), v1);
+
+    store.put(&v2, &entry);
+    assert_eq!(resolve(&store), v2);
+
+    store.put(&v3, &entry);
+    assert_eq!(resolve(&store), v3);
+}
+
+#[test]
+fn test_validate_withdrawal_rules() {
+    let env = Env::default();
+    let mut store = MemoryStore::new(&env);
+    let token = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[1; 32]);
+    let check = |store: &MemoryStore, token: &Address, amount: i128| {
+        validate_withdrawal(store, &commitment, token, amount)
+    };
+
+    assert_eq!(
+        check(&store, &token, 100).err(),
+        Some(QuickexError::CommitmentNotFound)
+    );
+
+    let mut entry = pending(&token, &owner, 100);
+    store.put(&commitment, &entry);
+    assert!(check(&store, &token, 100).is_ok());
+    assert_eq!(
+        check(&store, &token, 99).err(),
+        Some(QuickexError::AmountMismatch)
+    );
+    assert_eq!(
+        check(&store, &Address::generate(&env), 100).err(),
+        Some(QuickexError::TokenMismatch)
+    );
+
+    entry.expires_at = store.now;
+    store.put(&commitment, &entry);
+    assert_eq!(
+        check(&store, &token, 100).err(),
+        Some(QuickexError::EscrowExpired)
+    );
+
+    entry.expires_at = store.now + 1;
+    store.put(&commitment, &entry);
+    store.reserved.push_back(commitment.clone());
+    assert_eq!(
+        check(&store, &token, 100).err(),
+        Some(QuickexError::EscrowReserved)
+    );
+
+    store.closed.push_back(commitment.clone().into());
+    assert_eq!(
+        check(&store, &token, 100).err(),
+        Some(QuickexError::AlreadySpent)
+    );
+}
+
+#[test]
+fn test_validate_refund_rules() {
+    let env = Env::default();
+    let mut store = MemoryStore::new(&env);
+    let token = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[2; 32]);
+
+    let mut entry = pending(&token, &owner, 100);
+    store.put(&commitment, &entry);
+    assert_eq!(
+        validate_refund(&store, &commitment, &owner).err(),
+        Some(QuickexError::EscrowNotExpired)
+    );
+
+    entry.expires_at = store.now;
+    store.put(&commitment, &entry);
+    assert!(validate_refund(&store, &commitment, &owner).is_ok());
+    assert_eq!(
+        validate_refund(&store, &commitment, &Address::generate(&env)).err(),
+        Some(QuickexError::InvalidOwner)
+    );
+
+    store.reserved.push_back(commitment.clone());
+    assert_eq!(
+        validate_refund(&store, &commitment, &owner).err(),
+        Some(QuickexError::EscrowReserved)
+    );
+}
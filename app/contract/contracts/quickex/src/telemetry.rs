@@ -0,0 +1,87 @@
+//! # Withdrawal telemetry
+//!
+//! Repeated rejected withdrawals against commitments can mean someone is probing
+//! for salts. When the admin turns telemetry on, [`attempt_withdraw`] counts each
+//! rejection per [`WithdrawRejection`] class and emits `WithdrawRejected`, so
+//! operators can alert on a spike.
+//!
+//! A failed invocation rolls back every write and event, so a rejected `withdraw`
+//! cannot leave a record. `attempt_withdraw` therefore returns `Ok(None)` for a
+//! counted rejection instead of failing. The classes it counts
+//! are all raised before the withdrawal writes anything, so only the counter and
+//! the event are kept. Other errors, and every error while telemetry is off, fail
+//! the call as `withdraw` does.
+
+use soroban_sdk::{Address, Bytes, BytesN, Env, MuxedAddress};
+
+use crate::{
+    admin,
+    errors::QuickexError,
+    escrow, events, storage,
+    types::{WithdrawReceipt, WithdrawRejection},
+};
+
+/// Telemetry class of a withdrawal error, if it is one that is counted.
+fn classify(error: QuickexError) -> Option<WithdrawRejection> {
+    match error {
+        QuickexError::CommitmentNotFound | QuickexError::CommitmentMismatch => {
+            Some(WithdrawRejection::WrongSalt)
+        }
+        QuickexError::AlreadySpent => Some(WithdrawRejection::Spent),
+        QuickexError::EscrowExpired => Some(WithdrawRejection::Expired),
+        _ => None,
+    }
+}
+
+/// Withdraw like [`escrow::withdraw`], counting rejections while telemetry is on.
+///
+/// Returns the receipt, or `None` if the withdrawal was rejected and counted.
+///
+/// # Errors
+/// - Any error from [`escrow::withdraw`], except a counted rejection while telemetry
+///   is on.
+pub fn attempt_withdraw(
+    env: &Env,
+    token: Address,
+    amount: i128,
+    commitment: BytesN<32>,
+    to: MuxedAddress,
+    salt: Bytes,
+) -> Result<Option<WithdrawReceipt>, QuickexError> {
+    let owner = to.address();
+    let error = match escrow::withdraw(env, token, amount, Some(commitment.clone()), to, salt) {
+        Ok(receipt) => return Ok(Some(receipt)),
+        Err(error) => error,
+    };
+    match classify(error) {
+        Some(class) if storage::is_withdraw_telemetry(env) => {
+            let count = storage::increment_rejected_withdrawals(env, class);
+            events::publish_withdraw_rejected(env, commitment, owner, class, count);
+            Ok(None)
+        }
+        _ => Err(error),
+    }
+}
+
+/// Turn counting of rejected withdrawals on or off (**admin only**).
+///
+/// Counts are kept when telemetry is turned off and resume when it is turned back on.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+pub fn set_enabled(env: &Env, caller: Address, enabled: bool) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+    storage::set_withdraw_telemetry(env, enabled);
+    events::publish_withdraw_telemetry_updated(env, enabled, caller);
+    Ok(())
+}
+
+/// Whether rejected withdrawals are counted.
+pub fn is_enabled(env: &Env) -> bool {
+    storage::is_withdraw_telemetry(env)
+}
+
+/// Number of rejected withdrawals counted for `class`.
+pub fn rejected(env: &Env, class: WithdrawRejection) -> u64 {
+    storage::get_rejected_withdrawals(env, class)
+}
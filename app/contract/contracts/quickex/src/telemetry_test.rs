@@ -0,0 +1,96 @@
+//! Withdrawal telemetry tests.
+
+use crate::{errors::QuickexError, test_fixture::deploy_initialized, types::WithdrawRejection};
+use soroban_sdk::{
+    testutils::{Address as _, Events as _, Ledger as _},
+    token, Address, Bytes, BytesN, Map, Symbol, TryFromVal, Val,
+};
+
+#[test]
+fn test_attempt_withdraw_counts_rejections_only_while_enabled() {
+    let (env, client, admin, token) = deploy_initialized();
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &1_000);
+    let salt = Bytes::from_slice(&env, b"telemetry_salt");
+    let wrong_salt = Bytes::from_slice(&env, b"telemetry_guess");
+    let commitment = client.deposit(&token, &600, &owner, &salt, &0);
+
+    // Off by default: a rejection fails the call and nothing is counted.
+    assert!(!client.get_withdraw_telemetry());
+    assert_eq!(
+        client.try_attempt_withdraw(&token, &600, &commitment, &owner, &wrong_salt),
+        Err(Ok(QuickexError::CommitmentMismatch))
+    );
+
+    assert_eq!(
+        client.try_set_withdraw_telemetry(&owner, &true),
+        Err(Ok(QuickexError::Unauthorized))
+    );
+    client.set_withdraw_telemetry(&admin, &true);
+    assert!(client.get_withdraw_telemetry());
+
+    assert_eq!(
+        client.attempt_withdraw(&token, &600, &commitment, &owner, &wrong_salt),
+        None
+    );
+    let unknown = BytesN::from_array(&env, &[7; 32]);
+    assert_eq!(
+        client.attempt_withdraw(&token, &600, &unknown, &owner, &wrong_salt),
+        None
+    );
+    // A counted rejection leaves the escrow untouched.
+    assert_eq!(client.check_invariants(&token).locked, 600);
+
+    let receipt = client.attempt_withdraw(&token, &600, &commitment, &owner, &salt);
+    assert_eq!(receipt.unwrap().gross, 600);
+    assert_eq!(
+        client.attempt_withdraw(&token, &600, &commitment, &owner, &salt),
+        None
+    );
+
+    let expiring_salt = Bytes::from_slice(&env, b"telemetry_expiring");
+    let expiring = client.deposit(&token, &400, &owner, &expiring_salt, &60);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 61);
+    assert_eq!(
+        client.attempt_withdraw(&token, &400, &expiring, &owner, &expiring_salt),
+        None
+    );
+    let (_, _, data) = env.events().all().last().unwrap();
+    let data = Map::<Symbol, Val>::try_from_val(&env, &data).unwrap();
+    let class = data.get(Symbol::new(&env, "class")).unwrap();
+    assert_eq!(
+        WithdrawRejection::try_from_val(&env, &class).unwrap(),
+        WithdrawRejection::Expired
+    );
+    let count = data.get(Symbol::new(&env, "count")).unwrap();
+    assert_eq!(u64::try_from_val(&env, &count).unwrap(), 1);
+
+    // Errors outside the counted classes still fail the call.
+    assert_eq!(
+        client.try_attempt_withdraw(&token, &0, &commitment, &owner, &salt),
+        Err(Ok(QuickexError::InvalidAmount))
+    );
+
+    assert_eq!(
+        client.get_rejected_withdrawals(&WithdrawRejection::WrongSalt),
+        2
+    );
+    assert_eq!(
+        client.get_rejected_withdrawals(&WithdrawRejection::Spent),
+        1
+    );
+    assert_eq!(
+        client.get_rejected_withdrawals(&WithdrawRejection::Expired),
+        1
+    );
+
+    client.set_withdraw_telemetry(&admin, &false);
+    assert_eq!(
+        client.try_attempt_withdraw(&token, &600, &commitment, &owner, &salt),
+        Err(Ok(QuickexError::AlreadySpent))
+    );
+    assert_eq!(
+        client.get_rejected_withdrawals(&WithdrawRejection::Spent),
+        1
+    );
+}
@@ -22,16 +22,26 @@
 //! contract directory for how to extend the suite when adding new features.
 
 use crate::{
-    errors::QuickexError, storage::put_escrow, EscrowEntry, EscrowStatus, QuickexContract,
-    QuickexContractClient,
+    errors::QuickexError,
+    events::{EscrowCreatedEvent, EVENT_SCHEMA_VERSION},
+    storage::put_escrow,
+    types::{
+        AdminAction, Config, ErrorCategory, Role, UpgradeBlocker, UpgradeRecord, WithdrawRateLimit,
+        WithdrawVolumeLimit,
+    },
+    EscrowEntry, EscrowStatus, QuickexContract, QuickexContractClient,
 };
+use ed25519_dalek::{Signer, SigningKey};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    token,
-    xdr::ToXdr,
-    Address, Bytes, BytesN, ConversionError, Env, InvokeError,
+    testutils::{Address as _, Events as _, Ledger, MuxedAddress as _},
+    token, vec,
+    xdr::{AccountId, PublicKey, ScAddress, ToXdr, Uint256},
+    Address, Bytes, BytesN, ConversionError, Env, Event as _, InvokeError, MuxedAddress, Symbol,
+    TryFromVal,
 };
 
+extern crate std;
+
 fn setup<'a>() -> (Env, QuickexContractClient<'a>) {
     let env = Env::default();
     env.mock_all_auths();
@@ -63,6 +73,7 @@ fn setup_escrow(
         // Use the new storage system to put the escrow entry
         let storage_commitment: Bytes = commitment.into();
         put_escrow(env, &storage_commitment, &entry);
+        crate::storage::adjust_total_locked(env, token, amount).unwrap();
     });
 }
 
@@ -91,6 +102,7 @@ fn setup_escrow_with_owner(
     env.as_contract(contract_id, || {
         let storage_commitment: Bytes = commitment.into();
         put_escrow(env, &storage_commitment, &entry);
+        crate::storage::adjust_total_locked(env, token, amount).unwrap();
     });
 }
 
@@ -201,6 +213,221 @@ fn test_get_escrow_details_privacy_disabled_shows_full_details() {
     assert_eq!(view.status, EscrowStatus::Pending);
 }
 
+/// Helper: store a pending escrow for `owner` under a fixed commitment.
+fn setup_private_escrow(
+    env: &Env,
+    client: &QuickexContractClient,
+    owner: &Address,
+    amount: i128,
+) -> BytesN<32> {
+    let token = create_test_token(env);
+    let commitment = BytesN::from_array(env, &[42; 32]);
+    setup_escrow_with_owner(
+        env,
+        &client.address,
+        &token,
+        owner,
+        amount,
+        commitment.clone(),
+        0,
+    );
+    commitment
+}
+
+#[test]
+fn test_privacy_level_1_hides_amount_only() {
+    let (env, client) = setup();
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let commitment = setup_private_escrow(&env, &client, &owner, 700);
+
+    client.enable_privacy(&owner, &1);
+
+    let view = client.get_escrow_details(&commitment, &stranger).unwrap();
+    assert_eq!(view.amount, None);
+    assert_eq!(view.owner, Some(owner));
+    assert!(view.redacted);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+}
+
+#[test]
+fn test_privacy_level_2_hides_owner_amount_and_state() {
+    let (env, client) = setup();
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let commitment = setup_private_escrow(&env, &client, &owner, 700);
+
+    client.enable_privacy(&owner, &2);
+
+    let view = client.get_escrow_details(&commitment, &stranger).unwrap();
+    assert_eq!(view.amount, None);
+    assert_eq!(view.owner, None);
+    assert_eq!(client.get_commitment_state(&commitment), None);
+
+    // The owner still sees everything.
+    let own_view = client.get_escrow_details(&commitment, &owner).unwrap();
+    assert_eq!(own_view.amount, Some(700));
+    assert_eq!(own_view.owner, Some(owner.clone()));
+    assert!(!own_view.redacted);
+
+    // Dropping back to level 0 restores full disclosure.
+    client.enable_privacy(&owner, &0);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+    assert_eq!(client.privacy_history(&owner).len(), 2);
+}
+
+#[test]
+fn test_privacy_toggle_overrides_lower_level() {
+    let (env, client) = setup();
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let commitment = setup_private_escrow(&env, &client, &owner, 700);
+
+    client.enable_privacy(&owner, &1);
+    client.set_privacy(&owner, &true);
+
+    let view = client.get_escrow_details(&commitment, &stranger).unwrap();
+    assert_eq!(view.owner, None);
+    assert_eq!(client.get_commitment_state(&commitment), None);
+}
+
+/// `(amount, amount_magnitude)` of the last event the contract emitted.
+fn last_event_amounts(env: &Env) -> (Option<i128>, Option<u32>) {
+    let (_, _, data) = env.events().all().last().unwrap();
+    let data = soroban_sdk::Map::<Symbol, soroban_sdk::Val>::try_from_val(env, &data).unwrap();
+    let field = |name: &str| data.get(Symbol::new(env, name)).unwrap();
+    (
+        Option::<i128>::try_from_val(env, &field("amount")).unwrap(),
+        Option::<u32>::try_from_val(env, &field("amount_magnitude")).unwrap(),
+    )
+}
+
+#[test]
+fn test_escrow_events_publish_amount_magnitude_at_level_1() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &7_500);
+    let deposit = |amount: i128, salt: &[u8]| {
+        let salt = Bytes::from_slice(&env, salt);
+        let commitment = client.deposit(&token, &amount, &owner, &salt, &0);
+        (commitment, salt)
+    };
+
+    deposit(2_500, b"magnitude_public");
+    assert_eq!(last_event_amounts(&env), (Some(2_500), None));
+
+    client.enable_privacy(&owner, &1);
+    let (commitment, salt) = deposit(2_500, b"magnitude_hidden");
+    assert_eq!(last_event_amounts(&env), (None, Some(3)));
+    client.withdraw(&token, &2_500, &commitment, &owner, &salt);
+    assert_eq!(last_event_amounts(&env), (None, Some(3)));
+
+    client.enable_privacy(&owner, &2);
+    deposit(2_500, b"magnitude_hidden_all");
+    assert_eq!(last_event_amounts(&env), (None, None));
+
+    for (amount, magnitude) in [(1, 0), (9, 0), (10, 1), (999, 2), (1_000, 3)] {
+        assert_eq!(crate::events::amount_magnitude(amount), magnitude);
+    }
+}
+
+#[test]
+fn test_get_escrow_details_auditor_sees_hidden_fields() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let owner = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    let commitment = setup_private_escrow(&env, &client, &owner, 700);
+    client.enable_privacy(&owner, &2);
+
+    let view = client.get_escrow_details(&commitment, &auditor).unwrap();
+    assert!(view.redacted);
+    assert_eq!((view.amount, view.owner), (None, None));
+
+    client.grant_role(&admin, &Role::Auditor, &auditor);
+    let view = client.get_escrow_details(&commitment, &auditor).unwrap();
+    assert!(!view.redacted);
+    assert_eq!((view.amount, view.owner), (Some(700), Some(owner.clone())));
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, auditor);
+
+    client.revoke_role(&admin, &Role::Auditor, &auditor);
+    assert!(
+        client
+            .get_escrow_details(&commitment, &auditor)
+            .unwrap()
+            .redacted
+    );
+}
+
+#[test]
+fn test_get_escrow_details_without_auth_stays_redacted() {
+    let (env, client) = setup();
+    let owner = Address::generate(&env);
+    let commitment = setup_private_escrow(&env, &client, &owner, 700);
+    client.enable_privacy(&owner, &1);
+
+    // Naming the owner as caller without their signature does not lift the redaction.
+    env.set_auths(&[]);
+    assert!(client.try_get_escrow_details(&commitment, &owner).is_err());
+
+    // The redacted view needs no signature.
+    let stranger = Address::generate(&env);
+    let view = client.get_escrow_details(&commitment, &stranger).unwrap();
+    assert!(view.redacted);
+    assert_eq!(view.owner, Some(owner));
+}
+
+#[test]
+fn test_enable_privacy_invalid_level_fails() {
+    let (env, client) = setup();
+    let account = Address::generate(&env);
+
+    let result = client.try_enable_privacy(&account, &3);
+    assert_contract_error(result, QuickexError::InvalidPrivacyLevel);
+    assert_eq!(client.privacy_status(&account), None);
+}
+
+#[test]
+fn test_privacy_history_page_walks_newest_first() {
+    let (env, client) = setup();
+    let account = Address::generate(&env);
+
+    for (i, level) in [0u32, 1, 2, 1, 0].iter().enumerate() {
+        env.ledger().set_timestamp(1_000 + i as u64);
+        client.enable_privacy(&account, level);
+    }
+
+    let first = client.privacy_history_page(&account, &0, &2);
+    assert_eq!(first.entries.len(), 2);
+    assert_eq!(first.entries.get(0).unwrap().level, 0);
+    assert_eq!(first.entries.get(0).unwrap().timestamp, 1_004);
+    assert_eq!(first.entries.get(1).unwrap().level, 1);
+    assert_eq!(first.next_cursor, Some(2));
+
+    let second = client.privacy_history_page(&account, &2, &2);
+    assert_eq!(second.entries.get(0).unwrap().timestamp, 1_002);
+    assert_eq!(second.next_cursor, Some(4));
+
+    let last = client.privacy_history_page(&account, &4, &100);
+    assert_eq!(last.entries.len(), 1);
+    assert_eq!(last.entries.get(0).unwrap().timestamp, 1_000);
+    assert_eq!(last.next_cursor, None);
+
+    let past_end = client.privacy_history_page(&account, &10, &5);
+    assert_eq!(past_end.entries.len(), 0);
+    assert_eq!(past_end.next_cursor, None);
+}
+
 #[test]
 fn test_set_privacy_already_set_fails() {
     // Setting privacy to a value it already has must return PrivacyAlreadySet.
@@ -232,6 +459,271 @@ fn test_set_privacy_toggle_cycle_succeeds() {
     assert!(client.get_privacy(&account));
 }
 
+#[test]
+fn test_legacy_privacy_flag_is_read_and_replaced() {
+    let (env, client) = setup();
+    let account = Address::generate(&env);
+    let legacy_key = (Symbol::new(&env, "privacy_enabled"), account.clone());
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(&legacy_key, &true);
+    });
+
+    assert!(client.get_privacy(&account));
+    assert_contract_error(
+        client.try_set_privacy(&account, &true),
+        QuickexError::PrivacyAlreadySet,
+    );
+
+    client.set_privacy(&account, &false);
+    assert!(!client.get_privacy(&account));
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&legacy_key));
+    });
+}
+
+#[test]
+fn test_list_claimable_tracks_pending_escrows() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &300);
+    let salt_a = Bytes::from_slice(&env, b"claimable_a");
+    let salt_b = Bytes::from_slice(&env, b"claimable_b");
+
+    let first = client.deposit(&token, &100, &owner, &salt_a, &0);
+    let second = client.deposit(&token, &200, &owner, &salt_b, &0);
+    assert_eq!(
+        client.list_claimable(&owner),
+        soroban_sdk::vec![&env, first.clone(), second.clone()]
+    );
+
+    client.withdraw(&token, &100, &first, &owner, &salt_a);
+    assert_eq!(
+        client.list_claimable(&owner),
+        soroban_sdk::vec![&env, second]
+    );
+}
+
+#[test]
+fn test_list_claimable_respects_hide_all_privacy() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &400);
+
+    client.deposit(
+        &token,
+        &100,
+        &owner,
+        &Bytes::from_slice(&env, b"hidden_a"),
+        &0,
+    );
+    client.enable_privacy(&owner, &2);
+    assert!(client.list_claimable(&owner).is_empty());
+
+    // Escrows created while hidden are not indexed.
+    client.deposit(
+        &token,
+        &200,
+        &owner,
+        &Bytes::from_slice(&env, b"hidden_b"),
+        &0,
+    );
+    client.enable_privacy(&owner, &0);
+    assert!(client.list_claimable(&owner).is_empty());
+
+    let visible = client.deposit(
+        &token,
+        &1,
+        &owner,
+        &Bytes::from_slice(&env, b"hidden_c"),
+        &0,
+    );
+    assert_eq!(
+        client.list_claimable(&owner),
+        soroban_sdk::vec![&env, visible]
+    );
+}
+
+#[test]
+fn test_list_claimable_pages_through_buckets() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &70);
+    let salt = |i: u32| Bytes::from_array(&env, &i.to_be_bytes());
+    let mut commitments = std::vec::Vec::new();
+    for i in 0..70 {
+        commitments.push(client.deposit(&token, &1, &owner, &salt(i), &0));
+    }
+
+    let first = client.list_claimable_page(&owner, &0);
+    assert_eq!(first.commitments.len(), crate::storage::OWNER_BUCKET_SIZE);
+    assert_eq!(first.commitments.get(0), Some(commitments[0].clone()));
+    let second = client.list_claimable_page(&owner, &first.next_cursor.unwrap());
+    assert_eq!(second.next_cursor, Some(3));
+    let last = client.list_claimable_page(&owner, &3);
+    assert_eq!(last.commitments.len(), 6);
+    assert_eq!(last.next_cursor, None);
+
+    // Emptied buckets are skipped.
+    for (i, commitment) in commitments.iter().enumerate().take(64).skip(32) {
+        client.withdraw(&token, &1, commitment, &owner, &salt(i as u32));
+    }
+    assert_eq!(client.list_claimable_page(&owner, &2), last);
+    assert_eq!(client.list_claimable(&owner).len(), 38);
+
+    // Escrows indexed before buckets are listed first and still removed on withdrawal.
+    let legacy = client.deposit(&token, &1, &owner, &salt(99), &0);
+    env.as_contract(&client.address, || {
+        let persistent = env.storage().persistent();
+        persistent.remove(&crate::storage::DataKey::OwnerSlot(legacy.clone()));
+        persistent.set(
+            &crate::storage::DataKey::OwnerIndex(owner.clone()),
+            &vec![&env, legacy.clone()],
+        );
+    });
+    let page = client.list_claimable_page(&owner, &0);
+    assert_eq!(page.commitments, vec![&env, legacy.clone()]);
+    assert_eq!(page.next_cursor, Some(1));
+    client.withdraw(&token, &1, &legacy, &owner, &salt(99));
+    assert_eq!(client.list_claimable_page(&owner, &0), first);
+}
+
+#[test]
+fn test_withdraw_rate_limit_blocks_until_window_closes() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &300);
+
+    client.set_withdraw_rate_limit(&owner, &2, &3600);
+    assert_eq!(
+        client.get_withdraw_rate_limit(&owner),
+        Some(WithdrawRateLimit {
+            max_withdrawals: 2,
+            window_secs: 3600,
+        })
+    );
+
+    let salts = [b"limit_a", b"limit_b", b"limit_c"];
+    for salt in salts {
+        client.deposit(&token, &100, &owner, &Bytes::from_slice(&env, salt), &0);
+    }
+
+    let withdraw = |salt: &[u8; 7]| {
+        let salt = Bytes::from_slice(&env, salt);
+        let commitment = client.create_amount_commitment(&owner, &100, &salt);
+        client.try_withdraw(&token, &100, &commitment, &owner, &salt)
+    };
+    assert!(matches!(withdraw(salts[0]), Ok(Ok(_))));
+    assert!(matches!(withdraw(salts[1]), Ok(Ok(_))));
+    assert_eq!(
+        withdraw(salts[2]),
+        Err(Ok(QuickexError::WithdrawRateLimited))
+    );
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 3600);
+    assert!(matches!(withdraw(salts[2]), Ok(Ok(_))));
+}
+
+#[test]
+fn test_withdraw_rate_limit_removal_and_validation() {
+    let (env, client) = setup();
+    let account = Address::generate(&env);
+
+    let result = client.try_set_withdraw_rate_limit(&account, &1, &0);
+    assert_contract_error(result, QuickexError::InvalidRateLimit);
+
+    client.set_withdraw_rate_limit(&account, &1, &60);
+    client.set_withdraw_rate_limit(&account, &0, &0);
+    assert_eq!(client.get_withdraw_rate_limit(&account), None);
+}
+
+#[test]
+fn test_min_salt_len_rejects_weak_deposit() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &100);
+
+    client.set_min_salt_len(&admin, &16);
+    assert_eq!(client.get_salt_policy().unwrap().min_len, 16);
+
+    let weak = Bytes::from_slice(&env, b"short");
+    let result = client.try_deposit(&token, &100, &owner, &weak, &0);
+    assert_contract_error(result, QuickexError::WeakSalt);
+
+    let strong = client.generate_salt();
+    assert_eq!(strong.len(), 32);
+    let commitment = client.deposit(&token, &100, &owner, &strong, &0);
+    client.withdraw(&token, &100, &commitment, &owner, &strong);
+}
+
+#[test]
+fn test_deposit_rejects_empty_and_oversized_salts() {
+    let (env, client) = setup();
+    client.initialize(&Address::generate(&env));
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &100);
+
+    let result = client.try_deposit(&token, &100, &owner, &Bytes::new(&env), &0);
+    assert_contract_error(result, QuickexError::WeakSalt);
+
+    let oversized = Bytes::from_slice(&env, &[7u8; quickex_commitment::MAX_SALT_LEN + 1]);
+    let result = client.try_deposit(&token, &100, &owner, &oversized, &0);
+    assert_contract_error(result, QuickexError::InvalidSalt);
+    let commitment = BytesN::from_array(&env, &[0; 32]);
+    let result = client.try_withdraw(&token, &100, &commitment, &owner, &oversized);
+    assert_contract_error(result, QuickexError::InvalidSalt);
+
+    let salt = Bytes::from_slice(&env, &[7u8]);
+    let commitment = client.deposit(&token, &100, &owner, &salt, &0);
+    client.withdraw(&token, &100, &commitment, &owner, &salt);
+}
+
+#[test]
+fn test_min_salt_len_applies_to_new_escrows_only() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &200);
+    let weak = Bytes::from_slice(&env, b"weak");
+
+    let legacy = client.deposit(&token, &100, &owner, &weak, &0);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    client.set_min_salt_len(&admin, &16);
+
+    // The salt is only revealed at withdrawal for commitment-based deposits.
+    let fresh = client.create_token_commitment(&token, &owner, &50, &weak);
+    client.deposit_with_commitment(&owner, &token, &50, &fresh, &0);
+    let result = client.try_withdraw(&token, &50, &fresh, &owner, &weak);
+    assert_contract_error(result, QuickexError::WeakSalt);
+
+    // Escrows created before the minimum are exempt.
+    client.withdraw(&token, &100, &legacy, &owner, &weak);
+}
+
+#[test]
+fn test_set_min_salt_len_validation() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let result = client.try_set_min_salt_len(&Address::generate(&env), &16);
+    assert_contract_error(result, QuickexError::Unauthorized);
+
+    let result = client.try_set_min_salt_len(&admin, &1025);
+    assert_contract_error(result, QuickexError::InvalidSalt);
+    assert_eq!(client.get_salt_policy(), None);
+}
+
 fn create_test_token(env: &Env) -> Address {
     env.register_stellar_asset_contract_v2(Address::generate(env))
         .address()
@@ -276,41 +768,395 @@ fn test_successful_withdrawal() {
     let _ = client.withdraw(&token, &amount, &commitment, &to, &salt);
 }
 
+/// Helper: the Stellar account address controlled by `signer`.
+fn account_for(env: &Env, signer: &SigningKey) -> Address {
+    let sc_address = ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+        signer.verifying_key().to_bytes(),
+    ))));
+    Address::try_from_val(env, &sc_address).unwrap()
+}
+
+/// Helper: `signer`'s delegation signature authorizing payout of `commitment` to `payout_to`.
+fn sign_delegation(
+    env: &Env,
+    client: &QuickexContractClient,
+    signer: &SigningKey,
+    commitment: &BytesN<32>,
+    payout_to: &Address,
+) -> BytesN<64> {
+    let mut message = Bytes::from_slice(env, b"quickex/delegate");
+    message.append(&client.address.clone().to_xdr(env));
+    message.append(&commitment.clone().into());
+    message.append(&payout_to.clone().to_xdr(env));
+
+    let message: std::vec::Vec<u8> = message.iter().collect();
+    BytesN::from_array(env, &signer.sign(&message).to_bytes())
+}
+
 #[test]
-fn test_double_withdrawal_fails() {
+fn test_withdraw_delegated_pays_custodian() {
     let (env, client) = setup();
     let token = create_test_token(&env);
-    let to = Address::generate(&env);
-    let amount: i128 = 1000;
-    let salt = Bytes::from_slice(&env, b"test_salt_456");
+    let signer = SigningKey::from_bytes(&[21; 32]);
+    let owner = account_for(&env, &signer);
+    let custodian = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"delegated_salt");
 
-    let mut data = Bytes::new(&env);
-    let address_bytes: Bytes = to.clone().to_xdr(&env);
-    data.append(&address_bytes);
-    data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
-    data.append(&salt);
-    let commitment: BytesN<32> = env.crypto().sha256(&data).into();
-
-    setup_escrow(&env, &client.address, &token, amount, commitment.clone(), 0);
+    let commitment = client.create_amount_commitment(&owner, &900, &salt);
+    setup_escrow(&env, &client.address, &token, 900, commitment.clone(), 0);
+    token::StellarAssetClient::new(&env, &token).mint(&client.address, &900);
 
-    env.mock_all_auths();
+    let pubkey = BytesN::from_array(&env, &signer.verifying_key().to_bytes());
+    let signature = sign_delegation(&env, &client, &signer, &commitment, &custodian);
 
-    let token_client = token::StellarAssetClient::new(&env, &token);
-    token_client.mint(&client.address, &(amount * 2));
+    let receipt = client.withdraw_delegated(&token, &custodian, &900, &salt, &pubkey, &signature);
+    // The signature alone authorizes the payout; no address auth is required.
+    assert!(env.auths().is_empty());
+    assert_eq!(
+        (
+            receipt.commitment.clone(),
+            receipt.gross,
+            receipt.fee,
+            receipt.net
+        ),
+        (commitment.clone(), 900, 0, 900)
+    );
+    assert_eq!(client.get_receipt(&commitment), Some(receipt));
+    assert_eq!(token::Client::new(&env, &token).balance(&custodian), 900);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
 
-    let first_result = client.try_withdraw(&token, &amount, &commitment, &to, &salt);
-    assert!(first_result.is_ok());
-    assert_eq!(first_result.unwrap(), Ok(true));
-    let second_result = client.try_withdraw(&token, &amount, &commitment, &to, &salt);
-    assert_contract_error(second_result, QuickexError::AlreadySpent);
+    let result =
+        client.try_withdraw_delegated(&token, &custodian, &900, &salt, &pubkey, &signature);
+    assert_contract_error(result, QuickexError::AlreadySpent);
 }
 
 #[test]
-fn test_invalid_salt_fails() {
+fn test_withdraw_delegated_rejects_redirected_payout() {
     let (env, client) = setup();
     let token = create_test_token(&env);
-    let to = Address::generate(&env);
-    let amount: i128 = 1000;
+    let signer = SigningKey::from_bytes(&[22; 32]);
+    let owner = account_for(&env, &signer);
+    let custodian = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"delegated_salt");
+
+    let commitment = client.create_amount_commitment(&owner, &900, &salt);
+    setup_escrow(&env, &client.address, &token, 900, commitment.clone(), 0);
+
+    let pubkey = BytesN::from_array(&env, &signer.verifying_key().to_bytes());
+    let signature = sign_delegation(&env, &client, &signer, &commitment, &custodian);
+
+    let result = client.try_withdraw_delegated(&token, &attacker, &900, &salt, &pubkey, &signature);
+    assert!(result.is_err());
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+}
+
+#[test]
+fn test_withdraw_delegated_rejects_malformed_signature() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let signer = SigningKey::from_bytes(&[23; 32]);
+    let owner = account_for(&env, &signer);
+    let custodian = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"delegated_salt");
+
+    let commitment = client.create_amount_commitment(&owner, &900, &salt);
+    setup_escrow(&env, &client.address, &token, 900, commitment.clone(), 0);
+
+    let pubkey = BytesN::from_array(&env, &signer.verifying_key().to_bytes());
+    let mut bytes = sign_delegation(&env, &client, &signer, &commitment, &custodian).to_array();
+    // A scalar half at or above the group order is not a valid encoding.
+    bytes[32..].fill(0xff);
+    let signature = BytesN::from_array(&env, &bytes);
+
+    let result =
+        client.try_withdraw_delegated(&token, &custodian, &900, &salt, &pubkey, &signature);
+    assert_contract_error(result, QuickexError::InvalidSignature);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+}
+
+/// Helper: pending escrow for (`to`, `amount`, `salt`) funded in the contract, plus the
+/// commit-reveal announcement for `nonce`.
+fn setup_announced_escrow(
+    env: &Env,
+    client: &QuickexContractClient,
+    to: &Address,
+    amount: i128,
+    salt: &Bytes,
+    nonce: &BytesN<32>,
+) -> (Address, BytesN<32>) {
+    let token = create_test_token(env);
+    let commitment = client.create_amount_commitment(to, &amount, salt);
+    setup_escrow(env, &client.address, &token, amount, commitment, 0);
+    token::StellarAssetClient::new(env, &token).mint(&client.address, &amount);
+
+    let mut data = Bytes::from_slice(env, b"quickex/announce");
+    data.append(&to.clone().to_xdr(env));
+    data.append(&Bytes::from_slice(env, &amount.to_be_bytes()));
+    data.append(salt);
+    data.append(&nonce.clone().into());
+    (token, env.crypto().sha256(&data).into())
+}
+
+#[test]
+fn test_commit_reveal_withdrawal() {
+    let (env, client) = setup();
+    env.mock_all_auths();
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"reveal_salt");
+    let nonce = BytesN::from_array(&env, &[5; 32]);
+    let (token, announcement) = setup_announced_escrow(&env, &client, &to, 700, &salt, &nonce);
+
+    let announced_at = client.announce_withdraw(&announcement);
+    assert_eq!(announced_at, env.ledger().sequence());
+
+    env.ledger().with_mut(|l| l.sequence_number += 1);
+    assert!(client.finalize_withdraw(&token, &to, &700, &salt, &nonce));
+    assert_eq!(token::Client::new(&env, &token).balance(&to), 700);
+
+    // The announcement is consumed.
+    let result = client.try_finalize_withdraw(&token, &to, &700, &salt, &nonce);
+    assert_contract_error(result, QuickexError::AnnouncementNotFound);
+}
+
+#[test]
+fn test_commit_reveal_rejects_same_ledger_finalize() {
+    let (env, client) = setup();
+    env.mock_all_auths();
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"reveal_salt");
+    let nonce = BytesN::from_array(&env, &[6; 32]);
+    let (token, announcement) = setup_announced_escrow(&env, &client, &to, 700, &salt, &nonce);
+
+    client.announce_withdraw(&announcement);
+    let result = client.try_finalize_withdraw(&token, &to, &700, &salt, &nonce);
+    assert_contract_error(result, QuickexError::RevealTooEarly);
+
+    let result = client.try_announce_withdraw(&announcement);
+    assert_contract_error(result, QuickexError::AlreadyAnnounced);
+}
+
+#[test]
+fn test_commit_reveal_rejects_unannounced_recipient() {
+    let (env, client) = setup();
+    env.mock_all_auths();
+    let to = Address::generate(&env);
+    let sniper = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"reveal_salt");
+    let nonce = BytesN::from_array(&env, &[7; 32]);
+    let (token, announcement) = setup_announced_escrow(&env, &client, &to, 700, &salt, &nonce);
+
+    client.announce_withdraw(&announcement);
+    env.ledger().with_mut(|l| l.sequence_number += 1);
+
+    // Replaying the revealed salt and nonce with another address matches no announcement.
+    let result = client.try_finalize_withdraw(&token, &sniper, &700, &salt, &nonce);
+    assert_contract_error(result, QuickexError::AnnouncementNotFound);
+}
+
+#[test]
+fn test_withdraw_token_bound_commitment() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"v2_salt");
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &400);
+
+    let commitment = client.create_token_commitment(&token, &to, &400, &salt);
+    client.deposit_with_commitment(&depositor, &token, &400, &commitment, &0);
+
+    client.withdraw(&token, &400, &commitment, &to, &salt);
+    assert_eq!(token::Client::new(&env, &token).balance(&to), 400);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+#[test]
+fn test_withdraw_fixed_layout_commitment() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"v3_salt");
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &400);
+
+    let commitment = client.create_fixed_commitment(&token, &to, &400, &salt);
+    client.deposit_with_commitment(&depositor, &token, &400, &commitment, &0);
+
+    client.withdraw(&token, &400, &commitment, &to, &salt);
+    assert_eq!(token::Client::new(&env, &token).balance(&to), 400);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+#[test]
+fn test_get_receipt_after_withdraw() {
+    use soroban_sdk::testutils::storage::Temporary as _;
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let hidden = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&to, &300);
+    token::StellarAssetClient::new(&env, &token).mint(&hidden, &100);
+    let salt = Bytes::from_slice(&env, b"receipt_salt");
+
+    let commitment = client.deposit(&token, &300, &to, &salt, &0);
+    assert_eq!(client.get_receipt(&commitment), None);
+    let receipt = client.withdraw(&token, &300, &commitment, &to, &salt);
+    assert_eq!(client.get_receipt(&commitment), Some(receipt));
+    env.as_contract(&client.address, || {
+        let key = crate::storage::DataKey::Receipt(commitment.clone());
+        assert_eq!(
+            env.storage().temporary().get_ttl(&key),
+            crate::storage::ESCROW_ARCHIVE_TTL
+        );
+    });
+
+    // Owners hiding amounts get no stored receipt.
+    client.enable_privacy(&hidden, &1);
+    let commitment = client.deposit(&token, &100, &hidden, &salt, &0);
+    client.withdraw(&token, &100, &commitment, &hidden, &salt);
+    assert_eq!(client.get_receipt(&commitment), None);
+}
+
+#[test]
+fn test_withdraw_to_muxed_account() {
+    let (env, client) = setup();
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let token = asset.address();
+    // Classic accounts need a trustline to hold an asset, except its issuer.
+    let to = MuxedAddress::new(asset.issuer().address(), 42);
+    let depositor = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"muxed_salt");
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &400);
+
+    let commitment = client.create_token_commitment(&token, &to.address(), &400, &salt);
+    client.deposit_with_commitment(&depositor, &token, &400, &commitment, &0);
+
+    client.withdraw(&token, &400, &commitment, &to, &salt);
+    assert_eq!(token::Client::new(&env, &token).balance(&client.address), 0);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+#[test]
+fn test_withdraw_token_bound_commitment_with_other_token_fails() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let other = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"v2_salt");
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &400);
+
+    let commitment = client.create_token_commitment(&token, &to, &400, &salt);
+    client.deposit_with_commitment(&depositor, &token, &400, &commitment, &0);
+
+    // The commitment recomputed for `other` is not the one deposited.
+    let result = client.try_withdraw(&other, &400, &commitment, &to, &salt);
+    assert_contract_error(result, QuickexError::CommitmentMismatch);
+}
+
+#[test]
+fn test_withdraw_legacy_commitment_validates_token() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let other = create_test_token(&env);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"v1_salt");
+
+    let commitment = client.create_amount_commitment(&to, &400, &salt);
+    setup_escrow(&env, &client.address, &token, 400, commitment.clone(), 0);
+
+    let result = client.try_withdraw(&other, &400, &commitment, &to, &salt);
+    assert_contract_error(result, QuickexError::TokenMismatch);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+}
+
+#[test]
+fn test_withdraw_rejects_commitment_not_matching_arguments() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&to, &300);
+    let salt_a = Bytes::from_slice(&env, b"mismatch_a");
+    let salt_b = Bytes::from_slice(&env, b"mismatch_b");
+    let first = client.deposit(&token, &100, &to, &salt_a, &0);
+    let second = client.deposit(&token, &200, &to, &salt_b, &0);
+
+    // Valid arguments for `first`, but naming `second`: neither escrow moves.
+    let result = client.try_withdraw(&token, &100, &second, &to, &salt_a);
+    assert_contract_error(result, QuickexError::CommitmentMismatch);
+    assert_eq!(
+        client.get_commitment_state(&first),
+        Some(EscrowStatus::Pending)
+    );
+    assert_eq!(
+        client.get_commitment_state(&second),
+        Some(EscrowStatus::Pending)
+    );
+
+    client.withdraw(&token, &100, &first, &to, &salt_a);
+    assert_eq!(
+        client.get_commitment_state(&first),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+#[test]
+fn test_double_withdrawal_fails() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"test_salt_456");
+
+    let mut data = Bytes::new(&env);
+    let address_bytes: Bytes = to.clone().to_xdr(&env);
+    data.append(&address_bytes);
+    data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
+    data.append(&salt);
+    let commitment: BytesN<32> = env.crypto().sha256(&data).into();
+
+    setup_escrow(&env, &client.address, &token, amount, commitment.clone(), 0);
+
+    env.mock_all_auths();
+
+    let token_client = token::StellarAssetClient::new(&env, &token);
+    token_client.mint(&client.address, &(amount * 2));
+
+    let first_result = client.try_withdraw(&token, &amount, &commitment, &to, &salt);
+    assert!(first_result.is_ok());
+    assert!(first_result.unwrap().is_ok());
+    let second_result = client.try_withdraw(&token, &amount, &commitment, &to, &salt);
+    assert_contract_error(second_result, QuickexError::AlreadySpent);
+}
+
+#[test]
+fn test_invalid_salt_fails() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let amount: i128 = 1000;
     let correct_salt = Bytes::from_slice(&env, b"correct_salt");
     let wrong_salt = Bytes::from_slice(&env, b"wrong_salt");
 
@@ -325,7 +1171,7 @@ fn test_invalid_salt_fails() {
 
     env.mock_all_auths();
     let result = client.try_withdraw(&token, &amount, &commitment, &to, &wrong_salt);
-    assert_contract_error(result, QuickexError::CommitmentNotFound);
+    assert_contract_error(result, QuickexError::CommitmentMismatch);
 }
 
 #[test]
@@ -356,7 +1202,7 @@ fn test_invalid_amount_fails() {
     env.mock_all_auths();
 
     let result = client.try_withdraw(&token, &wrong_amount, &commitment, &to, &salt);
-    assert_contract_error(result, QuickexError::CommitmentNotFound);
+    assert_contract_error(result, QuickexError::CommitmentMismatch);
 }
 
 #[test]
@@ -481,9 +1327,58 @@ fn test_create_escrow() {
     let (env, client) = setup();
     let from = Address::generate(&env);
     let to = Address::generate(&env);
+    let token = create_test_token(&env);
     let amount = 1_000;
-    let escrow_id = client.create_escrow(&from, &to, &amount);
+    let escrow_id = client.create_escrow(&from, &to, &token, &amount);
     assert!(escrow_id > 0);
+    let events = env.events().all();
+    let event = EscrowCreatedEvent {
+        id: escrow_id,
+        from: from.clone(),
+        to: to.clone(),
+        token: token.clone(),
+        amount,
+        schema_version: EVENT_SCHEMA_VERSION,
+        sequence: client.event_sequence(),
+        timestamp: env.ledger().timestamp(),
+    };
+    assert_eq!(
+        events,
+        vec![
+            &env,
+            (client.address.clone(), event.topics(&env), event.data(&env))
+        ]
+    );
+
+    assert_eq!(
+        client.try_create_escrow(&from, &to, &token, &0),
+        Err(Ok(QuickexError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_create_escrow(&from, &from, &token, &amount),
+        Err(Ok(QuickexError::SameParticipants))
+    );
+    assert_eq!(
+        client.create_escrow(&from, &to, &token, &amount),
+        escrow_id + 1
+    );
+}
+
+#[test]
+fn test_create_escrow_requires_from_auth() {
+    let (env, client) = setup();
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    let token = create_test_token(&env);
+
+    client.create_escrow(&from, &to, &token, &1_000);
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, from);
+
+    env.set_auths(&[]);
+    assert!(client
+        .try_create_escrow(&from, &to, &token, &1_000)
+        .is_err());
 }
 
 #[test]
@@ -492,12 +1387,53 @@ fn test_health_check() {
     assert!(client.health_check());
 }
 
+#[test]
+fn test_event_sequence_counts_emitted_events() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    assert_eq!(client.event_sequence(), 0);
+
+    client.initialize(&admin);
+    assert_eq!(client.event_sequence(), 1);
+    client.set_guardian(&admin, &Some(Address::generate(&env)));
+    assert_eq!(client.event_sequence(), 2);
+
+    // Failed calls roll back their events and the counter with them.
+    let stranger = Address::generate(&env);
+    assert!(client.try_set_guardian(&stranger, &None).is_err());
+    assert_eq!(client.event_sequence(), 2);
+}
+
 #[test]
 fn test_canonical_error_code_ranges() {
     // Validation failures (100-199)
     assert_eq!(QuickexError::InvalidAmount as u32, 100);
     assert_eq!(QuickexError::InvalidSalt as u32, 101);
     assert_eq!(QuickexError::InvalidPrivacyLevel as u32, 102);
+    assert_eq!(QuickexError::InvalidPoint as u32, 103);
+    assert_eq!(QuickexError::InvalidDenomination as u32, 104);
+    assert_eq!(QuickexError::InvalidVerifyingKey as u32, 105);
+    assert_eq!(QuickexError::InvalidFee as u32, 106);
+    assert_eq!(QuickexError::InvalidRelayerConfig as u32, 107);
+    assert_eq!(QuickexError::InvalidRing as u32, 108);
+    assert_eq!(QuickexError::InvalidMemo as u32, 109);
+    assert_eq!(QuickexError::InvalidFieldElement as u32, 110);
+    assert_eq!(QuickexError::InvalidRateLimit as u32, 111);
+    assert_eq!(QuickexError::WeakSalt as u32, 112);
+    assert_eq!(QuickexError::UnapprovedAmount as u32, 113);
+    assert_eq!(QuickexError::InvalidThreshold as u32, 114);
+    assert_eq!(QuickexError::InvalidPauseOps as u32, 115);
+    assert_eq!(QuickexError::InvalidExecuteAfter as u32, 116);
+    assert_eq!(QuickexError::TokenNotAllowed as u32, 117);
+    assert_eq!(QuickexError::TooManyTokens as u32, 118);
+    assert_eq!(QuickexError::InvalidConfig as u32, 119);
+    assert_eq!(QuickexError::InvalidTimeout as u32, 120);
+    assert_eq!(QuickexError::InvalidBatch as u32, 121);
+    assert_eq!(QuickexError::UnknownFunction as u32, 122);
+    assert_eq!(QuickexError::InvalidTtl as u32, 123);
+    assert_eq!(QuickexError::UnsupportedAddress as u32, 124);
+    assert_eq!(QuickexError::SameParticipants as u32, 125);
+    assert_eq!(QuickexError::InvalidToken as u32, 126);
 
     // Auth/admin failures (200-299)
     assert_eq!(QuickexError::Unauthorized as u32, 200);
@@ -514,9 +1450,218 @@ fn test_canonical_error_code_ranges() {
     assert_eq!(QuickexError::EscrowExpired as u32, 307);
     assert_eq!(QuickexError::EscrowNotExpired as u32, 308);
     assert_eq!(QuickexError::InvalidOwner as u32, 309);
+    assert_eq!(QuickexError::InvalidProof as u32, 310);
+    assert_eq!(QuickexError::InvalidRoot as u32, 311);
+    assert_eq!(QuickexError::VerifyingKeyNotSet as u32, 312);
+    assert_eq!(QuickexError::MerkleTreeFull as u32, 313);
+    assert_eq!(QuickexError::RelayerNotActive as u32, 314);
+    assert_eq!(QuickexError::RelayerNotRegistered as u32, 315);
+    assert_eq!(QuickexError::StakeLocked as u32, 316);
+    assert_eq!(QuickexError::RelayerConfigNotSet as u32, 317);
+    assert_eq!(QuickexError::InvalidSignature as u32, 318);
+    assert_eq!(QuickexError::AnnouncementNotFound as u32, 319);
+    assert_eq!(QuickexError::RevealTooEarly as u32, 320);
+    assert_eq!(QuickexError::AlreadyAnnounced as u32, 321);
+    assert_eq!(QuickexError::TokenMismatch as u32, 322);
+    assert_eq!(QuickexError::WithdrawRateLimited as u32, 323);
+    assert_eq!(QuickexError::ActionNotFound as u32, 324);
+    assert_eq!(QuickexError::ActionAlreadyApproved as u32, 325);
+    assert_eq!(QuickexError::TimelockRequired as u32, 326);
+    assert_eq!(QuickexError::TimelockNotReady as u32, 327);
+    assert_eq!(QuickexError::ContractNotPaused as u32, 328);
+    assert_eq!(QuickexError::ExceedsSurplus as u32, 329);
+    assert_eq!(QuickexError::MigrationPending as u32, 330);
+    assert_eq!(QuickexError::NothingToMigrate as u32, 331);
+    assert_eq!(QuickexError::UpgradeVersionMismatch as u32, 332);
+    assert_eq!(QuickexError::SchemaDowngrade as u32, 333);
+    assert_eq!(QuickexError::AdminStillActive as u32, 334);
+    assert_eq!(QuickexError::TokenBlocked as u32, 335);
+    assert_eq!(QuickexError::CapExceeded as u32, 336);
+    assert_eq!(QuickexError::RateLimited as u32, 337);
+    assert_eq!(QuickexError::AccountBlocked as u32, 338);
+    assert_eq!(QuickexError::InsufficientFees as u32, 339);
+    assert_eq!(QuickexError::FunctionDisabled as u32, 340);
+    assert_eq!(QuickexError::EscrowReserved as u32, 341);
+    assert_eq!(QuickexError::TicketNotFound as u32, 342);
+    assert_eq!(QuickexError::TransferFailed as u32, 343);
+    assert_eq!(QuickexError::AmountMismatch as u32, 344);
 
     // Internal/unexpected conditions (900-999)
     assert_eq!(QuickexError::InternalError as u32, 900);
+    assert_eq!(QuickexError::ArithmeticOverflow as u32, 901);
+}
+
+#[test]
+fn test_error_info_reports_category_and_retryability() {
+    let (_, client) = setup();
+    let info = |code: u32| client.error_info(&code).unwrap();
+
+    let unauthorized = info(QuickexError::Unauthorized as u32);
+    assert_eq!(unauthorized.code, 200);
+    assert_eq!(unauthorized.category, ErrorCategory::Auth);
+    assert!(!unauthorized.retryable);
+
+    let paused = info(QuickexError::ContractPaused as u32);
+    assert_eq!(
+        (paused.category, paused.retryable),
+        (ErrorCategory::State, true)
+    );
+    let bad_amount = info(QuickexError::InvalidAmount as u32);
+    assert_eq!(
+        (bad_amount.category, bad_amount.retryable),
+        (ErrorCategory::Validation, false)
+    );
+    let rate_limited = info(QuickexError::RateLimited as u32);
+    assert_eq!(
+        (rate_limited.category, rate_limited.retryable),
+        (ErrorCategory::Limits, true)
+    );
+    assert_eq!(
+        info(QuickexError::ArithmeticOverflow as u32).category,
+        ErrorCategory::Internal
+    );
+
+    for code in [0, 99, 127, 202, 345, 903, u32::MAX] {
+        assert_eq!(client.error_info(&code), None);
+    }
+}
+
+#[test]
+fn test_error_codes_are_exported_in_exactly_one_band() {
+    use crate::errors::{
+        QuickexAuthError, QuickexInternalError, QuickexStateError, QuickexValidationError,
+    };
+
+    for code in 0..1_000 {
+        let error = soroban_sdk::Error::from_contract_error(code);
+        let bands = [
+            QuickexValidationError::try_from(error).is_ok(),
+            QuickexAuthError::try_from(error).is_ok(),
+            QuickexStateError::try_from(error).is_ok(),
+            QuickexInternalError::try_from(error).is_ok(),
+        ];
+        let defined = QuickexError::from_code(code).is_some();
+        assert_eq!(
+            bands.iter().filter(|in_band| **in_band).count(),
+            usize::from(defined),
+            "code {code}"
+        );
+    }
+}
+
+#[test]
+fn test_every_entrypoint_reports_the_contract_error() {
+    use soroban_sdk::xdr::{ScSpecEntry, ScSpecTypeDef};
+
+    let Some(spec) = crate::spec_fixture::contract_spec() else {
+        return;
+    };
+    let mut fallible = 0;
+    for entry in &spec {
+        match entry {
+            ScSpecEntry::FunctionV0(function) => {
+                for output in function.outputs.iter() {
+                    if let ScSpecTypeDef::Result(result) = output {
+                        assert_eq!(
+                            *result.error_type,
+                            ScSpecTypeDef::Error,
+                            "{} returns another error type",
+                            function.name.to_utf8_string_lossy()
+                        );
+                        fallible += 1;
+                    }
+                }
+            }
+            ScSpecEntry::UdtErrorEnumV0(errors) => {
+                for case in errors.cases.iter() {
+                    let error = QuickexError::from_code(case.value);
+                    assert_eq!(
+                        error.map(|error| std::format!("{error:?}")),
+                        Some(case.name.to_utf8_string_lossy()),
+                        "{} case {}",
+                        errors.name.to_utf8_string_lossy(),
+                        case.value
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    assert!(fallible > 0);
+}
+
+#[test]
+fn test_rejection_paths_report_their_own_error() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &10_000);
+    let salt = Bytes::from_slice(&env, b"rejection_paths_salt");
+
+    // The commitment is for 500, but only 400 is escrowed under it.
+    let commitment = client.create_token_commitment(&token, &owner, &500, &salt);
+    client.deposit_with_commitment(&owner, &token, &400, &commitment, &0);
+    assert_eq!(
+        client.try_withdraw(&token, &500, &commitment, &owner, &salt),
+        Err(Ok(QuickexError::AmountMismatch))
+    );
+    assert_eq!(
+        client.try_deposit_with_commitment(&owner, &token, &400, &commitment, &0),
+        Err(Ok(QuickexError::CommitmentAlreadyExists))
+    );
+
+    let expiring_salt = Bytes::from_slice(&env, b"rejection_paths_expiring");
+    let expiring = client.deposit(&token, &100, &owner, &expiring_salt, &60);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 61);
+    assert_eq!(
+        client.try_withdraw(&token, &100, &expiring, &owner, &expiring_salt),
+        Err(Ok(QuickexError::EscrowExpired))
+    );
+
+    client.set_min_salt_len(&admin, &32);
+    assert_eq!(
+        client.try_deposit(&token, &100, &owner, &Bytes::from_slice(&env, b"short"), &0),
+        Err(Ok(QuickexError::WeakSalt))
+    );
+    client.set_min_salt_len(&admin, &0);
+
+    client.set_token_cap(&admin, &token, &600);
+    assert_eq!(
+        client.try_deposit(
+            &token,
+            &300,
+            &owner,
+            &Bytes::from_slice(&env, b"capped"),
+            &0
+        ),
+        Err(Ok(QuickexError::CapExceeded))
+    );
+
+    client.allow_token(&admin, &create_test_token(&env));
+    assert_eq!(
+        client.try_deposit(
+            &token,
+            &100,
+            &owner,
+            &Bytes::from_slice(&env, b"unlisted"),
+            &0
+        ),
+        Err(Ok(QuickexError::TokenNotAllowed))
+    );
+
+    client.set_paused(&admin, &true);
+    assert_eq!(
+        client.try_deposit(
+            &token,
+            &100,
+            &owner,
+            &Bytes::from_slice(&env, b"paused"),
+            &0
+        ),
+        Err(Ok(QuickexError::ContractPaused))
+    );
 }
 
 /// Regression suite: deposit with commitment — create escrow (golden path).
@@ -547,15 +1692,89 @@ fn test_deposit() {
 }
 
 #[test]
-fn test_event_snapshot_escrow_deposited_schema() {
-    let env = Env::default();
-    env.mock_all_auths();
-
-    let user = Address::generate(&env);
-    let token_admin = Address::generate(&env);
+fn test_deposit_rejects_reused_commitment() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"reused_deposit_salt");
+    let token_client = token::Client::new(&env, &token);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &2000);
+
+    let commitment = client.deposit(&token, &1000, &owner, &salt, &0);
+    // A second deposit used to overwrite the pending escrow, stranding the first.
+    assert_contract_error(
+        client.try_deposit(&token, &1000, &owner, &salt, &0),
+        QuickexError::CommitmentAlreadyExists,
+    );
 
-    let token_id = env
-        .register_stellar_asset_contract_v2(token_admin.clone())
+    client.withdraw(&token, &1000, &commitment, &owner, &salt);
+    // Over a spent one, the new funds were locked for good behind its spent status.
+    assert_contract_error(
+        client.try_deposit(&token, &1000, &owner, &salt, &0),
+        QuickexError::CommitmentAlreadyExists,
+    );
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+    assert_eq!(token_client.balance(&owner), 2000);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_deposit_with_memo_stores_memo() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let user = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&user, &1000);
+
+    let commitment = BytesN::from_array(&env, &[11; 32]);
+    let memo = Bytes::from_slice(&env, &[0xAB; 96]);
+
+    assert_eq!(client.get_memo(&commitment), None);
+    client.deposit_with_memo(&user, &token, &500, &commitment, &0, &memo);
+
+    assert_eq!(client.get_memo(&commitment), Some(memo));
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+    assert_eq!(
+        token::Client::new(&env, &token).balance(&client.address),
+        500
+    );
+}
+
+#[test]
+fn test_deposit_with_memo_rejects_invalid_memo() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let user = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&user, &1000);
+
+    let commitment = BytesN::from_array(&env, &[12; 32]);
+
+    let empty = Bytes::new(&env);
+    let result = client.try_deposit_with_memo(&user, &token, &500, &commitment, &0, &empty);
+    assert_contract_error(result, QuickexError::InvalidMemo);
+
+    let too_long = Bytes::from_slice(&env, &[0u8; 1025]);
+    let result = client.try_deposit_with_memo(&user, &token, &500, &commitment, &0, &too_long);
+    assert_contract_error(result, QuickexError::InvalidMemo);
+
+    assert_eq!(client.get_commitment_state(&commitment), None);
+}
+
+#[test]
+fn test_event_snapshot_escrow_deposited_schema() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin.clone())
         .address();
     let token_client = token::StellarAssetClient::new(&env, &token_id);
     token_client.mint(&user, &1000);
@@ -590,6 +1809,24 @@ fn test_event_snapshot_escrow_withdrawn_schema() {
     let _ = client.withdraw(&token, &amount, &commitment, &to, &salt);
 }
 
+#[test]
+fn test_event_snapshot_nullifier_spent_schema() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"event_nullifier_salt");
+
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &1000);
+    let commitment = client.deposit(&token, &1000, &owner, &salt, &0);
+    client.withdraw(&token, &1000, &commitment, &owner, &salt);
+
+    // The nullifier needs the salt, so it cannot be recomputed from the commitment.
+    let nullifier = crate::commitment::escrow_nullifier(&env, &commitment, &salt);
+    let other = crate::commitment::escrow_nullifier(&env, &commitment, &Bytes::new(&env));
+    assert_ne!(nullifier, commitment);
+    assert_ne!(nullifier, other);
+}
+
 #[test]
 fn test_event_snapshot_escrow_refunded_schema() {
     let (env, client) = setup();
@@ -618,6 +1855,25 @@ fn test_event_snapshot_contract_paused_schema() {
     client.set_paused(&admin, &true);
 }
 
+#[test]
+fn test_event_snapshot_initialized_schema() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+}
+
+#[test]
+fn test_ensure_initialized_is_idempotent() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    assert_eq!(client.ensure_initialized(&admin), admin);
+    assert_eq!(client.ensure_initialized(&other), admin);
+    assert_eq!(client.get_admin(), Some(admin));
+}
+
 #[test]
 fn test_initialize_admin() {
     let (env, client) = setup();
@@ -647,6 +1903,75 @@ fn test_initialize_twice_fails() {
     assert_contract_error(result, QuickexError::AlreadyInitialized);
 }
 
+#[test]
+fn test_multisig_pause_requires_threshold() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let members = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let (a, b, c) = (
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+        members.get(2).unwrap(),
+    );
+
+    client.initialize(&admin);
+    client.initialize_multisig(&members, &2);
+    assert_eq!(client.get_admin(), None);
+    assert_eq!(client.get_admin_council().unwrap().threshold, 2);
+
+    // The single-admin path is closed.
+    assert_contract_error(
+        client.try_set_paused(&admin, &true),
+        QuickexError::Unauthorized,
+    );
+    assert_contract_error(
+        client.try_initialize(&admin),
+        QuickexError::AlreadyInitialized,
+    );
+
+    let id = client.propose_action(&a, &AdminAction::SetPaused(true));
+    assert!(!client.is_paused());
+    assert_contract_error(
+        client.try_approve_action(&a, &id),
+        QuickexError::ActionAlreadyApproved,
+    );
+
+    assert!(client.approve_action(&b, &id));
+    assert!(client.is_paused());
+    assert!(client.get_admin_action(&id).is_none());
+    assert_contract_error(
+        client.try_approve_action(&c, &id),
+        QuickexError::ActionNotFound,
+    );
+
+    let outsider = Address::generate(&env);
+    let result = client.try_propose_action(&outsider, &AdminAction::SetPaused(false));
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+}
+
+#[test]
+fn test_initialize_multisig_validation() {
+    let (env, client) = setup();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+
+    let result = client.try_initialize_multisig(&vec![&env, a.clone(), b.clone()], &0);
+    assert_contract_error(result, QuickexError::InvalidThreshold);
+    let result = client.try_initialize_multisig(&vec![&env, a.clone(), b.clone()], &3);
+    assert_contract_error(result, QuickexError::InvalidThreshold);
+    let result = client.try_initialize_multisig(&vec![&env, a.clone(), a.clone()], &1);
+    assert_contract_error(result, QuickexError::InvalidThreshold);
+
+    client.initialize_multisig(&vec![&env, a.clone(), b.clone()], &1);
+    let result = client.try_initialize_multisig(&vec![&env, a], &1);
+    assert_contract_error(result, QuickexError::AlreadyInitialized);
+}
+
 #[test]
 fn test_set_privacy_same_value_fails() {
     let (env, client) = setup();
@@ -716,6 +2041,250 @@ fn test_set_paused_by_admin() {
     assert!(!client.is_paused());
 }
 
+#[test]
+fn test_pauser_role_cannot_upgrade() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let ops = Address::generate(&env);
+    client.initialize(&admin);
+
+    assert!(client.has_role(&Role::Upgrader, &admin));
+    assert!(!client.has_role(&Role::Pauser, &ops));
+    assert_contract_error(
+        client.try_grant_role(&ops, &Role::Pauser, &ops),
+        QuickexError::Unauthorized,
+    );
+
+    client.grant_role(&admin, &Role::Pauser, &ops);
+    assert!(client.has_role(&Role::Pauser, &ops));
+    client.set_paused(&ops, &true);
+    assert!(client.is_paused());
+
+    let wasm_hash = BytesN::from_array(&env, &[7u8; 32]);
+    assert_contract_error(
+        client.try_upgrade(&ops, &wasm_hash, &1),
+        QuickexError::Unauthorized,
+    );
+    assert_contract_error(client.try_set_admin(&ops, &ops), QuickexError::Unauthorized);
+
+    client.revoke_role(&admin, &Role::Pauser, &ops);
+    assert!(!client.has_role(&Role::Pauser, &ops));
+    assert_contract_error(
+        client.try_set_paused(&ops, &false),
+        QuickexError::Unauthorized,
+    );
+}
+
+#[test]
+fn test_renounce_admin_requires_confirmation() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let ops = Address::generate(&env);
+    client.initialize(&admin);
+    client.grant_role(&admin, &Role::Pauser, &ops);
+
+    assert!(!client.renounce_admin(&admin));
+    let ready_at = client.get_renounce_ready_at().unwrap();
+    assert_eq!(ready_at, env.ledger().sequence() + 17_280);
+
+    env.ledger().with_mut(|l| l.sequence_number = ready_at - 1);
+    assert_contract_error(
+        client.try_renounce_admin(&admin),
+        QuickexError::TimelockNotReady,
+    );
+
+    env.ledger().with_mut(|l| l.sequence_number = ready_at);
+    assert!(client.renounce_admin(&admin));
+    assert!(client.is_admin_renounced());
+    assert_eq!(client.get_admin(), None);
+    assert_eq!(client.get_renounce_ready_at(), None);
+
+    // Role holders lose their powers and nobody can take over.
+    assert!(!client.has_role(&Role::Pauser, &ops));
+    assert_contract_error(
+        client.try_set_paused(&ops, &true),
+        QuickexError::Unauthorized,
+    );
+    assert_contract_error(
+        client.try_initialize(&ops),
+        QuickexError::AlreadyInitialized,
+    );
+}
+
+#[test]
+fn test_cancel_renounce_admin() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    client.renounce_admin(&admin);
+    client.cancel_renounce_admin(&admin);
+    assert_eq!(client.get_renounce_ready_at(), None);
+
+    // Renouncing while paused would freeze the contract.
+    client.set_paused(&admin, &true);
+    assert_contract_error(
+        client.try_renounce_admin(&admin),
+        QuickexError::ContractPaused,
+    );
+    assert_eq!(client.get_admin(), Some(admin));
+}
+
+#[test]
+fn test_pause_deposits_keeps_withdrawals_open() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"paused_ops_salt");
+    client.initialize(&admin);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &2000);
+    let commitment = client.deposit(&token, &1000, &owner, &salt, &0);
+
+    client.set_paused_ops(&admin, &1, &true);
+    assert_eq!(client.paused_ops(), 1);
+    assert!(client.is_paused());
+
+    let result = client.try_deposit(
+        &token,
+        &1000,
+        &owner,
+        &Bytes::from_slice(&env, b"other"),
+        &0,
+    );
+    assert_contract_error(result, QuickexError::ContractPaused);
+    client.withdraw(&token, &1000, &commitment, &owner, &salt);
+
+    // Pausing withdrawals too, then lifting only the deposit pause.
+    client.set_paused_ops(&admin, &(2 | 8), &true);
+    client.set_paused_ops(&admin, &1, &false);
+    assert_eq!(client.paused_ops(), 2 | 8);
+    let result = client.try_create_escrow(&owner, &owner, &token, &1);
+    assert_eq!(result, Err(Ok(QuickexError::ContractPaused)));
+
+    assert_contract_error(
+        client.try_set_paused_ops(&admin, &0, &true),
+        QuickexError::InvalidPauseOps,
+    );
+    assert_contract_error(
+        client.try_set_paused_ops(&admin, &16, &true),
+        QuickexError::InvalidPauseOps,
+    );
+
+    client.set_paused(&admin, &false);
+    assert_eq!(client.paused_ops(), 0);
+}
+
+#[test]
+fn test_disabled_function_fails_while_others_run() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"kill_switch_salt");
+    client.initialize(&admin);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &1000);
+    let commitment = client.deposit(&token, &1000, &owner, &salt, &0);
+
+    let withdraw = Symbol::new(&env, "withdraw");
+    client.disable_function(&admin, &withdraw);
+    assert!(client.is_function_disabled(&withdraw));
+    assert!(!client.is_paused());
+    assert_eq!(
+        client.try_withdraw(&token, &1000, &commitment, &owner, &salt),
+        Err(Ok(QuickexError::FunctionDisabled))
+    );
+    client.create_escrow(&owner, &Address::generate(&env), &token, &1);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_enable_function(&stranger, &withdraw),
+        Err(Ok(QuickexError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_disable_function(&admin, &Symbol::new(&env, "enable_function")),
+        Err(Ok(QuickexError::UnknownFunction))
+    );
+
+    client.enable_function(&admin, &withdraw);
+    assert!(!client.is_function_disabled(&withdraw));
+    client.withdraw(&token, &1000, &commitment, &owner, &salt);
+}
+
+#[test]
+fn test_guardian_can_only_pause() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_guardian(&admin, &Some(guardian.clone()));
+    assert_eq!(client.get_guardian(), Some(guardian.clone()));
+
+    client.set_paused(&guardian, &true);
+    assert!(client.is_paused());
+
+    assert_contract_error(
+        client.try_set_paused(&guardian, &false),
+        QuickexError::Unauthorized,
+    );
+    assert_contract_error(
+        client.try_set_paused_ops(&guardian, &1, &true),
+        QuickexError::Unauthorized,
+    );
+    assert_contract_error(
+        client.try_set_guardian(&guardian, &None),
+        QuickexError::Unauthorized,
+    );
+
+    client.set_paused(&admin, &false);
+    client.set_guardian(&admin, &None);
+    assert_contract_error(
+        client.try_set_paused(&guardian, &true),
+        QuickexError::Unauthorized,
+    );
+}
+
+#[test]
+fn test_recovery_address_claims_admin_after_inactivity() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let recovery = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_recovery_address(&admin, &Some(recovery.clone()));
+    assert_eq!(client.get_recovery_address(), Some(recovery.clone()));
+
+    let stranger = Address::generate(&env);
+    assert_contract_error(
+        client.try_claim_admin_after_inactivity(&stranger),
+        QuickexError::Unauthorized,
+    );
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + crate::admin::RECOVERY_INACTIVITY_SECS - 1);
+    assert_contract_error(
+        client.try_claim_admin_after_inactivity(&recovery),
+        QuickexError::AdminStillActive,
+    );
+
+    // Any admin call resets the clock.
+    client.set_paused(&admin, &false);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + crate::admin::RECOVERY_INACTIVITY_SECS - 1);
+    assert_contract_error(
+        client.try_claim_admin_after_inactivity(&recovery),
+        QuickexError::AdminStillActive,
+    );
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 1);
+    client.claim_admin_after_inactivity(&recovery);
+    assert_eq!(client.get_admin(), Some(recovery.clone()));
+    assert_eq!(client.get_recovery_address(), None);
+    assert_contract_error(
+        client.try_set_paused(&admin, &true),
+        QuickexError::Unauthorized,
+    );
+}
+
 #[test]
 fn test_set_paused_by_non_admin_fails() {
     let (env, client) = setup();
@@ -984,11 +2553,8 @@ fn test_verify_proof_view_spent_commitment() {
         expires_at: 0,
     };
 
-    let escrow_key = soroban_sdk::Symbol::new(&env, "escrow");
     env.as_contract(&client.address, || {
-        env.storage()
-            .persistent()
-            .set(&(escrow_key, commitment.clone()), &entry);
+        put_escrow(&env, &commitment.clone().into(), &entry);
     });
 
     let is_valid = client.verify_proof_view(&amount, &salt, &owner);
@@ -1111,7 +2677,7 @@ fn test_upgrade_by_admin() {
     // Note: In test environment, update_current_contract_wasm may fail
     // because the WASM hash doesn't exist, but the auth check should pass.
     // We use try_upgrade to verify auth passes (not Unauthorized error)
-    let result = client.try_upgrade(&admin, &new_wasm_hash);
+    let result = client.try_upgrade(&admin, &new_wasm_hash, &1);
 
     // The call should NOT fail with Unauthorized (Contract error #2)
     // It may fail with a host error because the WASM doesn't exist in test env
@@ -1145,7 +2711,7 @@ fn test_upgrade_by_non_admin_fails() {
     let new_wasm_hash = BytesN::from_array(&env, &[0u8; 32]);
 
     // Non-admin tries to upgrade - should fail with Unauthorized
-    let result = client.try_upgrade(&non_admin, &new_wasm_hash);
+    let result = client.try_upgrade(&non_admin, &new_wasm_hash, &1);
     assert_contract_error(result, QuickexError::Unauthorized);
 }
 
@@ -1158,47 +2724,268 @@ fn test_upgrade_without_admin_initialized_fails() {
     let new_wasm_hash = BytesN::from_array(&env, &[0u8; 32]);
 
     // Try to upgrade without admin set - should fail with Unauthorized
-    let result = client.try_upgrade(&caller, &new_wasm_hash);
+    let result = client.try_upgrade(&caller, &new_wasm_hash, &1);
     assert_contract_error(result, QuickexError::Unauthorized);
 }
 
-// ============================================================================
-// Timeout & Refund Tests
-// ============================================================================
+fn seed_pending_upgrade(env: &Env, contract: &Address, version: u32) {
+    env.as_contract(contract, || {
+        crate::storage::set_pending_upgrade(
+            env,
+            Some(&UpgradeRecord {
+                wasm_hash: BytesN::from_array(env, &[7u8; 32]),
+                version,
+                upgraded_at: env.ledger().timestamp(),
+            }),
+        );
+    });
+}
 
 #[test]
-fn test_withdrawal_fails_after_expiry() {
+fn test_check_upgrade_reports_blockers() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let wasm_hash = BytesN::from_array(&env, &[8u8; 32]);
+    client.initialize(&admin);
+
+    let report = client.check_upgrade(&wasm_hash);
+    assert!(report.allowed);
+    assert_eq!(
+        report.schema_version,
+        crate::migration::STORAGE_SCHEMA_VERSION
+    );
+
+    seed_pending_upgrade(&env, &client.address, crate::migration::CONTRACT_VERSION);
+    client.set_timelock_delay(&admin, &3_600);
+    let report = client.check_upgrade(&wasm_hash);
+    assert!(!report.allowed);
+    assert_eq!(
+        report.blockers,
+        vec![
+            &env,
+            UpgradeBlocker::TimelockRequired,
+            UpgradeBlocker::MigrationPending
+        ]
+    );
+    assert_eq!(report.scheduled_at, None);
+
+    client.schedule_upgrade(&admin, &wasm_hash, &1, &7_200);
+    assert_eq!(client.check_upgrade(&wasm_hash).scheduled_at, Some(7_200));
+}
+
+#[test]
+fn test_pending_migration_blocks_operations_until_migrate() {
     let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
     let token = create_test_token(&env);
-    let to = Address::generate(&env);
-    let amount: i128 = 1000;
-    let salt = Bytes::from_slice(&env, b"expiry_salt");
+    let commitment = BytesN::from_array(&env, &[9u8; 32]);
+    client.initialize(&admin);
 
-    let mut data = Bytes::new(&env);
-    let address_bytes: Bytes = to.clone().to_xdr(&env);
-    data.append(&address_bytes);
-    data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
-    data.append(&salt);
-    let commitment: BytesN<32> = env.crypto().sha256(&data).into();
+    seed_pending_upgrade(&env, &client.address, crate::migration::CONTRACT_VERSION);
+    let result = client.try_deposit_with_commitment(&user, &token, &500, &commitment, &0);
+    assert_contract_error(result, QuickexError::MigrationPending);
 
-    // Set expiry to 100 seconds from now
-    let now = env.ledger().timestamp();
-    let expires_at = now + 100;
-    setup_escrow(
+    assert_eq!(client.migrate(), crate::migration::STORAGE_SCHEMA_VERSION);
+    assert_eq!(
+        client.try_migrate(),
+        Err(Ok(QuickexError::NothingToMigrate))
+    );
+
+    let result = client.try_deposit_with_commitment(&user, &token, &500, &commitment, &0);
+    assert_ne!(result, Err(Ok(QuickexError::MigrationPending)));
+}
+
+#[test]
+fn test_migrate_rejects_wrong_code_version() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    seed_pending_upgrade(
         &env,
         &client.address,
-        &token,
-        amount,
-        commitment.clone(),
-        expires_at,
+        crate::migration::CONTRACT_VERSION + 1,
+    );
+    assert_eq!(
+        client.try_migrate(),
+        Err(Ok(QuickexError::UpgradeVersionMismatch))
     );
+}
 
-    // Mint tokens to contract so it CAN pay if it were valid
-    let token_client = token::StellarAssetClient::new(&env, &token);
-    token_client.mint(&client.address, &amount);
+#[test]
+fn test_version_reports_code_and_schema() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let code = crate::migration::CONTRACT_VERSION;
 
-    // 1. Withdrawal before expiry should work
-    env.ledger().set_timestamp(now + 50);
+    assert_eq!(client.version(), (code, 1));
+    client.initialize(&admin);
+    assert_eq!(
+        client.version(),
+        (code, crate::migration::STORAGE_SCHEMA_VERSION)
+    );
+}
+
+#[test]
+fn test_migrate_rejects_schema_downgrade() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    env.as_contract(&client.address, || {
+        crate::storage::set_schema_version(&env, crate::migration::STORAGE_SCHEMA_VERSION + 1);
+    });
+    seed_pending_upgrade(&env, &client.address, crate::migration::CONTRACT_VERSION);
+    assert_eq!(client.try_migrate(), Err(Ok(QuickexError::SchemaDowngrade)));
+}
+
+#[test]
+fn test_migrate_folds_legacy_paused_flag() {
+    let (env, client) = setup();
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&crate::storage::DataKey::Paused, &true);
+    });
+
+    assert_eq!(client.migrate(), crate::migration::STORAGE_SCHEMA_VERSION);
+    assert_eq!(client.paused_ops(), crate::admin::PAUSE_ALL);
+    env.as_contract(&client.address, || {
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&crate::storage::DataKey::Paused));
+    });
+}
+
+#[test]
+fn test_migrate_storage_moves_legacy_escrows() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"legacy_escrow_salt");
+    let commitment = client.create_amount_commitment(&to, &500, &salt);
+    let entry = EscrowEntry {
+        token: token.clone(),
+        amount: 500,
+        owner: to.clone(),
+        status: EscrowStatus::Pending,
+        created_at: env.ledger().timestamp(),
+        expires_at: 0,
+    };
+    let legacy_key = (Symbol::new(&env, "escrow"), commitment.clone());
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(&legacy_key, &entry);
+    });
+    token::StellarAssetClient::new(&env, &token).mint(&client.address, &500);
+    let unknown = BytesN::from_array(&env, &[7; 32]);
+
+    let batch = vec![&env, commitment.clone(), unknown];
+    assert_contract_error(
+        client.try_migrate_storage(&to, &batch),
+        QuickexError::Unauthorized,
+    );
+    assert_eq!(client.migrate_storage(&admin, &batch), 1);
+    assert_eq!(client.migrate_storage(&admin, &batch), 0);
+    assert_eq!(client.proof_of_reserves(&token), (500, 500));
+    assert_eq!(client.list_claimable(&to), vec![&env, commitment.clone()]);
+
+    client.withdraw(&token, &500, &commitment, &to, &salt);
+    assert_eq!(token::Client::new(&env, &token).balance(&to), 500);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&legacy_key));
+    });
+}
+
+#[test]
+fn test_debug_dump_entry_returns_raw_stored_value() {
+    use soroban_sdk::xdr::FromXdr;
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    assert_eq!(
+        client.storage_layout_version(),
+        crate::migration::STORAGE_SCHEMA_VERSION
+    );
+
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"dump_salt");
+    let commitment = client.create_amount_commitment(&to, &500, &salt);
+    let entry = EscrowEntry {
+        token,
+        amount: 500,
+        owner: to.clone(),
+        status: EscrowStatus::Pending,
+        created_at: env.ledger().timestamp(),
+        expires_at: 0,
+    };
+    env.as_contract(&client.address, || {
+        let legacy_key = (Symbol::new(&env, "escrow"), commitment.clone());
+        env.storage().persistent().set(&legacy_key, &entry);
+    });
+
+    assert_contract_error(
+        client.try_debug_dump_entry(&to, &commitment),
+        QuickexError::Unauthorized,
+    );
+    assert_eq!(
+        client.debug_dump_entry(&admin, &BytesN::from_array(&env, &[7; 32])),
+        None
+    );
+    // Legacy entries are dumped in their original field-map format...
+    assert_eq!(
+        client.debug_dump_entry(&admin, &commitment),
+        Some(entry.clone().to_xdr(&env))
+    );
+
+    // ...and migrated ones in the packed tuple format.
+    client.migrate_storage(&admin, &vec![&env, commitment.clone()]);
+    let dump = client.debug_dump_entry(&admin, &commitment).unwrap();
+    let packed = crate::types::PackedEscrowEntry::from_xdr(&env, &dump).unwrap();
+    assert_eq!((packed.1, packed.2), (500, to));
+}
+
+// ============================================================================
+// Timeout & Refund Tests
+// ============================================================================
+
+#[test]
+fn test_withdrawal_fails_after_expiry() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"expiry_salt");
+
+    let mut data = Bytes::new(&env);
+    let address_bytes: Bytes = to.clone().to_xdr(&env);
+    data.append(&address_bytes);
+    data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
+    data.append(&salt);
+    let commitment: BytesN<32> = env.crypto().sha256(&data).into();
+
+    // Set expiry to 100 seconds from now
+    let now = env.ledger().timestamp();
+    let expires_at = now + 100;
+    setup_escrow(
+        &env,
+        &client.address,
+        &token,
+        amount,
+        commitment.clone(),
+        expires_at,
+    );
+
+    // Mint tokens to contract so it CAN pay if it were valid
+    let token_client = token::StellarAssetClient::new(&env, &token);
+    token_client.mint(&client.address, &amount);
+
+    // 1. Withdrawal before expiry should work
+    env.ledger().set_timestamp(now + 50);
     let res = client.try_withdraw(&token, &amount, &commitment, &to, &salt);
     assert!(res.is_ok());
 
@@ -1227,6 +3014,214 @@ fn test_withdrawal_fails_after_expiry() {
     assert_eq!(res, Err(Ok(crate::errors::QuickexError::EscrowExpired)));
 }
 
+#[test]
+fn test_prune_spent_compacts_full_spent_entries() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"prune_salt");
+    let commitment = client.create_amount_commitment(&to, &100, &salt);
+    // A spent escrow stored in full, as before spent escrows were archived.
+    let entry = EscrowEntry {
+        token: token.clone(),
+        amount: 100,
+        owner: to.clone(),
+        status: EscrowStatus::Spent,
+        created_at: env.ledger().timestamp(),
+        expires_at: 0,
+    };
+    env.as_contract(&client.address, || {
+        put_escrow(&env, &commitment.clone().into(), &entry);
+    });
+    let pending = BytesN::from_array(&env, &[3; 32]);
+    setup_escrow(&env, &client.address, &token, 50, pending.clone(), 0);
+
+    let batch = vec![&env, commitment.clone(), pending.clone()];
+    assert_eq!(client.prune_spent(&batch), 1);
+    assert_eq!(client.prune_spent(&batch), 0);
+    assert_eq!(
+        client.get_commitment_state(&pending),
+        Some(EscrowStatus::Pending)
+    );
+    env.as_contract(&client.address, || {
+        let persistent = env.storage().persistent();
+        let key: Bytes = commitment.clone().into();
+        assert!(!persistent.has(&crate::storage::DataKey::Escrow(key.clone())));
+        assert_eq!(
+            persistent.get(&crate::storage::DataKey::SpentStatus(key)),
+            Some(EscrowStatus::Spent)
+        );
+    });
+
+    assert_contract_error(
+        client.try_withdraw(&token, &100, &commitment, &to, &salt),
+        QuickexError::AlreadySpent,
+    );
+}
+
+#[test]
+fn test_gc_collects_plain_withdrawn_and_refunded_escrows() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &300);
+    let withdrawn_salt = Bytes::from_slice(&env, b"gc_withdrawn");
+    let withdrawn = client.deposit(&token, &100, &owner, &withdrawn_salt, &0);
+    let refunded = client.deposit(
+        &token,
+        &200,
+        &owner,
+        &Bytes::from_slice(&env, b"gc_refunded"),
+        &10,
+    );
+
+    client.withdraw(&token, &100, &withdrawn, &owner, &withdrawn_salt);
+    env.ledger().with_mut(|l| l.timestamp += 10);
+    client.refund(&refunded, &owner);
+    assert_eq!(client.gc_queue_len(), 2);
+
+    assert_eq!(client.gc(&keeper, &10), 2);
+    assert_eq!(client.gc_queue_len(), 0);
+    env.as_contract(&client.address, || {
+        for commitment in [&withdrawn, &refunded] {
+            assert!(!env
+                .storage()
+                .persistent()
+                .has(&crate::storage::DataKey::Escrow(commitment.clone().into())));
+        }
+    });
+    // Only the status is left, and it still blocks a second claim or deposit.
+    assert_eq!(client.get_commitment_state(&withdrawn), None);
+    assert_contract_error(
+        client.try_withdraw(&token, &100, &withdrawn, &owner, &withdrawn_salt),
+        QuickexError::AlreadySpent,
+    );
+    assert_contract_error(
+        client.try_deposit(&token, &100, &owner, &withdrawn_salt, &0),
+        QuickexError::CommitmentAlreadyExists,
+    );
+}
+
+#[test]
+fn test_ephemeral_escrow_claim_refund_and_expiry() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &300);
+    let commitment_for = |salt: &[u8]| {
+        client.create_token_commitment(&token, &to, &100, &Bytes::from_slice(&env, salt))
+    };
+
+    for timeout in [0, crate::escrow::MAX_EPHEMERAL_TIMEOUT_SECS + 1] {
+        assert_contract_error(
+            client.try_deposit_ephemeral(&depositor, &token, &100, &commitment_for(b"a"), &timeout),
+            QuickexError::InvalidTimeout,
+        );
+    }
+
+    // Claimed within the window; the spent status outlives the temporary entries.
+    let claimed = commitment_for(b"ephemeral_claimed");
+    client.deposit_ephemeral(&depositor, &token, &100, &claimed, &30);
+    assert!(client.list_claimable(&depositor).is_empty());
+    let salt = Bytes::from_slice(&env, b"ephemeral_claimed");
+    client.withdraw(&token, &100, &claimed, &to, &salt);
+    assert_eq!(
+        client.get_commitment_state(&claimed),
+        Some(EscrowStatus::Spent)
+    );
+    env.as_contract(&client.address, || {
+        let persistent = env.storage().persistent();
+        assert!(persistent.has(&crate::storage::DataKey::SpentStatus(
+            claimed.clone().into()
+        )));
+        assert!(!persistent.has(&crate::storage::DataKey::EphemeralClaim(
+            claimed.clone().into()
+        )));
+    });
+
+    // Refunded after the window.
+    let refunded = commitment_for(b"ephemeral_refunded");
+    client.deposit_ephemeral(&depositor, &token, &100, &refunded, &30);
+    env.ledger().with_mut(|l| l.timestamp += 30);
+    client.refund(&refunded, &depositor);
+    assert_eq!(token::Client::new(&env, &token).balance(&depositor), 200);
+
+    // Still locked and refundable once the temporary entry outlives its TTL.
+    let lapsed = commitment_for(b"ephemeral_lapsed");
+    client.deposit_ephemeral(&depositor, &token, &100, &lapsed, &30);
+    env.ledger().with_mut(|l| {
+        l.sequence_number += 7 + crate::storage::EPHEMERAL_REFUND_LEDGERS;
+        l.timestamp += 30;
+    });
+    env.as_contract(&client.address, || {
+        assert!(!env
+            .storage()
+            .temporary()
+            .has(&crate::storage::DataKey::EphemeralEscrow(
+                lapsed.clone().into()
+            )));
+    });
+    assert_eq!(
+        client.get_commitment_state(&lapsed),
+        Some(EscrowStatus::Pending)
+    );
+    assert_eq!(client.proof_of_reserves(&token), (100, 100));
+    client.refund(&lapsed, &depositor);
+    assert_eq!(token::Client::new(&env, &token).balance(&depositor), 200);
+    assert_eq!(client.proof_of_reserves(&token), (0, 0));
+}
+
+#[test]
+fn test_archived_ephemeral_commitment_cannot_be_redeposited() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &200);
+    let salt = Bytes::from_slice(&env, b"ephemeral_reused");
+    let commitment = client.create_token_commitment(&token, &to, &100, &salt);
+
+    client.deposit_ephemeral(&depositor, &token, &100, &commitment, &30);
+    client.withdraw(&token, &100, &commitment, &to, &salt);
+
+    // Spent: the commitment cannot be funded and claimed a second time, even once the
+    // archive has expired.
+    env.as_contract(&client.address, || {
+        env.storage()
+            .temporary()
+            .remove(&crate::storage::DataKey::ArchivedEscrow(
+                commitment.clone().into(),
+            ));
+    });
+    assert_contract_error(
+        client.try_deposit_ephemeral(&depositor, &token, &100, &commitment, &30),
+        QuickexError::CommitmentAlreadyExists,
+    );
+    assert_contract_error(
+        client.try_deposit_with_commitment(&depositor, &token, &100, &commitment, &0),
+        QuickexError::CommitmentAlreadyExists,
+    );
+    assert_eq!(token::Client::new(&env, &token).balance(&depositor), 100);
+}
+
+#[test]
+fn test_deposit_ephemeral_checks_timeout_before_transfer() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    // Unfunded, so a transfer attempted first would fail with `TransferFailed`.
+    let depositor = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[9; 32]);
+
+    for timeout in [0, crate::escrow::MAX_EPHEMERAL_TIMEOUT_SECS + 1] {
+        assert_contract_error(
+            client.try_deposit_ephemeral(&depositor, &token, &100, &commitment, &timeout),
+            QuickexError::InvalidTimeout,
+        );
+    }
+}
+
 /// Regression suite: refund after expiry — golden path refund flow.
 #[test]
 fn test_refund_successful() {
@@ -1308,45 +3303,970 @@ fn test_double_refund_fails() {
     assert_eq!(res, Err(Ok(crate::errors::QuickexError::AlreadySpent)));
 }
 
-// ============================================================================
-// Regression suite: single full-flow golden path (run after upgrades)
-// ============================================================================
-
-/// Regression suite: one test that runs the minimal golden path — create commitment,
-/// deposit, toggle privacy, withdraw. Re-run with `cargo test regression_golden_path_full_flow`
-/// after contract or SDK upgrades to ensure core flows still work.
 #[test]
-fn regression_golden_path_full_flow() {
+fn test_spent_escrow_pruned_but_stays_spent() {
     let (env, client) = setup();
     let token = create_test_token(&env);
+    let owner = Address::generate(&env);
     let to = Address::generate(&env);
     let amount: i128 = 1000;
-    let salt = Bytes::from_slice(&env, b"regression_golden_salt");
-
-    // 1. Create and verify commitment
-    let commitment = client.create_amount_commitment(&to, &amount, &salt);
-    assert!(client.verify_amount_commitment(&commitment, &to, &amount, &salt));
+    let salt = Bytes::from_slice(&env, b"archive_salt");
 
-    // 2. Deposit: mint to `to` (owner) and deposit into escrow
-    let token_client = token::StellarAssetClient::new(&env, &token);
-    token_client.mint(&to, &amount);
-    let committed = client.deposit(&token, &amount, &to, &salt, &0);
-    assert_eq!(committed, commitment);
-    assert_eq!(token_client.balance(&client.address), amount);
-
-    // 3. Toggle privacy (must not break escrow or withdrawal)
-    client.set_privacy(&to, &true);
-    assert!(client.get_privacy(&to));
-    client.set_privacy(&to, &false);
-    assert!(!client.get_privacy(&to));
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &amount);
+    let commitment = client.deposit(&token, &amount, &owner, &salt, &0);
+    client.withdraw(&token, &amount, &commitment, &owner, &salt);
 
-    // 4. Withdraw
-    let ok = client.withdraw(&token, &amount, &commitment, &to, &salt);
-    assert!(ok);
-    assert_eq!(token_client.balance(&to), amount);
-    assert_eq!(token_client.balance(&client.address), 0);
+    // Spent entry stays queryable for indexers until pruned.
     assert_eq!(
         client.get_commitment_state(&commitment),
         Some(EscrowStatus::Spent)
     );
+    let details = client.get_escrow_details(&commitment, &to).unwrap();
+    assert_eq!(details.amount, Some(amount));
+
+    assert_eq!(client.prune_spent(&vec![&env, commitment.clone()]), 1);
+
+    // The entry is gone, but the spent status still blocks reuse.
+    assert!(client.get_escrow_details(&commitment, &to).is_none());
+    let res = client.try_withdraw(&token, &amount, &commitment, &owner, &salt);
+    assert_eq!(res, Err(Ok(QuickexError::AlreadySpent)));
+
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &amount);
+    let res = client.try_deposit_with_commitment(&owner, &token, &amount, &commitment, &0);
+    assert_eq!(res, Err(Ok(QuickexError::CommitmentAlreadyExists)));
+}
+
+#[test]
+fn test_keeper_extends_escrow_and_instance_ttl() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"ttl_salt");
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &1000);
+    let commitment = client.deposit(&token, &1000, &owner, &salt, &0);
+
+    let live_until = env.ledger().sequence() + env.storage().max_ttl();
+    assert_eq!(client.extend_escrow_ttl(&keeper, &commitment), live_until);
+    assert_eq!(client.extend_instance_ttl(&keeper), live_until);
+
+    let unknown = BytesN::from_array(&env, &[9; 32]);
+    assert_eq!(
+        client.try_extend_escrow_ttl(&keeper, &unknown),
+        Err(Ok(QuickexError::CommitmentNotFound))
+    );
+    client.withdraw(&token, &1000, &commitment, &owner, &salt);
+    assert_eq!(
+        client.try_extend_escrow_ttl(&keeper, &commitment),
+        Err(Ok(QuickexError::AlreadySpent))
+    );
+}
+
+#[test]
+fn test_bump_entries_extends_batch() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &300);
+    let first = client.deposit(
+        &token,
+        &100,
+        &owner,
+        &Bytes::from_slice(&env, b"bump_a"),
+        &0,
+    );
+    let second = client.deposit(
+        &token,
+        &200,
+        &owner,
+        &Bytes::from_slice(&env, b"bump_b"),
+        &0,
+    );
+    let unknown = BytesN::from_array(&env, &[9; 32]);
+    let batch = vec![&env, first.clone(), unknown, second.clone()];
+
+    let max_ttl = env.storage().max_ttl();
+    for extend_to in [0, max_ttl + 1] {
+        assert_eq!(
+            client.try_bump_entries(&keeper, &batch, &extend_to),
+            Err(Ok(QuickexError::InvalidTtl))
+        );
+    }
+
+    let extend_to = 100_000;
+    assert_eq!(client.bump_entries(&keeper, &batch, &extend_to), 2);
+    env.as_contract(&client.address, || {
+        use soroban_sdk::testutils::storage::Persistent as _;
+        for commitment in [first, second] {
+            let key = crate::storage::DataKey::Escrow(commitment.into());
+            assert_eq!(env.storage().persistent().get_ttl(&key), extend_to);
+        }
+    });
+}
+
+#[test]
+fn test_instance_ttl_self_bumps_and_bump_instance() {
+    use crate::storage::{INSTANCE_BUMP_THRESHOLD, INSTANCE_BUMP_TO};
+    use soroban_sdk::testutils::storage::Instance as _;
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &100);
+    let instance_ttl = || env.as_contract(&client.address, || env.storage().instance().get_ttl());
+    assert!(instance_ttl() < INSTANCE_BUMP_THRESHOLD);
+
+    client.deposit(
+        &token,
+        &100,
+        &owner,
+        &Bytes::from_slice(&env, b"bump_salt"),
+        &0,
+    );
+    assert_eq!(instance_ttl(), INSTANCE_BUMP_TO);
+
+    let max_ttl = env.storage().max_ttl();
+    for extend_to in [0, max_ttl + 1] {
+        assert_eq!(
+            client.try_bump_instance(&extend_to),
+            Err(Ok(QuickexError::InvalidTtl))
+        );
+    }
+    assert_eq!(
+        client.bump_instance(&max_ttl),
+        env.ledger().sequence() + max_ttl
+    );
+    assert_eq!(instance_ttl(), max_ttl);
+}
+
+#[test]
+fn test_restore_escrow_extends_claim_entries() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"restore_salt");
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &100);
+    let commitment = client.deposit(&token, &100, &owner, &salt, &0);
+
+    let max_ttl = env.storage().max_ttl();
+    env.ledger().with_mut(|l| l.sequence_number += 1_000);
+    assert_eq!(
+        client.restore_escrow(&commitment),
+        env.ledger().sequence() + max_ttl
+    );
+    env.as_contract(&client.address, || {
+        use crate::storage::DataKey;
+        use soroban_sdk::testutils::storage::Persistent as _;
+        let persistent = env.storage().persistent();
+        assert_eq!(
+            persistent.get_ttl(&DataKey::Escrow(commitment.clone().into())),
+            max_ttl
+        );
+        assert_eq!(
+            persistent.get_ttl(&DataKey::OwnerSlot(commitment.clone())),
+            max_ttl
+        );
+    });
+
+    assert_eq!(
+        client.try_restore_escrow(&BytesN::from_array(&env, &[7; 32])),
+        Err(Ok(QuickexError::CommitmentNotFound))
+    );
+    client.withdraw(&token, &100, &commitment, &owner, &salt);
+    assert_eq!(
+        client.try_restore_escrow(&commitment),
+        Err(Ok(QuickexError::AlreadySpent))
+    );
+}
+
+#[test]
+fn test_refund_writes_only_status() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"archive_refund");
+
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &amount);
+    let commitment = client.deposit(&token, &amount, &owner, &salt, &100);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 101);
+    client.refund(&commitment, &owner);
+
+    // The entry is left untouched (still packed as pending); only the status cell
+    // records the refund.
+    let key: Bytes = commitment.clone().into();
+    env.as_contract(&client.address, || {
+        assert!(crate::storage::is_closed(&env, &key));
+        let stored: crate::types::PackedEscrowEntry = env
+            .storage()
+            .persistent()
+            .get(&crate::storage::DataKey::Escrow(key.clone()))
+            .unwrap();
+        assert_eq!(stored.3 & 0xff, 0);
+    });
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Refunded)
+    );
+
+    let res = client.try_refund(&commitment, &owner);
+    assert_eq!(res, Err(Ok(QuickexError::AlreadySpent)));
+    assert_eq!(client.prune_spent(&vec![&env, commitment.clone()]), 1);
+    env.as_contract(&client.address, || {
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&crate::storage::DataKey::Escrow(key)));
+    });
+    assert_eq!(client.get_commitment_state(&commitment), None);
+    let res = client.try_refund(&commitment, &owner);
+    assert_eq!(res, Err(Ok(QuickexError::AlreadySpent)));
+}
+
+#[test]
+fn test_proof_of_reserves_tracks_pending_escrows() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let salt_a = Bytes::from_slice(&env, b"reserves_a");
+    let salt_b = Bytes::from_slice(&env, b"reserves_b");
+
+    assert_eq!(client.proof_of_reserves(&token), (0, 0));
+
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &1500);
+    let commitment = client.deposit(&token, &1000, &owner, &salt_a, &0);
+    let expiring = client.deposit(&token, &500, &owner, &salt_b, &100);
+    assert_eq!(client.proof_of_reserves(&token), (1500, 1500));
+
+    client.withdraw(&token, &1000, &commitment, &owner, &salt_a);
+    assert_eq!(client.proof_of_reserves(&token), (500, 500));
+
+    // Donations raise the balance without adding liabilities.
+    token::StellarAssetClient::new(&env, &token).mint(&client.address, &42);
+    assert_eq!(client.proof_of_reserves(&token), (542, 500));
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 101);
+    client.refund(&expiring, &owner);
+    assert_eq!(client.proof_of_reserves(&token), (42, 0));
+}
+
+#[test]
+fn test_emergency_withdraw_only_sweeps_surplus() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let rescuer = Address::generate(&env);
+    client.initialize(&admin);
+
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &1000);
+    client.deposit(
+        &token,
+        &1000,
+        &owner,
+        &Bytes::from_slice(&env, b"sweep_salt"),
+        &0,
+    );
+    // Airdrop straight to the contract.
+    token::StellarAssetClient::new(&env, &token).mint(&client.address, &75);
+    assert_eq!(client.sweepable_balance(&token), 75);
+
+    assert_contract_error(
+        client.try_emergency_withdraw(&admin, &token, &rescuer, &75),
+        QuickexError::ContractNotPaused,
+    );
+
+    client.set_paused(&admin, &true);
+    assert_contract_error(
+        client.try_emergency_withdraw(&admin, &token, &rescuer, &76),
+        QuickexError::ExceedsSurplus,
+    );
+    assert_contract_error(
+        client.try_emergency_withdraw(&rescuer, &token, &rescuer, &75),
+        QuickexError::Unauthorized,
+    );
+
+    client.emergency_withdraw(&admin, &token, &rescuer, &75);
+    assert_eq!(token::Client::new(&env, &token).balance(&rescuer), 75);
+    assert_eq!(client.sweepable_balance(&token), 0);
+    assert_eq!(client.proof_of_reserves(&token), (1000, 1000));
+}
+
+#[test]
+fn test_emergency_withdraw_refused_until_migrated() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let rescuer = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_paused(&admin, &true);
+    token::StellarAssetClient::new(&env, &token).mint(&client.address, &100);
+
+    // A negative locked total must not turn other liabilities into surplus.
+    env.as_contract(&client.address, || {
+        crate::storage::set_total_locked(&env, &token, -500);
+        crate::storage::set_schema_version(&env, crate::migration::STORAGE_SCHEMA_VERSION - 1);
+    });
+    assert_eq!(client.sweepable_balance(&token), 100);
+    assert_contract_error(
+        client.try_emergency_withdraw(&admin, &token, &rescuer, &1),
+        QuickexError::MigrationPending,
+    );
+
+    client.migrate();
+    assert_eq!(client.sweepable_balance(&token), 100);
+    client.emergency_withdraw(&admin, &token, &rescuer, &100);
+    assert_eq!(token::Client::new(&env, &token).balance(&rescuer), 100);
+}
+
+#[test]
+fn test_sweepable_balance_excludes_relayer_stakes() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let relayer = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_relayer_config(&admin, &token, &100, &0);
+
+    token::StellarAssetClient::new(&env, &token).mint(&relayer, &100);
+    client.stake_relayer(&relayer, &100);
+    token::StellarAssetClient::new(&env, &token).mint(&client.address, &10);
+    assert_eq!(client.sweepable_balance(&token), 10);
+
+    client.slash_relayer(&admin, &relayer, &40, &soroban_sdk::symbol_short!("grief"));
+    assert_eq!(client.sweepable_balance(&token), 10);
+}
+
+// ============================================================================
+// Regression suite: single full-flow golden path (run after upgrades)
+// ============================================================================
+
+/// Regression suite: one test that runs the minimal golden path — create commitment,
+/// deposit, toggle privacy, withdraw. Re-run with `cargo test regression_golden_path_full_flow`
+/// after contract or SDK upgrades to ensure core flows still work.
+#[test]
+fn regression_golden_path_full_flow() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"regression_golden_salt");
+
+    // 1. Create and verify commitment
+    let commitment = client.create_amount_commitment(&to, &amount, &salt);
+    assert!(client.verify_amount_commitment(&commitment, &to, &amount, &salt));
+
+    // 2. Deposit: mint to `to` (owner) and deposit into escrow
+    let token_client = token::StellarAssetClient::new(&env, &token);
+    token_client.mint(&to, &amount);
+    let committed = client.deposit(&token, &amount, &to, &salt, &0);
+    assert_eq!(committed, commitment);
+    assert_eq!(token_client.balance(&client.address), amount);
+
+    // 3. Toggle privacy (must not break escrow or withdrawal)
+    client.set_privacy(&to, &true);
+    assert!(client.get_privacy(&to));
+    client.set_privacy(&to, &false);
+    assert!(!client.get_privacy(&to));
+
+    // 4. Withdraw
+    client.withdraw(&token, &amount, &commitment, &to, &salt);
+    assert_eq!(token_client.balance(&to), amount);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+#[test]
+fn test_token_allowlist_gates_deposits() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let allowed = create_test_token(&env);
+    let other = create_test_token(&env);
+    client.initialize(&admin);
+    assert!(client.is_token_allowed(&other));
+
+    client.allow_token(&admin, &allowed);
+    assert!(client.is_token_allowed(&allowed));
+    assert!(!client.is_token_allowed(&other));
+
+    let commitment = BytesN::from_array(&env, &[4u8; 32]);
+    assert_contract_error(
+        client.try_deposit_with_commitment(&user, &other, &100, &commitment, &0),
+        QuickexError::TokenNotAllowed,
+    );
+
+    // Disallowing every token keeps the allowlist in force.
+    client.disallow_token(&admin, &allowed);
+    assert!(!client.is_token_allowed(&allowed));
+    assert_contract_error(
+        client.try_allow_token(&user, &other),
+        QuickexError::Unauthorized,
+    );
+}
+
+#[test]
+fn test_token_block_stops_deposits_and_optionally_withdrawals() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"blocked_salt");
+    client.initialize(&admin);
+
+    let commitment = client.create_amount_commitment(&to, &amount, &salt);
+    setup_escrow(&env, &client.address, &token, amount, commitment.clone(), 0);
+    token::StellarAssetClient::new(&env, &token).mint(&client.address, &amount);
+
+    client.block_token(&admin, &token, &true);
+    assert_eq!(client.get_token_block(&token), Some(true));
+    let user = Address::generate(&env);
+    let other = BytesN::from_array(&env, &[5u8; 32]);
+    assert_contract_error(
+        client.try_deposit_with_commitment(&user, &token, &100, &other, &0),
+        QuickexError::TokenBlocked,
+    );
+    assert_contract_error(
+        client.try_withdraw(&token, &amount, &commitment, &to, &salt),
+        QuickexError::TokenBlocked,
+    );
+
+    // Unfreezing withdrawals keeps deposits blocked.
+    client.block_token(&admin, &token, &false);
+    client.withdraw(&token, &amount, &commitment, &to, &salt);
+    assert_contract_error(
+        client.try_deposit_with_commitment(&user, &token, &100, &other, &0),
+        QuickexError::TokenBlocked,
+    );
+
+    client.unblock_token(&admin, &token);
+    assert_eq!(client.get_token_block(&token), None);
+}
+
+#[test]
+fn test_token_cap_limits_locked_total() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token = create_test_token(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&user, &1_000);
+    client.initialize(&admin);
+
+    client.set_token_cap(&admin, &token, &500);
+    assert_eq!(client.get_token_cap(&token), 500);
+
+    let first = BytesN::from_array(&env, &[1u8; 32]);
+    client.deposit_with_commitment(&user, &token, &400, &first, &0);
+    let second = BytesN::from_array(&env, &[2u8; 32]);
+    assert_contract_error(
+        client.try_deposit_with_commitment(&user, &token, &101, &second, &0),
+        QuickexError::CapExceeded,
+    );
+    client.deposit_with_commitment(&user, &token, &100, &second, &0);
+
+    client.set_token_cap(&admin, &token, &0);
+    let third = BytesN::from_array(&env, &[3u8; 32]);
+    client.deposit_with_commitment(&user, &token, &100, &third, &0);
+}
+
+#[test]
+fn test_global_cap_values_tokens_together() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let usd = create_test_token(&env);
+    let eur = create_test_token(&env);
+    let unvalued = create_test_token(&env);
+    for token in [&usd, &eur, &unvalued] {
+        token::StellarAssetClient::new(&env, token).mint(&user, &1_000);
+    }
+    client.initialize(&admin);
+
+    client.set_token_value(&admin, &usd, &10_000_000);
+    client.set_token_value(&admin, &eur, &20_000_000);
+    client.set_global_cap(&admin, &1_000);
+
+    client.deposit_with_commitment(&user, &usd, &400, &BytesN::from_array(&env, &[1u8; 32]), &0);
+    client.deposit_with_commitment(&user, &eur, &250, &BytesN::from_array(&env, &[2u8; 32]), &0);
+    assert_eq!(client.total_value_locked(), 900);
+
+    assert_contract_error(
+        client.try_deposit_with_commitment(
+            &user,
+            &eur,
+            &51,
+            &BytesN::from_array(&env, &[3u8; 32]),
+            &0,
+        ),
+        QuickexError::CapExceeded,
+    );
+    assert_contract_error(
+        client.try_deposit_with_commitment(
+            &user,
+            &unvalued,
+            &1,
+            &BytesN::from_array(&env, &[4u8; 32]),
+            &0,
+        ),
+        QuickexError::TokenNotAllowed,
+    );
+
+    client.set_global_cap(&admin, &0);
+    client.deposit_with_commitment(
+        &user,
+        &unvalued,
+        &1,
+        &BytesN::from_array(&env, &[4u8; 32]),
+        &0,
+    );
+}
+
+#[test]
+fn test_overflowing_value_fails_with_arithmetic_overflow() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token = create_test_token(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&user, &1_000);
+    client.initialize(&admin);
+    client.set_global_cap(&admin, &1_000);
+    let deposit = |amount: i128, seed: u8| {
+        let commitment = BytesN::from_array(&env, &[seed; 32]);
+        client.try_deposit_with_commitment(&user, &token, &amount, &commitment, &0)
+    };
+
+    client.set_token_value(&admin, &token, &i128::MAX);
+    assert_contract_error(deposit(2, 1), QuickexError::ArithmeticOverflow);
+
+    client.set_token_value(&admin, &token, &10_000_000);
+    assert!(deposit(2, 2).is_ok());
+    client.set_token_value(&admin, &token, &i128::MAX);
+    assert_eq!(
+        client.try_total_value_locked(),
+        Err(Ok(QuickexError::ArithmeticOverflow))
+    );
+    assert_contract_error(deposit(1, 3), QuickexError::ArithmeticOverflow);
+}
+
+#[test]
+fn test_withdraw_volume_limit_per_window() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let to = Address::generate(&env);
+    let token = create_test_token(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&user, &1_000);
+    client.initialize(&admin);
+
+    client.set_withdraw_volume_limit(&admin, &token, &500, &10);
+    assert_eq!(
+        client.get_withdraw_volume_limit(&token),
+        Some(WithdrawVolumeLimit {
+            max_amount: 500,
+            window_ledgers: 10,
+        })
+    );
+    assert_eq!(
+        client.try_set_withdraw_volume_limit(&admin, &token, &500, &0),
+        Err(Ok(QuickexError::InvalidRateLimit))
+    );
+
+    let [first, second, third] = [1u8, 2, 3].map(|i| {
+        let salt = Bytes::from_slice(&env, &[i; 16]);
+        let commitment = client.create_amount_commitment(&to, &300, &salt);
+        client.deposit_with_commitment(&user, &token, &300, &commitment, &0);
+        (commitment, salt)
+    });
+
+    env.ledger().set_sequence_number(100);
+    let (commitment, salt) = first;
+    client.withdraw(&token, &300, &commitment, &to, &salt);
+    let (commitment, salt) = second;
+    assert_eq!(
+        client.try_withdraw(&token, &300, &commitment, &to, &salt),
+        Err(Ok(QuickexError::RateLimited))
+    );
+
+    env.ledger().set_sequence_number(110);
+    client.withdraw(&token, &300, &commitment, &to, &salt);
+
+    client.set_withdraw_volume_limit(&admin, &token, &0, &0);
+    assert_eq!(client.get_withdraw_volume_limit(&token), None);
+    let (commitment, salt) = third;
+    client.withdraw(&token, &300, &commitment, &to, &salt);
+}
+
+#[test]
+fn test_blocked_account_cannot_deposit_or_receive() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let to = Address::generate(&env);
+    let token = create_test_token(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&user, &1_000);
+    client.initialize(&admin);
+
+    let salt = Bytes::from_slice(&env, &[7u8; 16]);
+    let commitment = client.create_amount_commitment(&to, &300, &salt);
+    client.deposit_with_commitment(&user, &token, &300, &commitment, &0);
+
+    client.block_account(&admin, &user);
+    client.block_account(&admin, &to);
+    assert!(client.is_blocked(&user));
+    assert_contract_error(
+        client.try_deposit_with_commitment(
+            &user,
+            &token,
+            &100,
+            &BytesN::from_array(&env, &[9u8; 32]),
+            &0,
+        ),
+        QuickexError::AccountBlocked,
+    );
+    assert_eq!(
+        client.try_withdraw(&token, &300, &commitment, &to, &salt),
+        Err(Ok(QuickexError::AccountBlocked))
+    );
+
+    client.unblock_account(&admin, &to);
+    assert!(!client.is_blocked(&to));
+    client.withdraw(&token, &300, &commitment, &to, &salt);
+}
+
+#[test]
+fn test_config_tunes_claim_window() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token = create_test_token(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&user, &1_000);
+    client.initialize(&admin);
+
+    let defaults = client.get_config();
+    assert_eq!(defaults.default_claim_window_secs, 0);
+    assert_eq!(defaults.reveal_delay_ledgers, 1);
+
+    let config = Config {
+        default_claim_window_secs: 3_600,
+        max_claim_window_secs: 86_400,
+        ..defaults.clone()
+    };
+    assert_eq!(
+        client.try_set_config(
+            &admin,
+            &Config {
+                default_claim_window_secs: 86_401,
+                ..config.clone()
+            }
+        ),
+        Err(Ok(QuickexError::InvalidConfig))
+    );
+    client.set_config(&admin, &config);
+    assert_eq!(client.get_config(), config);
+
+    let first = BytesN::from_array(&env, &[1u8; 32]);
+    client.deposit_with_commitment(&user, &token, &100, &first, &0);
+    let expires_at = client.get_escrow_details(&first, &user).unwrap().expires_at;
+    assert_eq!(expires_at, env.ledger().timestamp() + 3_600);
+
+    assert_contract_error(
+        client.try_deposit_with_commitment(
+            &user,
+            &token,
+            &100,
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &86_401,
+        ),
+        QuickexError::InvalidTimeout,
+    );
+}
+
+#[test]
+fn test_config_collects_flags() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    client.set_paused_ops(&admin, &crate::admin::PAUSE_REFUNDS, &true);
+    client.set_withdrawal_fee(&admin, &25);
+    client.set_global_cap(&admin, &1_000);
+    let config = client.get_config();
+    assert_eq!(config.paused_ops, crate::admin::PAUSE_REFUNDS);
+    assert_eq!(config.withdrawal_fee_bps, 25);
+    assert_eq!(config.global_cap, 1_000);
+
+    assert_eq!(
+        client.try_set_config(
+            &admin,
+            &Config {
+                withdrawal_fee_bps: 0,
+                ..config.clone()
+            }
+        ),
+        Err(Ok(QuickexError::InvalidConfig))
+    );
+    client.set_config(
+        &admin,
+        &Config {
+            reveal_delay_ledgers: 5,
+            ..config
+        },
+    );
+    assert_eq!(client.get_withdrawal_fee(), 25);
+    assert_eq!(client.get_config().reveal_delay_ledgers, 5);
+}
+
+#[test]
+fn test_migrate_moves_flags_into_config() {
+    let (env, client) = setup();
+    env.as_contract(&client.address, || {
+        let persistent = env.storage().persistent();
+        persistent.set(
+            &crate::storage::DataKey::PausedOps,
+            &crate::admin::PAUSE_DEPOSITS,
+        );
+        persistent.set(&crate::storage::DataKey::WithdrawalFeeBps, &30u32);
+        persistent.set(&crate::storage::DataKey::GlobalCap, &5_000i128);
+        crate::storage::set_schema_version(&env, 2);
+    });
+    assert_eq!(client.paused_ops(), crate::admin::PAUSE_DEPOSITS);
+
+    assert_eq!(client.migrate(), crate::migration::STORAGE_SCHEMA_VERSION);
+    let config = client.get_config();
+    assert_eq!(config.paused_ops, crate::admin::PAUSE_DEPOSITS);
+    assert_eq!(config.withdrawal_fee_bps, 30);
+    assert_eq!(config.global_cap, 5_000);
+    env.as_contract(&client.address, || {
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&crate::storage::DataKey::WithdrawalFeeBps));
+    });
+}
+
+#[test]
+fn test_migrate_moves_admin_to_instance_storage() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&crate::storage::DataKey::Admin, &admin);
+        crate::storage::set_schema_version(&env, 3);
+    });
+    assert_eq!(client.get_admin(), Some(admin.clone()));
+
+    assert_eq!(client.migrate(), crate::migration::STORAGE_SCHEMA_VERSION);
+    assert_eq!(client.get_admin(), Some(admin.clone()));
+    client.set_paused(&admin, &true);
+    env.as_contract(&client.address, || {
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&crate::storage::DataKey::Admin));
+        assert!(env
+            .storage()
+            .instance()
+            .has(&crate::storage::DataKey::Admin));
+    });
+}
+
+/// Simulate a deployment whose deposits predate `TotalLocked`: forget the locked total.
+fn forget_total_locked(env: &Env, client: &QuickexContractClient, token: &Address) {
+    env.as_contract(&client.address, || {
+        crate::storage::set_total_locked(env, token, 0);
+    });
+}
+
+#[test]
+fn test_payout_below_zero_locked_total_fails() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"untracked");
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &1000);
+    let commitment = client.deposit(&token, &1000, &owner, &salt, &0);
+    forget_total_locked(&env, &client, &token);
+
+    let result = client.try_withdraw(&token, &1000, &commitment, &owner, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::LockedTotalUnderflow)));
+    assert_eq!(client.sweepable_balance(&token), 1000);
+
+    // Direct transfers cannot be told apart from untracked deposits, so they are
+    // reserved too.
+    token::StellarAssetClient::new(&env, &token).mint(&client.address, &50);
+    assert_eq!(client.backfill_total_locked(&admin, &token), 1050);
+    assert_eq!(client.sweepable_balance(&token), 0);
+
+    client.withdraw(&token, &1000, &commitment, &owner, &salt);
+    assert_eq!(client.proof_of_reserves(&token), (50, 50));
+}
+
+#[test]
+fn test_migrate_backfills_locked_totals() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"pre_v5");
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &700);
+    let commitment = client.deposit(&token, &700, &owner, &salt, &0);
+    forget_total_locked(&env, &client, &token);
+    env.as_contract(&client.address, || {
+        crate::storage::set_schema_version(&env, 4);
+    });
+
+    assert_eq!(client.migrate(), crate::migration::STORAGE_SCHEMA_VERSION);
+    assert_eq!(client.proof_of_reserves(&token), (700, 700));
+    client.withdraw(&token, &700, &commitment, &owner, &salt);
+    assert_eq!(client.proof_of_reserves(&token), (0, 0));
+}
+
+#[test]
+fn test_backfill_total_locked_never_lowers_total() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &300);
+    client.deposit(
+        &token,
+        &300,
+        &owner,
+        &Bytes::from_slice(&env, b"tracked"),
+        &0,
+    );
+
+    assert_eq!(client.backfill_total_locked(&admin, &token), 300);
+    assert_eq!(
+        client.try_backfill_total_locked(&Address::generate(&env), &token),
+        Err(Ok(QuickexError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_admin_batch_applies_all_or_nothing() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    client.initialize(&admin);
+
+    let invalid = vec![
+        &env,
+        AdminAction::SetWithdrawalFee(30),
+        AdminAction::SetTokenCap(token.clone(), -1),
+    ];
+    assert_eq!(
+        client.try_admin_batch(&admin, &invalid),
+        Err(Ok(QuickexError::InvalidAmount))
+    );
+    assert_eq!(client.get_withdrawal_fee(), 0);
+    assert_eq!(
+        client.try_admin_batch(&admin, &vec![&env]),
+        Err(Ok(QuickexError::InvalidBatch))
+    );
+
+    let actions = vec![
+        &env,
+        AdminAction::SetPausedOps(crate::admin::PAUSE_DEPOSITS, true),
+        AdminAction::SetWithdrawalFee(30),
+        AdminAction::SetTokenCap(token.clone(), 500),
+        AdminAction::AllowToken(token.clone()),
+    ];
+    client.admin_batch(&admin, &actions);
+    assert_eq!(client.paused_ops(), crate::admin::PAUSE_DEPOSITS);
+    assert_eq!(client.get_withdrawal_fee(), 30);
+    assert_eq!(client.get_token_cap(&token), 500);
+    assert!(client.is_token_allowed(&token));
+}
+
+#[test]
+fn test_stats_track_escrow_transitions() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &600);
+    let salt_a = Bytes::from_slice(&env, b"stats_a");
+    let salt_b = Bytes::from_slice(&env, b"stats_b");
+    let salt_c = Bytes::from_slice(&env, b"stats_c");
+
+    let stats = client.stats();
+    assert_eq!((stats.pending, stats.spent, stats.refunded), (0, 0, 0));
+    assert!(stats.tokens.is_empty());
+
+    let withdrawn = client.deposit(&token, &100, &owner, &salt_a, &0);
+    let refunded = client.deposit(&token, &200, &owner, &salt_b, &60);
+    client.deposit(&token, &300, &owner, &salt_c, &0);
+    client.withdraw(&token, &100, &withdrawn, &owner, &salt_a);
+    env.ledger().with_mut(|l| l.timestamp += 60);
+    client.refund(&refunded, &owner);
+
+    let stats = client.stats();
+    assert_eq!((stats.pending, stats.spent, stats.refunded), (1, 1, 1));
+    assert_eq!(
+        stats.tokens.get(token),
+        Some(crate::types::TokenStats {
+            deposited: 600,
+            withdrawn: 100,
+            refunded: 200,
+        })
+    );
+}
+
+#[test]
+fn test_two_phase_withdraw_commit_abort_and_expiry() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let token_client = token::Client::new(&env, &token);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &300);
+    let salt_a = Bytes::from_slice(&env, b"two_phase_a");
+    let salt_b = Bytes::from_slice(&env, b"two_phase_b");
+    let first = client.deposit(&token, &100, &owner, &salt_a, &0);
+    let second = client.deposit(&token, &200, &owner, &salt_b, &0);
+
+    // A reserved escrow cannot be withdrawn or reserved again until committed.
+    let ticket = client.prepare_withdraw(&token, &100, &owner, &salt_a);
+    assert_contract_error(
+        client.try_withdraw(&token, &100, &first, &owner, &salt_a),
+        QuickexError::EscrowReserved,
+    );
+    assert_contract_error(
+        client.try_prepare_withdraw(&token, &100, &owner, &salt_a),
+        QuickexError::EscrowReserved,
+    );
+    assert!(client.commit_withdraw(&ticket));
+    assert_eq!(token_client.balance(&owner), 100);
+    assert_contract_error(
+        client.try_commit_withdraw(&ticket),
+        QuickexError::TicketNotFound,
+    );
+
+    // Aborting releases the escrow untouched.
+    let ticket = client.prepare_withdraw(&token, &200, &owner, &salt_b);
+    client.abort_withdraw(&ticket);
+    assert_contract_error(
+        client.try_abort_withdraw(&ticket),
+        QuickexError::TicketNotFound,
+    );
+    assert_eq!(token_client.balance(&owner), 100);
+
+    // An unsettled ticket lapses on its own.
+    let ticket = client.prepare_withdraw(&token, &200, &owner, &salt_b);
+    env.ledger()
+        .with_mut(|l| l.sequence_number += crate::escrow::WITHDRAW_TICKET_LEDGERS + 1);
+    assert_contract_error(
+        client.try_commit_withdraw(&ticket),
+        QuickexError::TicketNotFound,
+    );
+    client.withdraw(&token, &200, &second, &owner, &salt_b);
+    assert_eq!(token_client.balance(&owner), 300);
 }
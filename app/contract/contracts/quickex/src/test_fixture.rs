@@ -0,0 +1,42 @@
+//! Contract deployment shared by the unit test modules.
+
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+use crate::{QuickexContract, QuickexContractClient};
+
+/// A deployed contract with all auths mocked, not yet initialized.
+pub(crate) fn deploy<'a>() -> (Env, QuickexContractClient<'a>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = QuickexContractClient::new(&env, &env.register(QuickexContract, ()));
+    (env, client)
+}
+
+/// A deployed and initialized contract with all auths mocked.
+///
+/// Returns the env, client, admin and a new token.
+pub(crate) fn deploy_initialized<'a>() -> (Env, QuickexContractClient<'a>, Address, Address) {
+    let (env, client) = deploy();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let token = create_token(&env);
+    (env, client, admin, token)
+}
+
+/// [`deploy_initialized`] with `denomination` of the token enabled as a pool, and an
+/// unlimited budget for the pairing and curve arithmetic of note withdrawals.
+#[cfg(feature = "privacy-pool")]
+pub(crate) fn deploy_pool<'a>(
+    denomination: i128,
+) -> (Env, QuickexContractClient<'a>, Address, Address) {
+    let (env, client, admin, token) = deploy_initialized();
+    env.cost_estimate().budget().reset_unlimited();
+    client.set_denomination(&admin, &token, &denomination, &true);
+    (env, client, admin, token)
+}
+
+/// A new Stellar asset contract.
+pub(crate) fn create_token(env: &Env) -> Address {
+    env.register_stellar_asset_contract_v2(Address::generate(env))
+        .address()
+}
@@ -0,0 +1,220 @@
+//! # Timelock
+//!
+//! Delays sensitive admin actions so users can exit before they take effect.
+//!
+//! Once a non-zero delay is set, upgrades, admin transfers, and delay changes can no
+//! longer be made directly (they fail with [`QuickexError::TimelockRequired`]):
+//!
+//! ```text
+//! queue_action(caller, action)  -->  eta = now + delay
+//! execute_action(id)                 anyone, once now >= eta
+//! cancel_action(caller, id)          admin, any time before execution
+//! ```
+//!
+//! Council-approved actions of these kinds are queued the same way under their
+//! proposal id, and the council cancels them with [`AdminAction::CancelQueued`].
+//! Pausing and role changes are never delayed, so incident response stays fast.
+//!
+//! Upgrades also have a dedicated slot: [`schedule_upgrade`] queues one with an
+//! explicit, announced execution time (at least the delay away) and
+//! [`execute_upgrade`] runs it without needing the action id.
+
+use soroban_sdk::{Address, BytesN, Env};
+
+use crate::{
+    admin,
+    errors::QuickexError,
+    events::{
+        publish_admin_action_cancelled, publish_admin_action_queued,
+        publish_timelock_delay_updated, publish_upgrade_cancelled, publish_upgrade_scheduled,
+    },
+    migration, storage,
+    types::{AdminAction, QueuedAdminAction, Role},
+};
+
+/// Current delay in seconds (0 = no timelock).
+pub fn delay(env: &Env) -> u64 {
+    storage::get_timelock_delay(env)
+}
+
+/// Whether `action` must wait out the delay under the current configuration.
+pub fn is_timelocked(env: &Env, action: &AdminAction) -> bool {
+    let sensitive = matches!(
+        action,
+        AdminAction::Upgrade(..) | AdminAction::SetAdmin(_) | AdminAction::SetTimelockDelay(_)
+    );
+    sensitive && delay(env) > 0
+}
+
+/// Fail if a delay is set, for entrypoints that bypass the queue.
+///
+/// # Errors
+/// - [`QuickexError::TimelockRequired`] – the timelock is active.
+pub fn require_no_delay(env: &Env) -> Result<(), QuickexError> {
+    if delay(env) > 0 {
+        return Err(QuickexError::TimelockRequired);
+    }
+    Ok(())
+}
+
+/// Set the delay without queueing (**admin only**, only while no delay is set).
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::TimelockRequired`] – a delay is already set; queue
+///   [`AdminAction::SetTimelockDelay`] to change it.
+pub fn set_timelock_delay(env: &Env, caller: Address, delay: u64) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+    require_no_delay(env)?;
+
+    set_delay(env, delay);
+    Ok(())
+}
+
+pub(crate) fn set_delay(env: &Env, delay: u64) {
+    storage::set_timelock_delay(env, delay);
+    publish_timelock_delay_updated(env, delay);
+}
+
+/// Queue `action` under `action_id`, executable after the current delay.
+pub(crate) fn enqueue(env: &Env, action_id: u64, action: AdminAction, queued_by: &Address) {
+    let eta = env.ledger().timestamp().saturating_add(delay(env));
+    enqueue_at(env, action_id, action, queued_by, eta);
+}
+
+fn enqueue_at(env: &Env, action_id: u64, action: AdminAction, queued_by: &Address, eta: u64) {
+    let queued = QueuedAdminAction {
+        action: action.clone(),
+        queued_by: queued_by.clone(),
+        eta,
+    };
+    storage::put_queued_action(env, action_id, &queued);
+    publish_admin_action_queued(env, action_id, queued_by.clone(), action, eta);
+}
+
+/// Queue an admin action for execution after the delay. Returns the action id.
+///
+/// Caller needs the same rights as for performing the action directly (the admin, or
+/// the matching role for pause and upgrade).
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller may not perform `action`.
+/// - Any error from [`admin::validate_action`].
+pub fn queue_action(env: &Env, caller: Address, action: AdminAction) -> Result<u64, QuickexError> {
+    admin::require_action_auth(env, &caller, &action)?;
+    admin::validate_action(&action)?;
+
+    let action_id = storage::next_admin_action_id(env);
+    enqueue(env, action_id, action, &caller);
+    Ok(action_id)
+}
+
+/// Execute a queued action whose delay has elapsed. Callable by anyone.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – the admin has been renounced.
+/// - [`QuickexError::ActionNotFound`] – nothing is queued under `action_id`.
+/// - [`QuickexError::TimelockNotReady`] – the delay has not elapsed yet.
+/// - Any error from [`migration::require_upgradable`], for upgrades.
+pub fn execute_action(env: &Env, action_id: u64) -> Result<(), QuickexError> {
+    if admin::is_renounced(env) {
+        return Err(QuickexError::Unauthorized);
+    }
+    let queued = storage::get_queued_action(env, action_id).ok_or(QuickexError::ActionNotFound)?;
+    if env.ledger().timestamp() < queued.eta {
+        return Err(QuickexError::TimelockNotReady);
+    }
+    if matches!(queued.action, AdminAction::Upgrade(..)) {
+        migration::require_upgradable(env)?;
+    }
+
+    storage::remove_queued_action(env, action_id);
+    admin::execute(env, action_id, queued.action, &queued.queued_by);
+    Ok(())
+}
+
+/// Cancel a queued action (**admin only**).
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::ActionNotFound`] – nothing is queued under `action_id`.
+pub fn cancel_action(env: &Env, caller: Address, action_id: u64) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+    if storage::get_queued_action(env, action_id).is_none() {
+        return Err(QuickexError::ActionNotFound);
+    }
+
+    cancel(env, action_id);
+    Ok(())
+}
+
+/// Drop the queued action `action_id`, if any.
+pub(crate) fn cancel(env: &Env, action_id: u64) {
+    if let Some(queued) = storage::get_queued_action(env, action_id) {
+        storage::remove_queued_action(env, action_id);
+        if let AdminAction::Upgrade(wasm_hash, version) = &queued.action {
+            publish_upgrade_cancelled(env, action_id, wasm_hash.clone(), *version);
+        }
+        publish_admin_action_cancelled(env, action_id, queued.action);
+    }
+}
+
+/// Schedule an upgrade to run at `execute_after` (**admin or upgrader**). Returns the
+/// queued action id.
+///
+/// Replaces any previously scheduled upgrade (emitting `UpgradeCancelled` for it). The
+/// `UpgradeScheduled` event announces the hash, version, and execution time so users
+/// can exit first.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is neither the admin nor an upgrader.
+/// - [`QuickexError::InvalidExecuteAfter`] – `execute_after` is sooner than the delay.
+pub fn schedule_upgrade(
+    env: &Env,
+    caller: Address,
+    wasm_hash: BytesN<32>,
+    version: u32,
+    execute_after: u64,
+) -> Result<u64, QuickexError> {
+    admin::require_role(env, &caller, Role::Upgrader)?;
+    if execute_after < env.ledger().timestamp().saturating_add(delay(env)) {
+        return Err(QuickexError::InvalidExecuteAfter);
+    }
+
+    if let Some(previous) = storage::get_scheduled_upgrade(env) {
+        cancel(env, previous);
+    }
+    let action_id = storage::next_admin_action_id(env);
+    enqueue_at(
+        env,
+        action_id,
+        AdminAction::Upgrade(wasm_hash.clone(), version),
+        &caller,
+        execute_after,
+    );
+    storage::set_scheduled_upgrade(env, Some(action_id));
+    publish_upgrade_scheduled(env, action_id, wasm_hash, version, execute_after, caller);
+    Ok(action_id)
+}
+
+/// Run the scheduled upgrade once its time has come. Callable by anyone.
+///
+/// # Errors
+/// - [`QuickexError::ActionNotFound`] – no upgrade is scheduled (or it was cancelled).
+/// - Otherwise as [`execute_action`].
+pub fn execute_upgrade(env: &Env) -> Result<(), QuickexError> {
+    let action_id = storage::get_scheduled_upgrade(env).ok_or(QuickexError::ActionNotFound)?;
+    execute_action(env, action_id)?;
+    storage::set_scheduled_upgrade(env, None);
+    Ok(())
+}
+
+/// Get the scheduled upgrade, unless it has run or been cancelled.
+pub fn get_scheduled_upgrade(env: &Env) -> Option<QueuedAdminAction> {
+    storage::get_scheduled_upgrade(env).and_then(|id| storage::get_queued_action(env, id))
+}
+
+/// Get a queued action that has not been executed or cancelled.
+pub fn get_queued_action(env: &Env, action_id: u64) -> Option<QueuedAdminAction> {
+    storage::get_queued_action(env, action_id)
+}
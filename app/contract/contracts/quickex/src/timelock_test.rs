@@ -0,0 +1,140 @@
+//! Timelocked admin action tests.
+
+use crate::{errors::QuickexError, test_fixture::deploy_initialized, types::AdminAction};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN,
+};
+
+const DELAY: u64 = 86_400;
+
+#[test]
+fn test_admin_transfer_waits_for_delay() {
+    let (env, client, admin, _token) = deploy_initialized();
+    let new_admin = Address::generate(&env);
+    client.set_timelock_delay(&admin, &DELAY);
+    assert_eq!(client.get_timelock_delay(), DELAY);
+
+    assert_eq!(
+        client.try_set_admin(&admin, &new_admin),
+        Err(Ok(QuickexError::TimelockRequired))
+    );
+    let wasm_hash = BytesN::from_array(&env, &[1u8; 32]);
+    assert_eq!(
+        client.try_upgrade(&admin, &wasm_hash, &1),
+        Err(Ok(QuickexError::TimelockRequired))
+    );
+    assert_eq!(
+        client.try_set_timelock_delay(&admin, &0),
+        Err(Ok(QuickexError::TimelockRequired))
+    );
+
+    let id = client.queue_action(&admin, &AdminAction::SetAdmin(new_admin.clone()));
+    let queued = client.get_queued_action(&id).unwrap();
+    assert_eq!(queued.eta, env.ledger().timestamp() + DELAY);
+
+    env.ledger().set_timestamp(queued.eta - 1);
+    assert_eq!(
+        client.try_execute_action(&id),
+        Err(Ok(QuickexError::TimelockNotReady))
+    );
+
+    env.ledger().set_timestamp(queued.eta);
+    client.execute_action(&id);
+    assert_eq!(client.get_admin(), Some(new_admin));
+    assert!(client.get_queued_action(&id).is_none());
+    assert_eq!(
+        client.try_execute_action(&id),
+        Err(Ok(QuickexError::ActionNotFound))
+    );
+}
+
+#[test]
+fn test_cancel_queued_action() {
+    let (env, client, admin, _token) = deploy_initialized();
+    client.set_timelock_delay(&admin, &DELAY);
+
+    let id = client.queue_action(&admin, &AdminAction::SetTimelockDelay(0));
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_cancel_action(&stranger, &id),
+        Err(Ok(QuickexError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_queue_action(&stranger, &AdminAction::SetTimelockDelay(0)),
+        Err(Ok(QuickexError::Unauthorized))
+    );
+
+    client.cancel_action(&admin, &id);
+    env.ledger().set_timestamp(env.ledger().timestamp() + DELAY);
+    assert_eq!(
+        client.try_execute_action(&id),
+        Err(Ok(QuickexError::ActionNotFound))
+    );
+    assert_eq!(client.get_timelock_delay(), DELAY);
+}
+
+#[test]
+fn test_council_actions_are_queued_behind_delay() {
+    let (env, client, _admin, _token) = deploy_initialized();
+    let member = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    client.initialize_multisig(&vec![&env, member.clone()], &1);
+
+    // No delay yet, so this executes immediately.
+    client.propose_action(&member, &AdminAction::SetTimelockDelay(DELAY));
+    assert_eq!(client.get_timelock_delay(), DELAY);
+
+    // Pausing is never delayed.
+    client.propose_action(&member, &AdminAction::SetPaused(true));
+    assert!(client.is_paused());
+
+    let id = client.propose_action(&member, &AdminAction::SetAdmin(new_admin.clone()));
+    assert!(client.get_admin_action(&id).is_none());
+    assert_eq!(client.get_admin(), None);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + DELAY);
+    client.execute_action(&id);
+    assert_eq!(client.get_admin(), Some(new_admin));
+    assert!(client.get_admin_council().is_none());
+}
+
+#[test]
+fn test_scheduled_upgrade_respects_announced_time() {
+    let (env, client, admin, _token) = deploy_initialized();
+    client.set_timelock_delay(&admin, &DELAY);
+    let wasm_hash = BytesN::from_array(&env, &[1u8; 32]);
+    let now = env.ledger().timestamp();
+
+    assert_eq!(
+        client.try_schedule_upgrade(&admin, &wasm_hash, &2, &(now + DELAY - 1)),
+        Err(Ok(QuickexError::InvalidExecuteAfter))
+    );
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_schedule_upgrade(&stranger, &wasm_hash, &2, &(now + DELAY)),
+        Err(Ok(QuickexError::Unauthorized))
+    );
+
+    let first = client.schedule_upgrade(&admin, &wasm_hash, &2, &(now + DELAY));
+    let execute_after = now + 2 * DELAY;
+    let second = client.schedule_upgrade(&admin, &wasm_hash, &3, &execute_after);
+    assert!(client.get_queued_action(&first).is_none());
+
+    let scheduled = client.get_scheduled_upgrade().unwrap();
+    assert_eq!(scheduled.action, AdminAction::Upgrade(wasm_hash, 3));
+    assert_eq!(scheduled.eta, execute_after);
+
+    env.ledger().set_timestamp(execute_after - 1);
+    assert_eq!(
+        client.try_execute_upgrade(),
+        Err(Ok(QuickexError::TimelockNotReady))
+    );
+
+    client.cancel_action(&admin, &second);
+    assert!(client.get_scheduled_upgrade().is_none());
+    assert_eq!(
+        client.try_execute_upgrade(),
+        Err(Ok(QuickexError::ActionNotFound))
+    );
+}
@@ -0,0 +1,441 @@
+//! # Token Policy
+//!
+//! Admin controls over which tokens may enter the contract.
+//!
+//! ## Allowlist
+//!
+//! Until the admin allows a first token, every token is accepted. From then on,
+//! deposits of tokens not on the list fail with [`QuickexError::TokenNotAllowed`],
+//! even if every token is later disallowed again. This keeps scam and
+//! fee-on-transfer assets out of the escrow pool. Withdrawals and refunds of
+//! existing escrows are never affected.
+//!
+//! ## Blocklist
+//!
+//! For incident response (e.g. a wrapped asset exploited upstream), the admin or a
+//! [`Role::Pauser`] can block a single token. A blocked token cannot be deposited
+//! (even if allowlisted) and fails with [`QuickexError::TokenBlocked`]. Blocking
+//! with `freeze_withdrawals` also stops every withdrawal and refund of the token
+//! until it is unblocked; the rest of the contract keeps running.
+//!
+//! ## Deposit caps
+//!
+//! The admin can cap how much of a token the contract holds for users (its
+//! [`TotalLocked`](crate::storage::DataKey::TotalLocked) value), so a new deployment
+//! can ramp up exposure gradually. Deposits that would push the total above the cap
+//! fail with [`QuickexError::CapExceeded`].
+//!
+//! ## Global cap
+//!
+//! A single cap across all tokens bounds the blast radius of an undiscovered bug.
+//! Tokens are valued with admin-set prices (a naive oracle), in value units per
+//! whole token scaled by [`VALUE_SCALE`]:
+//!
+//! ```text
+//! total_value_locked = Σ TotalLocked(token) * value(token) / VALUE_SCALE
+//! ```
+//!
+//! While the global cap is set, deposits that would push the total above it fail
+//! with [`QuickexError::CapExceeded`], and tokens without a value cannot be
+//! deposited at all ([`QuickexError::TokenNotAllowed`]).
+//!
+//! ## Withdrawal volume limit
+//!
+//! An automatic brake against drain-style exploits: the admin can cap the amount of
+//! a token withdrawn per window of `window_ledgers` ledgers. The window opens at the
+//! first withdrawal after the previous one ended; withdrawals beyond the cap fail
+//! with [`QuickexError::RateLimited`] until it ends. Refunds are not counted.
+//!
+//! ## Payouts
+//!
+//! Withdrawals and refunds pay out through [`pay`], which turns a failing token
+//! contract into [`QuickexError::TransferFailed`]. The error reverts the call, so the
+//! escrow stays pending instead of being closed without the user being paid.
+
+use soroban_sdk::{token, Address, Env, Map, MuxedAddress};
+
+use crate::{
+    admin,
+    errors::QuickexError,
+    events::{
+        publish_global_cap_updated, publish_token_allowlist_updated,
+        publish_token_blocklist_updated, publish_token_cap_updated, publish_token_value_updated,
+        publish_withdraw_volume_limit_updated,
+    },
+    storage,
+    types::{Role, VolumeWindow, WithdrawVolumeLimit},
+};
+
+/// Add `token` to the deposit allowlist (**admin only**), enabling the allowlist.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+pub fn allow_token(env: &Env, caller: Address, token: Address) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+
+    update_allowlist(env, token, true, caller);
+    Ok(())
+}
+
+/// Remove `token` from the deposit allowlist (**admin only**).
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+pub fn disallow_token(env: &Env, caller: Address, token: Address) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+
+    update_allowlist(env, token, false, caller);
+    Ok(())
+}
+
+/// Add `token` to (enabling the allowlist) or remove it from the deposit allowlist.
+pub(crate) fn update_allowlist(env: &Env, token: Address, allowed: bool, admin: Address) {
+    if allowed {
+        storage::set_token_allowlist_enabled(env);
+    }
+    storage::set_token_allowed(env, &token, allowed);
+    publish_token_allowlist_updated(env, token, allowed, admin);
+}
+
+/// Whether deposits of `token` pass the allowlist.
+pub fn is_token_allowed(env: &Env, token: &Address) -> bool {
+    !storage::is_token_allowlist_enabled(env) || storage::is_token_allowed(env, token)
+}
+
+/// Block `token` (**admin or [`Role::Pauser`]**), optionally freezing its withdrawals
+/// and refunds. Blocking an already blocked token updates `freeze_withdrawals`.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is neither the admin nor a pauser.
+pub fn block_token(
+    env: &Env,
+    caller: Address,
+    token: Address,
+    freeze_withdrawals: bool,
+) -> Result<(), QuickexError> {
+    admin::require_role(env, &caller, Role::Pauser)?;
+
+    storage::set_token_block(env, &token, Some(freeze_withdrawals));
+    publish_token_blocklist_updated(env, token, true, freeze_withdrawals, caller);
+    Ok(())
+}
+
+/// Lift a block on `token` (**admin or [`Role::Pauser`]**).
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is neither the admin nor a pauser.
+pub fn unblock_token(env: &Env, caller: Address, token: Address) -> Result<(), QuickexError> {
+    admin::require_role(env, &caller, Role::Pauser)?;
+
+    storage::set_token_block(env, &token, None);
+    publish_token_blocklist_updated(env, token, false, false, caller);
+    Ok(())
+}
+
+/// `Some(freeze_withdrawals)` while `token` is blocked, `None` otherwise.
+pub fn token_block(env: &Env, token: &Address) -> Option<bool> {
+    storage::get_token_block(env, token)
+}
+
+/// Cap the total locked amount of `token` (**admin only**). `0` removes the cap.
+///
+/// Lowering the cap below the current total only blocks new deposits.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::InvalidAmount`] – `cap` is negative.
+pub fn set_token_cap(
+    env: &Env,
+    caller: Address,
+    token: Address,
+    cap: i128,
+) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+    if cap < 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+
+    update_token_cap(env, token, cap, caller);
+    Ok(())
+}
+
+pub(crate) fn update_token_cap(env: &Env, token: Address, cap: i128, admin: Address) {
+    storage::set_token_cap(env, &token, cap);
+    publish_token_cap_updated(env, token, cap, admin);
+}
+
+/// Deposit cap for `token` (`0` = uncapped).
+pub fn token_cap(env: &Env, token: &Address) -> i128 {
+    storage::get_token_cap(env, token)
+}
+
+/// Fixed-point scale of token values (7 decimals, like Stellar amounts).
+pub const VALUE_SCALE: i128 = 10_000_000;
+
+/// Most tokens that can have a value for the global cap.
+pub const MAX_VALUED_TOKENS: u32 = 32;
+
+/// Set the global cap in value units (**admin only**). `0` removes it.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::InvalidAmount`] – `cap` is negative.
+pub fn set_global_cap(env: &Env, caller: Address, cap: i128) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+    if cap < 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+
+    update_global_cap(env, cap, caller);
+    Ok(())
+}
+
+pub(crate) fn update_global_cap(env: &Env, cap: i128, admin: Address) {
+    storage::set_global_cap(env, cap);
+    publish_global_cap_updated(env, cap, admin);
+}
+
+/// Global cap in value units (`0` = uncapped).
+pub fn global_cap(env: &Env) -> i128 {
+    storage::get_global_cap(env)
+}
+
+/// Set the value of one whole `token` unit, scaled by [`VALUE_SCALE`] (**admin
+/// only**). `0` removes the value.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::InvalidAmount`] – `value` is negative.
+/// - [`QuickexError::TooManyTokens`] – [`MAX_VALUED_TOKENS`] tokens already have a value.
+pub fn set_token_value(
+    env: &Env,
+    caller: Address,
+    token: Address,
+    value: i128,
+) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+    if value < 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+
+    let mut values = storage::get_token_values(env);
+    if value == 0 {
+        values.remove(token.clone());
+    } else {
+        if !values.contains_key(token.clone()) && values.len() >= MAX_VALUED_TOKENS {
+            return Err(QuickexError::TooManyTokens);
+        }
+        values.set(token.clone(), value);
+    }
+    storage::set_token_values(env, &values);
+    publish_token_value_updated(env, token, value, caller);
+    Ok(())
+}
+
+/// Value of one whole `token` unit for the global cap (`0` = no value set).
+pub fn token_value(env: &Env, token: &Address) -> i128 {
+    storage::get_token_values(env)
+        .get(token.clone())
+        .unwrap_or(0)
+}
+
+/// Locked totals of all valued tokens, in value units.
+///
+/// # Errors
+/// - [`QuickexError::ArithmeticOverflow`] – the total does not fit an `i128`.
+pub fn total_value_locked(env: &Env) -> Result<i128, QuickexError> {
+    value_locked(env, &storage::get_token_values(env))
+}
+
+fn value_locked(env: &Env, values: &Map<Address, i128>) -> Result<i128, QuickexError> {
+    values.iter().try_fold(0i128, |total, (token, value)| {
+        to_value(storage::get_total_locked(env, &token), value)
+            .and_then(|locked| total.checked_add(locked))
+            .ok_or(QuickexError::ArithmeticOverflow)
+    })
+}
+
+/// `amount` of a token worth `value` per [`VALUE_SCALE`] units, in value units.
+fn to_value(amount: i128, value: i128) -> Option<i128> {
+    Some(amount.checked_mul(value)? / VALUE_SCALE)
+}
+
+/// Require that depositing `amount` of `token` keeps the total under the global cap.
+fn require_within_global_cap(env: &Env, token: &Address, amount: i128) -> Result<(), QuickexError> {
+    let cap = global_cap(env);
+    if cap == 0 {
+        return Ok(());
+    }
+    let values = storage::get_token_values(env);
+    let value = values
+        .get(token.clone())
+        .ok_or(QuickexError::TokenNotAllowed)?;
+    let locked = value_locked(env, &values)?;
+    let total = to_value(amount, value)
+        .and_then(|added| locked.checked_add(added))
+        .ok_or(QuickexError::ArithmeticOverflow)?;
+    if total > cap {
+        return Err(QuickexError::CapExceeded);
+    }
+    Ok(())
+}
+
+/// Require that `amount` of `token` may be deposited.
+///
+/// # Errors
+/// - [`QuickexError::TokenBlocked`] – `token` is blocked.
+/// - [`QuickexError::TokenNotAllowed`] – the allowlist is enabled and `token` is not on
+///   it, or the global cap is set and `token` has no value.
+/// - [`QuickexError::CapExceeded`] – the deposit would push the token over its cap, or
+///   the contract over the global cap.
+pub fn require_deposit_allowed(
+    env: &Env,
+    token: &Address,
+    amount: i128,
+) -> Result<(), QuickexError> {
+    if token_block(env, token).is_some() {
+        return Err(QuickexError::TokenBlocked);
+    }
+    if !is_token_allowed(env, token) {
+        return Err(QuickexError::TokenNotAllowed);
+    }
+    let cap = token_cap(env, token);
+    if cap > 0 {
+        let total = storage::get_total_locked(env, token)
+            .checked_add(amount)
+            .ok_or(QuickexError::ArithmeticOverflow)?;
+        if total > cap {
+            return Err(QuickexError::CapExceeded);
+        }
+    }
+    require_within_global_cap(env, token, amount)
+}
+
+/// Require that `token` may be paid out by a withdrawal or refund.
+///
+/// # Errors
+/// - [`QuickexError::TokenBlocked`] – `token` is blocked with withdrawals frozen.
+pub fn require_withdraw_allowed(env: &Env, token: &Address) -> Result<(), QuickexError> {
+    if token_block(env, token) == Some(true) {
+        return Err(QuickexError::TokenBlocked);
+    }
+    Ok(())
+}
+
+/// Transfer `amount` of `token` from the contract to `to`.
+///
+/// # Errors
+/// - [`QuickexError::TransferFailed`] – the token contract failed the transfer.
+pub(crate) fn pay(
+    env: &Env,
+    token: &Address,
+    to: impl Into<MuxedAddress>,
+    amount: i128,
+) -> Result<(), QuickexError> {
+    let contract = env.current_contract_address();
+    match token::Client::new(env, token).try_transfer(&contract, to, &amount) {
+        Ok(Ok(())) => Ok(()),
+        _ => Err(QuickexError::TransferFailed),
+    }
+}
+
+/// Transfer `amount` of `token` from `from` (which must authorize) to the contract.
+///
+/// # Errors
+/// - [`QuickexError::TransferFailed`] – the token contract failed the transfer, e.g.
+///   `from` holds too little or `token` is not a token contract.
+pub(crate) fn collect(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    amount: i128,
+) -> Result<(), QuickexError> {
+    let contract = env.current_contract_address();
+    match token::Client::new(env, token).try_transfer(from, contract, &amount) {
+        Ok(Ok(())) => Ok(()),
+        _ => Err(QuickexError::TransferFailed),
+    }
+}
+
+/// The contract's balance of `token`.
+///
+/// # Errors
+/// - [`QuickexError::InvalidToken`] – `token` is not a token contract.
+pub(crate) fn balance(env: &Env, token: &Address) -> Result<i128, QuickexError> {
+    let contract = env.current_contract_address();
+    match token::Client::new(env, token).try_balance(&contract) {
+        Ok(Ok(balance)) => Ok(balance),
+        _ => Err(QuickexError::InvalidToken),
+    }
+}
+
+/// Cap withdrawals of `token` to `max_amount` per `window_ledgers` ledgers (**admin
+/// only**). A `max_amount` of 0 removes the limit.
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::InvalidRateLimit`] – `max_amount` is negative, or positive with a
+///   zero window.
+pub fn set_withdraw_volume_limit(
+    env: &Env,
+    caller: Address,
+    token: Address,
+    max_amount: i128,
+    window_ledgers: u32,
+) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+    if max_amount < 0 || (max_amount > 0 && window_ledgers == 0) {
+        return Err(QuickexError::InvalidRateLimit);
+    }
+
+    if max_amount == 0 {
+        storage::remove_withdraw_volume_limit(env, &token);
+    } else {
+        let limit = WithdrawVolumeLimit {
+            max_amount,
+            window_ledgers,
+        };
+        storage::set_withdraw_volume_limit(env, &token, &limit);
+    }
+    publish_withdraw_volume_limit_updated(env, token, max_amount, window_ledgers, caller);
+    Ok(())
+}
+
+/// Withdrawal volume limit for `token`, if any.
+pub fn withdraw_volume_limit(env: &Env, token: &Address) -> Option<WithdrawVolumeLimit> {
+    storage::get_withdraw_volume_limit(env, token)
+}
+
+/// Count a withdrawal of `amount` of `token` against its volume limit, if it has one.
+///
+/// # Errors
+/// - [`QuickexError::RateLimited`] – the withdrawal would exceed the current window's cap.
+pub fn record_withdrawal_volume(
+    env: &Env,
+    token: &Address,
+    amount: i128,
+) -> Result<(), QuickexError> {
+    let Some(limit) = storage::get_withdraw_volume_limit(env, token) else {
+        return Ok(());
+    };
+
+    let ledger = env.ledger().sequence();
+    let mut window = match storage::get_volume_window(env, token) {
+        Some(w) if ledger < w.start_ledger.saturating_add(limit.window_ledgers) => w,
+        _ => VolumeWindow {
+            start_ledger: ledger,
+            volume: 0,
+        },
+    };
+    let volume = window
+        .volume
+        .checked_add(amount)
+        .ok_or(QuickexError::ArithmeticOverflow)?;
+    if volume > limit.max_amount {
+        return Err(QuickexError::RateLimited);
+    }
+
+    window.volume = volume;
+    storage::set_volume_window(env, token, &window);
+    Ok(())
+}
@@ -0,0 +1,115 @@
+//! Payouts through a token contract that fails its transfers.
+
+use crate::{
+    errors::QuickexError, test_fixture::deploy, types::EscrowStatus, QuickexContractClient,
+};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger as _},
+    Address, Bytes, Env, MuxedAddress,
+};
+
+const AMOUNT: i128 = 1_000;
+
+/// Token whose transfers succeed without moving balances until `set_failing(true)`,
+/// after which every transfer traps.
+#[contract]
+pub struct FailingToken;
+
+#[contractimpl]
+impl FailingToken {
+    pub fn set_failing(env: Env, failing: bool) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("failing"), &failing);
+    }
+
+    pub fn transfer(env: Env, _from: Address, _to: MuxedAddress, _amount: i128) {
+        let failing: bool = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("failing"))
+            .unwrap_or(false);
+        if failing {
+            panic!("transfer failed");
+        }
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    client: QuickexContractClient<'a>,
+    token: FailingTokenClient<'a>,
+    owner: Address,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let (env, client) = deploy();
+    client.initialize(&Address::generate(&env));
+    let token = FailingTokenClient::new(&env, &env.register(FailingToken, ()));
+    let owner = Address::generate(&env);
+    Setup {
+        env,
+        client,
+        token,
+        owner,
+    }
+}
+
+#[test]
+fn test_failed_withdraw_transfer_keeps_escrow_pending() {
+    let s = setup();
+    let salt = Bytes::from_slice(&s.env, b"failing_withdraw");
+    let commitment = s
+        .client
+        .deposit(&s.token.address, &AMOUNT, &s.owner, &salt, &0);
+
+    s.token.set_failing(&true);
+    assert_eq!(
+        s.client
+            .try_withdraw(&s.token.address, &AMOUNT, &commitment, &s.owner, &salt)
+            .err(),
+        Some(Ok(QuickexError::TransferFailed))
+    );
+    assert_eq!(
+        s.client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+
+    s.token.set_failing(&false);
+    s.client
+        .withdraw(&s.token.address, &AMOUNT, &commitment, &s.owner, &salt);
+    assert_eq!(
+        s.client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+#[test]
+fn test_failed_refund_transfer_keeps_escrow_pending() {
+    let s = setup();
+    let salt = Bytes::from_slice(&s.env, b"failing_refund");
+    let commitment = s
+        .client
+        .deposit(&s.token.address, &AMOUNT, &s.owner, &salt, &60);
+    s.env
+        .ledger()
+        .set_timestamp(s.env.ledger().timestamp() + 60);
+
+    s.token.set_failing(&true);
+    assert_eq!(
+        s.client.try_refund(&commitment, &s.owner).err(),
+        Some(Ok(QuickexError::TransferFailed))
+    );
+    assert_eq!(
+        s.client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+
+    s.token.set_failing(&false);
+    s.client.refund(&commitment, &s.owner);
+    assert_eq!(
+        s.client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Refunded)
+    );
+}
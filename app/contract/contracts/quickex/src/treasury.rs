@@ -0,0 +1,55 @@
+//! # Treasury
+//!
+//! Protocol fees kept from withdrawals (see [`crate::fees`]) stay in the contract,
+//! counted per token in [`AccruedFees`](crate::storage::DataKey::AccruedFees). This
+//! balance is segregated from escrow liabilities: it is excluded from
+//! [`sweepable_balance`](crate::admin::sweepable_balance), never used to pay out an
+//! escrow, and leaves the contract only through [`withdraw_fees`].
+
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    admin, compliance, errors::QuickexError, events::publish_fees_withdrawn, storage, tokens,
+};
+
+/// Protocol fees of `token` held by the contract.
+pub fn treasury_balance(env: &Env, token: &Address) -> i128 {
+    storage::get_accrued_fees(env, token)
+}
+
+/// Record `fee` of `token` as kept by the protocol.
+pub(crate) fn credit(env: &Env, token: &Address, fee: i128) -> Result<(), QuickexError> {
+    if fee > 0 {
+        storage::adjust_accrued_fees(env, token, fee)?;
+    }
+    Ok(())
+}
+
+/// Send `amount` of accrued `token` fees to `to` (**admin only**).
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::InvalidAmount`] – amount ≤ 0.
+/// - [`QuickexError::InsufficientFees`] – amount exceeds [`treasury_balance`].
+/// - [`QuickexError::AccountBlocked`] – `to` is on the compliance blocklist.
+pub fn withdraw_fees(
+    env: &Env,
+    caller: Address,
+    token: Address,
+    to: Address,
+    amount: i128,
+) -> Result<(), QuickexError> {
+    admin::require_admin(env, &caller)?;
+    if amount <= 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+    if amount > treasury_balance(env, &token) {
+        return Err(QuickexError::InsufficientFees);
+    }
+    compliance::require_not_blocked(env, &to)?;
+
+    storage::adjust_accrued_fees(env, &token, -amount)?;
+    tokens::pay(env, &token, &to, amount)?;
+    publish_fees_withdrawn(env, token, to, caller, amount);
+    Ok(())
+}
@@ -0,0 +1,122 @@
+//! # TTL Extension
+//!
+//! Pending escrows and the contract instance live in storage that expires unless its
+//! TTL is extended. Anyone can extend an entry with a plain `ExtendFootprintTTL`
+//! operation, which the contract never sees; keepers that extend through these
+//! entrypoints instead leave a `TtlExtended` event recording who extended what and
+//! until which ledger, so other keepers can skip entries that are already covered.
+//!
+//! [`bump_entries`] extends many escrows in one transaction, to a chosen TTL rather
+//! than the maximum, so keepers can top up a batch before it risks archival.
+//!
+//! Deposits, withdrawals, and refunds also top up the instance TTL themselves once it
+//! falls below [`storage::INSTANCE_BUMP_THRESHOLD`], and [`bump_instance`] lets anyone
+//! extend it without a keeper identity, so a contract in use never loses its config.
+//!
+//! An escrow nobody extended is archived, not lost. [`restore_escrow`] is the first
+//! half of a two-transaction recovery: the transaction calling it restores the
+//! archived entries in its footprint (automatically since protocol 23, or after a
+//! `RestoreFootprint` operation), and the call extends them to the maximum TTL so the
+//! claim submitted next finds them live.
+
+use soroban_sdk::{Address, Bytes, BytesN, Env, Vec};
+
+use crate::{
+    errors::QuickexError,
+    events::{publish_escrow_restored, publish_ttl_extended},
+    storage,
+};
+
+/// Extend the pending escrow under `commitment` to the maximum TTL. Returns the last
+/// ledger it now lives to.
+///
+/// # Errors
+/// - [`QuickexError::AlreadySpent`] – the escrow was withdrawn or refunded.
+/// - [`QuickexError::CommitmentNotFound`] – no escrow exists for `commitment`.
+pub fn extend_escrow(
+    env: &Env,
+    keeper: Address,
+    commitment: BytesN<32>,
+) -> Result<u32, QuickexError> {
+    keeper.require_auth();
+
+    let key: Bytes = commitment.clone().into();
+    let live_until = match storage::extend_escrow_ttl(env, &key) {
+        Some(ledger) => ledger,
+        None if storage::is_closed(env, &key) => return Err(QuickexError::AlreadySpent),
+        None => return Err(QuickexError::CommitmentNotFound),
+    };
+    publish_ttl_extended(env, keeper, Some(commitment), live_until);
+    Ok(live_until)
+}
+
+/// Extend a just-restored pending escrow and the entries its claim reads to the maximum
+/// TTL. Returns the last ledger the escrow now lives to.
+///
+/// Needs no authorization: it moves no funds, and anyone could extend the same
+/// entries with an `ExtendFootprintTTL` operation.
+///
+/// # Errors
+/// - [`QuickexError::AlreadySpent`] – the escrow was withdrawn or refunded.
+/// - [`QuickexError::CommitmentNotFound`] – no escrow exists for `commitment`.
+pub fn restore_escrow(env: &Env, commitment: BytesN<32>) -> Result<u32, QuickexError> {
+    let live_until = match storage::restore_escrow_ttl(env, &commitment) {
+        Some(ledger) => ledger,
+        None if storage::is_closed(env, &commitment.clone().into()) => {
+            return Err(QuickexError::AlreadySpent)
+        }
+        None => return Err(QuickexError::CommitmentNotFound),
+    };
+    publish_escrow_restored(env, commitment, live_until);
+    Ok(live_until)
+}
+
+/// Extend each escrow in `commitments` to live at least `extend_to` more ledgers, and
+/// return how many were found. Unknown, spent, and refunded commitments are skipped.
+///
+/// # Errors
+/// - [`QuickexError::InvalidTtl`] – `extend_to` is zero or above the maximum TTL.
+pub fn bump_entries(
+    env: &Env,
+    keeper: Address,
+    commitments: Vec<BytesN<32>>,
+    extend_to: u32,
+) -> Result<u32, QuickexError> {
+    keeper.require_auth();
+    if extend_to == 0 || extend_to > env.storage().max_ttl() {
+        return Err(QuickexError::InvalidTtl);
+    }
+
+    let live_until = env.ledger().sequence() + extend_to;
+    let mut bumped = 0;
+    for commitment in commitments.iter() {
+        if storage::bump_escrow_ttl(env, &commitment.clone().into(), extend_to) {
+            publish_ttl_extended(env, keeper.clone(), Some(commitment), live_until);
+            bumped += 1;
+        }
+    }
+    Ok(bumped)
+}
+
+/// Extend the contract instance so it lives at least `extend_to` more ledgers, and
+/// return that ledger. Needs no authorization and emits no event, like a plain
+/// `ExtendFootprintTTL` operation.
+///
+/// # Errors
+/// - [`QuickexError::InvalidTtl`] – `extend_to` is zero or above the maximum TTL.
+pub fn bump_instance(env: &Env, extend_to: u32) -> Result<u32, QuickexError> {
+    if extend_to == 0 || extend_to > env.storage().max_ttl() {
+        return Err(QuickexError::InvalidTtl);
+    }
+    Ok(storage::bump_instance_to(env, extend_to))
+}
+
+/// Extend the contract instance (admin, config, and counters) to the maximum TTL.
+/// Returns the last ledger it now lives to.
+pub fn extend_instance(env: &Env, keeper: Address) -> u32 {
+    keeper.require_auth();
+
+    let live_until = storage::extend_instance_ttl(env);
+    publish_ttl_extended(env, keeper, None, live_until);
+    live_until
+}
@@ -2,7 +2,7 @@
 //!
 //! See [`crate::storage`] for the storage schema and key layout.
 
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Map, Vec};
 
 /// Escrow entry status.
 ///
@@ -51,30 +51,480 @@ pub struct EscrowEntry {
     pub expires_at: u64,
 }
 
+/// Storage encoding of an [`EscrowEntry`]:
+/// `(token, amount, owner, packed_status, created_at, expires_at)`.
+///
+/// A tuple struct is stored as a positional vector, so unlike `EscrowEntry` (a map
+/// keyed by field names) it carries no field names. `packed_status` holds the status
+/// code in its low byte and the encoding version above it. See
+/// [`crate::storage::put_escrow`].
+#[contracttype]
+#[derive(Clone)]
+pub struct PackedEscrowEntry(
+    pub Address,
+    pub i128,
+    pub Address,
+    pub u32,
+    pub u64,
+    pub u64,
+);
+
+/// Hidden-amount escrow entry.
+///
+/// Stored under [`DataKey::HiddenEscrow`](crate::storage::DataKey::HiddenEscrow)(key) where
+/// `key = SHA256(XDR(to) || amount_commitment)`. Unlike [`EscrowEntry`] the amount is
+/// never persisted; only its Pedersen commitment is. See [`crate::pedersen`].
+#[contracttype]
+#[derive(Clone)]
+pub struct HiddenEscrowEntry {
+    /// Token contract address for the escrowed funds.
+    pub token: Address,
+    /// Pedersen commitment `amount·G + r·H` (uncompressed BLS12-381 G1 point).
+    pub amount_commitment: BytesN<96>,
+    /// Owner who deposited and may refund after expiry.
+    pub owner: Address,
+    /// Current status (Pending, Spent, Refunded).
+    pub status: EscrowStatus,
+    /// Ledger timestamp when the escrow was created.
+    pub created_at: u64,
+    /// Ledger timestamp after which withdrawal is blocked and refund is enabled.
+    /// A value of `0` means the escrow never expires (no timeout).
+    pub expires_at: u64,
+}
+
+/// Blinding point `R = r·H` of a hidden deposit, with a Schnorr proof of knowledge of `r`.
+///
+/// Made non-interactive with Fiat–Shamir; see [`crate::pedersen`] for the challenge.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlindingProof {
+    /// `R = r·H` (uncompressed G1 point).
+    pub blinding_point: BytesN<96>,
+    /// Nonce commitment `T = k·H` (uncompressed G1 point).
+    pub nonce_point: BytesN<96>,
+    /// Response `s = k + c·r` (big-endian, reduced mod the scalar field order).
+    pub response: BytesN<32>,
+}
+
+/// Per-denomination pool state.
+///
+/// Stored under [`DataKey::PoolState`](crate::storage::DataKey::PoolState)(token, denomination).
+#[contracttype]
+#[derive(Clone)]
+pub struct PoolState {
+    /// Number of notes ever deposited into the pool.
+    pub deposit_count: u64,
+    /// Number of pool notes withdrawn.
+    pub spent_count: u64,
+    /// Pending notes bucketed by deposit day (`created_at / 86400`). Days with no
+    /// pending notes are removed, so the map only grows with active days.
+    pub pending_by_day: Map<u64, u32>,
+}
+
+/// Anonymity-set statistics for a (token, denomination) pool.
+///
+/// Returned by [`QuickexContract::pool_stats`](crate::QuickexContract::pool_stats).
+/// Ages are measured from the deposit day to the current ledger time.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolStats {
+    /// Notes deposited and not yet withdrawn.
+    pub pending: u64,
+    /// Notes withdrawn.
+    pub spent: u64,
+    /// Pending notes deposited less than 1 day ago.
+    pub age_under_1d: u32,
+    /// Pending notes aged 1 to 7 days.
+    pub age_1d_to_7d: u32,
+    /// Pending notes aged 7 to 30 days.
+    pub age_7d_to_30d: u32,
+    /// Pending notes aged 30 days or more.
+    pub age_over_30d: u32,
+}
+
+/// Escrow volumes of one token, part of [`ContractStats`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenStats {
+    /// Sum of all escrow deposits.
+    pub deposited: i128,
+    /// Sum of escrow amounts withdrawn (before fees).
+    pub withdrawn: i128,
+    /// Sum of escrow amounts refunded.
+    pub refunded: i128,
+}
+
+/// Escrow counters and per-token volumes.
+///
+/// Returned by [`QuickexContract::stats`](crate::QuickexContract::stats). See
+/// [`crate::stats`] for what is counted.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractStats {
+    /// Escrows deposited and not yet withdrawn or refunded.
+    pub pending: u64,
+    /// Escrows withdrawn.
+    pub spent: u64,
+    /// Escrows refunded.
+    pub refunded: u64,
+    /// Volumes per token that has had an escrow.
+    pub tokens: Map<Address, TokenStats>,
+}
+
+/// Incremental Merkle tree of shielded notes for one (token, denomination) pool.
+///
+/// Stored under [`DataKey::PoolTree`](crate::storage::DataKey::PoolTree)(token, denomination).
+/// See [`crate::merkle`].
+#[contracttype]
+#[derive(Clone)]
+pub struct MerkleTree {
+    /// Index the next inserted leaf will occupy (equals the number of leaves).
+    pub next_index: u32,
+    /// Rightmost filled node at each level, used to compute the next root.
+    pub filled_subtrees: Vec<BytesN<32>>,
+    /// Current root.
+    pub root: BytesN<32>,
+    /// Most recent roots (oldest first, current root last). Proofs may target any of
+    /// them, so a deposit landing between proof generation and submission does not
+    /// invalidate the proof.
+    pub recent_roots: Vec<BytesN<32>>,
+}
+
+/// Scalar field a Poseidon hash is computed over.
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PoseidonField {
+    /// BLS12-381 scalar field, matching the Groth16 withdrawal verifier.
+    Bls12381,
+    /// BN254 scalar field, for circuits and tooling built on BN254.
+    Bn254,
+}
+
+/// Merkle authentication path for a leaf of a shielded pool tree.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerklePath {
+    /// Leaf index; bit `i` is 1 when the node at level `i` is a right child.
+    pub index: u32,
+    /// Sibling hashes from the leaf level up, one per tree level.
+    pub siblings: Vec<BytesN<32>>,
+}
+
+/// Groth16 proof (uncompressed BLS12-381 points).
+#[contracttype]
+#[derive(Clone)]
+pub struct Groth16Proof {
+    /// `A` in G1.
+    pub a: BytesN<96>,
+    /// `B` in G2.
+    pub b: BytesN<192>,
+    /// `C` in G1.
+    pub c: BytesN<96>,
+}
+
+/// Groth16 verifying key for the shielded withdrawal circuit.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifyingKey {
+    /// `α` in G1.
+    pub alpha: BytesN<96>,
+    /// `β` in G2.
+    pub beta: BytesN<192>,
+    /// `γ` in G2.
+    pub gamma: BytesN<192>,
+    /// `δ` in G2.
+    pub delta: BytesN<192>,
+    /// `IC_0..IC_n` in G1; one more than the number of public inputs.
+    pub ic: Vec<BytesN<96>>,
+}
+
+/// A registered verifying key and its version.
+///
+/// Stored under [`DataKey::VerifyingKey`](crate::storage::DataKey::VerifyingKey)(version).
+/// See [`crate::verifier`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifyingKeyRecord {
+    /// Version number, starting at 1 and incremented on every rotation.
+    pub version: u32,
+    /// The verifying key.
+    pub key: VerifyingKey,
+    /// Ledger timestamp when this version was registered.
+    pub registered_at: u64,
+}
+
+/// Public inputs of a shielded withdrawal.
+///
+/// The circuit proves knowledge of a note `(nullifier, secret)` whose leaf is in the
+/// tree with root `root`, and that `nullifier_hash` is derived from that note. The
+/// verifier feeds the proof these scalars, in order:
+///
+/// 1. `root` (reduced mod r)
+/// 2. `nullifier_hash` (must already be < r)
+/// 3. `SHA256(XDR(recipient))` (reduced mod r)
+///
+/// `token` and `denomination` select the pool; they are bound to the proof through
+/// `root`, which must be a recent root of that pool.
+#[contracttype]
+#[derive(Clone)]
+pub struct WithdrawPublicInputs {
+    /// Pool token.
+    pub token: Address,
+    /// Pool denomination.
+    pub denomination: i128,
+    /// Merkle root the proof was generated against.
+    pub root: BytesN<32>,
+    /// Nullifier hash; each note can only be withdrawn once.
+    pub nullifier_hash: BytesN<32>,
+    /// Address that receives the funds.
+    pub recipient: Address,
+}
+
+/// Relayer staking configuration.
+///
+/// Stored under [`DataKey::RelayerConfig`](crate::storage::DataKey::RelayerConfig).
+/// See [`crate::relayer`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelayerConfig {
+    /// Token relayers stake. Fixed once configured.
+    pub stake_token: Address,
+    /// Minimum stake for a relayer to be active.
+    pub min_stake: i128,
+    /// Seconds between `unstake` and `withdraw_stake`, during which stake stays slashable.
+    pub unbonding_period: u64,
+}
+
+/// Stake record of a relayer.
+///
+/// Stored under [`DataKey::Relayer`](crate::storage::DataKey::Relayer)(relayer).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelayerInfo {
+    /// Currently locked stake.
+    pub stake: i128,
+    /// Whether the relayer may submit relayed withdrawals.
+    pub active: bool,
+    /// Ledger timestamp when unbonding ends; `0` while bonded.
+    pub unlock_at: u64,
+    /// Total stake slashed so far.
+    pub slashed: i128,
+}
+
+/// Ring note registered by its one-time public key.
+///
+/// Stored under [`DataKey::RingNote`](crate::storage::DataKey::RingNote)(`SHA256(P)`).
+/// See [`crate::ring`].
+#[contracttype]
+#[derive(Clone)]
+pub struct RingNote {
+    /// Pool token.
+    pub token: Address,
+    /// Pool denomination.
+    pub denomination: i128,
+    /// Ledger timestamp when the note was deposited.
+    pub created_at: u64,
+}
+
+/// LSAG ring signature.
+#[contracttype]
+#[derive(Clone)]
+pub struct RingSignature {
+    /// Key image `I = x·Hp(P)` (uncompressed G1 point); links signatures by the same key.
+    pub key_image: BytesN<96>,
+    /// Initial challenge `c_0` (big-endian, reduced mod r).
+    pub challenge: BytesN<32>,
+    /// Responses `s_0..s_{N-1}`, one per ring member (big-endian, reduced mod r).
+    pub responses: Vec<BytesN<32>>,
+}
+
+/// One privacy level change.
+///
+/// Stored newest-first under [`DataKey::PrivacyLog`](crate::storage::DataKey::PrivacyLog)(account).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrivacyHistoryEntry {
+    /// Level that was set.
+    pub level: u32,
+    /// Ledger timestamp of the change.
+    pub timestamp: u64,
+}
+
+/// Account-configured cap on withdrawals per time window.
+///
+/// Set with [`QuickexContract::set_withdraw_rate_limit`](crate::QuickexContract::set_withdraw_rate_limit).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawRateLimit {
+    /// Withdrawals allowed per window.
+    pub max_withdrawals: u32,
+    /// Window length in seconds.
+    pub window_secs: u64,
+}
+
+/// Minimum salt length enforced on escrows created since `since`.
+///
+/// Set with [`QuickexContract::set_min_salt_len`](crate::QuickexContract::set_min_salt_len).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SaltPolicy {
+    /// Minimum salt length in bytes.
+    pub min_len: u32,
+    /// Ledger timestamp at which the minimum took effect.
+    pub since: u64,
+}
+
+/// Withdrawals counted in an account's current rate-limit window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawWindow {
+    /// Ledger timestamp at which the window opened.
+    pub start: u64,
+    /// Withdrawals performed since `start`.
+    pub count: u32,
+}
+
+/// One page of an account's privacy history.
+///
+/// Returned by [`QuickexContract::privacy_history_page`](crate::QuickexContract::privacy_history_page).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrivacyHistoryPage {
+    /// Entries, newest first.
+    pub entries: Vec<PrivacyHistoryEntry>,
+    /// Cursor for the next page; `None` when there are no older entries.
+    pub next_cursor: Option<u32>,
+}
+
+/// Escrow withdrawal reserved by `prepare_withdraw`, settled by `commit_withdraw` or
+/// released by `abort_withdraw`.
+///
+/// Stored in temporary storage under
+/// [`DataKey::WithdrawTicket`](crate::storage::DataKey::WithdrawTicket)(ticket id) until
+/// settled or [`WITHDRAW_TICKET_LEDGERS`](crate::escrow::WITHDRAW_TICKET_LEDGERS) pass.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawTicket {
+    /// Commitment of the reserved escrow.
+    pub commitment: BytesN<32>,
+    /// Token the escrow holds.
+    pub token: Address,
+    /// Recipient, who must authorize the commit or abort.
+    pub to: Address,
+    /// Escrow amount.
+    pub amount: i128,
+    /// Salt opening the commitment.
+    pub salt: Bytes,
+}
+
+/// Result of [`crate::solvency::check_invariants`] for one token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvariantReport {
+    /// Token checked.
+    pub token: Address,
+    /// Contract balance of the token.
+    pub balance: i128,
+    /// Owed to pending escrows and pool notes.
+    pub locked: i128,
+    /// Protocol fees held in the treasury.
+    pub accrued_fees: i128,
+    /// Relayer stakes, if the token is the stake token.
+    pub staked: i128,
+    /// Amount by which the balance falls short of what is owed (0 when solvent).
+    pub shortfall: i128,
+    /// Whether the balance covers everything owed.
+    pub solvent: bool,
+    /// Whether this check paused the contract.
+    pub auto_paused: bool,
+}
+
+/// Accounting record of a completed withdrawal.
+///
+/// Returned by [`QuickexContract::withdraw`](crate::QuickexContract::withdraw) and kept
+/// in temporary storage under [`DataKey::Receipt`](crate::storage::DataKey::Receipt)
+/// for [`ESCROW_ARCHIVE_TTL`](crate::storage::ESCROW_ARCHIVE_TTL) ledgers.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawReceipt {
+    /// Commitment of the spent escrow.
+    pub commitment: BytesN<32>,
+    /// Token paid out.
+    pub token: Address,
+    /// Escrow amount, before fees.
+    pub gross: i128,
+    /// Fees deducted: the protocol fee, plus the relayer fee for relayed withdrawals.
+    pub fee: i128,
+    /// Amount transferred to the recipient (`gross - fee`).
+    pub net: i128,
+    /// Ledger sequence the withdrawal was included in.
+    pub ledger: u32,
+}
+
+/// Class of a rejected withdrawal counted by the withdrawal telemetry.
+///
+/// See [`crate::telemetry`].
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WithdrawRejection {
+    /// No pending escrow matches the salt (`CommitmentNotFound` or `CommitmentMismatch`).
+    WrongSalt,
+    /// The escrow was already withdrawn or refunded (`AlreadySpent`).
+    Spent,
+    /// The escrow is past its expiry (`EscrowExpired`).
+    Expired,
+}
+
+/// One page of an owner's claimable escrows.
+///
+/// Returned by [`QuickexContract::list_claimable_page`](crate::QuickexContract::list_claimable_page).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimablePage {
+    /// Pending escrow commitments, oldest first.
+    pub commitments: Vec<BytesN<32>>,
+    /// Cursor for the next page; `None` when there are no newer escrows.
+    pub next_cursor: Option<u32>,
+}
+
+/// One page of the active relayers.
+///
+/// Returned by [`QuickexContract::get_active_relayers_page`](crate::QuickexContract::get_active_relayers_page).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelayerPage {
+    /// Active relayers, in activation order.
+    pub relayers: Vec<Address>,
+    /// Cursor for the next page; `None` when there are no later relayers.
+    pub next_cursor: Option<u32>,
+}
+
 /// Privacy-aware view of an escrow entry.
 ///
 /// Returned by [`QuickexContract::get_escrow_details`] instead of the raw
-/// [`EscrowEntry`]. Sensitive fields (`amount`, `owner`) are set to `None`
-/// when the escrow owner has privacy enabled and the caller is not the owner.
+/// [`EscrowEntry`]. Sensitive fields are set to `None` according to the escrow
+/// owner's effective privacy level (see [`crate::privacy`]) when the caller is
+/// neither the owner nor an [`Role::Auditor`].
 ///
 /// ## Field visibility
 ///
-/// | Field        | Privacy off | Privacy on + caller is owner | Privacy on + caller is stranger |
-/// |--------------|-------------|------------------------------|---------------------------------|
-/// | `token`      | ✓           | ✓                            | ✓                               |
-/// | `status`     | ✓           | ✓                            | ✓                               |
-/// | `created_at` | ✓           | ✓                            | ✓                               |
-/// | `expires_at` | ✓           | ✓                            | ✓                               |
-/// | `amount`     | ✓           | ✓                            | `None`                          |
-/// | `owner`      | ✓           | ✓                            | `None`                          |
+/// | Field        | Level 0, owner or auditor | Level 1 | Level 2 / privacy on |
+/// |--------------|---------------------------|---------|----------------------|
+/// | `token`      | ✓                         | ✓       | ✓                    |
+/// | `status`     | ✓                         | ✓       | ✓                    |
+/// | `created_at` | ✓                         | ✓       | ✓                    |
+/// | `expires_at` | ✓                         | ✓       | ✓                    |
+/// | `amount`     | ✓                         | `None`  | `None`               |
+/// | `owner`      | ✓                         | ✓       | `None`               |
+/// | `redacted`   | `false`                   | `true`  | `true`               |
 #[contracttype]
 #[derive(Clone)]
 pub struct PrivacyAwareEscrowView {
     /// Token contract address (always visible).
     pub token: Address,
-    /// Escrowed amount. `None` when privacy is enabled and caller is not the owner.
+    /// Escrowed amount. `None` at privacy level ≥ 1 when caller is not the owner.
     pub amount: Option<i128>,
-    /// Owner address. `None` when privacy is enabled and caller is not the owner.
+    /// Owner address. `None` at privacy level 2 when caller is not the owner.
     pub owner: Option<Address>,
     /// Current lifecycle status (always visible).
     pub status: EscrowStatus,
@@ -82,4 +532,215 @@ pub struct PrivacyAwareEscrowView {
     pub created_at: u64,
     /// Expiry timestamp; `0` means no expiry (always visible).
     pub expires_at: u64,
+    /// Whether fields were withheld from this caller.
+    pub redacted: bool,
+}
+
+/// Granular privilege that the admin can delegate to other addresses.
+///
+/// The admin implicitly holds every role.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// May pause and unpause the contract.
+    Pauser,
+    /// May upgrade the contract WASM.
+    Upgrader,
+    /// May change protocol fee settings.
+    FeeManager,
+    /// May view escrow details that the owner's privacy level hides.
+    Auditor,
+}
+
+/// Privileged action executed by the admin council once enough members approve.
+///
+/// See [`crate::admin::propose_action`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AdminAction {
+    /// Pause (`true`) or unpause (`false`) the contract.
+    SetPaused(bool),
+    /// Pause (`true`) or unpause (`false`) the operations in a bitmask.
+    SetPausedOps(u32, bool),
+    /// Upgrade the contract to a pre-uploaded WASM hash with the given code version.
+    Upgrade(BytesN<32>, u32),
+    /// Grant a role to an address.
+    GrantRole(Role, Address),
+    /// Revoke a role from an address.
+    RevokeRole(Role, Address),
+    /// Transfer admin rights (in council mode, hands control to a single admin).
+    SetAdmin(Address),
+    /// Change the timelock delay, in seconds.
+    SetTimelockDelay(u64),
+    /// Cancel a queued timelocked action by id.
+    CancelQueued(u64),
+    /// Set or clear the pause-only guardian.
+    SetGuardian(Option<Address>),
+    /// Set the protocol withdrawal fee in basis points.
+    SetWithdrawalFee(u32),
+    /// Cap the locked total of a token (`0` removes the cap).
+    SetTokenCap(Address, i128),
+    /// Set the global value cap (`0` removes it).
+    SetGlobalCap(i128),
+    /// Add a token to the deposit allowlist.
+    AllowToken(Address),
+    /// Remove a token from the deposit allowlist.
+    DisallowToken(Address),
+}
+
+/// Admin council that replaces the single admin.
+///
+/// Set once with [`QuickexContract::initialize_multisig`](crate::QuickexContract::initialize_multisig).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminCouncil {
+    /// Council members, without duplicates.
+    pub members: Vec<Address>,
+    /// Approvals needed to execute an action (1 ≤ threshold ≤ members).
+    pub threshold: u32,
+}
+
+/// Council action awaiting approvals.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingAdminAction {
+    pub action: AdminAction,
+    /// Members who have approved so far, proposer first.
+    pub approvals: Vec<Address>,
+    /// Ledger timestamp of the proposal.
+    pub proposed_at: u64,
+}
+
+/// Admin action waiting out the timelock delay.
+///
+/// See [`crate::timelock`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueuedAdminAction {
+    pub action: AdminAction,
+    /// Admin or council member that queued the action.
+    pub queued_by: Address,
+    /// Earliest ledger timestamp at which the action can be executed.
+    pub eta: u64,
+}
+
+/// Upgrade awaiting its storage migration.
+///
+/// See [`crate::migration`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeRecord {
+    /// Hash of the WASM the contract was upgraded to.
+    pub wasm_hash: BytesN<32>,
+    /// Code version of that WASM.
+    pub version: u32,
+    /// Ledger timestamp of the upgrade.
+    pub upgraded_at: u64,
+}
+
+/// Reason an upgrade would be refused right now.
+///
+/// See [`crate::migration::check_upgrade`].
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpgradeBlocker {
+    /// The admin was renounced; nobody can upgrade.
+    AdminRenounced,
+    /// A timelock delay is set; the upgrade must be scheduled instead.
+    TimelockRequired,
+    /// The previous upgrade has not been migrated yet.
+    MigrationPending,
+    /// Storage was written by a newer schema than the running code's.
+    SchemaAhead,
+}
+
+/// Result of [`crate::migration::check_upgrade`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeReport {
+    /// Whether `upgrade` would currently go through (given an authorized caller).
+    pub allowed: bool,
+    /// Everything that would make `upgrade` fail, empty when `allowed`.
+    pub blockers: Vec<UpgradeBlocker>,
+    /// Version of the running code.
+    pub code_version: u32,
+    /// Persisted storage schema version.
+    pub schema_version: u32,
+    /// Bitmask of paused operations, for operators who upgrade while paused.
+    pub paused_ops: u32,
+    /// Execution time of the scheduled upgrade, if one targets the checked hash.
+    pub scheduled_at: Option<u64>,
+}
+
+/// Cap on the amount of a token withdrawn per window of ledgers.
+///
+/// Set with [`QuickexContract::set_withdraw_volume_limit`](crate::QuickexContract::set_withdraw_volume_limit).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawVolumeLimit {
+    /// Most that can be withdrawn per window.
+    pub max_amount: i128,
+    /// Window length in ledgers.
+    pub window_ledgers: u32,
+}
+
+/// Amount of a token withdrawn in its current volume window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VolumeWindow {
+    /// Ledger sequence at which the window opened.
+    pub start_ledger: u32,
+    /// Amount withdrawn since `start_ledger`.
+    pub volume: i128,
+}
+
+/// Contract-wide flags and parameters, read in one call (see [`crate::config`]).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// Bitmask of paused operations (see [`crate::admin::PAUSE_ALL`]).
+    pub paused_ops: u32,
+    /// Protocol withdrawal fee in basis points.
+    pub withdrawal_fee_bps: u32,
+    /// Cap on the value locked across all tokens (0 = uncapped).
+    pub global_cap: i128,
+    /// Escrow timeout applied when a deposit passes `timeout_secs = 0`
+    /// (0 = such escrows never expire).
+    pub default_claim_window_secs: u64,
+    /// Longest escrow timeout a deposit may use (0 = no maximum).
+    pub max_claim_window_secs: u64,
+    /// Ledgers that must close between announcing and finalizing a withdrawal.
+    pub reveal_delay_ledgers: u32,
+    /// Seconds without admin activity before the recovery address may claim admin.
+    pub recovery_inactivity_secs: u64,
+}
+
+/// Kind of problem a [`QuickexError`](crate::errors::QuickexError) reports.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorCategory {
+    /// The caller lacks the required authority, ownership or signature.
+    Auth,
+    /// The contract or escrow is not in a state that allows the call.
+    State,
+    /// An argument is malformed, out of range or does not match stored data.
+    Validation,
+    /// A cap, rate limit or available balance would be exceeded.
+    Limits,
+    /// An internal invariant or arithmetic bound was hit.
+    Internal,
+}
+
+/// Client-facing metadata for one error code.
+///
+/// Returned by [`QuickexContract::error_info`](crate::QuickexContract::error_info).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ErrorInfo {
+    /// The error code, as returned in a failed invocation.
+    pub code: u32,
+    /// What kind of problem the error reports.
+    pub category: ErrorCategory,
+    /// Whether the same call may succeed later without changing its arguments.
+    pub retryable: bool,
 }
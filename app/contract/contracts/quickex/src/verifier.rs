@@ -0,0 +1,90 @@
+//! Versioned verifying-key registry for the shielded withdrawal circuit.
+//!
+//! Every call to [`set_verifying_key`] stores the key under the next version number
+//! and makes it current, so the circuit can be upgraded without redeploying the
+//! contract. Older versions stay readable for audit via [`get_verifying_key`] but are
+//! no longer used to verify withdrawals: proofs generated against a rotated-out key
+//! must be regenerated.
+
+use soroban_sdk::{Address, BytesN, Env};
+
+use crate::{
+    admin, bls,
+    errors::QuickexError,
+    events, storage,
+    types::{VerifyingKey, VerifyingKeyRecord},
+};
+
+/// Number of public inputs of the withdrawal circuit (root, nullifier hash, recipient).
+pub const WITHDRAW_PUBLIC_INPUTS: u32 = 3;
+
+fn is_g1(env: &Env, bytes: &BytesN<96>) -> bool {
+    bls::g1_checked(env, bytes).is_some()
+}
+
+fn is_g2(env: &Env, bytes: &BytesN<192>) -> bool {
+    bls::g2_checked(env, bytes).is_some()
+}
+
+fn validate(env: &Env, vk: &VerifyingKey) -> Result<(), QuickexError> {
+    if vk.ic.len() != WITHDRAW_PUBLIC_INPUTS + 1 {
+        return Err(QuickexError::InvalidVerifyingKey);
+    }
+    let g1_ok = is_g1(env, &vk.alpha) && vk.ic.iter().all(|p| is_g1(env, &p));
+    let g2_ok = is_g2(env, &vk.beta) && is_g2(env, &vk.gamma) && is_g2(env, &vk.delta);
+    if !g1_ok || !g2_ok {
+        return Err(QuickexError::InvalidVerifyingKey);
+    }
+    Ok(())
+}
+
+/// Register `vk` as the new current verifying key (**admin only**).
+///
+/// Returns the assigned version (1 for the first key).
+///
+/// # Errors
+/// - [`QuickexError::Unauthorized`] – caller is not the admin.
+/// - [`QuickexError::InvalidVerifyingKey`] – wrong number of `IC` points, or a point
+///   is not in its prime-order subgroup.
+pub fn set_verifying_key(
+    env: &Env,
+    caller: Address,
+    vk: VerifyingKey,
+) -> Result<u32, QuickexError> {
+    admin::require_admin(env, &caller)?;
+    validate(env, &vk)?;
+
+    let version = storage::get_verifying_key_version(env) + 1;
+    let record = VerifyingKeyRecord {
+        version,
+        key: vk,
+        registered_at: env.ledger().timestamp(),
+    };
+    storage::set_verifying_key(env, version, &record);
+    storage::set_verifying_key_version(env, version);
+
+    events::publish_verifying_key_updated(env, caller, version);
+
+    Ok(version)
+}
+
+/// Verifying key `version`, or the current one when `version` is `None`.
+///
+/// Returns `None` if no key has been registered (or `version` does not exist).
+pub fn get_verifying_key(env: &Env, version: Option<u32>) -> Option<VerifyingKeyRecord> {
+    let version = version.unwrap_or_else(|| storage::get_verifying_key_version(env));
+    if version == 0 {
+        return None;
+    }
+    storage::get_verifying_key(env, version)
+}
+
+/// The key currently used to verify withdrawals.
+///
+/// # Errors
+/// - [`QuickexError::VerifyingKeyNotSet`] – no key has been registered.
+pub fn current_verifying_key(env: &Env) -> Result<VerifyingKey, QuickexError> {
+    get_verifying_key(env, None)
+        .map(|record| record.key)
+        .ok_or(QuickexError::VerifyingKeyNotSet)
+}
@@ -0,0 +1,75 @@
+//! Verifying-key registry tests.
+
+use crate::{
+    errors::QuickexError,
+    shielded_test::{deposit_note, forge_proof, g1_mul, inputs_for, test_vk, DENOMINATION},
+    test_fixture::deploy_pool,
+};
+use soroban_sdk::{testutils::Address as _, Address};
+
+#[test]
+fn test_get_verifying_key_before_registration() {
+    let (_env, client, _admin, _token) = deploy_pool(DENOMINATION);
+
+    assert_eq!(client.get_verifying_key(&None), None);
+    assert_eq!(client.get_verifying_key(&Some(1)), None);
+}
+
+#[test]
+fn test_set_verifying_key_assigns_versions() {
+    let (env, client, admin, _token) = deploy_pool(DENOMINATION);
+    let vk1 = test_vk(&env);
+    let mut vk2 = test_vk(&env);
+    vk2.alpha = g1_mul(&env, 99);
+
+    assert_eq!(client.set_verifying_key(&admin, &vk1), 1);
+    assert_eq!(client.set_verifying_key(&admin, &vk2), 2);
+
+    let current = client.get_verifying_key(&None).unwrap();
+    assert_eq!(current.version, 2);
+    assert_eq!(current.key, vk2);
+
+    // Rotated-out versions stay readable.
+    let first = client.get_verifying_key(&Some(1)).unwrap();
+    assert_eq!(first.version, 1);
+    assert_eq!(first.key, vk1);
+    assert_eq!(client.get_verifying_key(&Some(3)), None);
+}
+
+#[test]
+fn test_rotation_invalidates_proofs_for_old_key() {
+    let (env, client, admin, token) = deploy_pool(DENOMINATION);
+    client.set_verifying_key(&admin, &test_vk(&env));
+    deposit_note(&env, &client, &token, 1);
+
+    let recipient = Address::generate(&env);
+    let inputs = inputs_for(&env, &client, &token, 7, &recipient);
+    let proof = forge_proof(&env, &inputs);
+
+    let mut rotated = test_vk(&env);
+    rotated.alpha = g1_mul(&env, 99);
+    client.set_verifying_key(&admin, &rotated);
+
+    let result = client.try_withdraw_with_proof(&proof, &inputs);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidProof)));
+}
+
+#[test]
+fn test_set_verifying_key_wrong_input_count_fails() {
+    let (env, client, admin, _token) = deploy_pool(DENOMINATION);
+    let mut vk = test_vk(&env);
+    vk.ic.pop_back();
+
+    let result = client.try_set_verifying_key(&admin, &vk);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidVerifyingKey)));
+    assert_eq!(client.get_verifying_key(&None), None);
+}
+
+#[test]
+fn test_set_verifying_key_by_non_admin_fails() {
+    let (env, client, _admin, _token) = deploy_pool(DENOMINATION);
+    let stranger = Address::generate(&env);
+
+    let result = client.try_set_verifying_key(&stranger, &test_vk(&env));
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+}
@@ -0,0 +1,264 @@
+//! # QuickEx Commitment Preimages
+//!
+//! The byte layout hashed into QuickEx escrow commitments, shared by the Soroban
+//! contract and off-chain Rust clients so both build exactly the same preimage.
+//!
+//! ```text
+//! v1:           SHA256(XDR(owner) || BE(amount) || salt)
+//! v2:           SHA256(0x02 || XDR(token) || XDR(owner) || BE(amount) || salt)
+//! v3:           SHA256(0x03 || KEY(token) || KEY(owner) || BE(amount) || salt)
+//! announcement: SHA256("quickex/announce" || XDR(to) || BE(amount) || salt || nonce)
+//! ```
+//!
+//! where `XDR(addr)` is the XDR encoding of the address as an `ScVal` (see
+//! [`account_address_xdr`] and [`contract_address_xdr`]) and `BE(amount)` is the
+//! 16-byte big-endian `i128`.
+//!
+//! Each kind of preimage starts with its own domain tag, so no two kinds can hash the
+//! same bytes: v1 with the leading `0x00` of `XDR(owner)`, v2 and v3 with their scheme
+//! byte, and announcements with [`ANNOUNCEMENT_TAG`].
+//!
+//! Scheme v3 replaces the XDR with a fixed 33-byte layout, so clients can build the
+//! preimage from raw keys without an XDR encoder:
+//!
+//! | Bytes | `KEY(addr)` field                                               |
+//! |-------|-----------------------------------------------------------------|
+//! | 0     | Type tag: [`ACCOUNT_TAG`] (`G...`) or [`CONTRACT_TAG`] (`C...`) |
+//! | 1..33 | ed25519 public key, or contract id                              |
+//!
+//! A v3 preimage is always `1 + 33 + 33 + 16 + salt.len()` bytes.
+//!
+//! The crate is `no_std` and dependency-free by default. Preimages are streamed into
+//! a [`Sink`]: the contract collects them into host `Bytes` and hashes with the host
+//! SHA-256, while clients enable the `sha2` feature and call [`amount_commitment`] or
+//! [`token_commitment`] directly.
+
+#![no_std]
+
+/// Longest salt accepted in a commitment, in bytes.
+pub const MAX_SALT_LEN: usize = 1024;
+
+/// Leading byte of a scheme v2 (token-bound) commitment preimage.
+///
+/// v1 preimages start with `XDR(owner)`, whose first byte is always `0x00`, so the
+/// two schemes can never produce the same preimage.
+pub const SCHEME_V2_TAG: u8 = 2;
+
+/// Leading byte of a scheme v3 (fixed-layout, token-bound) commitment preimage.
+pub const SCHEME_V3_TAG: u8 = 3;
+
+/// Domain tag prefixed to a withdrawal announcement preimage.
+///
+/// Without it an announcement for `salt || nonce` would be the v1 preimage of an
+/// escrow with that longer salt.
+pub const ANNOUNCEMENT_TAG: &[u8] = b"quickex/announce";
+
+/// Length of a fixed-layout address key: a type tag and a 32-byte key.
+pub const ADDRESS_KEY_LEN: usize = 33;
+
+/// Type tag of an account address (`G...`) in a fixed-layout address key.
+pub const ACCOUNT_TAG: u8 = 0;
+
+/// Type tag of a contract address (`C...`) in a fixed-layout address key.
+pub const CONTRACT_TAG: u8 = 1;
+
+/// Longest XDR encoding of an `ScVal::Address` (a muxed account), in bytes.
+pub const MAX_ADDRESS_XDR_LEN: usize = 48;
+
+/// `ScValType::Address`.
+const SCV_ADDRESS: [u8; 4] = [0, 0, 0, 18];
+
+/// Why a preimage could not be built.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CommitmentError {
+    /// Amount is negative.
+    NegativeAmount,
+    /// Salt is longer than [`MAX_SALT_LEN`].
+    SaltTooLong,
+}
+
+/// Receives preimage bytes in order.
+pub trait Sink {
+    fn write(&mut self, bytes: &[u8]);
+}
+
+/// XDR of a Stellar account address (`G...`) with ed25519 key `public_key`.
+pub fn account_address_xdr(public_key: &[u8; 32]) -> [u8; 44] {
+    let mut xdr = [0u8; 44];
+    xdr[..4].copy_from_slice(&SCV_ADDRESS);
+    // ScAddress::Account (0), PublicKey::Ed25519 (0).
+    xdr[12..].copy_from_slice(public_key);
+    xdr
+}
+
+/// XDR of a contract address (`C...`) with id `contract_id`.
+pub fn contract_address_xdr(contract_id: &[u8; 32]) -> [u8; 40] {
+    let mut xdr = [0u8; 40];
+    xdr[..4].copy_from_slice(&SCV_ADDRESS);
+    // ScAddress::Contract (1).
+    xdr[7] = 1;
+    xdr[8..].copy_from_slice(contract_id);
+    xdr
+}
+
+/// Fixed-layout key of a Stellar account address (`G...`) with ed25519 key `public_key`.
+pub fn account_address_key(public_key: &[u8; 32]) -> [u8; ADDRESS_KEY_LEN] {
+    address_key(ACCOUNT_TAG, public_key)
+}
+
+/// Fixed-layout key of a contract address (`C...`) with id `contract_id`.
+pub fn contract_address_key(contract_id: &[u8; 32]) -> [u8; ADDRESS_KEY_LEN] {
+    address_key(CONTRACT_TAG, contract_id)
+}
+
+/// Fixed-layout key of the address whose `ScVal` XDR is `xdr`.
+///
+/// Returns `None` for anything but an account or contract address.
+pub fn address_key_from_xdr(xdr: &[u8]) -> Option<[u8; ADDRESS_KEY_LEN]> {
+    let (tag, key) = if xdr.len() == 44 && xdr[..12] == account_address_xdr(&[0; 32])[..12] {
+        (ACCOUNT_TAG, &xdr[12..])
+    } else if xdr.len() == 40 && xdr[..8] == contract_address_xdr(&[0; 32])[..8] {
+        (CONTRACT_TAG, &xdr[8..])
+    } else {
+        return None;
+    };
+    Some(address_key(tag, key.try_into().ok()?))
+}
+
+fn address_key(tag: u8, key: &[u8; 32]) -> [u8; ADDRESS_KEY_LEN] {
+    let mut out = [0u8; ADDRESS_KEY_LEN];
+    out[0] = tag;
+    out[1..].copy_from_slice(key);
+    out
+}
+
+fn check(amount: i128, salt: &[u8]) -> Result<(), CommitmentError> {
+    if amount < 0 {
+        return Err(CommitmentError::NegativeAmount);
+    }
+    if salt.len() > MAX_SALT_LEN {
+        return Err(CommitmentError::SaltTooLong);
+    }
+    Ok(())
+}
+
+/// Write the v1 preimage `XDR(owner) || BE(amount) || salt`.
+///
+/// # Errors
+/// - [`CommitmentError::NegativeAmount`] – amount is negative.
+/// - [`CommitmentError::SaltTooLong`] – salt is longer than [`MAX_SALT_LEN`].
+pub fn write_amount_preimage<S: Sink>(
+    sink: &mut S,
+    owner_xdr: &[u8],
+    amount: i128,
+    salt: &[u8],
+) -> Result<(), CommitmentError> {
+    check(amount, salt)?;
+    sink.write(owner_xdr);
+    sink.write(&amount.to_be_bytes());
+    sink.write(salt);
+    Ok(())
+}
+
+/// Write the v2 preimage `0x02 || XDR(token) || XDR(owner) || BE(amount) || salt`.
+///
+/// # Errors
+/// Same as [`write_amount_preimage`].
+pub fn write_token_preimage<S: Sink>(
+    sink: &mut S,
+    token_xdr: &[u8],
+    owner_xdr: &[u8],
+    amount: i128,
+    salt: &[u8],
+) -> Result<(), CommitmentError> {
+    check(amount, salt)?;
+    sink.write(&[SCHEME_V2_TAG]);
+    sink.write(token_xdr);
+    write_amount_preimage(sink, owner_xdr, amount, salt)
+}
+
+/// Write the v3 preimage `0x03 || KEY(token) || KEY(owner) || BE(amount) || salt`.
+///
+/// # Errors
+/// Same as [`write_amount_preimage`].
+pub fn write_fixed_preimage<S: Sink>(
+    sink: &mut S,
+    token_key: &[u8; ADDRESS_KEY_LEN],
+    owner_key: &[u8; ADDRESS_KEY_LEN],
+    amount: i128,
+    salt: &[u8],
+) -> Result<(), CommitmentError> {
+    check(amount, salt)?;
+    sink.write(&[SCHEME_V3_TAG]);
+    sink.write(token_key);
+    write_amount_preimage(sink, owner_key, amount, salt)
+}
+
+/// Write the withdrawal announcement preimage
+/// `"quickex/announce" || XDR(to) || BE(amount) || salt || nonce`.
+///
+/// # Errors
+/// Same as [`write_amount_preimage`].
+pub fn write_announcement_preimage<S: Sink>(
+    sink: &mut S,
+    to_xdr: &[u8],
+    amount: i128,
+    salt: &[u8],
+    nonce: &[u8; 32],
+) -> Result<(), CommitmentError> {
+    check(amount, salt)?;
+    sink.write(ANNOUNCEMENT_TAG);
+    write_amount_preimage(sink, to_xdr, amount, salt)?;
+    sink.write(nonce);
+    Ok(())
+}
+
+#[cfg(feature = "sha2")]
+mod hash {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    impl Sink for Sha256 {
+        fn write(&mut self, bytes: &[u8]) {
+            self.update(bytes);
+        }
+    }
+
+    /// v1 commitment `SHA256(XDR(owner) || BE(amount) || salt)`.
+    pub fn amount_commitment(
+        owner_xdr: &[u8],
+        amount: i128,
+        salt: &[u8],
+    ) -> Result<[u8; 32], CommitmentError> {
+        let mut hasher = Sha256::new();
+        write_amount_preimage(&mut hasher, owner_xdr, amount, salt)?;
+        Ok(hasher.finalize().into())
+    }
+
+    /// v2 commitment `SHA256(0x02 || XDR(token) || XDR(owner) || BE(amount) || salt)`.
+    pub fn token_commitment(
+        token_xdr: &[u8],
+        owner_xdr: &[u8],
+        amount: i128,
+        salt: &[u8],
+    ) -> Result<[u8; 32], CommitmentError> {
+        let mut hasher = Sha256::new();
+        write_token_preimage(&mut hasher, token_xdr, owner_xdr, amount, salt)?;
+        Ok(hasher.finalize().into())
+    }
+
+    /// v3 commitment `SHA256(0x03 || KEY(token) || KEY(owner) || BE(amount) || salt)`.
+    pub fn fixed_commitment(
+        token_key: &[u8; ADDRESS_KEY_LEN],
+        owner_key: &[u8; ADDRESS_KEY_LEN],
+        amount: i128,
+        salt: &[u8],
+    ) -> Result<[u8; 32], CommitmentError> {
+        let mut hasher = Sha256::new();
+        write_fixed_preimage(&mut hasher, token_key, owner_key, amount, salt)?;
+        Ok(hasher.finalize().into())
+    }
+}
+
+#[cfg(feature = "sha2")]
+pub use hash::{amount_commitment, fixed_commitment, token_commitment};